@@ -4,10 +4,14 @@ use self::{
     consts::{ADMIN_EMAIL, CLIENT_URL, MAX_UPLOAD_LIMIT, SERVER_IP, SERVER_PORT},
     jwt::JwtLayer,
     services::notification_service::NotificationService,
-    tasks::queue_mailer::QueueMailer,
+    tasks::{
+        deadline_monitor::DeadlineMonitor, queue_mailer::QueueMailer,
+        recurring_ticket_scheduler::RecurringTicketScheduler, sla_monitor::SlaMonitor,
+        trash_purger::TrashPurger,
+    },
 };
 use anyhow::Context;
-use axum::{extract::DefaultBodyLimit, Extension, Router};
+use axum::{extract::DefaultBodyLimit, middleware, Extension, Router};
 use axum_csrf::{CsrfConfig, CsrfLayer};
 use axum_login::{
     tower_sessions::{MemoryStore, SessionManagerLayer},
@@ -17,7 +21,7 @@ use fang::AsyncQueue;
 use fang::NoTls;
 use fang::{AsyncQueueable, AsyncRunnable};
 use http::{
-    header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, ORIGIN},
+    header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE, ETAG, IF_MATCH, ORIGIN},
     HeaderValue, Method,
 };
 use lettre::Message;
@@ -33,13 +37,18 @@ pub mod auth_utils;
 pub mod config;
 pub mod consts;
 pub mod error;
+pub mod etag;
 pub mod jwt;
 pub mod login_controller;
+pub mod merge_patch;
+pub mod project_access;
+pub mod project_check;
 pub mod query;
 pub mod resources;
 pub mod services;
 pub mod tasks;
 pub mod template_models;
+pub mod ticket_path;
 pub mod validated_json;
 
 pub async fn serve(
@@ -64,10 +73,12 @@ pub fn router(store: Client, db: DatabaseConnection, queue: AsyncQueue<NoTls>) -
             Method::POST,
             Method::GET,
             Method::PUT,
+            Method::PATCH,
             Method::DELETE,
             Method::OPTIONS,
         ])
-        .allow_headers([ACCEPT, AUTHORIZATION, CONTENT_TYPE, ORIGIN])
+        .allow_headers([ACCEPT, AUTHORIZATION, CONTENT_TYPE, IF_MATCH, ORIGIN])
+        .expose_headers([ETAG])
         .allow_origin(CLIENT_URL.parse::<HeaderValue>().unwrap());
     let cors_layer = ServiceBuilder::new().layer(cors);
 
@@ -103,15 +114,60 @@ pub fn router(store: Client, db: DatabaseConnection, queue: AsyncQueue<NoTls>) -
         }
     });
 
+    let mut q = queue.clone();
+    let task = TrashPurger {};
+    tokio::spawn(async move {
+        if let Err(e) = q.schedule_task(&task as &dyn AsyncRunnable).await {
+            tracing::warn!("Unable to schedule task. Reason: {}", e);
+        }
+    });
+
+    let mut q = queue.clone();
+    let task = SlaMonitor {};
+    tokio::spawn(async move {
+        if let Err(e) = q.schedule_task(&task as &dyn AsyncRunnable).await {
+            tracing::warn!("Unable to schedule task. Reason: {}", e);
+        }
+    });
+
+    let mut q = queue.clone();
+    let task = RecurringTicketScheduler {};
+    tokio::spawn(async move {
+        if let Err(e) = q.schedule_task(&task as &dyn AsyncRunnable).await {
+            tracing::warn!("Unable to schedule task. Reason: {}", e);
+        }
+    });
+
+    let mut q = queue.clone();
+    let task = DeadlineMonitor {};
+    tokio::spawn(async move {
+        if let Err(e) = q.schedule_task(&task as &dyn AsyncRunnable).await {
+            tracing::warn!("Unable to schedule task. Reason: {}", e);
+        }
+    });
+
     Router::new()
         .merge(resources::ticket_attachments_resource::router())
+        .merge(resources::project_archive_resource::router())
         .layer(DefaultBodyLimit::max(1024 * 1024 * (*MAX_UPLOAD_LIMIT)))
         .merge(resources::preferences_resource::router())
+        .merge(resources::project_members_resource::router())
         .merge(resources::users_resource::router())
         .merge(resources::tickets_resource::router())
         .merge(resources::ticket_updates_resource::router())
         .merge(resources::comments_resource::router())
+        .merge(resources::custom_fields_resource::router())
+        .merge(resources::imports_resource::router())
+        .merge(resources::board_resource::router())
         .merge(resources::projects_resource::router())
+        .merge(resources::recurring_tickets_resource::router())
+        .merge(resources::reports_resource::router())
+        .merge(resources::saved_views_resource::router())
+        .merge(resources::sla_resource::router())
+        .merge(resources::sprints_resource::router())
+        .merge(resources::trash_resource::router())
+        .merge(resources::watchers_resource::router())
+        .route_layer(middleware::from_fn(project_access::project_access))
         .layer(jwt_layer)
         .merge(login_controller::router())
         .layer(auth_layer)
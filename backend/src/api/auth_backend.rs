@@ -1,6 +1,6 @@
 use axum_login::{AuthnBackend, UserId};
 use entity::users::{self, Entity as User};
-use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, EntityTrait, QueryFilter};
+use sea_orm::{ColumnTrait, DatabaseConnection, DbErr, QueryFilter};
 use shared::dtos::login_dto::LoginDto;
 
 pub type AuthSession = axum_login::AuthSession<AuthBackend>;
@@ -25,7 +25,7 @@ impl AuthnBackend for AuthBackend {
         &self,
         creds: Self::Credentials,
     ) -> Result<Option<Self::User>, Self::Error> {
-        let user = User::find()
+        let user = User::find_active()
             .filter(users::Column::Username.eq(creds.username.clone()))
             .one(&self.db)
             .await?;
@@ -42,7 +42,7 @@ impl AuthnBackend for AuthBackend {
     }
 
     async fn get_user(&self, user_id: &UserId<Self>) -> Result<Option<Self::User>, Self::Error> {
-        let user = User::find()
+        let user = User::find_active()
             .filter(users::Column::PublicId.eq(user_id.inner))
             .one(&self.db)
             .await?;
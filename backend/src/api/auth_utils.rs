@@ -1,6 +1,8 @@
 use super::error::AuthError;
+use entity::users;
 use http::{header::AUTHORIZATION, HeaderMap, StatusCode};
-use shared::api::auth::AuthScheme;
+use shared::{api::auth::AuthScheme, validation::user_validation::UserRole};
+use std::str::FromStr;
 
 pub fn extract_auth_from_header(
     headers: &HeaderMap,
@@ -20,3 +22,18 @@ pub fn extract_auth_from_header(
             code: Some(scheme.to_string()),
         })
 }
+
+pub fn has_role(user: &users::Model, role: UserRole) -> bool {
+    UserRole::from_str(user.role.as_str()).is_ok_and(|user_role| user_role == role)
+}
+
+pub fn require_role(user: &users::Model, role: UserRole) -> Result<(), AuthError> {
+    match has_role(user, role) {
+        true => Ok(()),
+        false => Err(AuthError {
+            status: StatusCode::FORBIDDEN,
+            message: format!("Only users with the {} role are allowed to do this", role),
+            code: None,
+        }),
+    }
+}
@@ -21,6 +21,9 @@ lazy_static! {
     pub static ref SMTP_USERNAME: String = set_smtp_username();
     pub static ref SMTP_PASSWORD: String = set_smtp_password();
     pub static ref SMTP_TLS_OFF: bool = set_smtp_tls_off();
+    pub static ref TRASH_RETENTION_DAYS: i64 = set_trash_retention_days();
+    pub static ref IMPORT_DIR: String = set_import_dir();
+    pub static ref DEADLINE_REMINDER_DAYS: Vec<i64> = set_deadline_reminder_days();
 }
 
 pub const SERVER_IP: IpAddr = IpAddr::V4(Ipv4Addr::new(0, 0, 0, 0));
@@ -28,6 +31,9 @@ pub const DEFAULT_PAGINATION_OFFSET: u64 = 0;
 pub const DEFAULT_PAGINATION_LIMIT: u64 = 5;
 
 const DEFAULT_PORT: u16 = 80;
+const DEFAULT_TRASH_RETENTION_DAYS: i64 = 30;
+const DEFAULT_IMPORT_DIR: &str = "imports";
+const DEFAULT_DEADLINE_REMINDER_DAYS: &str = "7,1";
 
 fn set_server_port() -> u16 {
     dotenvy::var("SERVER_PORT")
@@ -139,6 +145,40 @@ fn set_smtp_password() -> String {
         .unwrap()
 }
 
+fn set_trash_retention_days() -> i64 {
+    dotenvy::var("TRASH_RETENTION_DAYS")
+        .context("TRASH_RETENTION_DAYS must be defined in the environment file")
+        .and_then(|s| s.parse().map_err(|e: ParseIntError| Error::new(e)))
+        .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS)
+}
+
+fn set_import_dir() -> String {
+    dotenvy::var("IMPORT_DIR").unwrap_or(String::from(DEFAULT_IMPORT_DIR))
+}
+
+fn set_deadline_reminder_days() -> Vec<i64> {
+    parse_days(
+        dotenvy::var("DEADLINE_REMINDER_DAYS")
+            .unwrap_or(String::from(DEFAULT_DEADLINE_REMINDER_DAYS))
+            .as_str(),
+    )
+    .context("DEADLINE_REMINDER_DAYS must be a comma separated list of days")
+    .unwrap()
+}
+
+/// Parses a comma separated list of days, sorted from the longest to the shortest.
+fn parse_days(s: &str) -> Result<Vec<i64>, ParseIntError> {
+    let mut days = s
+        .split(',')
+        .map(str::trim)
+        .filter(|day| !day.is_empty())
+        .map(str::parse)
+        .collect::<Result<Vec<i64>, _>>()?;
+    days.sort_unstable_by(|a, b| b.cmp(a));
+    days.dedup();
+    Ok(days)
+}
+
 fn get_from_env_or_file_env(key: &str) -> Result<String, Error> {
     dotenvy::var(key).or(get_from_file(
         dotenvy::var(format!("{}_FILE", key)).context("File key not found"),
@@ -175,6 +215,13 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_days() {
+        assert_eq!(vec![14, 7, 1], parse_days("1, 7,14,7").unwrap());
+        assert!(parse_days("").unwrap().is_empty());
+        assert!(parse_days("7,soon").is_err());
+    }
+
     #[test]
     fn test_fallback_to_file() -> Result<(), Error> {
         let res = get_from_env_or_file_env("UNSET_TEST_KEY");
@@ -1,3 +1,4 @@
+use crate::api::query::jql::error::QueryError;
 use axum::{
     extract::rejection::{JsonRejection, PathRejection},
     http::{header, HeaderValue, StatusCode},
@@ -7,8 +8,10 @@ use sea_orm::DbErr;
 use serde::Serialize;
 use serde_json::json;
 use serde_valid::validation::Errors;
-use shared::api::error::{error_detail::ErrorDetail, error_response::ErrorResponse};
-use std::fmt::Display;
+use shared::api::error::{
+    error_detail::ErrorDetail, error_response::ErrorResponse, property_error::PropertyError,
+};
+use std::{collections::HashMap, fmt::Display};
 use strum::Display as StrumDisplay;
 use thiserror::Error;
 
@@ -19,7 +22,7 @@ pub struct JsonError {
     code: Option<String>,
     message: String,
     origin: Option<String>,
-    details: Option<Errors>,
+    details: Option<ErrorDetail>,
 }
 
 impl Display for JsonError {
@@ -70,6 +73,18 @@ impl From<(StatusCode, String, String)> for JsonError {
 
 impl From<(StatusCode, String, String, Errors)> for JsonError {
     fn from((status, message, origin, details): (StatusCode, String, String, Errors)) -> Self {
+        JsonError {
+            status,
+            code: Option::None,
+            message,
+            origin: Option::Some(origin),
+            details: Option::Some(ErrorDetail::from(details)),
+        }
+    }
+}
+
+impl From<(StatusCode, String, String, ErrorDetail)> for JsonError {
+    fn from((status, message, origin, details): (StatusCode, String, String, ErrorDetail)) -> Self {
         JsonError {
             status,
             code: Option::None,
@@ -110,7 +125,7 @@ impl IntoResponse for JsonError {
                 code: self.code.unwrap_or(self.status.to_string()),
                 message: self.message,
                 origin: self.origin.unwrap_or(String::from("unspecified")),
-                details: self.details.map(ErrorDetail::from),
+                details: self.details,
             })
             .to_string(),
         )
@@ -156,13 +171,21 @@ pub enum ApiError {
     PathExtractorRejection(#[from] PathRejection),
     ValidationRejection(#[from] Errors),
     DbAppError(#[from] DbErr),
-    HandlerError(#[from] JsonError),
+    /// Boxed, the error body would make every `Result` of the handlers large.
+    HandlerError(Box<JsonError>),
     AuthError(#[from] AuthError),
+    QueryRejection(#[from] QueryError),
 }
 
 impl ApiError {
     pub fn new(status_code: StatusCode, message: String) -> Self {
-        ApiError::HandlerError(JsonError::from((status_code, message)))
+        ApiError::from(JsonError::from((status_code, message)))
+    }
+}
+
+impl From<JsonError> for ApiError {
+    fn from(value: JsonError) -> Self {
+        ApiError::HandlerError(Box::new(value))
     }
 }
 
@@ -190,8 +213,23 @@ impl IntoResponse for ApiError {
                 db_error.to_string(),
                 String::from("db_error"),
             )),
-            ApiError::HandlerError(handler_error) => handler_error,
+            ApiError::HandlerError(handler_error) => *handler_error,
             ApiError::AuthError(auth_error) => JsonError::from(auth_error),
+            ApiError::QueryRejection(query_error) => JsonError::from((
+                StatusCode::BAD_REQUEST,
+                String::from("Invalid query"),
+                String::from("query_rejection"),
+                ErrorDetail {
+                    errors: None,
+                    properties: Some(HashMap::from([(
+                        String::from("jql"),
+                        PropertyError {
+                            errors: vec![query_error.to_string()],
+                            position: Some(query_error.position),
+                        },
+                    )])),
+                },
+            )),
         }
         .into_response()
     }
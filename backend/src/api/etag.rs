@@ -0,0 +1,120 @@
+use super::error::{ApiError, JsonError};
+use axum::{
+    extract::FromRequestParts,
+    http::{
+        header::{ETAG, IF_MATCH},
+        request::Parts,
+        StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use sea_orm::DbErr;
+use serde::Serialize;
+use shared::api::error::error_response::VERSION_CONFLICT_ORIGIN;
+
+/// Version the client based its changes on, taken from the mandatory `If-Match` header.
+/// `None` for `If-Match: *`, which matches any version.
+#[derive(Debug, Clone, Copy)]
+pub struct IfMatch(pub Option<u32>);
+
+impl IfMatch {
+    /// The version to save the changes against, fails unless it is the current one.
+    pub fn check(self, current: u32) -> Result<u32, ApiError> {
+        match self.0 {
+            Some(version) if version != current => Err(precondition_failed()),
+            _ => Ok(current),
+        }
+    }
+}
+
+impl<S> FromRequestParts<S> for IfMatch
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let value = parts.headers.get(IF_MATCH).ok_or_else(|| {
+            ApiError::new(
+                StatusCode::PRECONDITION_REQUIRED,
+                String::from("The If-Match header is required when updating"),
+            )
+        })?;
+        value
+            .to_str()
+            .ok()
+            .and_then(|value| match value.trim() {
+                "*" => Some(IfMatch(None)),
+                value => parse_etag(value).map(|version| IfMatch(Some(version))),
+            })
+            .ok_or_else(|| {
+                ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    String::from("Invalid If-Match header"),
+                )
+            })
+    }
+}
+
+/// JSON body sent together with the `ETag` of the version it represents.
+pub struct Versioned<T>(pub u32, pub T);
+
+impl<T: Serialize> IntoResponse for Versioned<T> {
+    fn into_response(self) -> Response {
+        ([(ETAG, etag(self.0))], Json(self.1)).into_response()
+    }
+}
+
+pub fn etag(version: u32) -> String {
+    format!("\"{}\"", version)
+}
+
+/// Weak tags are accepted as well, the tag carries nothing but the version.
+pub fn parse_etag(value: &str) -> Option<u32> {
+    value
+        .trim()
+        .trim_start_matches("W/")
+        .trim_matches('"')
+        .parse()
+        .ok()
+}
+
+pub fn precondition_failed() -> ApiError {
+    ApiError::from(JsonError::from((
+        StatusCode::PRECONDITION_FAILED,
+        String::from(
+            "It has been modified in the meantime, reload it and apply your changes again",
+        ),
+        String::from(VERSION_CONFLICT_ORIGIN),
+    )))
+}
+
+/// Updates filtered by version affect no rows when somebody else saved in the meantime.
+pub fn version_conflict(e: DbErr) -> ApiError {
+    match e {
+        DbErr::RecordNotUpdated => precondition_failed(),
+        e => e.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_etag() {
+        assert_eq!(parse_etag(&etag(3)), Some(3));
+        assert_eq!(parse_etag("W/\"12\""), Some(12));
+        assert_eq!(parse_etag(" 7 "), Some(7));
+        assert_eq!(parse_etag("\"abc\""), None);
+        assert_eq!(parse_etag("*"), None);
+    }
+
+    #[test]
+    fn test_if_match_wildcard() {
+        assert_eq!(IfMatch(None).check(5).ok(), Some(5));
+        assert_eq!(IfMatch(Some(5)).check(5).ok(), Some(5));
+        assert!(IfMatch(Some(4)).check(5).is_err());
+    }
+}
@@ -4,7 +4,7 @@ use entity::users::{self, Entity as User};
 use futures::Future;
 use http::{HeaderMap, Request, Response, StatusCode};
 use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, TokenData, Validation};
-use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter};
+use sea_orm::{ColumnTrait, DatabaseConnection, QueryFilter};
 use shared::api::auth::{AuthScheme, Claims};
 use std::pin::Pin;
 use tower::{Layer, Service};
@@ -101,7 +101,7 @@ where
                     *res.status_mut() = http::StatusCode::INTERNAL_SERVER_ERROR;
                     return Ok(res);
                 };
-                let Ok(Some(identity)) = User::find()
+                let Ok(Some(identity)) = User::find_active()
                     .filter(users::Column::PublicId.eq(claims.sub))
                     .one(db)
                     .await
@@ -0,0 +1,174 @@
+use axum::{
+    extract::{FromRequest, Request},
+    http::StatusCode,
+    Json,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use serde_valid::{validation::Errors, Validate};
+
+use super::error::ApiError;
+
+/// Body of a `PATCH` request, an RFC 7396 JSON Merge Patch.
+#[derive(Debug, Clone, Default)]
+pub struct MergePatch(pub Value);
+
+impl<S> FromRequest<S> for MergePatch
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        // Both `application/json` and `application/merge-patch+json` are accepted
+        let Json(value) = Json::<Value>::from_request(req, state).await?;
+        Ok(Self(value))
+    }
+}
+
+impl MergePatch {
+    /// Applies the patch on the serialized form of the target.
+    pub fn apply<T>(self, target: &T) -> Result<T, ApiError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        let mut value = serde_json::to_value(target)
+            .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+        merge(&mut value, self.0);
+        serde_json::from_value(value)
+            .map_err(|e| ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, e.to_string()))
+    }
+
+    /// Applies the patch, then validates the result the same way a full update would be.
+    /// Only the members of the patch are checked, stored values which no longer pass a rule,
+    /// e.g. a deadline which has passed, do not block changing other members.
+    pub fn apply_validated<T>(self, target: &T) -> Result<T, ApiError>
+    where
+        T: Serialize + DeserializeOwned + Validate,
+    {
+        let members = match &self.0 {
+            Value::Object(members) => Some(members.keys().cloned().collect::<Vec<String>>()),
+            _ => None,
+        };
+        let merged = self.apply(target)?;
+        match (merged.validate(), members) {
+            (Ok(()), _) => Ok(merged),
+            (Err(Errors::Object(mut errors)), Some(members)) => {
+                errors
+                    .properties
+                    .retain(|property, _| members.iter().any(|m| m == property));
+                match errors.errors.is_empty() && errors.properties.is_empty() {
+                    true => Ok(merged),
+                    false => Err(Errors::Object(errors).into()),
+                }
+            }
+            (Err(errors), _) => Err(errors.into()),
+        }
+    }
+}
+
+/// Members set to `null` are removed, objects are merged recursively, anything else replaces
+/// the target.
+pub fn merge(target: &mut Value, patch: Value) {
+    match patch {
+        Value::Object(members) => {
+            if !target.is_object() {
+                *target = Value::Object(Map::new());
+            }
+            if let Value::Object(map) = target {
+                for (key, value) in members {
+                    match value {
+                        Value::Null => {
+                            map.remove(&key);
+                        }
+                        value => merge(map.entry(key).or_insert(Value::Null), value),
+                    }
+                }
+            }
+        }
+        patch => *target = patch,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn merged(target: Value, patch: Value) -> Value {
+        let mut target = target;
+        merge(&mut target, patch);
+        target
+    }
+
+    #[test]
+    fn test_apply() {
+        assert_eq!(
+            merged(json!({"a": "b"}), json!({"a": "c"})),
+            json!({"a": "c"})
+        );
+        assert_eq!(
+            merged(json!({"a": "b"}), json!({"b": "c"})),
+            json!({"a": "b", "b": "c"})
+        );
+        assert_eq!(merged(json!({"a": "b"}), json!({"a": null})), json!({}));
+        assert_eq!(
+            merged(
+                json!({"a": {"b": "c"}}),
+                json!({"a": {"b": "d", "c": null}})
+            ),
+            json!({"a": {"b": "d"}})
+        );
+        assert_eq!(
+            merged(json!({"a": ["b"]}), json!({"a": "c"})),
+            json!({"a": "c"})
+        );
+        assert_eq!(merged(json!({"a": "foo"}), json!(null)), json!(null));
+        assert_eq!(
+            merged(json!(["a", "b"]), json!({"a": "b"})),
+            json!({"a": "b"})
+        );
+        assert_eq!(
+            merged(json!({}), json!({"a": {"bb": {"ccc": null}}})),
+            json!({"a": {"bb": {}}})
+        );
+    }
+
+    #[derive(Debug, PartialEq, Serialize, serde::Deserialize, Validate)]
+    struct Sample {
+        #[validate(min_length = 3)]
+        name: String,
+        note: Option<String>,
+    }
+
+    #[test]
+    fn test_apply_validated() {
+        let sample = Sample {
+            name: String::from("Sample"),
+            note: Some(String::from("Note")),
+        };
+        let patched = MergePatch(json!({"note": null}))
+            .apply_validated(&sample)
+            .unwrap();
+        assert_eq!(patched.name, "Sample");
+        assert_eq!(patched.note, None);
+        assert!(MergePatch(json!({"name": "ab"}))
+            .apply_validated(&sample)
+            .is_err());
+    }
+
+    #[test]
+    fn test_apply_validated_patched_members() {
+        let sample = Sample {
+            name: String::from("ab"),
+            note: None,
+        };
+        let patched = MergePatch(json!({"note": "Note"}))
+            .apply_validated(&sample)
+            .unwrap();
+        assert_eq!(patched.note, Some(String::from("Note")));
+        assert!(MergePatch(json!({"name": "cd"}))
+            .apply_validated(&sample)
+            .is_err());
+    }
+}
@@ -0,0 +1,219 @@
+use super::{
+    error::ApiError,
+    services::{membership_service::MembershipService, ticket_key_service::TicketKeyService},
+};
+use axum::{
+    extract::{FromRequestParts, MatchedPath, Path, Request},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use entity::{
+    comments::{self, Entity as Comment},
+    ticket_updates::{self, Entity as TicketUpdate},
+    users,
+};
+use sea_orm::{DatabaseConnection, EntityTrait, QuerySelect};
+use shared::dtos::project_member_dto::ProjectRole;
+use std::collections::HashMap;
+
+/// What the `{id}` of a route refers to, the project is found through it.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Scope {
+    Project,
+    Ticket,
+    Comment,
+    TicketUpdate,
+}
+
+/// The project role needed for a route, `None` for routes which are not about a single
+/// project. Reading needs a viewer, the work on tickets a contributor and everything else
+/// about the project itself an admin.
+pub fn required_access(method: &Method, path: &str) -> Option<(Scope, ProjectRole)> {
+    let read = method == Method::GET || method == Method::HEAD;
+    let sub = |prefix: &str| {
+        path.strip_prefix(prefix)
+            .filter(|rest| rest.is_empty() || rest.starts_with('/'))
+    };
+    if let Some(rest) = sub("/projects/{id}") {
+        let work = ["/tickets", "/board", "/sprints", "/recurring_tickets"];
+        let role = match read {
+            true => ProjectRole::Viewer,
+            false if work.iter().any(|p| rest.starts_with(p)) => ProjectRole::Contributor,
+            false => ProjectRole::Admin,
+        };
+        return Some((Scope::Project, role));
+    }
+    if let Some(rest) = sub("/tickets/{id}") {
+        // Following a ticket is reading it as well
+        let role = match read || rest == "/subscribe" || rest.starts_with("/watchers") {
+            true => ProjectRole::Viewer,
+            false => ProjectRole::Contributor,
+        };
+        return Some((Scope::Ticket, role));
+    }
+    let role = match read {
+        true => ProjectRole::Viewer,
+        false => ProjectRole::Contributor,
+    };
+    if sub("/comments/{id}").is_some() {
+        return Some((Scope::Comment, role));
+    }
+    if sub("/ticket_updates/{id}").is_some() {
+        return Some((Scope::TicketUpdate, role));
+    }
+    None
+}
+
+/// Rejects requests for projects, and for their tickets and comments, of users lacking the
+/// role needed. Runs after routing, the route tells what the request is about.
+pub async fn project_access(request: Request, next: Next) -> Result<Response, ApiError> {
+    let Some((scope, role)) = request
+        .extensions()
+        .get::<MatchedPath>()
+        .and_then(|path| required_access(request.method(), path.as_str()))
+    else {
+        return Ok(next.run(request).await);
+    };
+    let (mut parts, body) = request.into_parts();
+    let Path(params) = Path::<HashMap<String, String>>::from_request_parts(&mut parts, &()).await?;
+    let (Some(db), Some(user)) = (
+        parts.extensions.get::<DatabaseConnection>(),
+        parts.extensions.get::<users::Model>(),
+    ) else {
+        return Err(ApiError::new(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            String::from("Database connection or user missing"),
+        ));
+    };
+    // References which do not resolve are left to the handler
+    let reference = params.get("id").map(String::as_str).unwrap_or_default();
+    match scope {
+        Scope::Project => {
+            if let Ok(project_id) = reference.parse::<u64>() {
+                MembershipService::require(db, user, project_id, role).await?;
+            }
+        }
+        Scope::Ticket => {
+            if let Some(ticket_id) = TicketKeyService::resolve(db, reference).await? {
+                MembershipService::require_ticket(db, user, ticket_id, role).await?;
+            }
+        }
+        Scope::Comment | Scope::TicketUpdate => {
+            let id = reference.parse::<u64>().unwrap_or_default();
+            let ticket_id: Option<u64> = match scope {
+                Scope::Comment => {
+                    Comment::find_by_id(id)
+                        .select_only()
+                        .column(comments::Column::TicketId)
+                        .into_tuple()
+                        .one(db)
+                        .await?
+                }
+                _ => {
+                    TicketUpdate::find_by_id(id)
+                        .select_only()
+                        .column(ticket_updates::Column::TicketId)
+                        .into_tuple()
+                        .one(db)
+                        .await?
+                }
+            };
+            if let Some(ticket_id) = ticket_id {
+                MembershipService::require_ticket(db, user, ticket_id, role).await?;
+            }
+        }
+    }
+    Ok(next.run(Request::from_parts(parts, body)).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_access_read() {
+        for path in [
+            "/projects/{id}",
+            "/projects/{id}/tickets",
+            "/projects/{id}/members",
+            "/projects/{id}/reports/progress",
+        ] {
+            assert_eq!(
+                required_access(&Method::GET, path),
+                Some((Scope::Project, ProjectRole::Viewer)),
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_required_access_tickets() {
+        assert_eq!(
+            required_access(&Method::POST, "/projects/{id}/tickets"),
+            Some((Scope::Project, ProjectRole::Contributor))
+        );
+        assert_eq!(
+            required_access(&Method::POST, "/projects/{id}/sprints/{sprint_id}/start"),
+            Some((Scope::Project, ProjectRole::Contributor))
+        );
+        assert_eq!(
+            required_access(&Method::PATCH, "/tickets/{id}"),
+            Some((Scope::Ticket, ProjectRole::Contributor))
+        );
+        assert_eq!(
+            required_access(&Method::POST, "/tickets/{id}/attachments"),
+            Some((Scope::Ticket, ProjectRole::Contributor))
+        );
+        assert_eq!(
+            required_access(&Method::DELETE, "/comments/{id}"),
+            Some((Scope::Comment, ProjectRole::Contributor))
+        );
+    }
+
+    #[test]
+    fn test_required_access_project() {
+        for (method, path) in [
+            (Method::PUT, "/projects/{id}"),
+            (Method::DELETE, "/projects/{id}"),
+            (Method::POST, "/projects/{id}/fields"),
+            (Method::PUT, "/projects/{id}/members/{user_id}"),
+        ] {
+            assert_eq!(
+                required_access(&method, path),
+                Some((Scope::Project, ProjectRole::Admin)),
+                "{} {}",
+                method,
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn test_required_access_watch() {
+        assert_eq!(
+            required_access(&Method::POST, "/tickets/{id}/subscribe"),
+            Some((Scope::Ticket, ProjectRole::Viewer))
+        );
+        assert_eq!(
+            required_access(&Method::PUT, "/tickets/{id}/watchers/{user_id}"),
+            Some((Scope::Ticket, ProjectRole::Viewer))
+        );
+    }
+
+    #[test]
+    fn test_required_access_other_routes() {
+        for path in [
+            "/projects",
+            "/projects/archive",
+            "/tickets",
+            "/tickets/bulk",
+            "/comments",
+            "/users/{id}",
+            "/trash/{kind}/{id}/restore",
+        ] {
+            assert_eq!(required_access(&Method::POST, path), None, "{}", path);
+        }
+    }
+}
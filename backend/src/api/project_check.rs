@@ -0,0 +1,20 @@
+use super::error::ApiError;
+use axum::http::StatusCode;
+use entity::projects::{self, Entity as Project};
+use sea_orm::{ColumnTrait, ConnectionTrait, QueryFilter};
+
+/// Rejects references to projects which do not exist or are in the trash with a 404, for
+/// the routes below `/projects/{id}`.
+pub async fn check_project<C: ConnectionTrait>(db: &C, project_id: u64) -> Result<(), ApiError> {
+    match Project::find_active()
+        .filter(projects::Column::Id.eq(project_id))
+        .one(db)
+        .await?
+    {
+        Some(_) => Ok(()),
+        None => Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Project not found"),
+        )),
+    }
+}
@@ -1,3 +1,4 @@
 pub mod pagination;
+pub mod project_filter;
 pub mod search;
 pub mod ticket_filter;
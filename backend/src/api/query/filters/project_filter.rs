@@ -0,0 +1,74 @@
+use chrono::NaiveDate;
+use entity::{
+    projects,
+    users::{self, Entity as User},
+};
+use sea_orm::{ColumnTrait, Condition, QueryFilter, QuerySelect, QueryTrait};
+use serde::Deserialize;
+use shared::api::helper::empty_string_as_none;
+use uuid::Uuid;
+
+#[derive(Default, Deserialize)]
+pub struct ProjectFilter {
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub owner: Option<Uuid>,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub active: Option<bool>,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub deadline_from: Option<NaiveDate>,
+    /// Inclusive, so that e.g. the last day of a month can be given.
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub deadline_to: Option<NaiveDate>,
+}
+
+impl ProjectFilter {
+    pub fn to_condition(&self) -> Condition {
+        Condition::all()
+            .add_option(
+                self.owner.map(|public_id| {
+                    projects::Column::UserId.in_subquery(user_id_query(public_id))
+                }),
+            )
+            .add_option(
+                self.active
+                    .map(|active| projects::Column::Active.eq(i8::from(active))),
+            )
+            .add_option(
+                self.deadline_from
+                    .map(|d| projects::Column::Deadline.gte(d)),
+            )
+            .add_option(self.deadline_to.map(|d| projects::Column::Deadline.lte(d)))
+    }
+}
+
+fn user_id_query(public_id: Uuid) -> sea_orm::sea_query::SelectStatement {
+    User::find_active()
+        .select_only()
+        .column(users::Column::Id)
+        .filter(users::Column::PublicId.eq(public_id))
+        .into_query()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::Query;
+    use sea_orm::{DbBackend, EntityTrait};
+
+    #[test]
+    fn test_project_filter() {
+        let uri = "/projects?active=true&owner=&deadline_from=2024-05-01&deadline_to=2024-05-31"
+            .parse()
+            .unwrap();
+        let Query(filter) = Query::<ProjectFilter>::try_from_uri(&uri).unwrap();
+        assert!(filter.owner.is_none());
+        let sql = projects::Entity::find()
+            .filter(filter.to_condition())
+            .build(DbBackend::MySql)
+            .to_string();
+        assert!(sql.contains("`projects`.`active` = 1"));
+        assert!(sql.contains(
+            "`projects`.`deadline` >= '2024-05-01' AND `projects`.`deadline` <= '2024-05-31'"
+        ));
+    }
+}
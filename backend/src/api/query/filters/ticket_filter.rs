@@ -1,6 +1,270 @@
+use chrono::{DateTime, Utc};
+use entity::{
+    ticket_field_values::{self, Entity as TicketFieldValue},
+    tickets,
+    users::{self, Entity as User},
+};
+use sea_orm::{ColumnTrait, Condition, EntityTrait, QueryFilter, QuerySelect, QueryTrait};
 use serde::Deserialize;
+use shared::{
+    api::helper::{comma_separated_as_vec, empty_string_as_none},
+    validation::ticket_validation::{TicketPriority, TicketStatus},
+};
+use std::{collections::BTreeMap, str::FromStr};
+use uuid::Uuid;
 
-#[derive(Deserialize)]
+#[derive(Default, Deserialize)]
 pub struct TicketFilter {
     pub project_id: Option<u64>,
+    /// Only tickets not belonging to any project.
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub without_project: Option<bool>,
+    pub sprint_id: Option<u64>,
+    /// Only tickets not planned for any sprint, i.e. the backlog.
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub without_sprint: Option<bool>,
+    /// Comma separated list, e.g. `Started,Reviewing`.
+    #[serde(default, deserialize_with = "comma_separated_as_vec")]
+    pub status: Option<Vec<TicketStatus>>,
+    /// Comma separated list, e.g. `High,Critical`.
+    #[serde(default, deserialize_with = "comma_separated_as_vec")]
+    pub priority: Option<Vec<TicketPriority>>,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub assignee: Option<Uuid>,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub reporter: Option<Uuid>,
+    /// Only tickets without an assignee.
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub unassigned: Option<bool>,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub created_from: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub created_to: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub updated_from: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub updated_to: Option<DateTime<Utc>>,
+    /// Comma separated list of custom field values, e.g. `4:Production,7:Acme`. Tickets match
+    /// any of the values given for a field, and every field given.
+    #[serde(default, deserialize_with = "comma_separated_as_vec")]
+    pub field: Option<Vec<FieldFilter>>,
+    /// Ticket query expression, e.g. `status:Started,Reviewing priority>=High assignee:me`.
+    #[serde(default, deserialize_with = "empty_string_as_none")]
+    pub jql: Option<String>,
+}
+
+impl TicketFilter {
+    /// Tickets not belonging to any project yet.
+    pub fn unassigned_preset() -> Self {
+        Self {
+            without_project: Some(true),
+            ..Default::default()
+        }
+    }
+
+    /// Condition for every structured filter. The `jql` expression is compiled separately
+    /// as it depends on the requesting user.
+    pub fn to_condition(&self) -> Condition {
+        Condition::all()
+            .add_option(self.project_id.map(|id| tickets::Column::ProjectId.eq(id)))
+            .add_option(
+                self.without_project
+                    .filter(|b| *b)
+                    .map(|_| tickets::Column::ProjectId.is_null()),
+            )
+            .add_option(self.sprint_id.map(|id| tickets::Column::SprintId.eq(id)))
+            .add_option(
+                self.without_sprint
+                    .filter(|b| *b)
+                    .map(|_| tickets::Column::SprintId.is_null()),
+            )
+            .add_option(self.status.as_ref().map(|statuses| {
+                tickets::Column::Status.is_in(statuses.iter().map(|s| s.to_string()))
+            }))
+            .add_option(self.priority.as_ref().map(|priorities| {
+                tickets::Column::Priority.is_in(priorities.iter().map(|p| p.0.clone()))
+            }))
+            .add_option(
+                self.assignee
+                    .map(|public_id| tickets::Column::UserId.in_subquery(user_id_query(public_id))),
+            )
+            .add_option(
+                self.reporter.map(|public_id| {
+                    tickets::Column::ReporterId.in_subquery(user_id_query(public_id))
+                }),
+            )
+            .add_option(
+                self.unassigned
+                    .filter(|b| *b)
+                    .map(|_| tickets::Column::UserId.is_null()),
+            )
+            .add_option(self.created_from.map(|d| tickets::Column::CreatedAt.gte(d)))
+            .add_option(self.created_to.map(|d| tickets::Column::CreatedAt.lt(d)))
+            .add_option(self.updated_from.map(|d| tickets::Column::UpdatedAt.gte(d)))
+            .add_option(self.updated_to.map(|d| tickets::Column::UpdatedAt.lt(d)))
+            .add_option(self.field.as_ref().map(|filters| {
+                let mut values: BTreeMap<u64, Vec<&str>> = BTreeMap::new();
+                for filter in filters {
+                    values
+                        .entry(filter.field_id)
+                        .or_default()
+                        .push(filter.value.as_str());
+                }
+                values
+                    .into_iter()
+                    .fold(Condition::all(), |condition, (field_id, values)| {
+                        condition.add(
+                            tickets::Column::Id.in_subquery(field_value_query(field_id, values)),
+                        )
+                    })
+            }))
+    }
+}
+
+/// A value of a custom field, written as `<field id>:<value>`.
+pub struct FieldFilter {
+    pub field_id: u64,
+    pub value: String,
+}
+
+impl FromStr for FieldFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split_once(':')
+            .and_then(|(id, value)| {
+                id.trim().parse().ok().map(|field_id| Self {
+                    field_id,
+                    value: value.to_owned(),
+                })
+            })
+            .ok_or(format!(
+                "Invalid field filter '{}', expected '<field id>:<value>'",
+                s
+            ))
+    }
+}
+
+fn field_value_query(field_id: u64, values: Vec<&str>) -> sea_orm::sea_query::SelectStatement {
+    TicketFieldValue::find()
+        .select_only()
+        .column(ticket_field_values::Column::TicketId)
+        .filter(ticket_field_values::Column::FieldId.eq(field_id))
+        .filter(ticket_field_values::Column::Value.is_in(values))
+        .into_query()
+}
+
+fn user_id_query(public_id: Uuid) -> sea_orm::sea_query::SelectStatement {
+    User::find_active()
+        .select_only()
+        .column(users::Column::Id)
+        .filter(users::Column::PublicId.eq(public_id))
+        .into_query()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::extract::Query;
+    use sea_orm::DbBackend;
+
+    fn to_sql(uri: &str) -> String {
+        let Query(filter) = Query::<TicketFilter>::try_from_uri(&uri.parse().unwrap()).unwrap();
+        tickets::Entity::find()
+            .filter(filter.to_condition())
+            .build(DbBackend::MySql)
+            .to_string()
+    }
+
+    #[test]
+    fn test_status_and_priority_filter() {
+        let sql = to_sql("/tickets?status=Started,Reviewing&priority=High,Critical");
+        assert!(sql.contains("`tickets`.`status` IN ('Started', 'Reviewing')"));
+        assert!(sql.contains("`tickets`.`priority` IN ('High', 'Critical')"));
+    }
+
+    #[test]
+    fn test_user_filter() {
+        let sql = to_sql(
+            "/tickets?assignee=67e55044-10b1-426f-9247-bb680e5fe0c8\
+             &reporter=7f6c8d3a-2b1e-4c5d-9a8b-1c2d3e4f5a6b",
+        );
+        assert!(sql.contains("`tickets`.`user_id` IN (SELECT `users`.`id` FROM `users`"));
+        assert!(sql.contains("`tickets`.`reporter_id` IN (SELECT `users`.`id` FROM `users`"));
+        assert!(sql.contains("67e55044-10b1-426f-9247-bb680e5fe0c8"));
+        assert!(sql.contains("7f6c8d3a-2b1e-4c5d-9a8b-1c2d3e4f5a6b"));
+        assert!(to_sql("/tickets?unassigned=true").contains("`tickets`.`user_id` IS NULL"));
+        assert!(!to_sql("/tickets?unassigned=false").contains("`user_id` IS NULL"));
+    }
+
+    #[test]
+    fn test_date_range_filter() {
+        let sql = to_sql(
+            "/tickets?created_from=2024-05-01T00:00:00Z&created_to=2024-06-01T00:00:00Z\
+             &updated_from=2024-05-10T00:00:00Z&updated_to=2024-05-11T00:00:00Z",
+        );
+        assert!(sql.contains(
+            "`tickets`.`created_at` >= '2024-05-01 00:00:00.000000 +00:00' \
+             AND `tickets`.`created_at` < '2024-06-01 00:00:00.000000 +00:00'"
+        ));
+        assert!(sql.contains(
+            "`tickets`.`updated_at` >= '2024-05-10 00:00:00.000000 +00:00' \
+             AND `tickets`.`updated_at` < '2024-05-11 00:00:00.000000 +00:00'"
+        ));
+    }
+
+    #[test]
+    fn test_project_filter() {
+        let sql = to_sql("/tickets?project_id=3&sprint_id=5");
+        assert!(sql.contains("`tickets`.`project_id` = 3 AND `tickets`.`sprint_id` = 5"));
+        assert!(to_sql("/tickets?without_sprint=true").contains("`tickets`.`sprint_id` IS NULL"));
+        assert!(!to_sql("/tickets?without_project=").contains("`project_id` IS NULL"));
+        let sql = tickets::Entity::find()
+            .filter(TicketFilter::unassigned_preset().to_condition())
+            .build(DbBackend::MySql)
+            .to_string();
+        assert!(
+            sql.ends_with("WHERE `tickets`.`project_id` IS NULL"),
+            "{}",
+            sql
+        );
+    }
+
+    #[test]
+    fn test_count_filter() {
+        // The total of a page is counted with the condition the tickets are listed with
+        let uri = "/tickets?status=Created&priority=Low&unassigned=true&without_project=true";
+        let Query(filter) = Query::<TicketFilter>::try_from_uri(&uri.parse().unwrap()).unwrap();
+        let condition = filter.to_condition();
+        let list = tickets::Entity::find_active()
+            .filter(condition.clone())
+            .build(DbBackend::MySql)
+            .to_string();
+        let count = tickets::Entity::find_active()
+            .select_only()
+            .column_as(tickets::Column::Id.count(), "count")
+            .filter(condition)
+            .build(DbBackend::MySql)
+            .to_string();
+        let where_clause = |sql: &str| sql.split_once(" WHERE ").map(|(_, w)| w.to_owned());
+        assert!(list.contains("`tickets`.`status` IN ('Created')"));
+        assert_eq!(where_clause(&list), where_clause(&count));
+    }
+
+    #[test]
+    fn test_field_filter() {
+        let uri = "/tickets?field=4:Production,4:Staging,7:Acme"
+            .parse()
+            .unwrap();
+        let Query(filter) = Query::<TicketFilter>::try_from_uri(&uri).unwrap();
+        let sql = tickets::Entity::find()
+            .filter(filter.to_condition())
+            .build(DbBackend::MySql)
+            .to_string();
+        assert!(sql.contains(
+            "`field_id` = 4 AND `ticket_field_values`.`value` IN ('Production', 'Staging')"
+        ));
+        assert!(sql.contains("`field_id` = 7 AND `ticket_field_values`.`value` IN ('Acme')"));
+        assert!(FieldFilter::from_str("Production").is_err());
+    }
 }
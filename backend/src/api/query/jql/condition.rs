@@ -0,0 +1,369 @@
+use super::{
+    error::QueryError,
+    lexer::Operator,
+    parser::{Clause, QueryField, QueryValue, TicketQuery},
+};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use entity::{
+    sea_orm_active_enums::Priority,
+    tickets,
+    users::{self, Entity as User},
+};
+use sea_orm::{
+    sea_query::SimpleExpr, ColumnTrait, Condition, QueryFilter, QuerySelect, QueryTrait,
+};
+use shared::validation::ticket_validation::TicketStatus;
+use strum::IntoEnumIterator;
+use uuid::Uuid;
+
+const PRIORITIES: [Priority; 4] = [
+    Priority::Low,
+    Priority::Normal,
+    Priority::High,
+    Priority::Critical,
+];
+
+/// Values a query is evaluated against, such as the user `me` refers to.
+pub struct QueryContext {
+    pub user_id: u64,
+    pub now: DateTime<Utc>,
+}
+
+impl QueryContext {
+    pub fn new(user_id: u64) -> Self {
+        Self {
+            user_id,
+            now: Utc::now(),
+        }
+    }
+}
+
+impl TicketQuery {
+    /// Compiles the query into a condition on the tickets table.
+    pub fn to_condition(&self, context: &QueryContext) -> Result<Condition, QueryError> {
+        self.clauses
+            .iter()
+            .try_fold(Condition::all(), |condition, clause| {
+                Ok(condition.add(clause_condition(clause, context)?))
+            })
+    }
+}
+
+fn clause_condition(clause: &Clause, context: &QueryContext) -> Result<Condition, QueryError> {
+    match clause {
+        Clause::Text(QueryValue { text, .. }) => Ok(Condition::any()
+            .add(tickets::Column::Title.contains(text))
+            .add(tickets::Column::Description.contains(text))),
+        Clause::Field {
+            field,
+            operator,
+            values,
+            ..
+        } => match field {
+            QueryField::Project => project_condition(*operator, values),
+            QueryField::Status => status_condition(*operator, values),
+            QueryField::Priority => priority_condition(*operator, values),
+            QueryField::Assignee => assignee_condition(*operator, values, context),
+            QueryField::Created => created_condition(*operator, &values[0], context),
+        },
+    }
+}
+
+fn project_condition(operator: Operator, values: &[QueryValue]) -> Result<Condition, QueryError> {
+    let mut ids = vec![];
+    let mut none = false;
+    for value in values {
+        if value.text.eq_ignore_ascii_case("none") {
+            none = true;
+        } else {
+            ids.push(value.text.parse::<u64>().map_err(|_| {
+                QueryError::new(
+                    value.position,
+                    format!("Invalid project id '{}'", value.text),
+                )
+            })?);
+        }
+    }
+    Ok(nullable_membership(
+        operator,
+        tickets::Column::ProjectId,
+        (!ids.is_empty()).then(|| Condition::all().add(tickets::Column::ProjectId.is_in(ids))),
+        none,
+    ))
+}
+
+fn status_condition(operator: Operator, values: &[QueryValue]) -> Result<Condition, QueryError> {
+    let statuses = values
+        .iter()
+        .map(|value| {
+            TicketStatus::iter()
+                .find(|status| status.to_string().eq_ignore_ascii_case(&value.text))
+                .map(|status| status.to_string())
+                .ok_or_else(|| {
+                    QueryError::new(
+                        value.position,
+                        format!(
+                            "Unknown status '{}', expected one of: {}",
+                            value.text,
+                            TicketStatus::iter()
+                                .map(|s| s.to_string())
+                                .collect::<Vec<String>>()
+                                .join(", ")
+                        ),
+                    )
+                })
+        })
+        .collect::<Result<Vec<String>, QueryError>>()?;
+    Ok(match operator {
+        Operator::NotEq => Condition::all().add(tickets::Column::Status.is_not_in(statuses)),
+        _ => Condition::all().add(tickets::Column::Status.is_in(statuses)),
+    })
+}
+
+fn priority_condition(operator: Operator, values: &[QueryValue]) -> Result<Condition, QueryError> {
+    let ranks = values
+        .iter()
+        .map(|value| {
+            PRIORITIES
+                .iter()
+                .position(|p| priority_name(p).eq_ignore_ascii_case(&value.text))
+                .ok_or_else(|| {
+                    QueryError::new(
+                        value.position,
+                        format!(
+                            "Unknown priority '{}', expected one of: Low, Normal, High, Critical",
+                            value.text
+                        ),
+                    )
+                })
+        })
+        .collect::<Result<Vec<usize>, QueryError>>()?;
+    let matching = |predicate: &dyn Fn(usize) -> bool| -> Vec<Priority> {
+        PRIORITIES
+            .iter()
+            .enumerate()
+            .filter(|(rank, _)| predicate(*rank))
+            .map(|(_, p)| p.clone())
+            .collect()
+    };
+    let rank = ranks[0];
+    let priorities = match operator {
+        Operator::Eq | Operator::NotEq => matching(&|r| ranks.contains(&r)),
+        Operator::Gt => matching(&|r| r > rank),
+        Operator::Gte => matching(&|r| r >= rank),
+        Operator::Lt => matching(&|r| r < rank),
+        Operator::Lte => matching(&|r| r <= rank),
+    };
+    Ok(match operator {
+        Operator::NotEq => Condition::any()
+            .add(tickets::Column::Priority.is_not_in(priorities))
+            .add(tickets::Column::Priority.is_null()),
+        _ => Condition::all().add(tickets::Column::Priority.is_in(priorities)),
+    })
+}
+
+fn assignee_condition(
+    operator: Operator,
+    values: &[QueryValue],
+    context: &QueryContext,
+) -> Result<Condition, QueryError> {
+    let mut none = false;
+    let mut matches = Condition::any();
+    for value in values {
+        let text = value.text.as_str();
+        if text.eq_ignore_ascii_case("none") {
+            none = true;
+        } else if text.eq_ignore_ascii_case("me") {
+            matches = matches.add(tickets::Column::UserId.eq(context.user_id));
+        } else if let Ok(public_id) = Uuid::parse_str(text) {
+            matches = matches.add(user_subquery(users::Column::PublicId.eq(public_id)));
+        } else if text.contains('@') {
+            matches = matches.add(user_subquery(users::Column::Username.eq(text)));
+        } else {
+            return Err(QueryError::new(
+                value.position,
+                format!(
+                    "Invalid assignee '{}', expected me, none, an email address or a user id",
+                    text
+                ),
+            ));
+        }
+    }
+    Ok(nullable_membership(
+        operator,
+        tickets::Column::UserId,
+        (!matches.is_empty()).then_some(matches),
+        none,
+    ))
+}
+
+fn user_subquery(filter: SimpleExpr) -> SimpleExpr {
+    tickets::Column::UserId.in_subquery(
+        User::find_active()
+            .select_only()
+            .column(users::Column::Id)
+            .filter(filter)
+            .into_query(),
+    )
+}
+
+fn created_condition(
+    operator: Operator,
+    value: &QueryValue,
+    context: &QueryContext,
+) -> Result<Condition, QueryError> {
+    let column = tickets::Column::CreatedAt;
+    if let Some(since) = parse_relative(&value.text, context.now) {
+        // A relative date with `:` reads as "within the last ..."
+        return Ok(Condition::all().add(match operator {
+            Operator::Eq | Operator::Gte => column.gte(since),
+            Operator::Gt => column.gt(since),
+            Operator::Lt => column.lt(since),
+            Operator::Lte => column.lte(since),
+            Operator::NotEq => column.lt(since),
+        }));
+    }
+    let day = NaiveDate::parse_from_str(&value.text, "%Y-%m-%d").map_err(|_| {
+        QueryError::new(
+            value.position,
+            format!(
+                "Invalid date '{}', expected YYYY-MM-DD or a relative date like -7d",
+                value.text
+            ),
+        )
+    })?;
+    let start = day.and_hms_opt(0, 0, 0).unwrap().and_utc();
+    let end = start + Duration::days(1);
+    Ok(match operator {
+        Operator::Eq => Condition::all().add(column.gte(start)).add(column.lt(end)),
+        Operator::NotEq => Condition::any().add(column.lt(start)).add(column.gte(end)),
+        Operator::Gt => Condition::all().add(column.gte(end)),
+        Operator::Gte => Condition::all().add(column.gte(start)),
+        Operator::Lt => Condition::all().add(column.lt(start)),
+        Operator::Lte => Condition::all().add(column.lt(end)),
+    })
+}
+
+/// Parses relative dates like `-7d`, `-2w` or `-12h` into an instant before `now`. The
+/// leading `-` is required and the count is a plain number, anything else is no relative date.
+fn parse_relative(text: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let amount = text.strip_prefix('-')?;
+    let unit = amount.chars().last()?;
+    let count = &amount[..amount.len() - unit.len_utf8()];
+    if count.is_empty() || !count.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let count = count.parse::<i64>().ok()?;
+    let duration = match unit {
+        'h' => Duration::hours(count),
+        'd' => Duration::days(count),
+        'w' => Duration::weeks(count),
+        _ => return None,
+    };
+    Some(now - duration)
+}
+
+/// Membership test on a nullable column where `none` stands for NULL.
+fn nullable_membership<C: ColumnTrait>(
+    operator: Operator,
+    column: C,
+    matches: Option<Condition>,
+    none: bool,
+) -> Condition {
+    match operator {
+        Operator::NotEq => {
+            let mut condition = Condition::all();
+            if let Some(matches) = matches {
+                condition =
+                    condition.add(Condition::any().add(matches.not()).add(column.is_null()));
+            }
+            if none {
+                condition = condition.add(column.is_not_null());
+            }
+            condition
+        }
+        _ => {
+            let mut condition = Condition::any();
+            if let Some(matches) = matches {
+                condition = condition.add(matches);
+            }
+            if none {
+                condition = condition.add(column.is_null());
+            }
+            condition
+        }
+    }
+}
+
+fn priority_name(priority: &Priority) -> &'static str {
+    match priority {
+        Priority::Low => "Low",
+        Priority::Normal => "Normal",
+        Priority::High => "High",
+        Priority::Critical => "Critical",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use sea_orm::{DbBackend, EntityTrait};
+    use std::str::FromStr;
+
+    fn to_sql(source: &str) -> String {
+        let query = TicketQuery::from_str(source).unwrap();
+        let context = QueryContext {
+            user_id: 3,
+            now: Utc.with_ymd_and_hms(2024, 5, 10, 12, 0, 0).unwrap(),
+        };
+        tickets::Entity::find()
+            .filter(query.to_condition(&context).unwrap())
+            .build(DbBackend::MySql)
+            .to_string()
+    }
+
+    #[test]
+    fn test_condition_status_and_priority() {
+        let sql = to_sql("status:started,Reviewing priority>=High assignee:me");
+        assert!(sql.contains("`tickets`.`status` IN ('Started', 'Reviewing')"));
+        assert!(sql.contains("`tickets`.`priority` IN ('High', 'Critical')"));
+        assert!(sql.contains("`tickets`.`user_id` = 3"));
+    }
+
+    #[test]
+    fn test_condition_relative_date() {
+        let sql = to_sql("created>-7d");
+        assert!(sql.contains("`tickets`.`created_at` > '2024-05-03 12:00:00"));
+    }
+
+    #[test]
+    fn test_parse_relative() {
+        let now = Utc.with_ymd_and_hms(2024, 5, 10, 12, 0, 0).unwrap();
+        assert_eq!(
+            parse_relative("-7d", now),
+            Some(Utc.with_ymd_and_hms(2024, 5, 3, 12, 0, 0).unwrap())
+        );
+        assert_eq!(
+            parse_relative("-2w", now),
+            Some(Utc.with_ymd_and_hms(2024, 4, 26, 12, 0, 0).unwrap())
+        );
+        for text in ["7d", "+7d", "--7d", "-+7d", "-d", "-7x", "-"] {
+            assert_eq!(parse_relative(text, now), None, "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_condition_invalid_relative_date() {
+        let query = TicketQuery::from_str("created>7d").unwrap();
+        let error = query.to_condition(&QueryContext::new(1)).unwrap_err();
+        assert_eq!(error.position, 8);
+    }
+
+    #[test]
+    fn test_condition_invalid_value_position() {
+        let query = TicketQuery::from_str("project:12 status:Finished").unwrap();
+        let error = query.to_condition(&QueryContext::new(1)).unwrap_err();
+        assert_eq!(error.position, 18);
+    }
+}
@@ -0,0 +1,24 @@
+use std::fmt::Display;
+use thiserror::Error;
+
+#[derive(Clone, Debug, Eq, Error, PartialEq)]
+pub struct QueryError {
+    /// Zero based character position of the offending token.
+    pub position: usize,
+    pub message: String,
+}
+
+impl QueryError {
+    pub fn new(position: usize, message: impl Into<String>) -> Self {
+        Self {
+            position,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (at position {})", self.message, self.position + 1)
+    }
+}
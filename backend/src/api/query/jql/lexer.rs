@@ -0,0 +1,176 @@
+use super::error::QueryError;
+use std::{iter::Peekable, str::CharIndices};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Operator {
+    Eq,
+    NotEq,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+}
+
+impl Operator {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operator::Eq => ":",
+            Operator::NotEq => "!=",
+            Operator::Gt => ">",
+            Operator::Gte => ">=",
+            Operator::Lt => "<",
+            Operator::Lte => "<=",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TokenKind {
+    Word(String),
+    Quoted(String),
+    Operator(Operator),
+    Comma,
+}
+
+/// A token along with the (zero based) character position it starts at.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub position: usize,
+}
+
+pub struct Lexer<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    source: &'a str,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self {
+            chars: source.char_indices().peekable(),
+            source,
+        }
+    }
+
+    pub fn tokenize(mut self) -> Result<Vec<Token>, QueryError> {
+        let mut tokens = vec![];
+        while let Some(&(offset, c)) = self.chars.peek() {
+            let position = self.position_of(offset);
+            let kind = match c {
+                c if c.is_whitespace() => {
+                    self.chars.next();
+                    continue;
+                }
+                ',' => {
+                    self.chars.next();
+                    TokenKind::Comma
+                }
+                '"' => {
+                    self.chars.next();
+                    TokenKind::Quoted(self.quoted(position)?)
+                }
+                ':' | '=' | '!' | '<' | '>' => TokenKind::Operator(self.operator(position)?),
+                _ => TokenKind::Word(self.word()),
+            };
+            tokens.push(Token { kind, position });
+        }
+        Ok(tokens)
+    }
+
+    fn quoted(&mut self, start: usize) -> Result<String, QueryError> {
+        let mut value = String::new();
+        loop {
+            match self.chars.next() {
+                Some((_, '"')) => return Ok(value),
+                Some((_, '\\')) => match self.chars.next() {
+                    Some((_, escaped)) => value.push(escaped),
+                    None => break,
+                },
+                Some((_, c)) => value.push(c),
+                None => break,
+            }
+        }
+        Err(QueryError::new(start, "Unterminated quoted text"))
+    }
+
+    fn operator(&mut self, position: usize) -> Result<Operator, QueryError> {
+        let (_, first) = self.chars.next().unwrap();
+        let followed_by_eq = matches!(self.chars.peek(), Some((_, '=')));
+        let operator = match (first, followed_by_eq) {
+            (':', _) | ('=', _) => Operator::Eq,
+            ('!', true) => Operator::NotEq,
+            ('>', true) => Operator::Gte,
+            ('>', false) => Operator::Gt,
+            ('<', true) => Operator::Lte,
+            ('<', false) => Operator::Lt,
+            _ => return Err(QueryError::new(position, "Expected '=' after '!'")),
+        };
+        if followed_by_eq && first != ':' && first != '=' {
+            self.chars.next();
+        }
+        Ok(operator)
+    }
+
+    fn word(&mut self) -> String {
+        let mut value = String::new();
+        while let Some(&(_, c)) = self.chars.peek() {
+            if c.is_whitespace() || matches!(c, ',' | '"' | ':' | '=' | '!' | '<' | '>') {
+                break;
+            }
+            value.push(c);
+            self.chars.next();
+        }
+        value
+    }
+
+    fn position_of(&self, offset: usize) -> usize {
+        self.source[..offset].chars().count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(source: &str) -> Vec<TokenKind> {
+        Lexer::new(source)
+            .tokenize()
+            .unwrap()
+            .into_iter()
+            .map(|t| t.kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_field_with_list() {
+        assert_eq!(
+            kinds("status:Started,Reviewing"),
+            vec![
+                TokenKind::Word(String::from("status")),
+                TokenKind::Operator(Operator::Eq),
+                TokenKind::Word(String::from("Started")),
+                TokenKind::Comma,
+                TokenKind::Word(String::from("Reviewing")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_comparison_and_quoted() {
+        assert_eq!(
+            kinds("priority>=High \"login bug\""),
+            vec![
+                TokenKind::Word(String::from("priority")),
+                TokenKind::Operator(Operator::Gte),
+                TokenKind::Word(String::from("High")),
+                TokenKind::Quoted(String::from("login bug")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_reports_position() {
+        let error = Lexer::new("title:\"open").tokenize().unwrap_err();
+        assert_eq!(error.position, 6);
+    }
+}
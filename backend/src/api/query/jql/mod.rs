@@ -0,0 +1,4 @@
+pub mod condition;
+pub mod error;
+pub mod lexer;
+pub mod parser;
@@ -0,0 +1,236 @@
+use super::{
+    error::QueryError,
+    lexer::{Lexer, Operator, Token, TokenKind},
+};
+use std::str::FromStr;
+use strum::{Display, EnumIter, EnumString};
+
+#[derive(Clone, Copy, Debug, Display, EnumIter, EnumString, Eq, PartialEq)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum QueryField {
+    Project,
+    Status,
+    Priority,
+    Assignee,
+    Created,
+}
+
+impl QueryField {
+    fn is_ordered(&self) -> bool {
+        matches!(self, QueryField::Priority | QueryField::Created)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueryValue {
+    pub text: String,
+    pub position: usize,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Clause {
+    Text(QueryValue),
+    Field {
+        field: QueryField,
+        operator: Operator,
+        values: Vec<QueryValue>,
+        position: usize,
+    },
+}
+
+/// Conjunction of all the clauses of a ticket query expression.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TicketQuery {
+    pub clauses: Vec<Clause>,
+}
+
+impl FromStr for TicketQuery {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Parser::new(Lexer::new(s).tokenize()?, s.chars().count()).parse()
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn new(tokens: Vec<Token>, end: usize) -> Self {
+        Self {
+            tokens,
+            index: 0,
+            end,
+        }
+    }
+
+    fn parse(mut self) -> Result<TicketQuery, QueryError> {
+        let mut clauses = vec![];
+        while let Some(token) = self.next() {
+            let clause = match token.kind {
+                TokenKind::Quoted(text) => Clause::Text(QueryValue {
+                    text,
+                    position: token.position,
+                }),
+                TokenKind::Word(word) => match self.peek_operator() {
+                    Some(operator) => {
+                        self.index += 1;
+                        self.field_clause(word, operator, token.position)?
+                    }
+                    None => Clause::Text(QueryValue {
+                        text: word,
+                        position: token.position,
+                    }),
+                },
+                TokenKind::Operator(operator) => {
+                    return Err(QueryError::new(
+                        token.position,
+                        format!("Unexpected operator '{}'", operator.as_str()),
+                    ))
+                }
+                TokenKind::Comma => return Err(QueryError::new(token.position, "Unexpected ','")),
+            };
+            clauses.push(clause);
+        }
+        Ok(TicketQuery { clauses })
+    }
+
+    fn field_clause(
+        &mut self,
+        name: String,
+        operator: Operator,
+        position: usize,
+    ) -> Result<Clause, QueryError> {
+        let field = QueryField::from_str(name.as_str()).map_err(|_| {
+            QueryError::new(
+                position,
+                format!(
+                    "Unknown field '{}', expected one of: project, status, priority, assignee, created",
+                    name
+                ),
+            )
+        })?;
+        if !field.is_ordered() && !matches!(operator, Operator::Eq | Operator::NotEq) {
+            return Err(QueryError::new(
+                position,
+                format!(
+                    "Field '{}' does not support the '{}' operator",
+                    field,
+                    operator.as_str()
+                ),
+            ));
+        }
+        let mut values = vec![self.value(operator)?];
+        while self.peek_comma() {
+            self.index += 1;
+            values.push(self.value(operator)?);
+        }
+        if values.len() > 1 && !matches!(operator, Operator::Eq | Operator::NotEq) {
+            return Err(QueryError::new(
+                values[1].position,
+                format!(
+                    "Operator '{}' accepts a single value only",
+                    operator.as_str()
+                ),
+            ));
+        }
+        Ok(Clause::Field {
+            field,
+            operator,
+            values,
+            position,
+        })
+    }
+
+    fn value(&mut self, operator: Operator) -> Result<QueryValue, QueryError> {
+        match self.next() {
+            Some(Token {
+                kind: TokenKind::Word(text) | TokenKind::Quoted(text),
+                position,
+            }) => Ok(QueryValue { text, position }),
+            Some(Token { position, .. }) => Err(QueryError::new(
+                position,
+                format!("Expected a value after '{}'", operator.as_str()),
+            )),
+            None => Err(QueryError::new(
+                self.end,
+                format!("Expected a value after '{}'", operator.as_str()),
+            )),
+        }
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.index).cloned();
+        self.index += 1;
+        token
+    }
+
+    fn peek_operator(&self) -> Option<Operator> {
+        match self.tokens.get(self.index) {
+            Some(Token {
+                kind: TokenKind::Operator(operator),
+                ..
+            }) => Some(*operator),
+            _ => None,
+        }
+    }
+
+    fn peek_comma(&self) -> bool {
+        matches!(
+            self.tokens.get(self.index),
+            Some(Token {
+                kind: TokenKind::Comma,
+                ..
+            })
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_full_expression() {
+        let query = TicketQuery::from_str(
+            "project:12 status:Started,Reviewing priority>=High assignee:me created>-7d \"login bug\"",
+        )
+        .unwrap();
+        assert_eq!(query.clauses.len(), 6);
+        assert!(matches!(
+            &query.clauses[1],
+            Clause::Field { field: QueryField::Status, operator: Operator::Eq, values, .. } if values.len() == 2
+        ));
+        assert!(matches!(
+            &query.clauses[2],
+            Clause::Field {
+                field: QueryField::Priority,
+                operator: Operator::Gte,
+                ..
+            }
+        ));
+        assert!(
+            matches!(&query.clauses[5], Clause::Text(QueryValue { text, .. }) if text == "login bug")
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_field() {
+        let error = TicketQuery::from_str("status:Done prio:High").unwrap_err();
+        assert_eq!(error.position, 12);
+    }
+
+    #[test]
+    fn test_parse_missing_value() {
+        let error = TicketQuery::from_str("status:").unwrap_err();
+        assert_eq!(error.position, 7);
+    }
+
+    #[test]
+    fn test_parse_unsupported_operator() {
+        assert!(TicketQuery::from_str("status>Started").is_err());
+    }
+}
@@ -1,3 +1,4 @@
 pub mod filters;
+pub mod jql;
 pub mod ordering;
 pub mod redirect;
@@ -0,0 +1,65 @@
+use crate::api::{
+    error::ApiError, project_check::check_project, services::board_service::BoardService,
+    validated_json::ValidatedJson,
+};
+use axum::{
+    extract::{Json, Path},
+    routing::{get, put},
+    Extension, Router,
+};
+use axum_extra::extract::WithRejection;
+use entity::board_columns::{self, Entity as BoardColumn};
+use sea_orm::{ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, Set, TransactionTrait};
+use shared::dtos::board_dto::BoardDto;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/projects/{id}/board", get(get_board))
+        .route("/projects/{id}/board", put(put_board))
+}
+
+async fn get_board(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+) -> Result<Json<BoardDto>, ApiError> {
+    check_project(&*db, project_id).await?;
+    Ok(Json(BoardService::board(&*db, project_id).await?))
+}
+
+/// Replaces the columns of the board, they are stored in the given order.
+async fn put_board(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+    WithRejection(ValidatedJson(board), _): WithRejection<ValidatedJson<BoardDto>, ApiError>,
+) -> Result<Json<BoardDto>, ApiError> {
+    check_project(&*db, project_id).await?;
+    let txn = db.begin().await?;
+    BoardColumn::delete_many()
+        .filter(board_columns::Column::ProjectId.eq(project_id))
+        .exec(&txn)
+        .await?;
+    let columns = board
+        .columns
+        .iter()
+        .enumerate()
+        .map(|(position, column)| board_columns::ActiveModel {
+            project_id: Set(project_id),
+            name: Set(column.name.to_owned()),
+            statuses: Set(column
+                .statuses
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>()
+                .join(",")),
+            position: Set(position as u32),
+            hidden: Set(column.hidden as i8),
+            wip_limit: Set(column.wip_limit),
+            wip_enforced: Set(column.wip_enforced as i8),
+            ..Default::default()
+        })
+        .collect::<Vec<board_columns::ActiveModel>>();
+    BoardColumn::insert_many(columns).exec(&txn).await?;
+    let board = BoardService::board(&txn, project_id).await?;
+    txn.commit().await?;
+    Ok(Json(board))
+}
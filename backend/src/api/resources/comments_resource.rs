@@ -2,15 +2,25 @@ use axum::{
     extract::{Json, Path},
     http::StatusCode,
     response::IntoResponse,
-    routing::{delete, get, post, put},
+    routing::{delete, get, patch, post, put},
     Extension, Router,
 };
 use axum_extra::extract::WithRejection;
-use entity::{comments, comments::Entity as Comment};
-use sea_orm::{ActiveModelTrait, DatabaseConnection, DeleteResult, EntityTrait, Set};
+use entity::{comments, comments::Entity as Comment, tickets, users};
+use redis::Client;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, DbErr, DeleteResult,
+    EntityTrait, JoinType, QueryFilter, QuerySelect, RelationTrait, Set,
+};
 use serde::{Deserialize, Serialize};
+use shared::dtos::project_member_dto::ProjectRole;
 
-use crate::api::error::{ApiError, JsonError};
+use crate::api::{
+    error::{ApiError, JsonError},
+    etag::{version_conflict, IfMatch, Versioned},
+    merge_patch::MergePatch,
+    services::{membership_service::MembershipService, watcher_service::WatcherService},
+};
 
 #[allow(dead_code)]
 #[derive(Serialize, Deserialize)]
@@ -26,34 +36,58 @@ pub fn router() -> Router {
         .route("/comments", get(get_comments))
         .route("/comments/{id}", get(get_comment))
         .route("/comments/{id}", put(put_comment))
+        .route("/comments/{id}", patch(patch_comment))
         .route("/comments/{id}", delete(delete_comment))
 }
 
+/// Comments on the tickets the user may see, the ones on tickets in the trash are left out.
 async fn get_comments(
     db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
 ) -> Result<Json<Vec<comments::Model>>, ApiError> {
-    let list = Comment::find().all(&*db).await?;
+    let list = Comment::find()
+        .join(JoinType::InnerJoin, comments::Relation::Tickets.def())
+        .filter(tickets::Column::DeletedAt.is_null())
+        .filter(MembershipService::visible_tickets(&auth_user))
+        .all(&*db)
+        .await?;
     Ok(Json(list))
 }
 
 async fn get_comment(
     db: Extension<DatabaseConnection>,
     WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
-) -> Result<Json<comments::Model>, ApiError> {
-    Comment::find_by_id(id).one(&*db).await?.map_or(
+) -> Result<Versioned<comments::Model>, ApiError> {
+    find_comment(&*db, id).await?.map_or(
         Err(ApiError::new(
             StatusCode::NOT_FOUND,
             String::from("Not found"),
         )),
-        |comment| Ok(Json(comment)),
+        |comment| Ok(Versioned(comment.version, comment)),
     )
 }
 
+/// The comment, unless its ticket is in the trash.
+async fn find_comment<C: ConnectionTrait>(
+    db: &C,
+    id: u64,
+) -> Result<Option<comments::Model>, DbErr> {
+    Comment::find_by_id(id)
+        .join(JoinType::InnerJoin, comments::Relation::Tickets.def())
+        .filter(tickets::Column::DeletedAt.is_null())
+        .one(db)
+        .await
+}
+
 async fn post_comment(
+    Extension(store): Extension<Client>,
     db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
     WithRejection(Json(model), _): WithRejection<Json<comments::Model>, ApiError>,
 ) -> Result<Json<comments::Model>, ApiError> {
     println!("New comment on ticket({})", model.ticket_id);
+    MembershipService::require_ticket(&*db, &auth_user, model.ticket_id, ProjectRole::Contributor)
+        .await?;
     let comment = comments::ActiveModel {
         text: Set(model.text.to_owned()),
         ticket_id: Set(model.ticket_id.to_owned()),
@@ -62,27 +96,64 @@ async fn post_comment(
     }
     .insert(&*db)
     .await?;
+    WatcherService::auto_watch(&store, &*db, &[comment.ticket_id], &[comment.user_id]).await?;
     Ok(Json(comment))
 }
 
 async fn put_comment(
     db: Extension<DatabaseConnection>,
     WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
+    if_match: IfMatch,
     WithRejection(Json(update), _): WithRejection<Json<comments::Model>, ApiError>,
-) -> Result<Json<comments::Model>, ApiError> {
-    let original_result = Comment::find_by_id(id).one(&*db).await?;
+) -> Result<Versioned<comments::Model>, ApiError> {
+    let original_result = find_comment(&*db, id).await?;
     match original_result {
         Some(original) => {
-            let updated = comments::ActiveModel {
+            let version = if_match.check(original.version)?;
+            let updated = Comment::update(comments::ActiveModel {
                 id: Set(original.id),
                 text: Set(update.text.to_owned()),
                 ticket_id: Set(original.ticket_id),
                 user_id: Set(original.user_id),
+                version: Set(version + 1),
                 ..Default::default()
-            }
-            .update(&*db)
-            .await?;
-            Ok(Json(updated))
+            })
+            .filter(comments::Column::Version.eq(version))
+            .exec(&*db)
+            .await
+            .map_err(version_conflict)?;
+            Ok(Versioned(updated.version, updated))
+        }
+        None => Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        )),
+    }
+}
+
+/// Only the text of a comment can be changed, the other fields of the patch are ignored.
+async fn patch_comment(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
+    if_match: IfMatch,
+    WithRejection(patch, _): WithRejection<MergePatch, ApiError>,
+) -> Result<Versioned<comments::Model>, ApiError> {
+    let original_result = find_comment(&*db, id).await?;
+    match original_result {
+        Some(original) => {
+            let version = if_match.check(original.version)?;
+            let update = patch.apply(&original)?;
+            let updated = Comment::update(comments::ActiveModel {
+                id: Set(original.id),
+                text: Set(update.text),
+                version: Set(version + 1),
+                ..Default::default()
+            })
+            .filter(comments::Column::Version.eq(version))
+            .exec(&*db)
+            .await
+            .map_err(version_conflict)?;
+            Ok(Versioned(updated.version, updated))
         }
         None => Err(ApiError::new(
             StatusCode::NOT_FOUND,
@@ -0,0 +1,157 @@
+use crate::api::{
+    error::{ApiError, JsonError},
+    project_check::check_project,
+    services::custom_field_service::CustomFieldService,
+    validated_json::ValidatedJson,
+};
+use axum::{
+    extract::{Json, Path},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get, post, put},
+    Extension, Router,
+};
+use axum_extra::extract::WithRejection;
+use entity::{
+    custom_fields::{self, Entity as CustomField},
+    ticket_field_values::{self, Entity as TicketFieldValue},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryTrait, Set, TransactionTrait,
+};
+use shared::dtos::custom_field_dto::CustomFieldDto;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/projects/{id}/fields", get(get_fields))
+        .route("/projects/{id}/fields", post(post_field))
+        .route("/projects/{id}/fields/{field_id}", put(put_field))
+        .route("/projects/{id}/fields/{field_id}", delete(delete_field))
+}
+
+async fn get_fields(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+) -> Result<Json<Vec<CustomFieldDto>>, ApiError> {
+    check_project(&*db, project_id).await?;
+    Ok(Json(
+        CustomFieldService::definitions(&*db, project_id).await?,
+    ))
+}
+
+async fn post_field(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+    WithRejection(ValidatedJson(model), _): WithRejection<ValidatedJson<CustomFieldDto>, ApiError>,
+) -> Result<Json<CustomFieldDto>, ApiError> {
+    check_project(&*db, project_id).await?;
+    check_name(&*db, project_id, &model.name, None).await?;
+    let field = custom_fields::ActiveModel {
+        project_id: Set(project_id),
+        name: Set(model.name.to_owned()),
+        field_type: Set(model.field_type.to_string()),
+        options: Set(model.options.join(",")),
+        required: Set(model.required as i8),
+        ..Default::default()
+    }
+    .insert(&*db)
+    .await?;
+    Ok(Json(field.into()))
+}
+
+/// The type of a field cannot change, the stored values would not match it anymore. Values of
+/// removed options are dropped.
+async fn put_field(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path((project_id, field_id)), _): WithRejection<Path<(u64, u64)>, ApiError>,
+    WithRejection(ValidatedJson(update), _): WithRejection<ValidatedJson<CustomFieldDto>, ApiError>,
+) -> Result<Json<CustomFieldDto>, ApiError> {
+    let original = find_field(&*db, project_id, field_id).await?;
+    if original.field_type != update.field_type.to_string() {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            String::from("The type of a field cannot be changed"),
+        ));
+    }
+    check_name(&*db, project_id, &update.name, Some(field_id)).await?;
+
+    let txn = db.begin().await?;
+    let updated = custom_fields::ActiveModel {
+        id: Set(original.id),
+        project_id: Set(original.project_id),
+        name: Set(update.name.to_owned()),
+        field_type: Set(original.field_type),
+        options: Set(update.options.join(",")),
+        required: Set(update.required as i8),
+    }
+    .update(&txn)
+    .await?;
+    if update.field_type.has_options() {
+        TicketFieldValue::delete_many()
+            .filter(ticket_field_values::Column::FieldId.eq(field_id))
+            .filter(ticket_field_values::Column::Value.is_not_in(update.options.clone()))
+            .exec(&txn)
+            .await?;
+    }
+    txn.commit().await?;
+    Ok(Json(updated.into()))
+}
+
+/// Deletes the field together with its values.
+async fn delete_field(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path((project_id, field_id)), _): WithRejection<Path<(u64, u64)>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    find_field(&*db, project_id, field_id).await?;
+    let txn = db.begin().await?;
+    TicketFieldValue::delete_many()
+        .filter(ticket_field_values::Column::FieldId.eq(field_id))
+        .exec(&txn)
+        .await?;
+    let deleted = CustomField::delete_by_id(field_id).exec(&txn).await?;
+    txn.commit().await?;
+    Ok(JsonError::from((
+        StatusCode::NO_CONTENT,
+        format!("Deleted {}", deleted.rows_affected),
+    )))
+}
+
+async fn find_field<C: ConnectionTrait>(
+    db: &C,
+    project_id: u64,
+    field_id: u64,
+) -> Result<custom_fields::Model, ApiError> {
+    CustomField::find_by_id(field_id)
+        .filter(custom_fields::Column::ProjectId.eq(project_id))
+        .one(db)
+        .await?
+        .ok_or(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        ))
+}
+
+/// Names are unique within a project, they label the form inputs and table columns.
+async fn check_name<C: ConnectionTrait>(
+    db: &C,
+    project_id: u64,
+    name: &str,
+    field_id: Option<u64>,
+) -> Result<(), ApiError> {
+    let existing = CustomField::find()
+        .filter(custom_fields::Column::ProjectId.eq(project_id))
+        .filter(custom_fields::Column::Name.eq(name))
+        .apply_if(field_id, |query, id| {
+            query.filter(custom_fields::Column::Id.ne(id))
+        })
+        .one(db)
+        .await?;
+    match existing {
+        Some(_) => Err(ApiError::new(
+            StatusCode::CONFLICT,
+            String::from("Field name already in use"),
+        )),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,105 @@
+use crate::api::{
+    auth_utils::require_role, consts::IMPORT_DIR, error::ApiError, project_check::check_project,
+    services::import_service::ImportService, tasks::ticket_importer::TicketImporter,
+    validated_json::ValidatedJson,
+};
+use axum::{
+    extract::{Json, Path},
+    http::StatusCode,
+    routing::{get, post},
+    Extension, Router,
+};
+use axum_extra::extract::WithRejection;
+use chrono::Utc;
+use entity::{
+    import_jobs::{self, Entity as ImportJob},
+    users,
+};
+use fang::{AsyncQueue, AsyncQueueable, AsyncRunnable, NoTls};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder, Set,
+};
+use shared::{
+    dtos::import_dto::{ImportJobDto, ImportRequestDto, ImportState},
+    validation::user_validation::UserRole,
+};
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/projects/{id}/imports", get(get_imports))
+        .route("/projects/{id}/imports", post(post_import))
+        .route("/projects/{id}/imports/{job_id}", get(get_import))
+}
+
+/// Imports into the project, the latest first.
+async fn get_imports(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+) -> Result<Json<Vec<ImportJobDto>>, ApiError> {
+    check_project(&*db, project_id).await?;
+    let jobs = ImportJob::find()
+        .filter(import_jobs::Column::ProjectId.eq(project_id))
+        .order_by_desc(import_jobs::Column::Id)
+        .all(&*db)
+        .await?;
+    Ok(Json(jobs.into_iter().map(ImportJobDto::from).collect()))
+}
+
+/// Progress of the import.
+async fn get_import(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path((project_id, job_id)), _): WithRejection<Path<(u64, u64)>, ApiError>,
+) -> Result<Json<ImportJobDto>, ApiError> {
+    let job = ImportJob::find_by_id(job_id)
+        .filter(import_jobs::Column::ProjectId.eq(project_id))
+        .one(&*db)
+        .await?
+        .ok_or(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Import not found"),
+        ))?;
+    Ok(Json(job.into()))
+}
+
+/// Queues the import of an export file from the import directory of the server.
+async fn post_import(
+    db: Extension<DatabaseConnection>,
+    Extension(mut queue): Extension<AsyncQueue<NoTls>>,
+    Extension(auth_user): Extension<users::Model>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+    WithRejection(ValidatedJson(model), _): WithRejection<
+        ValidatedJson<ImportRequestDto>,
+        ApiError,
+    >,
+) -> Result<Json<ImportJobDto>, ApiError> {
+    require_role(&auth_user, UserRole::Manager)?;
+    check_project(&*db, project_id).await?;
+    let path = ImportService::resolve(std::path::Path::new(IMPORT_DIR.as_str()), &model.path)
+        .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e))?;
+    if !tokio::fs::metadata(&path)
+        .await
+        .is_ok_and(|metadata| metadata.is_file())
+    {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            format!("The import directory has no file '{}'.", model.path),
+        ));
+    }
+    let job = import_jobs::ActiveModel {
+        project_id: Set(project_id),
+        source: Set(model.source.to_string()),
+        path: Set(model.path.trim().to_owned()),
+        state: Set(ImportState::Queued.to_string()),
+        user_id: Set(Some(auth_user.id)),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .insert(&*db)
+    .await?;
+    let task = TicketImporter { job_id: job.id };
+    queue
+        .insert_task(&task as &dyn AsyncRunnable)
+        .await
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok(Json(job.into()))
+}
@@ -1,7 +1,19 @@
+pub mod board_resource;
 pub mod comments_resource;
+pub mod custom_fields_resource;
+pub mod imports_resource;
 pub mod preferences_resource;
+pub mod project_archive_resource;
+pub mod project_members_resource;
 pub mod projects_resource;
+pub mod recurring_tickets_resource;
+pub mod reports_resource;
+pub mod saved_views_resource;
+pub mod sla_resource;
+pub mod sprints_resource;
 pub mod ticket_attachments_resource;
 pub mod ticket_updates_resource;
 pub mod tickets_resource;
+pub mod trash_resource;
 pub mod users_resource;
+pub mod watchers_resource;
@@ -0,0 +1,143 @@
+use crate::api::{
+    auth_utils::require_role,
+    consts::BUCKET_NAME,
+    error::ApiError,
+    services::archive_service::{ArchiveService, ARCHIVE_VERSION},
+};
+use axum::{
+    body::Bytes,
+    extract::{Json, Path, Query},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    routing::{get, post},
+    Extension, Router,
+};
+use axum_extra::extract::WithRejection;
+use entity::{
+    projects::{self, Entity as Project},
+    users,
+};
+use object_store::aws::AmazonS3Builder;
+use sea_orm::{ColumnTrait, DatabaseConnection, QueryFilter, TransactionTrait};
+use serde::Deserialize;
+use shared::{dtos::project_archive_dto::ProjectRestoreDto, validation::user_validation::UserRole};
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/projects/{id}/archive", get(get_archive))
+        .route("/projects/archive", post(post_archive))
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RestoreOptions {
+    #[serde(default)]
+    dry_run: bool,
+    /// Key to restore the project under, by default the key it was exported with.
+    #[serde(default)]
+    key: Option<String>,
+}
+
+/// The project as a zip archive, to restore it on another instance or keep it as a backup.
+async fn get_archive(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    require_role(&auth_user, UserRole::Manager)?;
+    let project = Project::find_active()
+        .filter(projects::Column::Id.eq(project_id))
+        .one(&*db)
+        .await?
+        .ok_or(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Project not found"),
+        ))?;
+    let mut archive = ArchiveService::collect(&*db, &project).await?;
+    let files = match archive.attachments.is_empty() {
+        true => Vec::new(),
+        false => {
+            let bucket = AmazonS3Builder::from_env()
+                .with_bucket_name(BUCKET_NAME.clone())
+                .build()
+                .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+            ArchiveService::files(&bucket, &mut archive).await
+        }
+    };
+    for warning in archive.warnings.iter() {
+        tracing::warn!("Exporting project {}: {}", project.id, warning);
+    }
+    let zip = ArchiveService::write(&archive, &files)
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Ok((
+        [
+            (header::CONTENT_TYPE, String::from("application/zip")),
+            (
+                header::CONTENT_DISPOSITION,
+                format!(
+                    "attachment; filename=\"{}-v{}.zip\"",
+                    project.key, ARCHIVE_VERSION
+                ),
+            ),
+        ],
+        zip,
+    ))
+}
+
+/// Restores an archive as a new project. A dry run only reports the conflicts. Users are
+/// matched by username, the entries of users not found are attributed to the one restoring.
+async fn post_archive(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    Query(options): Query<RestoreOptions>,
+    body: Bytes,
+) -> Result<Json<ProjectRestoreDto>, ApiError> {
+    require_role(&auth_user, UserRole::Manager)?;
+    let (archive, mut zip) = ArchiveService::read(body.to_vec())
+        .map_err(|e| ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, e))?;
+    let key = options
+        .key
+        .map(|key| key.trim().to_uppercase())
+        .unwrap_or(archive.project.key.to_owned());
+    ArchiveService::check(&archive, &key)
+        .map_err(|e| ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, e))?;
+
+    let plan = ArchiveService::plan(&*db, &archive, &key).await?;
+    let mut report = ProjectRestoreDto {
+        dry_run: options.dry_run,
+        project_id: None,
+        key,
+        tickets: archive.tickets.len() as u32,
+        comments: archive.comments.len() as u32,
+        history: archive.history.len() as u32,
+        attachments: archive.attachments.len() as u32,
+        conflicts: plan.conflicts.clone(),
+    };
+    if options.dry_run {
+        return Ok(Json(report));
+    }
+    if plan.is_blocked() {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            String::from("Project key already in use"),
+        ));
+    }
+
+    let txn = db.begin().await?;
+    let restored = ArchiveService::restore(&txn, &archive, &plan, auth_user.id).await?;
+    txn.commit().await?;
+    let bucket = match archive.attachments.is_empty() {
+        true => None,
+        false => AmazonS3Builder::from_env()
+            .with_bucket_name(BUCKET_NAME.clone())
+            .build()
+            .map_err(|e| tracing::warn!("Amazon bucket could not be built. Reason: {}", e))
+            .ok(),
+    };
+    let (attachments, conflicts) =
+        ArchiveService::restore_attachments(&*db, bucket.as_ref(), &mut zip, &archive, &restored)
+            .await?;
+    report.project_id = Some(restored.project_id);
+    report.attachments = attachments;
+    report.conflicts.extend(conflicts);
+    Ok(Json(report))
+}
@@ -0,0 +1,148 @@
+use crate::api::{
+    error::ApiError, project_check::check_project, services::membership_service::MembershipService,
+};
+use axum::{
+    extract::{Json, Path},
+    http::StatusCode,
+    routing::{delete, get, put},
+    Extension, Router,
+};
+use axum_extra::extract::WithRejection;
+use entity::{
+    project_members::{self, Entity as ProjectMember},
+    users::{self, Entity as User},
+};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    TransactionTrait,
+};
+use shared::dtos::project_member_dto::{ProjectMemberDto, ProjectMemberRoleDto, ProjectRole};
+use std::str::FromStr;
+use uuid::Uuid;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/projects/{id}/members", get(get_members))
+        .route("/projects/{id}/members/me", get(get_own_role))
+        .route("/projects/{id}/members/{user_id}", put(put_member))
+        .route("/projects/{id}/members/{user_id}", delete(delete_member))
+}
+
+/// Members of the project by name. Members in the trash are left out.
+async fn get_members(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+) -> Result<Json<Vec<ProjectMemberDto>>, ApiError> {
+    check_project(&*db, project_id).await?;
+    let members = ProjectMember::find()
+        .find_also_related(User)
+        .filter(project_members::Column::ProjectId.eq(project_id))
+        .filter(users::Column::DeletedAt.is_null())
+        .order_by_asc(users::Column::Name)
+        .all(&*db)
+        .await?
+        .into_iter()
+        .filter_map(|(member, user)| user.map(|user| to_dto(&member, user)))
+        .collect();
+    Ok(Json(members))
+}
+
+/// The role of the signed in user in the project, managers administer every project.
+async fn get_own_role(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+) -> Result<Json<ProjectMemberRoleDto>, ApiError> {
+    check_project(&*db, project_id).await?;
+    let role =
+        MembershipService::require(&*db, &auth_user, project_id, ProjectRole::Viewer).await?;
+    Ok(Json(ProjectMemberRoleDto { role }))
+}
+
+/// Gives the user the role in the project, adding the user to the members if needed.
+async fn put_member(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path((project_id, user_id)), _): WithRejection<Path<(u64, Uuid)>, ApiError>,
+    WithRejection(Json(model), _): WithRejection<Json<ProjectMemberRoleDto>, ApiError>,
+) -> Result<Json<ProjectMemberDto>, ApiError> {
+    check_project(&*db, project_id).await?;
+    let user = find_user(&*db, user_id).await?;
+    let txn = db.begin().await?;
+    if model.role != ProjectRole::Admin {
+        check_last_admin(&txn, project_id, user.id).await?;
+    }
+    MembershipService::set_role(&txn, project_id, user.id, model.role).await?;
+    txn.commit().await?;
+    Ok(Json(ProjectMemberDto {
+        user_id: user.public_id,
+        name: user.name,
+        username: user.username.to_string(),
+        role: model.role,
+    }))
+}
+
+/// Removes the user from the members. Projects keep at least one admin.
+async fn delete_member(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path((project_id, user_id)), _): WithRejection<Path<(u64, Uuid)>, ApiError>,
+) -> Result<StatusCode, ApiError> {
+    check_project(&*db, project_id).await?;
+    let user = find_user(&*db, user_id).await?;
+    let txn = db.begin().await?;
+    check_last_admin(&txn, project_id, user.id).await?;
+    let result = ProjectMember::delete_many()
+        .filter(project_members::Column::ProjectId.eq(project_id))
+        .filter(project_members::Column::UserId.eq(user.id))
+        .exec(&txn)
+        .await?;
+    txn.commit().await?;
+    match result.rows_affected {
+        0 => Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Member not found"),
+        )),
+        _ => Ok(StatusCode::NO_CONTENT),
+    }
+}
+
+/// Fails if the user is the only admin of the project, nobody could manage its members then.
+async fn check_last_admin<C: ConnectionTrait>(
+    db: &C,
+    project_id: u64,
+    user_id: u64,
+) -> Result<(), ApiError> {
+    let is_admin = ProjectMember::find()
+        .filter(project_members::Column::ProjectId.eq(project_id))
+        .filter(project_members::Column::UserId.eq(user_id))
+        .filter(project_members::Column::Role.eq(ProjectRole::Admin.to_string()))
+        .one(db)
+        .await?
+        .is_some();
+    match is_admin && MembershipService::admins(db, project_id).await? <= 1 {
+        true => Err(ApiError::new(
+            StatusCode::CONFLICT,
+            String::from("The project needs at least one admin"),
+        )),
+        false => Ok(()),
+    }
+}
+
+fn to_dto(member: &project_members::Model, user: users::Model) -> ProjectMemberDto {
+    ProjectMemberDto {
+        user_id: user.public_id,
+        name: user.name,
+        username: user.username.to_string(),
+        role: ProjectRole::from_str(&member.role).unwrap_or(ProjectRole::Viewer),
+    }
+}
+
+async fn find_user<C: ConnectionTrait>(db: &C, user_id: Uuid) -> Result<users::Model, ApiError> {
+    User::find_active()
+        .filter(users::Column::PublicId.eq(user_id))
+        .one(db)
+        .await?
+        .ok_or(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("User not found"),
+        ))
+}
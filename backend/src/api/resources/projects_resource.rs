@@ -1,33 +1,51 @@
 use crate::api::{
     error::{ApiError, JsonError},
+    etag::{version_conflict, IfMatch, Versioned},
+    merge_patch::MergePatch,
     query::{
-        filters::pagination::{Pagination, TotalCount},
+        filters::{
+            pagination::{Pagination, TotalCount},
+            project_filter::ProjectFilter,
+            search::Search,
+        },
         ordering::Ordering,
     },
+    services::{
+        board_service::BoardService, custom_field_service::CustomFieldService,
+        label_service::LabelService, membership_service::MembershipService,
+        parent_service::ParentService, rank_service::RankService,
+        ticket_key_service::TicketKeyService, ticket_update_service::TicketUpdateService,
+    },
     validated_json::ValidatedJson,
 };
 use axum::{
     extract::{Json, Path, Query},
     http::StatusCode,
     response::IntoResponse,
-    routing::{delete, get, post, put},
+    routing::{delete, get, patch, post, put},
     Extension, Router,
 };
 use axum_extra::extract::WithRejection;
+use chrono::Utc;
 use entity::{
     projects, projects::Entity as Project, tickets, tickets::Entity as Ticket, users,
     users::Entity as User,
 };
 use migration::Expr;
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DeleteResult, EntityTrait,
-    QueryFilter, QueryOrder, QuerySelect, QueryTrait, RelationTrait, Set,
+    ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DatabaseConnection, DbErr,
+    EntityTrait, QueryFilter, QueryOrder, QuerySelect, QueryTrait, RelationTrait, Set,
+    TransactionTrait, UpdateResult,
 };
 use shared::dtos::{
     page::Page, project_dto::ProjectTickets as ProjectTicketsDto, ticket_dto::TicketQueryResult,
 };
 use shared::dtos::{project_dto::ProjectQueryResult, ticket_dto::TicketDto};
-use shared::{dtos::project_dto::ProjectDto, validation::ticket_validation::TicketStatus};
+use shared::{
+    dtos::{project_dto::ProjectDto, project_member_dto::ProjectRole},
+    validation::ticket_validation::{TicketStatus, TicketValidation},
+};
+use std::{collections::BTreeSet, str::FromStr};
 
 pub fn router() -> Router {
     Router::new()
@@ -37,23 +55,34 @@ pub fn router() -> Router {
         .route("/projects/{id}/tickets", get(get_project_tickets))
         .route("/projects/{id}/tickets", post(post_project_tickets))
         .route("/projects/{id}", put(put_project))
+        .route("/projects/{id}", patch(patch_project))
         .route("/projects/{id}", delete(delete_project))
 }
 
+/// The projects the user is a member of, searched by summary. The total counts the projects
+/// matching the filter and the search.
 async fn get_projects(
     db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    Query(filter): Query<ProjectFilter>,
+    Query(search): Query<Search>,
     Query(pagination): Query<Pagination>,
     Query(ordering): Query<Ordering>,
 ) -> Result<Json<Page<ProjectDto>>, ApiError> {
-    let total = Project::find()
+    let condition = filter
+        .to_condition()
+        .add(MembershipService::visible_projects(&auth_user))
+        .add_option(search.q.map(|q| projects::Column::Summary.contains(q)));
+    let total = Project::find_active()
         .select_only()
         .column_as(projects::Column::Id.count(), "count")
+        .filter(condition.clone())
         .into_model::<TotalCount>()
         .one(&*db)
         .await?
         .unwrap()
         .count;
-    let mut select = Project::find();
+    let mut select = Project::find_active().filter(condition);
     if let Some(sort) = ordering.sort.and_then(|s| sort_to_column(s.as_str())) {
         select = select.order_by::<projects::Column>(sort, ordering.order.0);
     }
@@ -86,34 +115,42 @@ fn sort_to_column(s: &str) -> Option<projects::Column> {
 async fn get_project(
     db: Extension<DatabaseConnection>,
     WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
-) -> Result<Json<ProjectDto>, ApiError> {
-    Project::find()
+) -> Result<Versioned<ProjectDto>, ApiError> {
+    find_project(&*db, id).await?.map_or(
+        Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        )),
+        |project| Ok(Versioned(project.version, project.into())),
+    )
+}
+
+async fn find_project<C: ConnectionTrait>(
+    db: &C,
+    id: u64,
+) -> Result<Option<ProjectQueryResult>, DbErr> {
+    Project::find_active()
         .filter(projects::Column::Id.eq(id))
         .columns([
             projects::Column::Id,
             projects::Column::Summary,
             projects::Column::Deadline,
             projects::Column::Active,
+            projects::Column::Key,
+            projects::Column::Version,
         ])
         .column_as(users::Column::PublicId, "user_id")
         .join(sea_orm::JoinType::LeftJoin, projects::Relation::Users.def())
         .into_model::<ProjectQueryResult>()
-        .one(&*db)
-        .await?
-        .map_or(
-            Err(ApiError::new(
-                StatusCode::NOT_FOUND,
-                String::from("Not found"),
-            )),
-            |project| Ok(Json(project.into())),
-        )
+        .one(db)
+        .await
 }
 
 async fn get_project_tickets(
     db: Extension<DatabaseConnection>,
     WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
 ) -> Result<Json<Vec<TicketDto>>, ApiError> {
-    let list = Ticket::find()
+    let list = Ticket::find_active()
         .filter(tickets::Column::ProjectId.eq(id))
         .columns([
             tickets::Column::Id,
@@ -122,42 +159,117 @@ async fn get_project_tickets(
             tickets::Column::ProjectId,
             tickets::Column::Status,
             tickets::Column::Priority,
+            tickets::Column::TicketKey,
+            tickets::Column::Version,
+            tickets::Column::SprintId,
+            tickets::Column::ParentId,
         ])
         .column_as(users::Column::PublicId, "user_id")
         .join(sea_orm::JoinType::LeftJoin, tickets::Relation::Users.def())
+        .order_by_asc(tickets::Column::BoardRank)
+        .order_by_asc(tickets::Column::Id)
         .into_model::<TicketQueryResult>()
         .all(&*db)
         .await?;
-    Ok(Json(
-        list.iter().map(|m| m.into()).collect::<Vec<TicketDto>>(),
-    ))
+    let mut list = list.iter().map(|m| m.into()).collect::<Vec<TicketDto>>();
+    CustomFieldService::attach(&*db, &mut list).await?;
+    LabelService::attach(&*db, &mut list).await?;
+    Ok(Json(list))
 }
 
+/// Moves the tickets to the project and selects them for work. Nothing changes if any of them
+/// cannot be selected, e.g. a closed ticket or one more than the limit of the column allows.
 async fn post_project_tickets(
     db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
     WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
     WithRejection(ValidatedJson(tickets_dto), _): WithRejection<
         ValidatedJson<ProjectTicketsDto>,
         ApiError,
     >,
 ) -> Result<Json<Vec<TicketDto>>, ApiError> {
-    Ticket::update_many()
-        .col_expr(
-            <entity::prelude::Tickets as EntityTrait>::Column::ProjectId,
-            Expr::value(id),
+    // Taking tickets away from another project is working on that project as well
+    for ticket_id in tickets_dto.tickets.iter() {
+        MembershipService::require_ticket(&*db, &auth_user, *ticket_id, ProjectRole::Contributor)
+            .await?;
+    }
+    let txn = db.begin().await?;
+    let listed = Ticket::find_active()
+        .filter(tickets::Column::Id.is_in(tickets_dto.tickets.clone()))
+        .order_by_asc(tickets::Column::Id)
+        .all(&txn)
+        .await?;
+    // Tickets coming from elsewhere get a key of this project, their old keys keep resolving
+    let moved: Vec<u64> = listed
+        .iter()
+        .filter(|ticket| ticket.project_id != Some(id))
+        .map(|ticket| ticket.id)
+        .collect();
+    // The tickets are selected one after the other, so that the limit of the column counts
+    // the ones selected before
+    for ticket in listed.iter() {
+        let previous = TicketStatus::from_str(ticket.status.as_str()).unwrap_or_default();
+        let reference = ticket.ticket_key.clone().unwrap_or(ticket.id.to_string());
+        TicketValidation::transition_validation(previous, TicketStatus::Selected, true)
+            .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, format!("{}: {}", reference, e)))?;
+        let on_board = ticket.project_id == Some(id);
+        BoardService::check_wip_limit(
+            &txn,
+            id,
+            ticket.id,
+            on_board.then_some(previous),
+            TicketStatus::Selected,
         )
-        .col_expr(
-            <entity::prelude::Tickets as EntityTrait>::Column::Status,
-            Expr::value(TicketStatus::Selected.to_string()),
+        .await?
+        .map_err(|e| ApiError::new(StatusCode::CONFLICT, format!("{}: {}", reference, e)))?;
+        Ticket::update_many()
+            .col_expr(tickets::Column::ProjectId, Expr::value(id))
+            .col_expr(
+                tickets::Column::Status,
+                Expr::value(TicketStatus::Selected.to_string()),
+            )
+            .col_expr(
+                tickets::Column::Version,
+                Expr::col(tickets::Column::Version).add(1),
+            )
+            .filter(tickets::Column::Id.eq(ticket.id))
+            .exec(&txn)
+            .await?;
+        TicketUpdateService::record(
+            &txn,
+            ticket.id,
+            previous,
+            TicketStatus::Selected,
+            auth_user.id,
         )
-        .filter(<entity::prelude::Tickets as EntityTrait>::Column::Id.is_in(tickets_dto.tickets))
-        .exec(&*db)
         .await?;
-    let list = Ticket::find()
+    }
+    // Sprints belong to the previous project as well
+    Ticket::update_many()
+        .col_expr(tickets::Column::SprintId, Expr::value(None::<u64>))
+        .filter(tickets::Column::Id.is_in(moved.clone()))
+        .exec(&txn)
+        .await?;
+    ParentService::detach(&txn, &moved).await?;
+    for ticket_id in moved {
+        TicketKeyService::assign(&txn, ticket_id, id).await?;
+        let board_rank = RankService::last(&txn, id).await?;
+        Ticket::update_many()
+            .col_expr(tickets::Column::BoardRank, Expr::value(board_rank))
+            .filter(tickets::Column::Id.eq(ticket_id))
+            .exec(&txn)
+            .await?;
+        // The values belonged to the fields of the previous project
+        CustomFieldService::clear(&txn, ticket_id).await?;
+    }
+    txn.commit().await?;
+    let list = Ticket::find_active()
         .filter(
             Condition::all()
                 .add(<entity::prelude::Tickets as EntityTrait>::Column::ProjectId.eq(id)),
         )
+        .order_by_asc(tickets::Column::BoardRank)
+        .order_by_asc(tickets::Column::Id)
         .all(&*db)
         .await?;
     Ok(Json(
@@ -165,13 +277,15 @@ async fn post_project_tickets(
     ))
 }
 
+/// Creates the project, its creator and its owner administer it.
 async fn post_project(
     db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
     WithRejection(ValidatedJson(model), _): WithRejection<ValidatedJson<ProjectDto>, ApiError>,
 ) -> Result<Json<ProjectDto>, ApiError> {
     println!("Project(): '{}'", model.summary);
 
-    let Some(user_id) = User::find()
+    let Some(user_id) = User::find_active()
         .select_only()
         .column(users::Column::Id)
         .filter(users::Column::PublicId.eq(model.user_id))
@@ -185,63 +299,219 @@ async fn post_project(
         ));
     };
 
+    check_key(&*db, &model.key, None).await?;
+
+    let txn = db.begin().await?;
     let project = projects::ActiveModel {
+        key: Set(model.key.to_owned()),
         summary: Set(model.summary.to_owned()),
         deadline: Set(model.deadline.map(|d| d.date_naive())),
         user_id: Set(user_id),
         active: Set(model.active),
         ..Default::default()
     }
-    .insert(&*db)
+    .insert(&txn)
     .await?;
+    for member in BTreeSet::from([auth_user.id, user_id]) {
+        MembershipService::set_role(&txn, project.id, member, ProjectRole::Admin).await?;
+    }
+    txn.commit().await?;
     Ok(Json(project.into()))
 }
 
+/// Replaces the project, the owner is given by the public id like for a new project.
 async fn put_project(
     db: Extension<DatabaseConnection>,
     WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
-    WithRejection(Json(update), _): WithRejection<Json<projects::Model>, ApiError>,
-) -> impl IntoResponse {
-    let original_result = Project::find_by_id(id).one(&*db).await?;
-    match original_result {
-        Some(original) => {
-            let updated = projects::ActiveModel {
-                id: Set(original.id),
-                summary: Set(update.summary.to_owned()),
-                deadline: Set(update.deadline.to_owned()),
-                user_id: Set(update.user_id),
-                active: Set(update.active),
-            }
-            .update(&*db)
-            .await?;
-            Ok(Json(updated))
-        }
-        None => Err(ApiError::new(
+    if_match: IfMatch,
+    WithRejection(ValidatedJson(update), _): WithRejection<ValidatedJson<ProjectDto>, ApiError>,
+) -> Result<Versioned<ProjectDto>, ApiError> {
+    let Some(original) = Project::find_active()
+        .filter(projects::Column::Id.eq(id))
+        .one(&*db)
+        .await?
+    else {
+        return Err(ApiError::new(
             StatusCode::NOT_FOUND,
             String::from("Not found"),
-        )),
+        ));
+    };
+    let version = if_match.check(original.version)?;
+
+    let Some(user_id) = User::find_active()
+        .select_only()
+        .column(users::Column::Id)
+        .filter(users::Column::PublicId.eq(update.user_id))
+        .into_tuple()
+        .one(&*db)
+        .await?
+    else {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            String::from("User not found"),
+        ));
+    };
+
+    check_key(&*db, &update.key, Some((original.id, &original.key))).await?;
+
+    let updated = Project::update(projects::ActiveModel {
+        id: Set(original.id),
+        summary: Set(update.summary.to_owned()),
+        deadline: Set(update.deadline.map(|d| d.date_naive())),
+        user_id: Set(user_id),
+        active: Set(update.active),
+        key: Set(update.key.to_owned()),
+        version: Set(version + 1),
+        ..Default::default()
+    })
+    .filter(projects::Column::Version.eq(version))
+    .exec(&*db)
+    .await
+    .map_err(version_conflict)?;
+    // A new owner administers the project
+    if user_id != original.user_id {
+        MembershipService::set_role(&*db, id, user_id, ProjectRole::Admin).await?;
     }
+    Ok(Versioned(
+        updated.version,
+        ProjectDto {
+            user_id: update.user_id,
+            ..updated.into()
+        },
+    ))
 }
 
-async fn delete_project(
+/// Changes only the fields present in the merge patch, they are validated with the same rules
+/// as the ones of a new project.
+async fn patch_project(
     db: Extension<DatabaseConnection>,
     WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
-) -> impl IntoResponse {
-    projects::ActiveModel {
+    if_match: IfMatch,
+    WithRejection(patch, _): WithRejection<MergePatch, ApiError>,
+) -> Result<Versioned<ProjectDto>, ApiError> {
+    let Some(current) = find_project(&*db, id).await? else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        ));
+    };
+    let version = if_match.check(current.version)?;
+    let previous_owner = current.user_id;
+    let previous_key = current.key.clone();
+    let update = patch.apply_validated(&ProjectDto::from(current))?;
+
+    let Some(user_id) = User::find_active()
+        .select_only()
+        .column(users::Column::Id)
+        .filter(users::Column::PublicId.eq(update.user_id))
+        .into_tuple()
+        .one(&*db)
+        .await?
+    else {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            String::from("User not found"),
+        ));
+    };
+
+    check_key(&*db, &update.key, Some((id, &previous_key))).await?;
+
+    let updated = Project::update(projects::ActiveModel {
         id: Set(id),
+        summary: Set(update.summary.to_owned()),
+        deadline: Set(update.deadline.map(|d| d.date_naive())),
+        user_id: Set(user_id),
+        active: Set(update.active),
+        key: Set(update.key.to_owned()),
+        version: Set(version + 1),
         ..Default::default()
-    }
-    .delete(&*db)
+    })
+    .filter(projects::Column::Version.eq(version))
+    .exec(&*db)
     .await
-    .map_or_else(
-        |e| JsonError::from((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())).into_response(),
-        |DeleteResult { rows_affected }| match rows_affected {
-            0 => {
-                JsonError::from((StatusCode::NOT_FOUND, String::from("Not found"))).into_response()
-            }
-            n => {
-                JsonError::from((StatusCode::NO_CONTENT, format!("Deleted {}", n))).into_response()
-            }
+    .map_err(version_conflict)?;
+    // A new owner administers the project
+    if update.user_id != previous_owner {
+        MembershipService::set_role(&*db, id, user_id, ProjectRole::Admin).await?;
+    }
+    Ok(Versioned(
+        updated.version,
+        ProjectDto {
+            user_id: update.user_id,
+            ..updated.into()
         },
-    )
+    ))
+}
+
+/// Moves the project to the trash together with its tickets. Restoring the project brings
+/// back the tickets deleted along with it.
+async fn delete_project(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let now = Utc::now();
+    let txn = db.begin().await?;
+    let UpdateResult { rows_affected } = Project::update_many()
+        .col_expr(projects::Column::DeletedAt, Expr::value(now))
+        .col_expr(projects::Column::DeletedBy, Expr::value(auth_user.id))
+        .filter(projects::Column::Id.eq(id))
+        .filter(projects::Column::DeletedAt.is_null())
+        .exec(&txn)
+        .await?;
+    if rows_affected == 0 {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        ));
+    }
+    Ticket::update_many()
+        .col_expr(tickets::Column::DeletedAt, Expr::value(now))
+        .col_expr(tickets::Column::DeletedBy, Expr::value(auth_user.id))
+        .filter(tickets::Column::ProjectId.eq(id))
+        .filter(tickets::Column::DeletedAt.is_null())
+        .exec(&txn)
+        .await?;
+    txn.commit().await?;
+    Ok(JsonError::from((
+        StatusCode::NO_CONTENT,
+        format!("Deleted {}", rows_affected),
+    )))
+}
+
+/// Project keys are unique. Changing a key only affects new ticket keys, the existing ones
+/// remain the same. A key whose tickets are kept in other projects can therefore not be
+/// taken over, the new tickets would get their keys again. `project` is the id and the
+/// current key of the project changed, `None` for a new one.
+async fn check_key<C: ConnectionTrait>(
+    db: &C,
+    key: &str,
+    project: Option<(u64, &str)>,
+) -> Result<(), ApiError> {
+    if project.is_some_and(|(_, current)| current == key) {
+        return Ok(());
+    }
+    let existing = Project::find()
+        .filter(projects::Column::Key.eq(key))
+        .apply_if(project, |query, (id, _)| {
+            query.filter(projects::Column::Id.ne(id))
+        })
+        .one(db)
+        .await?;
+    if existing.is_some() {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            String::from("Project key already in use"),
+        ));
+    }
+    match TicketKeyService::foreign_keys(key, project.map(|(id, _)| id))
+        .one(db)
+        .await?
+    {
+        Some(_) => Err(ApiError::new(
+            StatusCode::CONFLICT,
+            String::from("Project key was used before by another project"),
+        )),
+        None => Ok(()),
+    }
 }
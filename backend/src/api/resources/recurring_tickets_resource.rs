@@ -0,0 +1,176 @@
+use crate::api::{
+    error::{ApiError, JsonError},
+    project_check::check_project,
+    services::recurring_ticket_service::RecurringTicketService,
+    validated_json::ValidatedJson,
+};
+use axum::{
+    extract::{Json, Path},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get, post, put},
+    Extension, Router,
+};
+use axum_extra::extract::WithRejection;
+use chrono::Utc;
+use entity::{
+    recurring_tickets::{self, Entity as RecurringTicket},
+    users::{self, Entity as User},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set,
+};
+use shared::dtos::recurring_ticket_dto::RecurringTicketDto;
+use uuid::Uuid;
+
+pub fn router() -> Router {
+    Router::new()
+        .route(
+            "/projects/{id}/recurring_tickets",
+            get(get_recurring_tickets),
+        )
+        .route(
+            "/projects/{id}/recurring_tickets",
+            post(post_recurring_ticket),
+        )
+        .route(
+            "/projects/{id}/recurring_tickets/{recurring_id}",
+            put(put_recurring_ticket),
+        )
+        .route(
+            "/projects/{id}/recurring_tickets/{recurring_id}",
+            delete(delete_recurring_ticket),
+        )
+}
+
+async fn get_recurring_tickets(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+) -> Result<Json<Vec<RecurringTicketDto>>, ApiError> {
+    check_project(&*db, project_id).await?;
+    let list = RecurringTicket::find()
+        .find_also_related(User)
+        .filter(recurring_tickets::Column::ProjectId.eq(project_id))
+        .order_by_asc(recurring_tickets::Column::Id)
+        .all(&*db)
+        .await?;
+    Ok(Json(
+        list.into_iter()
+            .map(|(definition, user)| with_next_run((definition, user.map(|u| u.public_id)).into()))
+            .collect(),
+    ))
+}
+
+/// The first ticket is created at the first occurrence after now.
+async fn post_recurring_ticket(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+    WithRejection(ValidatedJson(model), _): WithRejection<
+        ValidatedJson<RecurringTicketDto>,
+        ApiError,
+    >,
+) -> Result<Json<RecurringTicketDto>, ApiError> {
+    check_project(&*db, project_id).await?;
+    RecurringTicketService::validate(&model.cron)?;
+    let user_id = find_user(&*db, model.user_id).await?;
+    let definition = recurring_tickets::ActiveModel {
+        project_id: Set(project_id),
+        title: Set(model.title.to_owned()),
+        description: Set(model.description.to_owned()),
+        priority: Set(model.priority.clone()),
+        user_id: Set(user_id),
+        reporter_id: Set(Some(auth_user.id)),
+        cron: Set(model.cron.trim().to_owned()),
+        active: Set(!model.paused as i8),
+        created_at: Set(Utc::now()),
+        ..Default::default()
+    }
+    .insert(&*db)
+    .await?;
+    Ok(Json(with_next_run((definition, model.user_id).into())))
+}
+
+/// Changes the template and the schedule. Occurrences already created are not repeated.
+async fn put_recurring_ticket(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path((project_id, recurring_id)), _): WithRejection<Path<(u64, u64)>, ApiError>,
+    WithRejection(ValidatedJson(update), _): WithRejection<
+        ValidatedJson<RecurringTicketDto>,
+        ApiError,
+    >,
+) -> Result<Json<RecurringTicketDto>, ApiError> {
+    let original = find_recurring_ticket(&*db, project_id, recurring_id).await?;
+    RecurringTicketService::validate(&update.cron)?;
+    let user_id = find_user(&*db, update.user_id).await?;
+    let mut definition: recurring_tickets::ActiveModel = original.into();
+    definition.title = Set(update.title.to_owned());
+    definition.description = Set(update.description.to_owned());
+    definition.priority = Set(update.priority.clone());
+    definition.user_id = Set(user_id);
+    definition.cron = Set(update.cron.trim().to_owned());
+    definition.active = Set(!update.paused as i8);
+    let definition = definition.update(&*db).await?;
+    Ok(Json(with_next_run((definition, update.user_id).into())))
+}
+
+/// Tickets created from the definition are kept.
+async fn delete_recurring_ticket(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path((project_id, recurring_id)), _): WithRejection<Path<(u64, u64)>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    find_recurring_ticket(&*db, project_id, recurring_id).await?;
+    let deleted = RecurringTicket::delete_by_id(recurring_id)
+        .exec(&*db)
+        .await?;
+    Ok(JsonError::from((
+        StatusCode::NO_CONTENT,
+        format!("Deleted {}", deleted.rows_affected),
+    )))
+}
+
+fn with_next_run(mut definition: RecurringTicketDto) -> RecurringTicketDto {
+    if !definition.paused {
+        definition.next_run_at = RecurringTicketService::schedule(&definition.cron)
+            .ok()
+            .and_then(|schedule| schedule.upcoming(Utc).next());
+    }
+    definition
+}
+
+async fn find_user<C: ConnectionTrait>(
+    db: &C,
+    public_id: Option<Uuid>,
+) -> Result<Option<u64>, ApiError> {
+    let Some(public_id) = public_id else {
+        return Ok(None);
+    };
+    User::find_active()
+        .select_only()
+        .column(users::Column::Id)
+        .filter(users::Column::PublicId.eq(public_id))
+        .into_tuple()
+        .one(db)
+        .await?
+        .map(Some)
+        .ok_or(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            String::from("User not found"),
+        ))
+}
+
+async fn find_recurring_ticket<C: ConnectionTrait>(
+    db: &C,
+    project_id: u64,
+    recurring_id: u64,
+) -> Result<recurring_tickets::Model, ApiError> {
+    RecurringTicket::find_by_id(recurring_id)
+        .filter(recurring_tickets::Column::ProjectId.eq(project_id))
+        .one(db)
+        .await?
+        .ok_or(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        ))
+}
@@ -0,0 +1,327 @@
+use crate::api::{
+    error::ApiError,
+    project_check::check_project,
+    services::report_service::{ReportService, TicketHistory, MAX_REPORT_DAYS},
+    ticket_path::TicketPath,
+};
+use axum::{
+    extract::{Json, Path, Query},
+    http::StatusCode,
+    routing::get,
+    Extension, Router,
+};
+use axum_extra::extract::WithRejection;
+use chrono::{Duration, NaiveDate, NaiveTime, Utc};
+use entity::{
+    sea_orm_active_enums::Priority,
+    sprints::{self, Entity as Sprint},
+    tickets,
+    users::{self, Entity as User},
+};
+use sea_orm::{
+    ActiveEnum, ColumnTrait, Condition, DatabaseConnection, EntityTrait, Iterable, QueryFilter,
+    QueryOrder, QuerySelect,
+};
+use serde::Deserialize;
+use shared::{
+    dtos::{
+        report_dto::{
+            CumulativeFlowDto, FlowGroupDto, FlowReportDto, ProgressReportDto, SprintVelocityDto,
+            StatusPercentilesDto, TimeInStatusDto, VelocityReportDto,
+        },
+        sprint_dto::SprintState,
+    },
+    validation::ticket_validation::TicketStatus,
+};
+use strum::IntoEnumIterator;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/projects/{id}/reports/progress", get(get_progress))
+        .route("/projects/{id}/reports/velocity", get(get_velocity))
+        .route("/projects/{id}/reports/cycle_time", get(get_cycle_time))
+        .route(
+            "/projects/{id}/reports/cumulative_flow",
+            get(get_cumulative_flow),
+        )
+        .route("/tickets/{id}/time_in_status", get(get_time_in_status))
+}
+
+#[derive(Deserialize)]
+pub struct ProgressFilter {
+    pub sprint_id: Option<u64>,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlowGrouping {
+    #[default]
+    Project,
+    Priority,
+    Assignee,
+}
+
+#[derive(Deserialize)]
+pub struct FlowFilter {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    #[serde(default)]
+    pub group_by: FlowGrouping,
+}
+
+/// Daily scope and finished tickets of a sprint, by default from its start until its end or
+/// today, or of the whole project, by default over the last 30 days. Tickets count in the
+/// sprint they are in now.
+async fn get_progress(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+    Query(filter): Query<ProgressFilter>,
+) -> Result<Json<ProgressReportDto>, ApiError> {
+    check_project(&*db, project_id).await?;
+    let today = Utc::now().date_naive();
+    let (condition, from, to) = match filter.sprint_id {
+        Some(sprint_id) => {
+            let sprint = Sprint::find_by_id(sprint_id)
+                .filter(sprints::Column::ProjectId.eq(project_id))
+                .one(&*db)
+                .await?
+                .ok_or(ApiError::new(
+                    StatusCode::NOT_FOUND,
+                    String::from("Sprint not found"),
+                ))?;
+            let last = sprint
+                .completed_at
+                .map_or(sprint.end_date.min(today), |c| c.date_naive());
+            (
+                Condition::all().add(tickets::Column::SprintId.eq(sprint_id)),
+                filter.from.unwrap_or(sprint.start_date),
+                filter.to.unwrap_or(last),
+            )
+        }
+        None => {
+            let to = filter.to.unwrap_or(today);
+            (
+                Condition::all().add(tickets::Column::ProjectId.eq(project_id)),
+                filter.from.unwrap_or(to - Duration::days(29)),
+                to,
+            )
+        }
+    };
+    if (to - from).num_days() >= MAX_REPORT_DAYS {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            format!("Reports cover at most {} days", MAX_REPORT_DAYS),
+        ));
+    }
+    let histories = ReportService::histories(&*db, condition).await?;
+    Ok(Json(ProgressReportDto {
+        sprint_id: filter.sprint_id,
+        points: ReportService::progress(&histories, from, to),
+    }))
+}
+
+/// Tickets finished in each completed sprint of the project, oldest sprint first.
+async fn get_velocity(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+) -> Result<Json<VelocityReportDto>, ApiError> {
+    check_project(&*db, project_id).await?;
+    let completed_sprints = Sprint::find()
+        .filter(sprints::Column::ProjectId.eq(project_id))
+        .filter(sprints::Column::State.eq(SprintState::Completed.to_string()))
+        .order_by_asc(sprints::Column::StartDate)
+        .order_by_asc(sprints::Column::Id)
+        .all(&*db)
+        .await?;
+    let mut sprints = Vec::with_capacity(completed_sprints.len());
+    for sprint in completed_sprints {
+        let histories = ReportService::histories(
+            &*db,
+            Condition::all().add(tickets::Column::SprintId.eq(sprint.id)),
+        )
+        .await?;
+        let completed_at = sprint.completed_at.map_or(
+            sprint.end_date.and_time(NaiveTime::MIN) + Duration::days(1),
+            |c| c.naive_utc(),
+        );
+        sprints.push(SprintVelocityDto {
+            sprint_id: sprint.id,
+            name: sprint.name,
+            completed: ReportService::finished_at(&histories, completed_at),
+        });
+    }
+    let average =
+        ReportService::average(&sprints.iter().map(|s| s.completed).collect::<Vec<u64>>());
+    Ok(Json(VelocityReportDto { sprints, average }))
+}
+
+/// Lead time, cycle time and time in status percentiles of the tickets of the project
+/// finished in the range, by default over the last 90 days, for the whole project or grouped
+/// by priority or assignee.
+async fn get_cycle_time(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+    Query(filter): Query<FlowFilter>,
+) -> Result<Json<FlowReportDto>, ApiError> {
+    check_project(&*db, project_id).await?;
+    let (from, to) = report_range(&filter, 90)?;
+    let start = from.and_time(NaiveTime::MIN);
+    let end = to.and_time(NaiveTime::MIN) + Duration::days(1);
+    let histories = ReportService::histories(
+        &*db,
+        Condition::all().add(tickets::Column::ProjectId.eq(project_id)),
+    )
+    .await?
+    .into_iter()
+    .filter(|h| h.finished_at().is_some_and(|at| at >= start && at < end))
+    .collect::<Vec<TicketHistory>>();
+
+    let groups = match filter.group_by {
+        FlowGrouping::Project => vec![flow_group(
+            String::from("All tickets"),
+            histories.iter().collect(),
+        )],
+        FlowGrouping::Priority => Priority::iter()
+            .map(Some)
+            .chain([None])
+            .map(|priority| {
+                let name = priority
+                    .as_ref()
+                    .map_or(String::from("No priority"), |p| p.to_value());
+                flow_group(
+                    name,
+                    histories
+                        .iter()
+                        .filter(|h| h.priority == priority)
+                        .collect(),
+                )
+            })
+            .filter(|g| g.count > 0)
+            .collect(),
+        FlowGrouping::Assignee => {
+            let mut user_ids = histories
+                .iter()
+                .filter_map(|h| h.user_id)
+                .collect::<Vec<u64>>();
+            user_ids.sort();
+            user_ids.dedup();
+            let users: Vec<(u64, String)> = User::find()
+                .select_only()
+                .columns([users::Column::Id, users::Column::Name])
+                .filter(users::Column::Id.is_in(user_ids))
+                .order_by_asc(users::Column::Name)
+                .into_tuple()
+                .all(&*db)
+                .await?;
+            users
+                .into_iter()
+                .map(|(id, name)| (Some(id), name))
+                .chain([(None, String::from("Unassigned"))])
+                .map(|(user_id, name)| {
+                    flow_group(
+                        name,
+                        histories.iter().filter(|h| h.user_id == user_id).collect(),
+                    )
+                })
+                .filter(|g| g.count > 0)
+                .collect()
+        }
+    };
+    Ok(Json(FlowReportDto { from, to, groups }))
+}
+
+/// Tickets of the project in each status at the end of every day of the range, by default
+/// over the last 30 days.
+async fn get_cumulative_flow(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+    Query(filter): Query<FlowFilter>,
+) -> Result<Json<CumulativeFlowDto>, ApiError> {
+    check_project(&*db, project_id).await?;
+    let (from, to) = report_range(&filter, 30)?;
+    let histories = ReportService::histories(
+        &*db,
+        Condition::all().add(tickets::Column::ProjectId.eq(project_id)),
+    )
+    .await?;
+    Ok(Json(CumulativeFlowDto {
+        statuses: TicketStatus::iter().collect(),
+        points: ReportService::cumulative_flow(&histories, from, to),
+    }))
+}
+
+/// Time the ticket spent in each status, with its lead and cycle time once it is finished.
+async fn get_time_in_status(
+    db: Extension<DatabaseConnection>,
+    TicketPath(id): TicketPath,
+) -> Result<Json<TimeInStatusDto>, ApiError> {
+    let history = ReportService::histories(&*db, Condition::all().add(tickets::Column::Id.eq(id)))
+        .await?
+        .pop()
+        .ok_or(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        ))?;
+    Ok(Json(ReportService::time_in_status(
+        &history,
+        Utc::now().naive_utc(),
+    )))
+}
+
+fn flow_group(group: String, histories: Vec<&TicketHistory>) -> FlowGroupDto {
+    let lead_times = histories
+        .iter()
+        .filter_map(|h| h.lead_time())
+        .collect::<Vec<Duration>>();
+    let cycle_times = histories
+        .iter()
+        .filter_map(|h| h.cycle_time())
+        .collect::<Vec<Duration>>();
+    let now = Utc::now().naive_utc();
+    let durations = histories
+        .iter()
+        .map(|h| h.time_in_status(now))
+        .collect::<Vec<_>>();
+    FlowGroupDto {
+        group,
+        count: histories.len() as u64,
+        lead_time: ReportService::percentiles(&lead_times),
+        cycle_time: ReportService::percentiles(&cycle_times),
+        time_in_status: TicketStatus::iter()
+            .filter(|status| !status.is_finished())
+            .map(|status| StatusPercentilesDto {
+                status,
+                // Only tickets which went through the status
+                hours: ReportService::percentiles(
+                    &durations
+                        .iter()
+                        .flatten()
+                        .filter(|(s, d)| *s == status && *d > Duration::zero())
+                        .map(|(_, d)| *d)
+                        .collect::<Vec<Duration>>(),
+                ),
+            })
+            .collect(),
+    }
+}
+
+/// The range of the filter, ending today and spanning `days` by default.
+fn report_range(filter: &FlowFilter, days: i64) -> Result<(NaiveDate, NaiveDate), ApiError> {
+    let to = filter.to.unwrap_or(Utc::now().date_naive());
+    let from = filter.from.unwrap_or(to - Duration::days(days - 1));
+    if from > to {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            String::from("The start of the range is after its end"),
+        ));
+    }
+    if (to - from).num_days() >= MAX_REPORT_DAYS {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            format!("Reports cover at most {} days", MAX_REPORT_DAYS),
+        ));
+    }
+    Ok((from, to))
+}
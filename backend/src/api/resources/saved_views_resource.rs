@@ -0,0 +1,210 @@
+use crate::api::{
+    error::{ApiError, JsonError},
+    services::membership_service::MembershipService,
+    validated_json::ValidatedJson,
+};
+use axum::{
+    extract::{Json, Path, Query},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get, post, put},
+    Extension, Router,
+};
+use axum_extra::extract::WithRejection;
+use entity::{
+    projects::{self, Entity as Project},
+    saved_views::{self, Entity as SavedView},
+    users,
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DeleteResult, EntityTrait,
+    QueryFilter, QueryOrder, QuerySelect, QueryTrait, Set,
+};
+use serde::Deserialize;
+use shared::dtos::{project_member_dto::ProjectRole, saved_view_dto::SavedViewDto};
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/views", post(post_view))
+        .route("/views", get(get_views))
+        .route("/views/{id}", get(get_view))
+        .route("/views/{id}", put(put_view))
+        .route("/views/{id}", delete(delete_view))
+}
+
+#[derive(Deserialize)]
+pub struct SavedViewFilter {
+    pub project_id: Option<u64>,
+}
+
+/// Views of the user, plus the ones shared with a project (with any project of the user if no
+/// project is given).
+async fn get_views(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    Query(filter): Query<SavedViewFilter>,
+) -> Result<Json<Vec<SavedViewDto>>, ApiError> {
+    let visible = Project::find_active()
+        .select_only()
+        .column(projects::Column::Id)
+        .filter(MembershipService::visible_projects(&auth_user))
+        .into_query();
+    let shared = match filter.project_id {
+        Some(project_id) => Condition::all().add(saved_views::Column::ProjectId.eq(project_id)),
+        None => Condition::all(),
+    }
+    .add(saved_views::Column::ProjectId.in_subquery(visible));
+    let list = SavedView::find()
+        .filter(
+            Condition::any()
+                .add(saved_views::Column::UserId.eq(auth_user.id))
+                .add(shared),
+        )
+        .order_by_asc(saved_views::Column::Name)
+        .all(&*db)
+        .await?;
+    Ok(Json(
+        list.iter()
+            .map(|m| to_dto(m, &auth_user))
+            .collect::<Vec<SavedViewDto>>(),
+    ))
+}
+
+/// A view of the user, or one shared with a project the user is a member of.
+async fn get_view(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
+) -> Result<Json<SavedViewDto>, ApiError> {
+    let not_found = || ApiError::new(StatusCode::NOT_FOUND, String::from("Not found"));
+    let view = SavedView::find_by_id(id)
+        .one(&*db)
+        .await?
+        .ok_or_else(not_found)?;
+    if view.user_id != auth_user.id {
+        let project_id = view.project_id.ok_or_else(not_found)?;
+        MembershipService::require(&*db, &auth_user, project_id, ProjectRole::Viewer).await?;
+    }
+    Ok(Json(to_dto(&view, &auth_user)))
+}
+
+async fn post_view(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    WithRejection(ValidatedJson(model), _): WithRejection<ValidatedJson<SavedViewDto>, ApiError>,
+) -> Result<Json<SavedViewDto>, ApiError> {
+    check_project(&db, &auth_user, model.project_id).await?;
+    let view = saved_views::ActiveModel {
+        name: Set(model.name.to_owned()),
+        user_id: Set(auth_user.id),
+        project_id: Set(model.project_id),
+        filter: Set(model.filter.to_owned()),
+        sort: Set(model.sort.to_owned()),
+        sort_order: Set(model.order.to_owned()),
+        columns: Set(model.columns.join(",")),
+        ..Default::default()
+    }
+    .insert(&*db)
+    .await?;
+    Ok(Json(to_dto(&view, &auth_user)))
+}
+
+async fn put_view(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
+    WithRejection(ValidatedJson(update), _): WithRejection<ValidatedJson<SavedViewDto>, ApiError>,
+) -> Result<Json<SavedViewDto>, ApiError> {
+    let original = find_owned_view(&db, id, &auth_user).await?;
+    check_project(&db, &auth_user, update.project_id).await?;
+    let updated = saved_views::ActiveModel {
+        id: Set(original.id),
+        name: Set(update.name.to_owned()),
+        user_id: Set(original.user_id),
+        project_id: Set(update.project_id),
+        filter: Set(update.filter.to_owned()),
+        sort: Set(update.sort.to_owned()),
+        sort_order: Set(update.order.to_owned()),
+        columns: Set(update.columns.join(",")),
+    }
+    .update(&*db)
+    .await?;
+    Ok(Json(to_dto(&updated, &auth_user)))
+}
+
+async fn delete_view(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
+) -> impl IntoResponse {
+    if let Err(e) = find_owned_view(&db, id, &auth_user).await {
+        return e.into_response();
+    }
+    saved_views::ActiveModel {
+        id: Set(id),
+        ..Default::default()
+    }
+    .delete(&*db)
+    .await
+    .map_or_else(
+        |e| JsonError::from((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())).into_response(),
+        |DeleteResult { rows_affected }| match rows_affected {
+            0 => {
+                JsonError::from((StatusCode::NOT_FOUND, String::from("Not found"))).into_response()
+            }
+            n => {
+                JsonError::from((StatusCode::NO_CONTENT, format!("Deleted {}", n))).into_response()
+            }
+        },
+    )
+}
+
+/// Only the owner can change a view, even if it is shared with a project.
+async fn find_owned_view(
+    db: &DatabaseConnection,
+    id: u64,
+    auth_user: &users::Model,
+) -> Result<saved_views::Model, ApiError> {
+    match SavedView::find_by_id(id).one(db).await? {
+        Some(view) if view.user_id == auth_user.id => Ok(view),
+        Some(view) if view.project_id.is_some() => Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            String::from("Only the owner can change this view"),
+        )),
+        _ => Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        )),
+    }
+}
+
+/// Views are shared only with existing projects the user is a member of.
+async fn check_project(
+    db: &DatabaseConnection,
+    auth_user: &users::Model,
+    project_id: Option<u64>,
+) -> Result<(), ApiError> {
+    let Some(id) = project_id else {
+        return Ok(());
+    };
+    if Project::find_active()
+        .filter(projects::Column::Id.eq(id))
+        .one(db)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            String::from("Project not found"),
+        ));
+    }
+    MembershipService::require(db, auth_user, id, ProjectRole::Viewer).await?;
+    Ok(())
+}
+
+fn to_dto(model: &saved_views::Model, auth_user: &users::Model) -> SavedViewDto {
+    SavedViewDto {
+        owned: model.user_id == auth_user.id,
+        ..model.into()
+    }
+}
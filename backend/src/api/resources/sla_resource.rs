@@ -0,0 +1,129 @@
+use crate::api::{
+    error::ApiError, project_check::check_project, services::sla_service::SlaService,
+    ticket_path::TicketPath, validated_json::ValidatedJson,
+};
+use axum::{
+    extract::{Json, Path},
+    routing::{get, put},
+    Extension, Router,
+};
+use axum_extra::extract::WithRejection;
+use chrono::Utc;
+use entity::{
+    sla_policies::{self, Entity as SlaPolicy},
+    tickets,
+};
+use sea_orm::{
+    ColumnTrait, Condition, DatabaseConnection, EntityTrait, QueryFilter, Set, TransactionTrait,
+};
+use shared::{
+    dtos::sla_dto::{SlaPoliciesDto, TicketSlaDto},
+    validation::ticket_validation::TicketStatus,
+};
+use strum::IntoEnumIterator;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/projects/{id}/sla_policies", get(get_policies))
+        .route("/projects/{id}/sla_policies", put(put_policies))
+        .route("/projects/{id}/sla", get(get_project_slas))
+        .route("/tickets/{id}/sla", get(get_ticket_sla))
+}
+
+async fn get_policies(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+) -> Result<Json<SlaPoliciesDto>, ApiError> {
+    check_project(&*db, project_id).await?;
+    Ok(Json(SlaPoliciesDto {
+        policies: SlaService::policies(&*db, project_id).await?,
+    }))
+}
+
+/// Replaces the policies of the project. Priorities without a policy have no SLA.
+async fn put_policies(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+    WithRejection(ValidatedJson(model), _): WithRejection<ValidatedJson<SlaPoliciesDto>, ApiError>,
+) -> Result<Json<SlaPoliciesDto>, ApiError> {
+    check_project(&*db, project_id).await?;
+    let txn = db.begin().await?;
+    SlaPolicy::delete_many()
+        .filter(sla_policies::Column::ProjectId.eq(project_id))
+        .exec(&txn)
+        .await?;
+    if !model.policies.is_empty() {
+        let policies = model
+            .policies
+            .iter()
+            .map(|policy| {
+                // Without business hours the columns hold the default calendar, unused
+                let calendar = policy.business_hours.clone().unwrap_or_default();
+                sla_policies::ActiveModel {
+                    project_id: Set(project_id),
+                    priority: Set(policy.priority.clone()),
+                    response_minutes: Set(policy.response_minutes),
+                    resolution_minutes: Set(policy.resolution_minutes),
+                    business_hours: Set(policy.business_hours.is_some() as i8),
+                    day_start: Set(calendar.start),
+                    day_end: Set(calendar.end),
+                    work_days: Set(calendar
+                        .days
+                        .iter()
+                        .map(|d| d.to_string())
+                        .collect::<Vec<String>>()
+                        .join(",")),
+                    utc_offset_minutes: Set(calendar.utc_offset_minutes),
+                    ..Default::default()
+                }
+            })
+            .collect::<Vec<sla_policies::ActiveModel>>();
+        SlaPolicy::insert_many(policies).exec(&txn).await?;
+    }
+    let policies = SlaService::policies(&txn, project_id).await?;
+    txn.commit().await?;
+    Ok(Json(SlaPoliciesDto { policies }))
+}
+
+/// Clocks of the unfinished tickets of the project which have a policy.
+async fn get_project_slas(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+) -> Result<Json<Vec<TicketSlaDto>>, ApiError> {
+    check_project(&*db, project_id).await?;
+    Ok(Json(
+        SlaService::ticket_slas(
+            &*db,
+            Condition::all()
+                .add(tickets::Column::ProjectId.eq(project_id))
+                .add(
+                    tickets::Column::Status.is_not_in(
+                        TicketStatus::iter()
+                            .filter(|s| s.is_finished())
+                            .map(|s| s.to_string()),
+                    ),
+                ),
+            Utc::now().naive_utc(),
+        )
+        .await?,
+    ))
+}
+
+/// Clocks of the ticket, without any if there is no policy for it.
+async fn get_ticket_sla(
+    db: Extension<DatabaseConnection>,
+    TicketPath(id): TicketPath,
+) -> Result<Json<TicketSlaDto>, ApiError> {
+    let sla = SlaService::ticket_slas(
+        &*db,
+        Condition::all().add(tickets::Column::Id.eq(id)),
+        Utc::now().naive_utc(),
+    )
+    .await?
+    .pop()
+    .unwrap_or(TicketSlaDto {
+        ticket_id: id,
+        clocks: Vec::new(),
+    });
+    Ok(Json(sla))
+}
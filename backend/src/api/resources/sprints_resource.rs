@@ -0,0 +1,206 @@
+use crate::api::{
+    error::{ApiError, JsonError},
+    project_check::check_project,
+    services::sprint_service::SprintService,
+    validated_json::ValidatedJson,
+};
+use axum::{
+    extract::{Json, Path},
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{delete, get, post, put},
+    Extension, Router,
+};
+use axum_extra::extract::WithRejection;
+use chrono::Utc;
+use entity::sprints::{self, Entity as Sprint};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, EntityTrait, QueryFilter,
+    QueryOrder, QuerySelect, Set, TransactionTrait,
+};
+use shared::dtos::sprint_dto::{CompleteSprintDto, SprintDto, SprintState};
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/projects/{id}/sprints", get(get_sprints))
+        .route("/projects/{id}/sprints", post(post_sprint))
+        .route("/projects/{id}/sprints/{sprint_id}", put(put_sprint))
+        .route("/projects/{id}/sprints/{sprint_id}", delete(delete_sprint))
+        .route(
+            "/projects/{id}/sprints/{sprint_id}/start",
+            post(start_sprint),
+        )
+        .route(
+            "/projects/{id}/sprints/{sprint_id}/complete",
+            post(complete_sprint),
+        )
+}
+
+/// Sprints of the project, by start date.
+async fn get_sprints(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+) -> Result<Json<Vec<SprintDto>>, ApiError> {
+    check_project(&*db, project_id).await?;
+    let list = Sprint::find()
+        .filter(sprints::Column::ProjectId.eq(project_id))
+        .order_by_asc(sprints::Column::StartDate)
+        .order_by_asc(sprints::Column::Id)
+        .all(&*db)
+        .await?;
+    Ok(Json(list.into_iter().map(SprintDto::from).collect()))
+}
+
+/// New sprints are always planned, they are started separately.
+async fn post_sprint(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path(project_id), _): WithRejection<Path<u64>, ApiError>,
+    WithRejection(ValidatedJson(model), _): WithRejection<ValidatedJson<SprintDto>, ApiError>,
+) -> Result<Json<SprintDto>, ApiError> {
+    check_project(&*db, project_id).await?;
+    let sprint = sprints::ActiveModel {
+        project_id: Set(project_id),
+        name: Set(model.name.to_owned()),
+        goal: Set(model.goal.to_owned()),
+        start_date: Set(model.start_date),
+        end_date: Set(model.end_date),
+        state: Set(SprintState::Planned.to_string()),
+        ..Default::default()
+    }
+    .insert(&*db)
+    .await?;
+    Ok(Json(sprint.into()))
+}
+
+/// Changes the name, goal and dates. The state only changes by starting and completing.
+async fn put_sprint(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path((project_id, sprint_id)), _): WithRejection<Path<(u64, u64)>, ApiError>,
+    WithRejection(ValidatedJson(update), _): WithRejection<ValidatedJson<SprintDto>, ApiError>,
+) -> Result<Json<SprintDto>, ApiError> {
+    let original = find_sprint(&*db, project_id, sprint_id).await?;
+    let updated = sprints::ActiveModel {
+        id: Set(original.id),
+        project_id: Set(original.project_id),
+        name: Set(update.name.to_owned()),
+        goal: Set(update.goal.to_owned()),
+        start_date: Set(update.start_date),
+        end_date: Set(update.end_date),
+        state: Set(original.state),
+        completed_at: Set(original.completed_at),
+    }
+    .update(&*db)
+    .await?;
+    Ok(Json(updated.into()))
+}
+
+/// Deletes a sprint which is not active, its tickets go back to the backlog.
+async fn delete_sprint(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path((project_id, sprint_id)), _): WithRejection<Path<(u64, u64)>, ApiError>,
+) -> Result<impl IntoResponse, ApiError> {
+    let sprint = find_sprint(&*db, project_id, sprint_id).await?;
+    if sprint.state == SprintState::Active.to_string() {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            String::from("The active sprint has to be completed before deleting it"),
+        ));
+    }
+    let txn = db.begin().await?;
+    SprintService::clear(&txn, sprint_id).await?;
+    let deleted = Sprint::delete_by_id(sprint_id).exec(&txn).await?;
+    txn.commit().await?;
+    Ok(JsonError::from((
+        StatusCode::NO_CONTENT,
+        format!("Deleted {}", deleted.rows_affected),
+    )))
+}
+
+/// Only planned sprints can be started, and only while no other sprint of the project is
+/// active. The sprints of the project stay locked until the sprint is started, so that two
+/// sprints started at the same time cannot both become active.
+async fn start_sprint(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path((project_id, sprint_id)), _): WithRejection<Path<(u64, u64)>, ApiError>,
+) -> Result<Json<SprintDto>, ApiError> {
+    let txn = db.begin().await?;
+    let sprints = Sprint::find()
+        .filter(sprints::Column::ProjectId.eq(project_id))
+        .lock_exclusive()
+        .all(&txn)
+        .await?;
+    let Some(sprint) = sprints.iter().find(|s| s.id == sprint_id).cloned() else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        ));
+    };
+    if sprint.state != SprintState::Planned.to_string() {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            String::from("Only planned sprints can be started"),
+        ));
+    }
+    if sprints
+        .iter()
+        .any(|s| s.state == SprintState::Active.to_string())
+    {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            String::from("Another sprint of the project is already active"),
+        ));
+    }
+    let mut sprint: sprints::ActiveModel = sprint.into();
+    sprint.state = Set(SprintState::Active.to_string());
+    let sprint = sprint.update(&txn).await?;
+    txn.commit().await?;
+    Ok(Json(sprint.into()))
+}
+
+/// Completes the active sprint. Tickets which are not done move to the given planned sprint
+/// of the project, or back to the backlog.
+async fn complete_sprint(
+    db: Extension<DatabaseConnection>,
+    WithRejection(Path((project_id, sprint_id)), _): WithRejection<Path<(u64, u64)>, ApiError>,
+    WithRejection(Json(request), _): WithRejection<Json<CompleteSprintDto>, ApiError>,
+) -> Result<Json<SprintDto>, ApiError> {
+    let sprint = find_sprint(&*db, project_id, sprint_id).await?;
+    if sprint.state != SprintState::Active.to_string() {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            String::from("Only the active sprint can be completed"),
+        ));
+    }
+    if let Some(next_sprint_id) = request.next_sprint_id {
+        let next = find_sprint(&*db, project_id, next_sprint_id).await?;
+        if next.state != SprintState::Planned.to_string() {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                String::from("Unfinished tickets can only move to a planned sprint"),
+            ));
+        }
+    }
+    let txn = db.begin().await?;
+    SprintService::move_unfinished(&txn, sprint_id, request.next_sprint_id).await?;
+    let mut sprint: sprints::ActiveModel = sprint.into();
+    sprint.state = Set(SprintState::Completed.to_string());
+    sprint.completed_at = Set(Some(Utc::now()));
+    let sprint = sprint.update(&txn).await?;
+    txn.commit().await?;
+    Ok(Json(sprint.into()))
+}
+
+async fn find_sprint<C: ConnectionTrait>(
+    db: &C,
+    project_id: u64,
+    sprint_id: u64,
+) -> Result<sprints::Model, ApiError> {
+    Sprint::find_by_id(sprint_id)
+        .filter(sprints::Column::ProjectId.eq(project_id))
+        .one(db)
+        .await?
+        .ok_or(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        ))
+}
@@ -2,6 +2,7 @@ use crate::api::{
     consts::{ADMIN_EMAIL, BUCKET_NAME},
     error::ApiError,
     services::notification_service::NotificationService,
+    ticket_path::TicketPath,
 };
 use anyhow::Context;
 use axum::{
@@ -14,6 +15,7 @@ use axum_extra::extract::WithRejection;
 use axum_typed_multipart::{FieldData, TryFromMultipart, TypedMultipart};
 use entity::{
     ticket_attachments::{self, Model},
+    tickets::{self, Entity as Ticket},
     users,
 };
 use http::StatusCode;
@@ -22,7 +24,10 @@ use object_store::{
     aws::{AmazonS3, AmazonS3Builder},
     ObjectStore, WriteMultipart,
 };
-use sea_orm::{ActiveModelTrait, DatabaseConnection, Set};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DatabaseConnection, PaginatorTrait,
+    QueryFilter, Set,
+};
 use serde::{Deserialize, Serialize};
 use std::path;
 use tempfile::NamedTempFile;
@@ -47,9 +52,10 @@ pub async fn upload_file(
     db: Extension<DatabaseConnection>,
     _request_user: Extension<users::Model>,
     Extension(notification_service): Extension<NotificationService>,
-    WithRejection(Path(ticket_id), _): WithRejection<Path<u64>, ApiError>,
+    TicketPath(ticket_id): TicketPath,
     TypedMultipart(UploadForm { file }): TypedMultipart<UploadForm>,
 ) -> Result<Json<UploadResponse>, ApiError> {
+    check_ticket(&*db, ticket_id).await?;
     let file_name = file.metadata.file_name.unwrap_or(String::from("data.bin"));
     let path = path::Path::new("/tmp").join(file_name.clone());
 
@@ -111,8 +117,11 @@ pub async fn upload_file(
 
 #[axum::debug_handler]
 pub async fn download_file(
-    WithRejection(Path((ticket_id, file_name)), _): WithRejection<Path<(u64, String)>, ApiError>,
+    db: Extension<DatabaseConnection>,
+    TicketPath(ticket_id): TicketPath,
+    WithRejection(Path((_, file_name)), _): WithRejection<Path<(String, String)>, ApiError>,
 ) -> Result<impl IntoResponse, ApiError> {
+    check_ticket(&*db, ticket_id).await?;
     let bucket: AmazonS3 = AmazonS3Builder::from_env()
         .with_bucket_name(BUCKET_NAME.clone())
         .build()
@@ -130,6 +139,21 @@ pub async fn download_file(
     Ok(axum::body::Body::from_stream(stream))
 }
 
+/// Attachments of tickets in the trash are not found.
+async fn check_ticket<C: ConnectionTrait>(db: &C, ticket_id: u64) -> Result<(), ApiError> {
+    match Ticket::find_active()
+        .filter(tickets::Column::Id.eq(ticket_id))
+        .count(db)
+        .await?
+    {
+        0 => Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        )),
+        _ => Ok(()),
+    }
+}
+
 fn send_upload_notification(
     notification_service: NotificationService,
     path: String,
@@ -5,10 +5,14 @@ use axum::{
     Extension, Router,
 };
 use axum_extra::extract::WithRejection;
-use entity::{ticket_updates, ticket_updates::Entity as TicketUpdate};
-use sea_orm::{ActiveModelTrait, DatabaseConnection, EntityTrait, Set};
+use entity::{ticket_updates, ticket_updates::Entity as TicketUpdate, tickets, users};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, DatabaseConnection, EntityTrait, JoinType, QueryFilter,
+    QuerySelect, RelationTrait, Set,
+};
+use shared::dtos::project_member_dto::ProjectRole;
 
-use crate::api::error::ApiError;
+use crate::api::{error::ApiError, services::membership_service::MembershipService};
 
 pub fn router() -> Router {
     Router::new()
@@ -17,10 +21,17 @@ pub fn router() -> Router {
         .route("/ticket_updates/{id}", get(get_ticket_update))
 }
 
+/// Updates of the tickets the user may see, the ones of tickets in the trash are left out.
 async fn get_ticket_updates(
     db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
 ) -> Result<Json<Vec<ticket_updates::Model>>, ApiError> {
-    let list = TicketUpdate::find().all(&*db).await?;
+    let list = TicketUpdate::find()
+        .join(JoinType::InnerJoin, ticket_updates::Relation::Tickets.def())
+        .filter(tickets::Column::DeletedAt.is_null())
+        .filter(MembershipService::visible_tickets(&auth_user))
+        .all(&*db)
+        .await?;
     Ok(Json(list))
 }
 
@@ -28,23 +39,31 @@ async fn get_ticket_update(
     db: Extension<DatabaseConnection>,
     WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
 ) -> Result<Json<ticket_updates::Model>, ApiError> {
-    TicketUpdate::find_by_id(id).one(&*db).await?.map_or(
-        Err(ApiError::new(
-            StatusCode::NOT_FOUND,
-            String::from("Not found"),
-        )),
-        |ticket_update| Ok(Json(ticket_update)),
-    )
+    TicketUpdate::find_by_id(id)
+        .join(JoinType::InnerJoin, ticket_updates::Relation::Tickets.def())
+        .filter(tickets::Column::DeletedAt.is_null())
+        .one(&*db)
+        .await?
+        .map_or(
+            Err(ApiError::new(
+                StatusCode::NOT_FOUND,
+                String::from("Not found"),
+            )),
+            |ticket_update| Ok(Json(ticket_update)),
+        )
 }
 
 async fn post_ticket_update(
     db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
     WithRejection(Json(model), _): WithRejection<Json<ticket_updates::Model>, ApiError>,
 ) -> Result<Json<ticket_updates::Model>, ApiError> {
     println!(
         "TicketUpdate(): {} -> {}",
         model.previous_state, model.next_state
     );
+    MembershipService::require_ticket(&*db, &auth_user, model.ticket_id, ProjectRole::Contributor)
+        .await?;
     let ticket_update = ticket_updates::ActiveModel {
         previous_state: Set(model.previous_state.to_owned()),
         next_state: Set(model.next_state.to_owned()),
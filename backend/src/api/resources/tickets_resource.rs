@@ -1,94 +1,401 @@
 use crate::api::{
     error::{ApiError, JsonError},
+    etag::{version_conflict, IfMatch, Versioned},
+    merge_patch::MergePatch,
     query::{
         filters::{
             pagination::{Pagination, TotalCount},
             search::Search,
             ticket_filter::TicketFilter,
         },
+        jql::{condition::QueryContext, parser::TicketQuery},
         ordering::Ordering,
     },
+    services::{
+        board_service::BoardService,
+        csv_service::{CsvService, RowErrors},
+        custom_field_service::CustomFieldService,
+        label_service::LabelService,
+        membership_service::MembershipService,
+        parent_service::ParentService,
+        rank_service::RankService,
+        similarity_service::SimilarityService,
+        sprint_service::SprintService,
+        ticket_key_service::TicketKeyService,
+        ticket_update_service::TicketUpdateService,
+        watcher_service::WatcherService,
+    },
     tasks::queue_mailer::{
-        push_to_queue, ticket_id_subscriber_set, ticket_update_id, UpdateMessageKey,
+        push_bulk_to_queue, push_to_queue, ticket_id_subscriber_set, ticket_update_id,
+        UpdateMessageKey,
     },
+    ticket_path::TicketPath,
+    validated_json::ValidatedJson,
 };
 use axum::{
-    extract::{Json, Path, Query},
-    http::StatusCode,
+    body::Body,
+    extract::{Json, Query},
+    http::{header, StatusCode},
     response::IntoResponse,
-    routing::{delete, get, post, put},
+    routing::{delete, get, patch, post, put},
     Extension, Router,
 };
 use axum_extra::extract::WithRejection;
+use chrono::Utc;
 use entity::{
+    comments,
+    custom_fields::Entity as CustomField,
+    projects::{self, Entity as Project},
+    ticket_labels::{self, Entity as TicketLabel},
     tickets::{self, Entity as Ticket},
     users::{self, Entity as User},
 };
+use futures::{future, stream, StreamExt};
 use redis::{Client, Commands};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DeleteResult, EntityTrait, Order,
-    QueryFilter, QueryOrder, QuerySelect, QueryTrait, RelationTrait, Set,
+    sea_query::Expr, ActiveModelTrait, ActiveValue, ColumnTrait, Condition, ConnectionTrait,
+    DatabaseConnection, DatabaseTransaction, DbErr, EntityTrait, IntoSimpleExpr, Order,
+    QueryFilter, QueryOrder, QuerySelect, QueryTrait, RelationTrait, Select, Set, TransactionTrait,
+    UpdateResult,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use shared::{
+    dtos::{
+        bulk_dto::{BulkAction, BulkItemResult, BulkTicketRequest, BulkTicketResponse},
+        page::Page,
+        project_member_dto::ProjectRole,
+        ticket_csv_dto::{TicketImportDto, TicketImportResultDto},
+        ticket_dto::{
+            MoveTicketDto, SimilarTicketDto, TicketDraftDto, TicketDto, TicketField,
+            TicketQueryResult,
+        },
+    },
+    validation::ticket_validation::{TicketStatus, TicketValidation},
 };
-use shared::dtos::{
-    page::Page,
-    ticket_dto::{TicketDto, TicketQueryResult},
+use std::{
+    collections::{BTreeSet, HashMap},
+    str::FromStr,
 };
 
+/// Tickets read from the database at once by the export.
+const EXPORT_PAGE_SIZE: u64 = 500;
+
+#[derive(Deserialize)]
+pub struct ImportOptions {
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
 pub fn router() -> Router {
     Router::new()
         .route("/tickets", post(post_ticket))
         .route("/tickets", get(get_tickets))
         .route("/tickets/unassigned", get(get_unassigned_tickets))
+        .route("/tickets/bulk", post(post_tickets_bulk))
+        .route("/tickets/similar", post(post_similar_tickets))
+        .route("/tickets/export", get(export_tickets))
+        .route("/tickets/import", post(import_tickets))
         .route("/tickets/{id}", get(get_ticket))
         .route("/tickets/{id}", put(put_ticket))
+        .route("/tickets/{id}", patch(patch_ticket))
         .route("/tickets/{id}", delete(delete_ticket))
+        .route("/tickets/{id}/move", post(move_ticket))
+        .route("/tickets/{id}/duplicates", post(post_duplicate))
         .route("/tickets/{id}/subscribe", post(subscribe_to_ticket))
         .route("/tickets/{id}/is_subscribed", get(is_subscribed))
 }
 
 async fn get_tickets(
     db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
     Query(filter): Query<TicketFilter>,
     Query(search): Query<Search>,
     Query(pagination): Query<Pagination>,
     Query(ordering): Query<Ordering>,
 ) -> Result<Json<Page<TicketDto>>, ApiError> {
-    let total = Ticket::find()
+    let (condition, select) = ticket_query(&db, &auth_user, filter, search, ordering).await?;
+    let total = Ticket::find_active()
         .select_only()
         .column_as(tickets::Column::Id.count(), "count")
+        .filter(condition)
         .into_model::<TotalCount>()
         .one(&*db)
         .await?
         .unwrap()
         .count;
-    let mut select = match ordering.sort.and_then(|s| sort_to_column(s.as_str())) {
-        Some(sort) => Ticket::find().order_by::<tickets::Column>(sort, ordering.order.0),
-        None => Ticket::find().order_by(tickets::Column::Id, Order::Asc),
-    };
-    select = match search.q {
-        Some(q) => select.filter(Condition::all().add(tickets::Column::Title.contains(q))),
-        None => select,
-    };
-    select = match filter.project_id {
-        Some(id) => select.filter(
-            Condition::all()
-                .add(<entity::prelude::Tickets as EntityTrait>::Column::ProjectId.eq(id)),
-        ),
-        None => select,
-    };
     let list = select
         .apply_if(pagination.limit, QuerySelect::limit)
         .offset(pagination.offset)
         .all(&*db)
         .await?;
+    let mut list = list.iter().map(|m| m.into()).collect::<Vec<TicketDto>>();
+    CustomFieldService::attach(&*db, &mut list).await?;
+    LabelService::attach(&*db, &mut list).await?;
     Ok(Json(Page::<TicketDto> {
-        list: list.iter().map(|m| m.into()).collect::<Vec<TicketDto>>(),
+        list,
         total,
         offset: pagination.offset.unwrap(),
         limit: pagination.limit.unwrap(),
     }))
 }
 
+/// The condition of the filter, the search and the query, and the tickets matching it in the
+/// requested order. Only the tickets the user may see match.
+async fn ticket_query(
+    db: &DatabaseConnection,
+    auth_user: &users::Model,
+    filter: TicketFilter,
+    search: Search,
+    ordering: Ordering,
+) -> Result<(Condition, Select<tickets::Entity>), ApiError> {
+    let mut condition = filter
+        .to_condition()
+        .add(MembershipService::visible_tickets(auth_user))
+        .add_option(search.q.map(|q| tickets::Column::Title.contains(q)));
+    if let Some(jql) = &filter.jql {
+        condition = condition.add(
+            TicketQuery::from_str(jql.as_str())?.to_condition(&QueryContext::new(auth_user.id))?,
+        );
+    }
+    // Custom fields sort as `cf.<field id>`
+    let sort = match ordering.sort.as_deref().map(TicketField::from_str) {
+        Some(Ok(TicketField::Custom(field_id))) => CustomField::find_by_id(field_id)
+            .one(db)
+            .await?
+            .map(|field| CustomFieldService::sort_expr(&field.into())),
+        _ => ordering
+            .sort
+            .as_deref()
+            .and_then(sort_to_column)
+            .map(|column| column.into_simple_expr()),
+    };
+    let select = match sort {
+        Some(sort) => Ticket::find_active()
+            .order_by(sort, ordering.order.0)
+            .order_by(tickets::Column::Id, Order::Asc),
+        None => Ticket::find_active().order_by(tickets::Column::Id, Order::Asc),
+    };
+    Ok((condition.clone(), select.filter(condition)))
+}
+
+/// All tickets of the same query as `get_tickets` as CSV. The pages are read one after the
+/// other while the response is sent.
+async fn export_tickets(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    Query(filter): Query<TicketFilter>,
+    Query(search): Query<Search>,
+    Query(ordering): Query<Ordering>,
+) -> Result<impl IntoResponse, ApiError> {
+    let (_, select) = ticket_query(&db, &auth_user, filter, search, ordering).await?;
+    let header = CsvService::write([CsvService::header()])
+        .map_err(|e| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let db = db.0;
+    let pages = stream::unfold(Some(0), move |offset| {
+        let (db, select) = (db.clone(), select.clone());
+        async move {
+            let offset = offset?;
+            match export_page(&db, select, offset).await {
+                Ok((csv, count)) => Some((
+                    Ok(csv),
+                    (count == EXPORT_PAGE_SIZE).then_some(offset + EXPORT_PAGE_SIZE),
+                )),
+                Err(e) => Some((Err(e), None)),
+            }
+        }
+    });
+    Ok((
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (
+                header::CONTENT_DISPOSITION,
+                "attachment; filename=\"tickets.csv\"",
+            ),
+        ],
+        Body::from_stream(stream::once(future::ready(Ok(header))).chain(pages)),
+    ))
+}
+
+/// Rows of a page of the export, and the number of tickets in it.
+async fn export_page(
+    db: &DatabaseConnection,
+    select: Select<tickets::Entity>,
+    offset: u64,
+) -> Result<(Vec<u8>, u64), DbErr> {
+    let models = select
+        .limit(EXPORT_PAGE_SIZE)
+        .offset(offset)
+        .all(db)
+        .await?;
+    let user_ids = models
+        .iter()
+        .filter_map(|m| m.user_id)
+        .collect::<Vec<u64>>();
+    let usernames: Vec<(u64, String)> = match user_ids.is_empty() {
+        true => Vec::new(),
+        false => {
+            User::find()
+                .select_only()
+                .columns([users::Column::Id, users::Column::Username])
+                .filter(users::Column::Id.is_in(user_ids))
+                .into_tuple()
+                .all(db)
+                .await?
+        }
+    };
+    let mut tickets = models
+        .iter()
+        .map(TicketDto::from)
+        .collect::<Vec<TicketDto>>();
+    LabelService::attach(db, &mut tickets).await?;
+    let csv = CsvService::write(models.iter().zip(tickets.iter()).map(|(model, ticket)| {
+        let assignee = usernames
+            .iter()
+            .find(|(id, _)| Some(*id) == model.user_id)
+            .map(|(_, username)| username.as_str());
+        CsvService::record(ticket, assignee, model.created_at, model.updated_at)
+    }))
+    .map_err(|e| DbErr::Custom(e.to_string()))?;
+    Ok((csv, models.len() as u64))
+}
+
+/// Creates a ticket for each row of the CSV file, all of them or none. A dry run only
+/// validates the rows.
+async fn import_tickets(
+    Extension(store): Extension<Client>,
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    Query(options): Query<ImportOptions>,
+    WithRejection(Json(import), _): WithRejection<Json<TicketImportDto>, ApiError>,
+) -> Result<Json<TicketImportResultDto>, ApiError> {
+    let (rows, mut row_errors) =
+        CsvService::read(&import).map_err(|errors| import_rejection(errors, RowErrors::new()))?;
+
+    let project_ids = rows
+        .iter()
+        .filter_map(|row| row.ticket.project_id)
+        .collect::<BTreeSet<u64>>();
+    let found: Vec<u64> = Project::find_active()
+        .select_only()
+        .column(projects::Column::Id)
+        .filter(projects::Column::Id.is_in(project_ids))
+        .into_tuple()
+        .all(&*db)
+        .await?;
+    // Projects the user does not contribute to are not found either
+    let mut projects = Vec::with_capacity(found.len());
+    for project_id in found {
+        if MembershipService::role(&*db, &auth_user, project_id)
+            .await?
+            .is_some_and(|role| role >= ProjectRole::Contributor)
+        {
+            projects.push(project_id);
+        }
+    }
+    let usernames = rows
+        .iter()
+        .filter_map(|row| row.assignee.clone())
+        .collect::<BTreeSet<String>>();
+    let users: Vec<(u64, String)> = User::find_active()
+        .select_only()
+        .columns([users::Column::Id, users::Column::Username])
+        .filter(users::Column::Username.is_in(usernames))
+        .into_tuple()
+        .all(&*db)
+        .await?;
+    let mut accepted = Vec::with_capacity(rows.len());
+    for row in rows {
+        let mut messages = Vec::new();
+        if let Some(project_id) = row.ticket.project_id.filter(|p| !projects.contains(p)) {
+            messages.push(format!("Project not found: {}", project_id));
+        }
+        let user_id = row.assignee.as_ref().and_then(|username| {
+            let user_id = users
+                .iter()
+                .find(|(_, u)| u.eq_ignore_ascii_case(username))
+                .map(|(id, _)| *id);
+            if user_id.is_none() {
+                messages.push(format!("User not found: '{}'", username));
+            }
+            user_id
+        });
+        match messages.is_empty() {
+            true => accepted.push((row.ticket, user_id)),
+            false => row_errors.entry(row.line).or_default().extend(messages),
+        }
+    }
+    if !row_errors.is_empty() {
+        return Err(import_rejection(Vec::new(), row_errors));
+    }
+    if options.dry_run {
+        return Ok(Json(TicketImportResultDto {
+            dry_run: true,
+            rows: accepted.len() as u64,
+            ids: Vec::new(),
+        }));
+    }
+
+    let txn = db.begin().await?;
+    let mut ids = Vec::with_capacity(accepted.len());
+    for (ticket, user_id) in accepted.iter() {
+        let board_rank = match ticket.project_id {
+            Some(project_id) => RankService::last(&txn, project_id).await?,
+            None => String::new(),
+        };
+        let created = tickets::ActiveModel {
+            title: Set(ticket.title.to_owned()),
+            description: Set(ticket.description.to_owned()),
+            project_id: Set(ticket.project_id),
+            status: Set(ticket.status.to_string()),
+            user_id: Set(*user_id),
+            priority: Set(Some(ticket.priority.0.clone())),
+            reporter_id: Set(Some(auth_user.id)),
+            board_rank: Set(board_rank),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        if let Some(project_id) = created.project_id {
+            TicketKeyService::assign(&txn, created.id, project_id).await?;
+        }
+        LabelService::save(&txn, created.id, &ticket.labels).await?;
+        ids.push(created.id);
+    }
+    txn.commit().await?;
+
+    // Like created tickets, imported ones are watched by their reporter and assignee
+    WatcherService::auto_watch(&store, &*db, &ids, &[auth_user.id]).await?;
+    let assignees = accepted
+        .iter()
+        .filter_map(|(_, user_id)| *user_id)
+        .collect::<BTreeSet<u64>>();
+    for assignee in assignees {
+        let assigned = accepted
+            .iter()
+            .zip(ids.iter())
+            .filter(|((_, user_id), _)| *user_id == Some(assignee))
+            .map(|(_, id)| *id)
+            .collect::<Vec<u64>>();
+        WatcherService::auto_watch(&store, &*db, &assigned, &[assignee]).await?;
+    }
+
+    Ok(Json(TicketImportResultDto {
+        dry_run: false,
+        rows: ids.len() as u64,
+        ids,
+    }))
+}
+
+fn import_rejection(errors: Vec<String>, rows: RowErrors) -> ApiError {
+    ApiError::from(JsonError::from((
+        StatusCode::UNPROCESSABLE_ENTITY,
+        String::from("Import Error"),
+        String::from("import_rejection"),
+        CsvService::error_detail(errors, rows),
+    )))
+}
+
 fn sort_to_column(s: &str) -> Option<tickets::Column> {
     match s {
         "id" => Some(tickets::Column::Id),
@@ -96,18 +403,21 @@ fn sort_to_column(s: &str) -> Option<tickets::Column> {
         "description" => Some(tickets::Column::Description),
         "priority" => Some(tickets::Column::Priority),
         "status" => Some(tickets::Column::Status),
+        "created_at" => Some(tickets::Column::CreatedAt),
+        "updated_at" => Some(tickets::Column::UpdatedAt),
+        "rank" => Some(tickets::Column::BoardRank),
         _ => None,
     }
 }
 
 async fn get_unassigned_tickets(
     db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
 ) -> Result<Json<Vec<TicketDto>>, ApiError> {
-    let list = Ticket::find()
-        .filter(
-            Condition::all()
-                .add(<entity::prelude::Tickets as EntityTrait>::Column::ProjectId.is_null()),
-        )
+    let list = Ticket::find_active()
+        .filter(TicketFilter::unassigned_preset().to_condition())
+        .filter(MembershipService::visible_tickets(&auth_user))
+        .order_by(tickets::Column::Id, Order::Asc)
         .all(&*db)
         .await?;
     Ok(Json(
@@ -117,9 +427,25 @@ async fn get_unassigned_tickets(
 
 async fn get_ticket(
     db: Extension<DatabaseConnection>,
-    WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
-) -> Result<Json<TicketDto>, ApiError> {
-    Ticket::find()
+    TicketPath(id): TicketPath,
+) -> Result<Versioned<TicketDto>, ApiError> {
+    let Some(ticket) = find_ticket(&*db, id).await? else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        ));
+    };
+    let mut dto = TicketDto::from(ticket);
+    CustomFieldService::attach(&*db, std::slice::from_mut(&mut dto)).await?;
+    LabelService::attach(&*db, std::slice::from_mut(&mut dto)).await?;
+    Ok(Versioned(dto.version, dto))
+}
+
+async fn find_ticket<C: ConnectionTrait>(
+    db: &C,
+    id: u64,
+) -> Result<Option<TicketQueryResult>, DbErr> {
+    Ticket::find_active()
         .filter(tickets::Column::Id.eq(id))
         .columns([
             tickets::Column::Id,
@@ -128,69 +454,224 @@ async fn get_ticket(
             tickets::Column::ProjectId,
             tickets::Column::Status,
             tickets::Column::Priority,
+            tickets::Column::TicketKey,
+            tickets::Column::Version,
+            tickets::Column::SprintId,
+            tickets::Column::ParentId,
         ])
         .column_as(users::Column::PublicId, "user_id")
         .join(sea_orm::JoinType::LeftJoin, tickets::Relation::Users.def())
         .into_model::<TicketQueryResult>()
-        .one(&*db)
-        .await?
-        .map_or(
-            Err(ApiError::new(
-                StatusCode::NOT_FOUND,
-                String::from("Not found"),
-            )),
-            |ticket| Ok(Json(ticket.into())),
-        )
+        .one(db)
+        .await
 }
 
 async fn post_ticket(
+    Extension(store): Extension<Client>,
     db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
     WithRejection(Json(model), _): WithRejection<Json<TicketDto>, ApiError>,
 ) -> Result<Json<TicketDto>, ApiError> {
     println!("Ticket(): '{}'", model.title);
 
-    let user_id = User::find()
+    let user_id = User::find_active()
         .select_only()
         .column(users::Column::Id)
         .filter(users::Column::PublicId.eq(model.user_id))
         .into_tuple()
         .one(&*db)
         .await?;
+    if let Some(project_id) = model.project_id {
+        MembershipService::require(&*db, &auth_user, project_id, ProjectRole::Contributor).await?;
+    }
+    CustomFieldService::validate(&*db, model.project_id, &model.fields).await?;
+    SprintService::validate(&*db, model.project_id, model.sprint_id, None).await?;
+    ParentService::validate(&*db, model.project_id, None, model.parent_id, None).await?;
+    LabelService::validate(&model.labels)?;
 
-    let ticket = tickets::ActiveModel {
+    let txn = db.begin().await?;
+    let board_rank = match model.project_id {
+        Some(project_id) => RankService::last(&txn, project_id).await?,
+        None => String::new(),
+    };
+    let mut ticket = tickets::ActiveModel {
         title: Set(model.title.to_owned()),
         description: Set(model.description.to_owned()),
         project_id: Set(model.project_id.to_owned()),
         status: Set(model.status.to_string()),
         user_id: Set(user_id),
         priority: Set(Some(model.priority.0)),
+        reporter_id: Set(Some(auth_user.id)),
+        sprint_id: Set(model.sprint_id),
+        board_rank: Set(board_rank),
+        parent_id: Set(model.parent_id),
         ..Default::default()
     }
-    .insert(&*db)
+    .insert(&txn)
     .await?;
-    Ok(Json(ticket.into()))
+    if let Some(project_id) = ticket.project_id {
+        ticket.ticket_key = Some(TicketKeyService::assign(&txn, ticket.id, project_id).await?);
+    }
+    CustomFieldService::save(&txn, ticket.id, &model.fields).await?;
+    let labels = LabelService::save(&txn, ticket.id, &model.labels).await?;
+    txn.commit().await?;
+    // The reporter and the assignee watch the ticket
+    let watchers = [Some(auth_user.id), user_id]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<u64>>();
+    WatcherService::auto_watch(&store, &*db, &[ticket.id], &watchers).await?;
+    Ok(Json(TicketDto {
+        labels,
+        fields: model.fields,
+        ..ticket.into()
+    }))
 }
 
 async fn put_ticket(
     Extension(store): Extension<Client>,
     db: Extension<DatabaseConnection>,
     Extension(auth_user): Extension<users::Model>,
-    WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
-    WithRejection(Json(update), _): WithRejection<Json<TicketDto>, ApiError>,
-) -> Result<Json<TicketDto>, ApiError> {
-    let original_result = Ticket::find_by_id(id).one(&*db).await?;
+    TicketPath(id): TicketPath,
+    if_match: IfMatch,
+    WithRejection(Json(mut body), _): WithRejection<Json<Value>, ApiError>,
+) -> Result<Versioned<TicketDto>, ApiError> {
+    keep_absent_members(&*db, id, &mut body).await?;
+    let update = serde_json::from_value::<TicketDto>(body)
+        .map_err(|e| ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, e.to_string()))?;
+    update_ticket(store, &db, auth_user, id, if_match, update).await
+}
+
+/// Labels and custom field values missing from a full update keep their current values
+/// instead of being cleared, clients which do not know about them cannot remove them.
+async fn keep_absent_members<C: ConnectionTrait>(
+    db: &C,
+    id: u64,
+    body: &mut Value,
+) -> Result<(), ApiError> {
+    let Value::Object(members) = body else {
+        return Ok(());
+    };
+    if members.contains_key("labels") && members.contains_key("fields") {
+        return Ok(());
+    }
+    // A missing ticket is reported by the update
+    let Some(current) = find_ticket(db, id).await? else {
+        return Ok(());
+    };
+    let mut current = TicketDto::from(current);
+    CustomFieldService::attach(db, std::slice::from_mut(&mut current)).await?;
+    LabelService::attach(db, std::slice::from_mut(&mut current)).await?;
+    if !members.contains_key("labels") {
+        members.insert(String::from("labels"), json!(current.labels));
+    }
+    if !members.contains_key("fields") {
+        members.insert(String::from("fields"), json!(current.fields));
+    }
+    Ok(())
+}
+
+/// Changes only the fields present in the merge patch. The merged ticket is validated and
+/// saved like a full update.
+async fn patch_ticket(
+    Extension(store): Extension<Client>,
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    TicketPath(id): TicketPath,
+    if_match: IfMatch,
+    WithRejection(patch, _): WithRejection<MergePatch, ApiError>,
+) -> Result<Versioned<TicketDto>, ApiError> {
+    let Some(current) = find_ticket(&*db, id).await? else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        ));
+    };
+    let mut current = TicketDto::from(current);
+    CustomFieldService::attach(&*db, std::slice::from_mut(&mut current)).await?;
+    LabelService::attach(&*db, std::slice::from_mut(&mut current)).await?;
+    let update = patch.apply_validated(&current)?;
+    update_ticket(store, &db, auth_user, id, if_match, update).await
+}
+
+async fn update_ticket(
+    store: Client,
+    db: &DatabaseConnection,
+    auth_user: users::Model,
+    id: u64,
+    if_match: IfMatch,
+    update: TicketDto,
+) -> Result<Versioned<TicketDto>, ApiError> {
+    let original_result = Ticket::find_active()
+        .filter(tickets::Column::Id.eq(id))
+        .one(db)
+        .await?;
 
-    let user_id = User::find()
+    let user_id = User::find_active()
         .select_only()
         .column(users::Column::Id)
         .filter(users::Column::PublicId.eq(update.user_id))
         .into_tuple()
-        .one(&*db)
+        .one(db)
         .await?;
 
     match original_result {
         Some(original) => {
-            let updated = tickets::ActiveModel {
+            let version = if_match.check(original.version)?;
+            let previous = TicketStatus::from_str(original.status.as_str()).unwrap_or_default();
+            TicketValidation::transition_validation(
+                previous,
+                update.status,
+                update.project_id.is_some(),
+            )
+            .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e))?;
+            // Moving the ticket is working on the other project as well
+            if let Some(project_id) = update
+                .project_id
+                .filter(|p| original.project_id != Some(*p))
+            {
+                MembershipService::require(db, &auth_user, project_id, ProjectRole::Contributor)
+                    .await?;
+            }
+            CustomFieldService::validate(db, update.project_id, &update.fields).await?;
+            SprintService::validate(db, update.project_id, update.sprint_id, original.sprint_id)
+                .await?;
+            // The previous parent belongs to the previous project
+            let previous_parent = original
+                .parent_id
+                .filter(|_| original.project_id == update.project_id);
+            ParentService::validate(
+                db,
+                update.project_id,
+                Some(id),
+                update.parent_id,
+                previous_parent,
+            )
+            .await?;
+            LabelService::validate(&update.labels)?;
+            if let Some(project_id) = update.project_id {
+                // A ticket from another project is not on this board yet
+                let on_board = original.project_id == Some(project_id);
+                let previous = on_board.then_some(previous);
+                BoardService::check_wip_limit(db, project_id, id, previous, update.status)
+                    .await?
+                    .map_err(|e| ApiError::new(StatusCode::CONFLICT, e))?;
+            }
+
+            let txn = db.begin().await?;
+            // Children stay behind in the previous project
+            if original.project_id != update.project_id {
+                ParentService::detach(&txn, &[original.id]).await?;
+            }
+            // Tickets joining a project go to the end of its board
+            let board_rank = match update.project_id {
+                Some(project_id) if original.project_id != Some(project_id) => {
+                    Set(RankService::last(&txn, project_id).await?)
+                }
+                _ => ActiveValue::NotSet,
+            };
+            // Somebody else may have saved since the version check above
+            let mut updated = Ticket::update(tickets::ActiveModel {
                 id: Set(original.id),
                 title: Set(update.title.to_owned()),
                 description: Set(update.description.to_owned()),
@@ -198,14 +679,43 @@ async fn put_ticket(
                 project_id: Set(update.project_id),
                 user_id: Set(user_id),
                 priority: Set(Some(update.priority.0)),
+                sprint_id: Set(update.sprint_id),
+                board_rank,
+                parent_id: Set(update.parent_id),
+                version: Set(version + 1),
                 ..Default::default()
+            })
+            .filter(tickets::Column::Version.eq(version))
+            .exec(&txn)
+            .await
+            .map_err(version_conflict)?;
+            TicketUpdateService::record(&txn, original.id, previous, update.status, auth_user.id)
+                .await?;
+            if let Some(project_id) = updated
+                .project_id
+                .filter(|p| original.project_id != Some(*p))
+            {
+                updated.ticket_key =
+                    Some(TicketKeyService::assign(&txn, original.id, project_id).await?);
             }
-            .update(&*db)
-            .await?;
+            CustomFieldService::save(&txn, original.id, &update.fields).await?;
+            let labels = LabelService::save(&txn, original.id, &update.labels).await?;
+            txn.commit().await?;
 
+            // A new assignee starts watching before the update email goes out
+            if let Some(assignee) = user_id.filter(|u| original.user_id != Some(*u)) {
+                WatcherService::auto_watch(&store, db, &[original.id], &[assignee]).await?;
+            }
             notify_subscribers(store, auth_user.clone(), updated.clone());
 
-            Ok(Json(updated.into()))
+            Ok(Versioned(
+                updated.version,
+                TicketDto {
+                    labels,
+                    fields: update.fields,
+                    ..updated.into()
+                },
+            ))
         }
         None => Err(ApiError::new(
             StatusCode::NOT_FOUND,
@@ -214,33 +724,359 @@ async fn put_ticket(
     }
 }
 
+/// Changes the position of the ticket on the board of its project. The position is not part of
+/// the content of the ticket, so the version stays the same.
+async fn move_ticket(
+    db: Extension<DatabaseConnection>,
+    TicketPath(id): TicketPath,
+    WithRejection(Json(request), _): WithRejection<Json<MoveTicketDto>, ApiError>,
+) -> Result<Json<TicketDto>, ApiError> {
+    let Some(ticket) = Ticket::find_active()
+        .filter(tickets::Column::Id.eq(id))
+        .one(&*db)
+        .await?
+    else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        ));
+    };
+    let Some(project_id) = ticket.project_id else {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            String::from("Only tickets of a project have a position on its board"),
+        ));
+    };
+    let txn = db.begin().await?;
+    let after = find_neighbour(&txn, project_id, request.after).await?;
+    let before = find_neighbour(&txn, project_id, request.before).await?;
+    RankService::move_between(&txn, &ticket, after.as_ref(), before.as_ref()).await?;
+    txn.commit().await?;
+    let Some(moved) = find_ticket(&*db, id).await? else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        ));
+    };
+    Ok(Json(moved.into()))
+}
+
+/// A neighbour has to be on the same board as the moved ticket.
+async fn find_neighbour<C: ConnectionTrait>(
+    db: &C,
+    project_id: u64,
+    id: Option<u64>,
+) -> Result<Option<tickets::Model>, ApiError> {
+    let Some(id) = id else {
+        return Ok(None);
+    };
+    Ticket::find_active()
+        .filter(tickets::Column::Id.eq(id))
+        .filter(tickets::Column::ProjectId.eq(project_id))
+        .one(db)
+        .await?
+        .map(Some)
+        .ok_or(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            String::from("Neighbour not found in the project of the ticket"),
+        ))
+}
+
+/// Moves the ticket to the trash, it is purged after the retention period.
 async fn delete_ticket(
     db: Extension<DatabaseConnection>,
-    WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
+    Extension(auth_user): Extension<users::Model>,
+    TicketPath(id): TicketPath,
 ) -> impl IntoResponse {
-    tickets::ActiveModel {
-        id: sea_orm::ActiveValue::Set(id),
+    Ticket::update_many()
+        .col_expr(tickets::Column::DeletedAt, Expr::value(Utc::now()))
+        .col_expr(tickets::Column::DeletedBy, Expr::value(auth_user.id))
+        .filter(tickets::Column::Id.eq(id))
+        .filter(tickets::Column::DeletedAt.is_null())
+        .exec(&*db)
+        .await
+        .map_or_else(
+            |e| JsonError::from((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())).into_response(),
+            |UpdateResult { rows_affected }| match rows_affected {
+                0 => JsonError::from((StatusCode::NOT_FOUND, String::from("Not found")))
+                    .into_response(),
+                n => JsonError::from((StatusCode::NO_CONTENT, format!("Deleted {}", n)))
+                    .into_response(),
+            },
+        )
+}
+
+/// Applies one action to many tickets in a single transaction. Every ticket is validated
+/// like a single update would be; tickets failing validation are reported and left untouched,
+/// while a database error rolls back the whole request.
+async fn post_tickets_bulk(
+    Extension(store): Extension<Client>,
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    WithRejection(ValidatedJson(request), _): WithRejection<
+        ValidatedJson<BulkTicketRequest>,
+        ApiError,
+    >,
+) -> Result<Json<BulkTicketResponse>, ApiError> {
+    let txn = db.begin().await?;
+
+    let assignee_id = match request.action {
+        BulkAction::Assignee(Some(public_id)) => Some(
+            User::find_active()
+                .select_only()
+                .column(users::Column::Id)
+                .filter(users::Column::PublicId.eq(public_id))
+                .into_tuple()
+                .one(&txn)
+                .await?
+                .ok_or(ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    String::from("User not found"),
+                ))?,
+        ),
+        _ => None,
+    };
+    if let BulkAction::Project(Some(project_id)) = request.action {
+        if Project::find_active()
+            .filter(projects::Column::Id.eq(project_id))
+            .one(&txn)
+            .await?
+            .is_none()
+        {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                String::from("Project not found"),
+            ));
+        }
+        MembershipService::require(&txn, &auth_user, project_id, ProjectRole::Contributor).await?;
+    }
+
+    let tickets = Ticket::find_active()
+        .filter(tickets::Column::Id.is_in(request.ids.clone()))
+        .all(&txn)
+        .await?;
+    let mut roles = HashMap::new();
+    let project_ids = tickets
+        .iter()
+        .filter_map(|ticket| ticket.project_id)
+        .collect::<BTreeSet<u64>>();
+    for project_id in project_ids {
+        let role = MembershipService::role(&txn, &auth_user, project_id).await?;
+        roles.insert(project_id, role);
+    }
+    let mut results = Vec::with_capacity(request.ids.len());
+    for id in request.ids.iter() {
+        let ticket = tickets.iter().find(|ticket| ticket.id == *id);
+        let role = ticket
+            .and_then(|ticket| ticket.project_id)
+            .map(|project_id| roles.get(&project_id).copied().flatten());
+        let outcome = match (ticket, role) {
+            (None, _) | (_, Some(None)) => Err(String::from("Not found")),
+            (_, Some(Some(role))) if role < ProjectRole::Contributor => Err(String::from(
+                "Only project members with the contributor role may do this",
+            )),
+            (Some(ticket), _) => {
+                apply_bulk_action(&txn, ticket, &request.action, assignee_id, auth_user.id).await?
+            }
+        };
+        results.push(BulkItemResult {
+            id: *id,
+            success: outcome.is_ok(),
+            error: outcome.err(),
+        });
+    }
+
+    txn.commit().await?;
+
+    let changed = tickets
+        .iter()
+        .filter(|ticket| results.iter().any(|r| r.id == ticket.id && r.success))
+        .cloned()
+        .collect::<Vec<tickets::Model>>();
+    if let Some(assignee) = assignee_id {
+        let ids = changed.iter().map(|ticket| ticket.id).collect::<Vec<u64>>();
+        WatcherService::auto_watch(&store, &*db, &ids, &[assignee]).await?;
+    }
+    notify_bulk_subscribers(store, auth_user, request.action, changed);
+
+    Ok(Json(results.into()))
+}
+
+/// Open tickets resembling the ticket about to be created, to report it there instead.
+async fn post_similar_tickets(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    WithRejection(Json(draft), _): WithRejection<Json<TicketDraftDto>, ApiError>,
+) -> Result<Json<Vec<SimilarTicketDto>>, ApiError> {
+    if let Some(project_id) = draft.project_id {
+        MembershipService::require(&*db, &auth_user, project_id, ProjectRole::Viewer).await?;
+    }
+    Ok(Json(SimilarityService::similar(&*db, &draft).await?))
+}
+
+/// Reports the draft as a duplicate of the ticket instead of creating it. The draft becomes a
+/// comment of the ticket and the reporter watches it. Only the open tickets of the draft's
+/// project qualify, the same ones suggested as similar.
+async fn post_duplicate(
+    Extension(store): Extension<Client>,
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    TicketPath(id): TicketPath,
+    WithRejection(ValidatedJson(draft), _): WithRejection<ValidatedJson<TicketDraftDto>, ApiError>,
+) -> Result<Json<TicketDto>, ApiError> {
+    let Some(ticket) = Ticket::find_active()
+        .filter(tickets::Column::Id.eq(id))
+        .one(&*db)
+        .await?
+    else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        ));
+    };
+    if ticket.project_id != draft.project_id {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            String::from("The ticket belongs to another project"),
+        ));
+    }
+    let status = TicketStatus::from_str(ticket.status.as_str()).unwrap_or_default();
+    if status.is_finished() {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            String::from("The ticket is finished already"),
+        ));
+    }
+    comments::ActiveModel {
+        text: Set(format!(
+            "Reported again as a duplicate: {}\n\n{}",
+            draft.title, draft.description
+        )),
+        ticket_id: Set(ticket.id),
+        user_id: Set(auth_user.id),
         ..Default::default()
     }
-    .delete(&*db)
-    .await
-    .map_or_else(
-        |e| JsonError::from((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())).into_response(),
-        |DeleteResult { rows_affected }| match rows_affected {
-            0 => {
-                JsonError::from((StatusCode::NOT_FOUND, String::from("Not found"))).into_response()
+    .insert(&*db)
+    .await?;
+    WatcherService::auto_watch(&store, &*db, &[ticket.id], &[auth_user.id]).await?;
+    Ok(Json(ticket.into()))
+}
+
+/// The outer error aborts the transaction, the inner one only fails the given ticket.
+async fn apply_bulk_action(
+    txn: &DatabaseTransaction,
+    ticket: &tickets::Model,
+    action: &BulkAction,
+    assignee_id: Option<u64>,
+    user_id: u64,
+) -> Result<Result<(), String>, DbErr> {
+    let previous = TicketStatus::from_str(ticket.status.as_str()).unwrap_or_default();
+    if previous == TicketStatus::Closed
+        && !matches!(action, BulkAction::Status(_) | BulkAction::Delete)
+    {
+        return Ok(Err(String::from(
+            "Closed tickets have to be reopened before changing them.",
+        )));
+    }
+
+    let mut model = tickets::ActiveModel {
+        id: ActiveValue::Unchanged(ticket.id),
+        ..Default::default()
+    };
+    let mut next = previous;
+    match action {
+        BulkAction::Status(status) => {
+            if let Err(e) = TicketValidation::transition_validation(
+                previous,
+                *status,
+                ticket.project_id.is_some(),
+            ) {
+                return Ok(Err(e));
             }
-            n => {
-                JsonError::from((StatusCode::NO_CONTENT, format!("Deleted {}", n))).into_response()
+            if let Some(project_id) = ticket.project_id {
+                if let Err(e) = BoardService::check_wip_limit(
+                    txn,
+                    project_id,
+                    ticket.id,
+                    Some(previous),
+                    *status,
+                )
+                .await?
+                {
+                    return Ok(Err(e));
+                }
             }
-        },
-    )
+            next = *status;
+        }
+        BulkAction::Priority(priority) => model.priority = Set(Some(priority.0.clone())),
+        BulkAction::Assignee(_) => model.user_id = Set(assignee_id),
+        BulkAction::Project(Some(project_id)) => {
+            model.project_id = Set(Some(*project_id));
+            if previous == TicketStatus::Created {
+                next = TicketStatus::Selected;
+            }
+            if ticket.project_id != Some(*project_id) {
+                TicketKeyService::assign(txn, ticket.id, *project_id).await?;
+                CustomFieldService::clear(txn, ticket.id).await?;
+                ParentService::detach(txn, &[ticket.id]).await?;
+                model.sprint_id = Set(None);
+                model.board_rank = Set(RankService::last(txn, *project_id).await?);
+            }
+        }
+        BulkAction::Project(None) => {
+            model.project_id = Set(None);
+            model.sprint_id = Set(None);
+            next = TicketStatus::Created;
+            CustomFieldService::clear(txn, ticket.id).await?;
+            ParentService::detach(txn, &[ticket.id]).await?;
+        }
+        BulkAction::AddLabel(label) => {
+            let name = label.trim().to_owned();
+            if TicketLabel::find()
+                .filter(ticket_labels::Column::TicketId.eq(ticket.id))
+                .filter(ticket_labels::Column::Name.eq(name.as_str()))
+                .one(txn)
+                .await?
+                .is_none()
+            {
+                ticket_labels::ActiveModel {
+                    ticket_id: Set(ticket.id),
+                    name: Set(name),
+                    ..Default::default()
+                }
+                .insert(txn)
+                .await?;
+            }
+            return Ok(Ok(()));
+        }
+        BulkAction::RemoveLabel(label) => {
+            TicketLabel::delete_many()
+                .filter(ticket_labels::Column::TicketId.eq(ticket.id))
+                .filter(ticket_labels::Column::Name.eq(label.trim()))
+                .exec(txn)
+                .await?;
+            return Ok(Ok(()));
+        }
+        BulkAction::Delete => {
+            model.deleted_at = Set(Some(Utc::now()));
+            model.deleted_by = Set(Some(user_id));
+        }
+    }
+    if next != previous {
+        model.status = Set(next.to_string());
+    }
+    if model.is_changed() {
+        model.version = Set(ticket.version + 1);
+        model.update(txn).await?;
+    }
+    TicketUpdateService::record(txn, ticket.id, previous, next, user_id).await?;
+    Ok(Ok(()))
 }
 
 async fn subscribe_to_ticket(
     Extension(store): Extension<Client>,
     Extension(auth_user): Extension<users::Model>,
-    WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
+    TicketPath(id): TicketPath,
 ) -> impl IntoResponse {
     match store.get_connection() {
         Ok(mut con) => {
@@ -270,7 +1106,7 @@ async fn subscribe_to_ticket(
 async fn is_subscribed(
     Extension(store): Extension<Client>,
     Extension(auth_user): Extension<users::Model>,
-    WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
+    TicketPath(id): TicketPath,
 ) -> impl IntoResponse {
     match store.get_connection() {
         Ok(mut con) => {
@@ -299,7 +1135,7 @@ fn notify_subscribers(store: Client, user: users::Model, ticket: tickets::Model)
         if let Ok(mut con) = store.get_connection() {
             tokio::spawn(async move {
                 // Prepare message
-                let subject = format!("Ticket {} has been updated", ticket.id);
+                let subject = format!("Ticket {} has been updated", ticket_label(&ticket));
                 let body = format!(
                     "Ticket update\nTitle: {}\nStatus: {}\nUpdated by: {}",
                     ticket.title.clone(),
@@ -328,3 +1164,47 @@ fn notify_subscribers(store: Client, user: users::Model, ticket: tickets::Model)
         }
     }
 }
+
+fn notify_bulk_subscribers(
+    store: Client,
+    user: users::Model,
+    action: BulkAction,
+    tickets: Vec<tickets::Model>,
+) {
+    if tickets.is_empty() {
+        return;
+    }
+    if let Ok(mut con) = store.get_connection() {
+        tokio::spawn(async move {
+            let subject = format!("{} tickets have been updated", tickets.len());
+            let body = format!(
+                "Bulk ticket update\n{}\nUpdated by: {}\n\n{}",
+                action,
+                user.username,
+                tickets
+                    .iter()
+                    .map(|ticket| format!("{} {}", ticket_label(ticket), ticket.title))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+            );
+            let ids = tickets.iter().map(|ticket| ticket.id).collect::<Vec<u64>>();
+            if let Err(e) = push_bulk_to_queue::<i64>(
+                &mut con,
+                &ids,
+                user.username.to_string().as_str(),
+                subject,
+                body,
+            ) {
+                tracing::error!("Bulk update message queuing failed: '{}'", e.to_string())
+            }
+        });
+    }
+}
+
+/// The key of the ticket in emails, or its id if it does not belong to a project.
+fn ticket_label(ticket: &tickets::Model) -> String {
+    ticket
+        .ticket_key
+        .clone()
+        .unwrap_or_else(|| format!("#{}", ticket.id))
+}
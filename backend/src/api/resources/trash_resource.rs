@@ -0,0 +1,210 @@
+use crate::api::{
+    auth_utils::require_role,
+    consts::TRASH_RETENTION_DAYS,
+    error::{ApiError, JsonError},
+};
+use axum::{
+    extract::{Json, Path},
+    http::StatusCode,
+    routing::{get, post},
+    Extension, Router,
+};
+use axum_extra::extract::WithRejection;
+use chrono::{DateTime, Duration, Utc};
+use entity::{
+    projects::{self, Entity as Project},
+    tickets::{self, Entity as Ticket},
+    users::{self, Entity as User},
+};
+use sea_orm::{
+    sea_query::Expr, ColumnTrait, DatabaseConnection, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect, TransactionTrait,
+};
+use shared::{
+    dtos::trash_dto::{TrashEntryDto, TrashKind},
+    validation::user_validation::UserRole,
+};
+use std::collections::HashMap;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/trash/{kind}", get(get_trash))
+        .route("/trash/{kind}/{id}/restore", post(restore))
+}
+
+type TrashRow = (u64, String, Option<DateTime<Utc>>, Option<u64>);
+
+/// Deleted entries of the given kind, most recently deleted first.
+async fn get_trash(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    WithRejection(Path(kind), _): WithRejection<Path<TrashKind>, ApiError>,
+) -> Result<Json<Vec<TrashEntryDto>>, ApiError> {
+    require_role(&auth_user, UserRole::Manager)?;
+    let rows: Vec<TrashRow> = match kind {
+        TrashKind::Tickets => {
+            Ticket::find()
+                .select_only()
+                .columns([
+                    tickets::Column::Id,
+                    tickets::Column::Title,
+                    tickets::Column::DeletedAt,
+                    tickets::Column::DeletedBy,
+                ])
+                .filter(tickets::Column::DeletedAt.is_not_null())
+                .order_by_desc(tickets::Column::DeletedAt)
+                .into_tuple()
+                .all(&*db)
+                .await?
+        }
+        TrashKind::Projects => {
+            Project::find()
+                .select_only()
+                .columns([
+                    projects::Column::Id,
+                    projects::Column::Summary,
+                    projects::Column::DeletedAt,
+                    projects::Column::DeletedBy,
+                ])
+                .filter(projects::Column::DeletedAt.is_not_null())
+                .order_by_desc(projects::Column::DeletedAt)
+                .into_tuple()
+                .all(&*db)
+                .await?
+        }
+        TrashKind::Users => {
+            User::find()
+                .select_only()
+                .columns([
+                    users::Column::Id,
+                    users::Column::Name,
+                    users::Column::DeletedAt,
+                    users::Column::DeletedBy,
+                ])
+                .filter(users::Column::DeletedAt.is_not_null())
+                .order_by_desc(users::Column::DeletedAt)
+                .into_tuple()
+                .all(&*db)
+                .await?
+        }
+    };
+
+    // Deleters may be in the trash themselves, so every user is considered here
+    let names: HashMap<u64, String> = User::find()
+        .select_only()
+        .columns([users::Column::Id, users::Column::Name])
+        .filter(users::Column::Id.is_in(rows.iter().filter_map(|row| row.3)))
+        .into_tuple::<(u64, String)>()
+        .all(&*db)
+        .await?
+        .into_iter()
+        .collect();
+
+    Ok(Json(
+        rows.into_iter()
+            .filter_map(|(id, label, deleted_at, deleted_by)| {
+                deleted_at.map(|deleted_at| TrashEntryDto {
+                    id,
+                    kind,
+                    label,
+                    deleted_at,
+                    deleted_by: deleted_by.and_then(|user_id| names.get(&user_id).cloned()),
+                    purge_at: deleted_at + Duration::days(*TRASH_RETENTION_DAYS),
+                })
+            })
+            .collect(),
+    ))
+}
+
+async fn restore(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    WithRejection(Path((kind, id)), _): WithRejection<Path<(TrashKind, u64)>, ApiError>,
+) -> Result<JsonError, ApiError> {
+    require_role(&auth_user, UserRole::Manager)?;
+    let restored = match kind {
+        TrashKind::Tickets => {
+            let Some(ticket) = Ticket::find_by_id(id)
+                .filter(tickets::Column::DeletedAt.is_not_null())
+                .one(&*db)
+                .await?
+            else {
+                return Err(not_found());
+            };
+            if let Some(project_id) = ticket.project_id {
+                if Project::find_active()
+                    .filter(projects::Column::Id.eq(project_id))
+                    .one(&*db)
+                    .await?
+                    .is_none()
+                {
+                    return Err(ApiError::new(
+                        StatusCode::CONFLICT,
+                        String::from("The project of the ticket is in the trash, restore it first"),
+                    ));
+                }
+            }
+            Ticket::update_many()
+                .col_expr(
+                    tickets::Column::DeletedAt,
+                    Expr::value(None::<DateTime<Utc>>),
+                )
+                .col_expr(tickets::Column::DeletedBy, Expr::value(None::<u64>))
+                .filter(tickets::Column::Id.eq(id))
+                .exec(&*db)
+                .await?
+                .rows_affected
+        }
+        TrashKind::Projects => {
+            let Some(project) = Project::find_by_id(id)
+                .filter(projects::Column::DeletedAt.is_not_null())
+                .one(&*db)
+                .await?
+            else {
+                return Err(not_found());
+            };
+            let txn = db.begin().await?;
+            let restored = Project::update_many()
+                .col_expr(
+                    projects::Column::DeletedAt,
+                    Expr::value(None::<DateTime<Utc>>),
+                )
+                .col_expr(projects::Column::DeletedBy, Expr::value(None::<u64>))
+                .filter(projects::Column::Id.eq(id))
+                .exec(&txn)
+                .await?
+                .rows_affected;
+            // Only the tickets deleted along with the project, not the ones deleted before
+            Ticket::update_many()
+                .col_expr(
+                    tickets::Column::DeletedAt,
+                    Expr::value(None::<DateTime<Utc>>),
+                )
+                .col_expr(tickets::Column::DeletedBy, Expr::value(None::<u64>))
+                .filter(tickets::Column::ProjectId.eq(id))
+                .filter(tickets::Column::DeletedAt.eq(project.deleted_at))
+                .exec(&txn)
+                .await?;
+            txn.commit().await?;
+            restored
+        }
+        TrashKind::Users => {
+            User::update_many()
+                .col_expr(users::Column::DeletedAt, Expr::value(None::<DateTime<Utc>>))
+                .col_expr(users::Column::DeletedBy, Expr::value(None::<u64>))
+                .filter(users::Column::Id.eq(id))
+                .filter(users::Column::DeletedAt.is_not_null())
+                .exec(&*db)
+                .await?
+                .rows_affected
+        }
+    };
+    match restored {
+        0 => Err(not_found()),
+        n => Ok(JsonError::from((StatusCode::OK, format!("Restored {}", n)))),
+    }
+}
+
+fn not_found() -> ApiError {
+    ApiError::new(StatusCode::NOT_FOUND, String::from("Not found"))
+}
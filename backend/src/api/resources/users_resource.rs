@@ -1,5 +1,7 @@
 use crate::api::{
-    error::{ApiError, JsonError},
+    auth_utils::{has_role, require_role},
+    error::{ApiError, AuthError, JsonError},
+    merge_patch::MergePatch,
     query::{
         filters::{pagination::Pagination, search::Search},
         ordering::Ordering,
@@ -10,16 +12,20 @@ use axum::{
     extract::{Json, Path, Query},
     http::StatusCode,
     response::IntoResponse,
-    routing::{delete, get, post, put},
+    routing::{delete, get, patch, post, put},
     Extension, Router,
 };
 use axum_extra::extract::WithRejection;
+use chrono::Utc;
 use entity::{users, users::Entity as User};
 use sea_orm::{
-    ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, DeleteResult, EntityTrait, Order,
-    QueryFilter, QueryOrder, QuerySelect, QueryTrait, Set,
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, Condition, DatabaseConnection, EntityTrait,
+    Order, QueryFilter, QueryOrder, QuerySelect, QueryTrait, Set, UpdateResult,
+};
+use shared::{
+    dtos::user_dto::UserDto,
+    validation::user_validation::{OptionUserRole, UserRole},
 };
-use shared::{dtos::user_dto::UserDto, validation::user_validation::OptionUserRole};
 use uuid::Uuid;
 
 pub fn router() -> Router {
@@ -28,6 +34,7 @@ pub fn router() -> Router {
         .route("/users", get(get_users))
         .route("/users/{id}", get(get_user))
         .route("/users/{id}", put(put_user))
+        .route("/users/{id}", patch(patch_user))
         .route("/users/{id}", delete(delete_user))
 }
 
@@ -38,8 +45,8 @@ async fn get_users(
     Query(ordering): Query<Ordering>,
 ) -> Result<Json<Vec<UserDto>>, ApiError> {
     let mut select = match ordering.sort.and_then(|s| sort_to_column(s.as_str())) {
-        Some(sort) => User::find().order_by::<users::Column>(sort, ordering.order.0),
-        None => User::find().order_by(users::Column::Id, Order::Asc),
+        Some(sort) => User::find_active().order_by::<users::Column>(sort, ordering.order.0),
+        None => User::find_active().order_by(users::Column::Id, Order::Asc),
     };
     select = match search.q {
         Some(q) => select.filter(Condition::all().add(users::Column::Name.contains(q))),
@@ -69,7 +76,7 @@ async fn get_user(
     db: Extension<DatabaseConnection>,
     WithRejection(Path(id), _): WithRejection<Path<Uuid>, ApiError>,
 ) -> Result<Json<UserDto>, ApiError> {
-    User::find()
+    User::find_active()
         .filter(users::Column::PublicId.eq(id))
         .one(&*db)
         .await?
@@ -104,7 +111,10 @@ async fn put_user(
     WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
     WithRejection(Json(update), _): WithRejection<Json<UserDto>, ApiError>,
 ) -> Result<Json<UserDto>, ApiError> {
-    let original_result = User::find_by_id(id).one(&*db).await?;
+    let original_result = User::find_active()
+        .filter(users::Column::Id.eq(id))
+        .one(&*db)
+        .await?;
     match original_result {
         Some(original) => {
             let updated = users::ActiveModel {
@@ -114,6 +124,7 @@ async fn put_user(
                 password: Set(update.password.unwrap().to_owned()),
                 role: Set(update.role.map_or(String::from(""), |r| r.to_string())),
                 public_id: Set(update.public_id.unwrap()),
+                ..Default::default()
             }
             .update(&*db)
             .await?;
@@ -126,25 +137,74 @@ async fn put_user(
     }
 }
 
-async fn delete_user(
+/// Changes only the fields present in the merge patch. The username and the public id stay
+/// the same, the password is kept unless the patch contains a new one. Users change their own
+/// account, managers every account and the roles.
+async fn patch_user(
     db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
     WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
-) -> impl IntoResponse {
-    users::ActiveModel {
-        id: Set(id),
+    WithRejection(patch, _): WithRejection<MergePatch, ApiError>,
+) -> Result<Json<UserDto>, ApiError> {
+    if auth_user.id != id && !has_role(&auth_user, UserRole::Manager) {
+        return Err(AuthError {
+            status: StatusCode::FORBIDDEN,
+            message: String::from("Only managers may change the accounts of other users"),
+            code: None,
+        }
+        .into());
+    }
+    let Some(original) = User::find_active()
+        .filter(users::Column::Id.eq(id))
+        .one(&*db)
+        .await?
+    else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("Not found"),
+        ));
+    };
+    let current = UserDto::from(&original);
+    let role = current.role;
+    let update = patch.apply_validated(&UserDto {
+        password: Some(original.password.clone()),
+        ..current
+    })?;
+    if update.role != role {
+        require_role(&auth_user, UserRole::Manager)?;
+    }
+    let updated = users::ActiveModel {
+        id: Set(original.id),
+        name: Set(update.name.to_owned()),
+        password: Set(update.password.unwrap_or(original.password)),
+        role: Set(OptionUserRole(update.role).to_string()),
         ..Default::default()
     }
-    .delete(&*db)
-    .await
-    .map_or_else(
-        |e| JsonError::from((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())).into_response(),
-        |DeleteResult { rows_affected }| match rows_affected {
-            0 => {
-                JsonError::from((StatusCode::NOT_FOUND, String::from("Not found"))).into_response()
-            }
-            n => {
-                JsonError::from((StatusCode::NO_CONTENT, format!("Deleted {}", n))).into_response()
-            }
-        },
-    )
+    .update(&*db)
+    .await?;
+    Ok(Json(updated.into()))
+}
+
+/// Moves the user to the trash, they cannot log in anymore until restored.
+async fn delete_user(
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    WithRejection(Path(id), _): WithRejection<Path<u64>, ApiError>,
+) -> impl IntoResponse {
+    User::update_many()
+        .col_expr(users::Column::DeletedAt, Expr::value(Utc::now()))
+        .col_expr(users::Column::DeletedBy, Expr::value(auth_user.id))
+        .filter(users::Column::Id.eq(id))
+        .filter(users::Column::DeletedAt.is_null())
+        .exec(&*db)
+        .await
+        .map_or_else(
+            |e| JsonError::from((StatusCode::INTERNAL_SERVER_ERROR, e.to_string())).into_response(),
+            |UpdateResult { rows_affected }| match rows_affected {
+                0 => JsonError::from((StatusCode::NOT_FOUND, String::from("Not found")))
+                    .into_response(),
+                n => JsonError::from((StatusCode::NO_CONTENT, format!("Deleted {}", n)))
+                    .into_response(),
+            },
+        )
 }
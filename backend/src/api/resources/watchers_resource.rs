@@ -0,0 +1,110 @@
+use crate::api::{
+    auth_utils::require_role,
+    error::ApiError,
+    services::{membership_service::MembershipService, watcher_service::WatcherService},
+    ticket_path::TicketPath,
+};
+use axum::{
+    extract::{Json, Path},
+    http::StatusCode,
+    routing::{delete, get, put},
+    Extension, Router,
+};
+use axum_extra::extract::WithRejection;
+use entity::users::{self, Entity as User};
+use redis::{Client, RedisError};
+use sea_orm::{ColumnTrait, DatabaseConnection, QueryFilter, QueryOrder};
+use shared::{
+    dtos::{project_member_dto::ProjectRole, watcher_dto::WatcherDto},
+    validation::user_validation::UserRole,
+};
+use uuid::Uuid;
+
+pub fn router() -> Router {
+    Router::new()
+        .route("/tickets/{id}/watchers", get(get_watchers))
+        .route("/tickets/{id}/watchers/{user_id}", put(put_watcher))
+        .route("/tickets/{id}/watchers/{user_id}", delete(delete_watcher))
+}
+
+/// Users watching the ticket, by name. Usernames without an active user are left out.
+async fn get_watchers(
+    Extension(store): Extension<Client>,
+    db: Extension<DatabaseConnection>,
+    TicketPath(id): TicketPath,
+) -> Result<Json<Vec<WatcherDto>>, ApiError> {
+    let usernames = WatcherService::usernames(&store, id).map_err(store_error)?;
+    if usernames.is_empty() {
+        return Ok(Json(Vec::new()));
+    }
+    let watchers = User::find_active()
+        .filter(users::Column::Username.is_in(usernames))
+        .order_by_asc(users::Column::Name)
+        .all(&*db)
+        .await?
+        .into_iter()
+        .map(|user| WatcherDto {
+            user_id: user.public_id,
+            name: user.name,
+            username: user.username.to_string(),
+        })
+        .collect();
+    Ok(Json(watchers))
+}
+
+/// Adds the user to the watchers. Only managers add somebody else.
+async fn put_watcher(
+    Extension(store): Extension<Client>,
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    TicketPath(id): TicketPath,
+    WithRejection(Path((_, user_id)), _): WithRejection<Path<(String, Uuid)>, ApiError>,
+) -> Result<StatusCode, ApiError> {
+    let user = find_user(&db, &auth_user, user_id).await?;
+    // Watchers get the update emails, so they have to be able to read the ticket
+    MembershipService::require_ticket(&*db, &user, id, ProjectRole::Viewer)
+        .await
+        .map_err(|_| {
+            ApiError::new(
+                StatusCode::BAD_REQUEST,
+                String::from("The user is no member of the project of the ticket"),
+            )
+        })?;
+    WatcherService::watch(&store, id, &[user.username.to_string()]).map_err(store_error)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Removes the user from the watchers. Only managers remove somebody else.
+async fn delete_watcher(
+    Extension(store): Extension<Client>,
+    db: Extension<DatabaseConnection>,
+    Extension(auth_user): Extension<users::Model>,
+    TicketPath(id): TicketPath,
+    WithRejection(Path((_, user_id)), _): WithRejection<Path<(String, Uuid)>, ApiError>,
+) -> Result<StatusCode, ApiError> {
+    let user = find_user(&db, &auth_user, user_id).await?;
+    WatcherService::unwatch(&store, id, user.username.to_string().as_str()).map_err(store_error)?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn find_user(
+    db: &DatabaseConnection,
+    auth_user: &users::Model,
+    user_id: Uuid,
+) -> Result<users::Model, ApiError> {
+    if auth_user.public_id != user_id {
+        require_role(auth_user, UserRole::Manager)?;
+    }
+    User::find_active()
+        .filter(users::Column::PublicId.eq(user_id))
+        .one(db)
+        .await?
+        .ok_or(ApiError::new(
+            StatusCode::NOT_FOUND,
+            String::from("User not found"),
+        ))
+}
+
+fn store_error(e: RedisError) -> ApiError {
+    ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+}
@@ -0,0 +1,888 @@
+use super::{
+    label_service::LabelService, membership_service::MembershipService,
+    ticket_key_service::TicketKeyService,
+};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use entity::{
+    comments::{self, Entity as Comment},
+    project_members::{self, Entity as ProjectMember},
+    projects,
+    sea_orm_active_enums::Priority,
+    sprints::{self, Entity as Sprint},
+    ticket_attachments::{self, Entity as TicketAttachment},
+    ticket_keys::{self, Entity as TicketKey},
+    ticket_updates::{self, Entity as TicketUpdate},
+    tickets::{self, Entity as Ticket},
+    users::{self, Entity as User},
+};
+use object_store::{aws::AmazonS3, ObjectStore, WriteMultipart};
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DbErr,
+    EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+use serde::{Deserialize, Serialize};
+use serde_valid::{validation::Errors, Validate};
+use shared::{
+    api::error::error_detail::ErrorDetail,
+    dtos::{
+        project_archive_dto::{RestoreConflictDto, RestoreConflictKind},
+        project_dto::ProjectDto,
+        project_member_dto::ProjectRole,
+    },
+};
+use std::str::FromStr;
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    io::{Cursor, Read, Write},
+};
+use zip::{write::FileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+pub const ARCHIVE_FORMAT: &str = "project-archive";
+/// Version of the archive format. Archives of older versions are restored, newer ones are
+/// refused.
+pub const ARCHIVE_VERSION: u32 = 2;
+/// Entry of the archive with everything but the attachment files.
+const MANIFEST: &str = "archive.json";
+
+/// The zip file read, to take the attachment files from.
+pub type ArchiveFiles = ZipArchive<Cursor<Vec<u8>>>;
+
+/// A project as written to the archive. Ids are the ones of the exporting instance, they only
+/// connect the entries of the archive.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ProjectArchive {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub project: ArchivedProject,
+    pub users: Vec<ArchivedUser>,
+    /// Added in version 2, projects of older archives only get the owner and the one restoring.
+    #[serde(default)]
+    pub members: Vec<ArchivedMember>,
+    #[serde(default)]
+    pub sprints: Vec<ArchivedSprint>,
+    pub tickets: Vec<ArchivedTicket>,
+    #[serde(default)]
+    pub comments: Vec<ArchivedComment>,
+    #[serde(default)]
+    pub history: Vec<ArchivedUpdate>,
+    #[serde(default)]
+    pub attachments: Vec<ArchivedAttachment>,
+    /// Problems of the export, e.g. attachment files missing in the object store.
+    #[serde(default)]
+    pub warnings: Vec<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ArchivedProject {
+    pub id: u64,
+    pub key: String,
+    pub summary: String,
+    pub deadline: Option<NaiveDate>,
+    pub active: bool,
+    pub user_id: u64,
+    pub ticket_sequence: u64,
+}
+
+/// Users are matched by their username, passwords are not exported.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ArchivedUser {
+    pub id: u64,
+    pub name: String,
+    pub username: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ArchivedMember {
+    pub user_id: u64,
+    pub role: String,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ArchivedSprint {
+    pub id: u64,
+    pub name: String,
+    pub goal: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    pub state: String,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ArchivedTicket {
+    pub id: u64,
+    pub key: Option<String>,
+    pub title: String,
+    pub description: String,
+    pub status: String,
+    pub priority: Option<Priority>,
+    pub user_id: Option<u64>,
+    pub reporter_id: Option<u64>,
+    pub sprint_id: Option<u64>,
+    pub parent_id: Option<u64>,
+    pub board_rank: String,
+    #[serde(default)]
+    pub labels: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ArchivedComment {
+    pub ticket_id: u64,
+    pub user_id: u64,
+    pub text: String,
+    pub timestamp: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ArchivedUpdate {
+    pub ticket_id: u64,
+    pub user_id: u64,
+    pub previous_state: String,
+    pub next_state: String,
+    pub timestamp: NaiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ArchivedAttachment {
+    pub ticket_id: u64,
+    pub file_name: String,
+}
+
+impl ArchivedAttachment {
+    /// Entry of the file in the archive.
+    pub fn entry(&self) -> String {
+        format!("attachments/{}/{}", self.ticket_id, self.file_name)
+    }
+}
+
+/// How the archive is restored, found out before anything is written.
+#[derive(Debug, Default)]
+pub struct RestorePlan {
+    pub key: String,
+    /// Local user of each archived user found by username.
+    pub users: HashMap<u64, u64>,
+    /// Archived tickets keeping their key.
+    pub kept_keys: HashSet<u64>,
+    pub conflicts: Vec<RestoreConflictDto>,
+}
+
+impl RestorePlan {
+    /// A taken project key keeps the archive from being restored.
+    pub fn is_blocked(&self) -> bool {
+        self.conflicts
+            .iter()
+            .any(|conflict| conflict.kind == RestoreConflictKind::ProjectKey)
+    }
+}
+
+/// Local ids of the restored entries by their archived ids.
+#[derive(Debug, Default)]
+pub struct Restored {
+    pub project_id: u64,
+    pub tickets: HashMap<u64, u64>,
+}
+
+pub struct ArchiveService {}
+
+impl ArchiveService {
+    /// The project with its members, its tickets, their comments, history and attachments, and
+    /// the users referenced. Tickets in the trash are not exported.
+    pub async fn collect<C: ConnectionTrait>(
+        db: &C,
+        project: &projects::Model,
+    ) -> Result<ProjectArchive, DbErr> {
+        let tickets = Ticket::find_active()
+            .filter(tickets::Column::ProjectId.eq(project.id))
+            .order_by_asc(tickets::Column::Id)
+            .all(db)
+            .await?;
+        let ticket_ids = tickets.iter().map(|t| t.id).collect::<Vec<u64>>();
+        let members = ProjectMember::find()
+            .filter(project_members::Column::ProjectId.eq(project.id))
+            .order_by_asc(project_members::Column::Id)
+            .all(db)
+            .await?;
+        let mut labels = LabelService::load(db, &ticket_ids).await?;
+        let sprints = Sprint::find()
+            .filter(sprints::Column::ProjectId.eq(project.id))
+            .order_by_asc(sprints::Column::Id)
+            .all(db)
+            .await?;
+        let comments = Comment::find()
+            .filter(comments::Column::TicketId.is_in(ticket_ids.clone()))
+            .order_by_asc(comments::Column::Id)
+            .all(db)
+            .await?;
+        let history = TicketUpdate::find()
+            .filter(ticket_updates::Column::TicketId.is_in(ticket_ids.clone()))
+            .order_by_asc(ticket_updates::Column::Id)
+            .all(db)
+            .await?;
+        let attachments = TicketAttachment::find()
+            .filter(ticket_attachments::Column::TicketId.is_in(ticket_ids))
+            .order_by_asc(ticket_attachments::Column::Id)
+            .all(db)
+            .await?;
+
+        let user_ids = std::iter::once(project.user_id)
+            .chain(members.iter().map(|m| m.user_id))
+            .chain(
+                tickets
+                    .iter()
+                    .flat_map(|t| t.user_id.into_iter().chain(t.reporter_id)),
+            )
+            .chain(comments.iter().map(|c| c.user_id))
+            .chain(history.iter().map(|u| u.user_id))
+            .collect::<BTreeSet<u64>>();
+        let users = User::find()
+            .filter(users::Column::Id.is_in(user_ids))
+            .order_by_asc(users::Column::Id)
+            .all(db)
+            .await?;
+
+        Ok(ProjectArchive {
+            format: String::from(ARCHIVE_FORMAT),
+            version: ARCHIVE_VERSION,
+            exported_at: Utc::now(),
+            project: ArchivedProject {
+                id: project.id,
+                key: project.key.to_owned(),
+                summary: project.summary.to_owned(),
+                deadline: project.deadline,
+                active: project.active != 0,
+                user_id: project.user_id,
+                ticket_sequence: project.ticket_sequence,
+            },
+            users: users
+                .into_iter()
+                .map(|user| ArchivedUser {
+                    id: user.id,
+                    name: user.name,
+                    username: user.username.to_string(),
+                })
+                .collect(),
+            members: members
+                .into_iter()
+                .map(|member| ArchivedMember {
+                    user_id: member.user_id,
+                    role: member.role,
+                })
+                .collect(),
+            sprints: sprints
+                .into_iter()
+                .map(|sprint| ArchivedSprint {
+                    id: sprint.id,
+                    name: sprint.name,
+                    goal: sprint.goal,
+                    start_date: sprint.start_date,
+                    end_date: sprint.end_date,
+                    state: sprint.state,
+                    completed_at: sprint.completed_at,
+                })
+                .collect(),
+            tickets: tickets
+                .into_iter()
+                .map(|ticket| ArchivedTicket {
+                    labels: labels.remove(&ticket.id).unwrap_or_default(),
+                    id: ticket.id,
+                    key: ticket.ticket_key,
+                    title: ticket.title,
+                    description: ticket.description,
+                    status: ticket.status,
+                    priority: ticket.priority,
+                    user_id: ticket.user_id,
+                    reporter_id: ticket.reporter_id,
+                    sprint_id: ticket.sprint_id,
+                    parent_id: ticket.parent_id,
+                    board_rank: ticket.board_rank,
+                    created_at: ticket.created_at,
+                    updated_at: ticket.updated_at,
+                })
+                .collect(),
+            comments: comments
+                .into_iter()
+                .map(|comment| ArchivedComment {
+                    ticket_id: comment.ticket_id,
+                    user_id: comment.user_id,
+                    text: comment.text,
+                    timestamp: comment.timestamp,
+                })
+                .collect(),
+            history: history
+                .into_iter()
+                .map(|update| ArchivedUpdate {
+                    ticket_id: update.ticket_id,
+                    user_id: update.user_id,
+                    previous_state: update.previous_state,
+                    next_state: update.next_state,
+                    timestamp: update.timestamp,
+                })
+                .collect(),
+            attachments: attachments
+                .into_iter()
+                .filter_map(|attachment| {
+                    let file_name = attachment.path.rsplit('/').next()?.to_owned();
+                    Some(ArchivedAttachment {
+                        ticket_id: attachment.ticket_id,
+                        file_name,
+                    })
+                })
+                .collect(),
+            warnings: Vec::new(),
+        })
+    }
+
+    /// Reads the attachment files from the object store. Missing files are left out of the
+    /// archive with a warning, so a lost file does not keep the project from being exported.
+    pub async fn files(bucket: &AmazonS3, archive: &mut ProjectArchive) -> Vec<(String, Vec<u8>)> {
+        let mut files = Vec::new();
+        let mut kept = Vec::new();
+        for attachment in archive.attachments.drain(..) {
+            let raw_path = format!(
+                "tickets/{}/attachments/{}",
+                attachment.ticket_id, attachment.file_name
+            );
+            let bytes = match bucket
+                .get(&object_store::path::Path::from(raw_path.clone()))
+                .await
+            {
+                Ok(result) => result.bytes().await,
+                Err(e) => Err(e),
+            };
+            match bytes {
+                Ok(bytes) => {
+                    files.push((attachment.entry(), bytes.to_vec()));
+                    kept.push(attachment);
+                }
+                Err(e) => archive
+                    .warnings
+                    .push(format!("Attachment {} was not exported: {}", raw_path, e)),
+            }
+        }
+        archive.attachments = kept;
+        files
+    }
+
+    /// Writes the archive with the attachment files as a zip file.
+    pub fn write(
+        archive: &ProjectArchive,
+        files: &[(String, Vec<u8>)],
+    ) -> zip::result::ZipResult<Vec<u8>> {
+        let options = FileOptions::default().compression_method(CompressionMethod::Deflated);
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        zip.start_file(MANIFEST, options)?;
+        zip.write_all(&serde_json::to_vec_pretty(archive).map_err(std::io::Error::from)?)?;
+        for (entry, bytes) in files {
+            zip.start_file(entry.as_str(), options)?;
+            zip.write_all(bytes)?;
+        }
+        Ok(zip.finish()?.into_inner())
+    }
+
+    /// Reads the archive from a zip file written by [`Self::write`].
+    pub fn read(bytes: Vec<u8>) -> Result<(ProjectArchive, ArchiveFiles), String> {
+        let mut zip =
+            ZipArchive::new(Cursor::new(bytes)).map_err(|e| format!("Not a zip file: {}", e))?;
+        let mut manifest = String::new();
+        zip.by_name(MANIFEST)
+            .map_err(|_| format!("The archive has no {}.", MANIFEST))?
+            .read_to_string(&mut manifest)
+            .map_err(|e| e.to_string())?;
+        let archive: ProjectArchive = serde_json::from_str(&manifest)
+            .map_err(|e| format!("The {} is not valid: {}", MANIFEST, e))?;
+        if archive.format != ARCHIVE_FORMAT {
+            return Err(format!("Not a {}: '{}'", ARCHIVE_FORMAT, archive.format));
+        }
+        if archive.version > ARCHIVE_VERSION {
+            return Err(format!(
+                "Archives of version {} are not supported, {} is the latest.",
+                archive.version, ARCHIVE_VERSION
+            ));
+        }
+        Ok((archive, zip))
+    }
+
+    pub fn file(zip: &mut ArchiveFiles, entry: &str) -> Option<Vec<u8>> {
+        let mut file = zip.by_name(entry).ok()?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes).ok()?;
+        Some(bytes)
+    }
+
+    /// Checks the project of the archive restored under the key with the rules of a new
+    /// project. The deadline is left out, one which has passed is restored as it is.
+    pub fn check(archive: &ProjectArchive, key: &str) -> Result<(), String> {
+        let project = ProjectDto {
+            summary: archive.project.summary.to_owned(),
+            key: key.to_owned(),
+            ..Default::default()
+        };
+        match project.validate() {
+            Ok(()) => Ok(()),
+            Err(Errors::Object(mut errors)) => {
+                // Only the members taken from the archive, which leaves the deadline out
+                errors
+                    .properties
+                    .retain(|property, _| property == "summary" || property == "key");
+                match errors.errors.is_empty() && errors.properties.is_empty() {
+                    true => Ok(()),
+                    false => Err(ErrorDetail::from(Errors::Object(errors))
+                        .messages()
+                        .join(" ")),
+                }
+            }
+            Err(errors) => Err(ErrorDetail::from(errors).messages().join(" ")),
+        }
+    }
+
+    /// A ticket keeps its key when the project keeps its key and nobody has it already.
+    pub fn keeps_key(archived_project_key: &str, key: &str, ticket_key: Option<&str>) -> bool {
+        archived_project_key == key
+            && ticket_key.is_some_and(|ticket_key| {
+                ticket_key
+                    .strip_prefix(key)
+                    .and_then(|rest| rest.strip_prefix('-'))
+                    .is_some_and(|number| number.parse::<u64>().is_ok())
+            })
+    }
+
+    /// Finds the conflicts of restoring the archive under the key.
+    pub async fn plan<C: ConnectionTrait>(
+        db: &C,
+        archive: &ProjectArchive,
+        key: &str,
+    ) -> Result<RestorePlan, DbErr> {
+        let mut plan = RestorePlan {
+            key: key.to_owned(),
+            ..Default::default()
+        };
+        if projects::Entity::find()
+            .filter(projects::Column::Key.eq(key))
+            .one(db)
+            .await?
+            .is_some()
+        {
+            plan.conflicts.push(RestoreConflictDto {
+                kind: RestoreConflictKind::ProjectKey,
+                subject: key.to_owned(),
+                message: String::from("A project has the key already, restore under another key."),
+            });
+        }
+
+        let usernames = archive
+            .users
+            .iter()
+            .map(|user| user.username.to_owned())
+            .collect::<Vec<String>>();
+        let local: Vec<(u64, String)> = User::find_active()
+            .select_only()
+            .columns([users::Column::Id, users::Column::Username])
+            .filter(users::Column::Username.is_in(usernames))
+            .into_tuple()
+            .all(db)
+            .await?;
+        for user in archive.users.iter() {
+            match local
+                .iter()
+                .find(|(_, username)| username.eq_ignore_ascii_case(&user.username))
+            {
+                Some((id, _)) => {
+                    plan.users.insert(user.id, *id);
+                }
+                None => plan.conflicts.push(RestoreConflictDto {
+                    kind: RestoreConflictKind::User,
+                    subject: user.username.to_owned(),
+                    message: format!(
+                        "Nobody is {} here, their tickets, comments and history are attributed to you.",
+                        user.name
+                    ),
+                }),
+            }
+        }
+
+        let candidates = archive
+            .tickets
+            .iter()
+            .filter(|t| Self::keeps_key(&archive.project.key, key, t.key.as_deref()))
+            .filter_map(|t| t.key.clone())
+            .collect::<Vec<String>>();
+        let taken: HashSet<String> = match candidates.is_empty() {
+            true => HashSet::new(),
+            false => TicketKey::find()
+                .select_only()
+                .column(ticket_keys::Column::TicketKey)
+                .filter(ticket_keys::Column::TicketKey.is_in(candidates))
+                .into_tuple::<String>()
+                .all(db)
+                .await?
+                .into_iter()
+                .collect(),
+        };
+        for ticket in archive.tickets.iter() {
+            match (&ticket.key, Self::keeps_key(&archive.project.key, key, ticket.key.as_deref())) {
+                (Some(ticket_key), true) if taken.contains(ticket_key) => {
+                    plan.conflicts.push(RestoreConflictDto {
+                        kind: RestoreConflictKind::TicketKey,
+                        subject: ticket_key.to_owned(),
+                        message: String::from("A ticket has the key already, the next key of the project is given instead."),
+                    })
+                }
+                (_, true) => {
+                    plan.kept_keys.insert(ticket.id);
+                }
+                _ => (),
+            }
+        }
+        plan.conflicts
+            .extend(archive.warnings.iter().map(|warning| RestoreConflictDto {
+                kind: RestoreConflictKind::Attachment,
+                subject: String::new(),
+                message: warning.to_owned(),
+            }));
+        Ok(plan)
+    }
+
+    /// Creates the project and its entries under new ids. Has to run in one transaction, a
+    /// failed restore leaves nothing behind.
+    pub async fn restore<C: ConnectionTrait>(
+        db: &C,
+        archive: &ProjectArchive,
+        plan: &RestorePlan,
+        user_id: u64,
+    ) -> Result<Restored, DbErr> {
+        let user = |id: u64| plan.users.get(&id).copied().unwrap_or(user_id);
+        let kept_keys = archive
+            .tickets
+            .iter()
+            .any(|t| plan.kept_keys.contains(&t.id));
+        let project = projects::ActiveModel {
+            key: Set(plan.key.to_owned()),
+            summary: Set(archive.project.summary.to_owned()),
+            deadline: Set(archive.project.deadline),
+            user_id: Set(user(archive.project.user_id)),
+            active: Set(archive.project.active as i8),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+
+        // Members not found here are left out, the owner and the one restoring administer it
+        for member in archive.members.iter() {
+            if let (Some(member_id), Ok(role)) = (
+                plan.users.get(&member.user_id),
+                ProjectRole::from_str(&member.role),
+            ) {
+                MembershipService::set_role(db, project.id, *member_id, role).await?;
+            }
+        }
+        for admin in BTreeSet::from([project.user_id, user_id]) {
+            MembershipService::set_role(db, project.id, admin, ProjectRole::Admin).await?;
+        }
+
+        let mut sprint_ids = HashMap::new();
+        for sprint in archive.sprints.iter() {
+            let saved = sprints::ActiveModel {
+                project_id: Set(project.id),
+                name: Set(sprint.name.to_owned()),
+                goal: Set(sprint.goal.to_owned()),
+                start_date: Set(sprint.start_date),
+                end_date: Set(sprint.end_date),
+                state: Set(sprint.state.to_owned()),
+                completed_at: Set(sprint.completed_at),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+            sprint_ids.insert(sprint.id, saved.id);
+        }
+
+        let mut restored = Restored {
+            project_id: project.id,
+            tickets: HashMap::new(),
+        };
+        for ticket in archive.tickets.iter() {
+            let key = ticket
+                .key
+                .clone()
+                .filter(|_| plan.kept_keys.contains(&ticket.id));
+            let saved = tickets::ActiveModel {
+                title: Set(ticket.title.to_owned()),
+                description: Set(ticket.description.to_owned()),
+                project_id: Set(Some(project.id)),
+                status: Set(ticket.status.to_owned()),
+                // Users not found here are no assignees, but still the reporters
+                user_id: Set(ticket.user_id.and_then(|id| plan.users.get(&id).copied())),
+                priority: Set(ticket.priority.clone()),
+                reporter_id: Set(ticket.reporter_id.map(user)),
+                sprint_id: Set(ticket.sprint_id.and_then(|id| sprint_ids.get(&id).copied())),
+                board_rank: Set(ticket.board_rank.to_owned()),
+                ticket_key: Set(key.clone()),
+                created_at: Set(ticket.created_at),
+                updated_at: Set(ticket.updated_at),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+            if let Some(key) = key {
+                ticket_keys::ActiveModel {
+                    ticket_key: Set(key),
+                    ticket_id: Set(saved.id),
+                    ..Default::default()
+                }
+                .insert(db)
+                .await?;
+            }
+            LabelService::save(db, saved.id, &ticket.labels).await?;
+            restored.tickets.insert(ticket.id, saved.id);
+        }
+
+        // Parents outside of the project are not restored
+        for ticket in archive.tickets.iter() {
+            let Some(parent_id) = ticket
+                .parent_id
+                .and_then(|id| restored.tickets.get(&id).copied())
+            else {
+                continue;
+            };
+            tickets::ActiveModel {
+                id: ActiveValue::Unchanged(restored.tickets[&ticket.id]),
+                parent_id: Set(Some(parent_id)),
+                ..Default::default()
+            }
+            .update(db)
+            .await?;
+        }
+
+        // New keys continue after the kept ones
+        if kept_keys {
+            projects::Entity::update_many()
+                .col_expr(
+                    projects::Column::TicketSequence,
+                    Expr::value(archive.project.ticket_sequence),
+                )
+                .filter(projects::Column::Id.eq(project.id))
+                .exec(db)
+                .await?;
+        }
+        for ticket in archive
+            .tickets
+            .iter()
+            .filter(|t| !plan.kept_keys.contains(&t.id))
+        {
+            TicketKeyService::assign(db, restored.tickets[&ticket.id], project.id).await?;
+        }
+
+        for comment in archive.comments.iter() {
+            let Some(ticket_id) = restored.tickets.get(&comment.ticket_id) else {
+                continue;
+            };
+            comments::ActiveModel {
+                text: Set(comment.text.to_owned()),
+                ticket_id: Set(*ticket_id),
+                user_id: Set(user(comment.user_id)),
+                timestamp: Set(comment.timestamp),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+        }
+        for update in archive.history.iter() {
+            let Some(ticket_id) = restored.tickets.get(&update.ticket_id) else {
+                continue;
+            };
+            ticket_updates::ActiveModel {
+                ticket_id: Set(*ticket_id),
+                user_id: Set(user(update.user_id)),
+                previous_state: Set(update.previous_state.to_owned()),
+                next_state: Set(update.next_state.to_owned()),
+                timestamp: Set(update.timestamp),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+        }
+        Ok(restored)
+    }
+
+    /// Stores the attachment files of the restored tickets. Files which cannot be stored are
+    /// reported, the project is restored already.
+    pub async fn restore_attachments<C: ConnectionTrait>(
+        db: &C,
+        bucket: Option<&AmazonS3>,
+        zip: &mut ArchiveFiles,
+        archive: &ProjectArchive,
+        restored: &Restored,
+    ) -> Result<(u32, Vec<RestoreConflictDto>), DbErr> {
+        let mut count = 0;
+        let mut conflicts = Vec::new();
+        for attachment in archive.attachments.iter() {
+            let Some(ticket_id) = restored.tickets.get(&attachment.ticket_id) else {
+                continue;
+            };
+            let conflict = |message: String| RestoreConflictDto {
+                kind: RestoreConflictKind::Attachment,
+                subject: attachment.file_name.to_owned(),
+                message,
+            };
+            let Some(bytes) = Self::file(zip, &attachment.entry()) else {
+                conflicts.push(conflict(String::from("The file is not in the archive.")));
+                continue;
+            };
+            let Some(bucket) = bucket else {
+                conflicts.push(conflict(String::from("The object store is not available.")));
+                continue;
+            };
+            let raw_path = format!("tickets/{}/attachments/{}", ticket_id, attachment.file_name);
+            let stored = match bucket
+                .put_multipart(&object_store::path::Path::from(raw_path.clone()))
+                .await
+            {
+                Ok(upload) => {
+                    let mut writer = WriteMultipart::new(upload);
+                    writer.write(&bytes);
+                    writer.finish().await.map(|_| ())
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = stored {
+                conflicts.push(conflict(e.to_string()));
+                continue;
+            }
+            ticket_attachments::ActiveModel {
+                ticket_id: Set(*ticket_id),
+                local_path: Set(None),
+                path: Set(raw_path),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+            count += 1;
+        }
+        Ok((count, conflicts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn archive() -> ProjectArchive {
+        let now = Utc::now();
+        ProjectArchive {
+            format: String::from(ARCHIVE_FORMAT),
+            version: ARCHIVE_VERSION,
+            exported_at: now,
+            project: ArchivedProject {
+                id: 3,
+                key: String::from("OPS"),
+                summary: String::from("Operations"),
+                deadline: None,
+                active: true,
+                user_id: 1,
+                ticket_sequence: 2,
+            },
+            users: vec![ArchivedUser {
+                id: 1,
+                name: String::from("Dev"),
+                username: String::from("dev@example.com"),
+            }],
+            members: vec![ArchivedMember {
+                user_id: 1,
+                role: String::from("admin"),
+            }],
+            sprints: Vec::new(),
+            tickets: vec![ArchivedTicket {
+                id: 7,
+                key: Some(String::from("OPS-2")),
+                title: String::from("Login page crashes"),
+                description: String::from("Error 500 every time"),
+                status: String::from("Created"),
+                priority: Some(Priority::High),
+                user_id: Some(1),
+                reporter_id: Some(1),
+                sprint_id: None,
+                parent_id: None,
+                board_rank: String::from("m"),
+                labels: vec![String::from("auth")],
+                created_at: now,
+                updated_at: now,
+            }],
+            comments: Vec::new(),
+            history: Vec::new(),
+            attachments: vec![ArchivedAttachment {
+                ticket_id: 7,
+                file_name: String::from("trace.log"),
+            }],
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_write_read() {
+        let archive = archive();
+        let bytes = ArchiveService::write(
+            &archive,
+            &[(archive.attachments[0].entry(), b"stack trace".to_vec())],
+        )
+        .unwrap();
+        let (read, mut zip) = ArchiveService::read(bytes).unwrap();
+        assert_eq!(read, archive);
+        assert_eq!(
+            ArchiveService::file(&mut zip, "attachments/7/trace.log"),
+            Some(b"stack trace".to_vec())
+        );
+        assert_eq!(
+            ArchiveService::file(&mut zip, "attachments/7/missing"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_read_errors() {
+        assert!(ArchiveService::read(b"not a zip".to_vec()).is_err());
+        let mut newer = archive();
+        newer.version = ARCHIVE_VERSION + 1;
+        let bytes = ArchiveService::write(&newer, &[]).unwrap();
+        assert!(
+            ArchiveService::read(bytes).is_err(),
+            "Archives of a newer version should be refused."
+        );
+        let mut other = archive();
+        other.format = String::from("something-else");
+        assert!(ArchiveService::read(ArchiveService::write(&other, &[]).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        let mut archive = archive();
+        assert_eq!(ArchiveService::check(&archive, "OPS"), Ok(()));
+        assert_eq!(ArchiveService::check(&archive, "OPS2"), Ok(()));
+        assert!(ArchiveService::check(&archive, "ops").is_err());
+        archive.project.deadline = Some(NaiveDate::from_ymd_opt(2020, 1, 31).unwrap());
+        assert_eq!(
+            ArchiveService::check(&archive, "OPS"),
+            Ok(()),
+            "A deadline which has passed should not block restoring."
+        );
+        archive.project.summary = String::from("Ops");
+        assert!(ArchiveService::check(&archive, "OPS").is_err_and(|e| e.starts_with("summary: ")));
+    }
+
+    #[test]
+    fn test_keeps_key() {
+        assert!(ArchiveService::keeps_key("OPS", "OPS", Some("OPS-12")));
+        assert!(
+            !ArchiveService::keeps_key("OPS", "OPS2", Some("OPS-12")),
+            "Tickets of a project restored under another key should get new keys."
+        );
+        assert!(
+            !ArchiveService::keeps_key("OPS", "OPS", Some("WEB-3")),
+            "Keys from before a move should not be kept."
+        );
+        assert!(!ArchiveService::keeps_key("OPS", "OPS", Some("OPSX-3")));
+        assert!(!ArchiveService::keeps_key("OPS", "OPS", None));
+    }
+}
@@ -0,0 +1,65 @@
+use entity::{
+    board_columns::{self, Entity as BoardColumn},
+    tickets::{self, Entity as Ticket},
+};
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder,
+};
+use shared::{
+    dtos::board_dto::{BoardColumnDto, BoardDto},
+    validation::ticket_validation::TicketStatus,
+};
+
+pub struct BoardService {}
+
+impl BoardService {
+    /// Columns configured for the project, or the standard board if there are none.
+    pub async fn board<C: ConnectionTrait>(db: &C, project_id: u64) -> Result<BoardDto, DbErr> {
+        let columns = BoardColumn::find()
+            .filter(board_columns::Column::ProjectId.eq(project_id))
+            .order_by_asc(board_columns::Column::Position)
+            .order_by_asc(board_columns::Column::Id)
+            .all(db)
+            .await?;
+        match columns.is_empty() {
+            true => Ok(BoardDto::standard()),
+            false => Ok(BoardDto {
+                columns: columns.into_iter().map(BoardColumnDto::from).collect(),
+            }),
+        }
+    }
+
+    /// Checks whether the ticket can move from one status to another without exceeding an
+    /// enforced limit. Moves within a column do not change the number of its tickets. The
+    /// previous status is `None` for tickets joining the board from another project. The outer
+    /// error is a database failure, the inner one explains the rejection.
+    pub async fn check_wip_limit<C: ConnectionTrait>(
+        db: &C,
+        project_id: u64,
+        ticket_id: u64,
+        previous: Option<TicketStatus>,
+        next: TicketStatus,
+    ) -> Result<Result<(), String>, DbErr> {
+        let board = Self::board(db, project_id).await?;
+        let Some(column) = board
+            .column_of(&next)
+            .filter(|c| c.wip_enforced && !previous.is_some_and(|previous| c.contains(&previous)))
+        else {
+            return Ok(Ok(()));
+        };
+        let count = Ticket::find_active()
+            .filter(tickets::Column::ProjectId.eq(project_id))
+            .filter(tickets::Column::Status.is_in(column.statuses.iter().map(|s| s.to_string())))
+            .filter(tickets::Column::Id.ne(ticket_id))
+            .count(db)
+            .await?;
+        match column.is_full(count as usize) {
+            true => Ok(Err(format!(
+                "The column {} has reached its limit of {} tickets.",
+                column.name,
+                column.wip_limit.unwrap_or_default()
+            ))),
+            false => Ok(Ok(())),
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use chrono::{DateTime, Utc};
+use csv::{ReaderBuilder, Writer};
+use serde_valid::Validate;
+use shared::{
+    api::error::{error_detail::ErrorDetail, property_error::PropertyError},
+    dtos::{
+        ticket_csv_dto::{TicketColumn, TicketImportDto},
+        ticket_dto::TicketDto,
+    },
+    validation::ticket_validation::{TicketPriority, TicketStatus, TicketValidation},
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+use strum::IntoEnumIterator;
+
+/// Separator of the labels within their column.
+pub const LABEL_SEPARATOR: char = ';';
+
+/// Error messages of the rows, by the line they start at.
+pub type RowErrors = BTreeMap<u64, Vec<String>>;
+
+/// A row accepted for import, with its assignee by username.
+pub struct ImportRow {
+    pub line: u64,
+    pub ticket: TicketDto,
+    pub assignee: Option<String>,
+}
+
+pub struct CsvService {}
+
+impl CsvService {
+    /// Columns of the export. Id, key and timestamps are not imported.
+    pub fn header() -> Vec<String> {
+        ["id", "key"]
+            .into_iter()
+            .map(String::from)
+            .chain(TicketColumn::iter().map(|column| column.to_string()))
+            .chain(["created_at", "updated_at"].into_iter().map(String::from))
+            .collect()
+    }
+
+    pub fn record(
+        ticket: &TicketDto,
+        assignee: Option<&str>,
+        created_at: DateTime<Utc>,
+        updated_at: DateTime<Utc>,
+    ) -> Vec<String> {
+        let id = ticket.id.map_or(String::new(), |id| id.to_string());
+        let key = ticket.key.clone().unwrap_or_default();
+        [id, key]
+            .into_iter()
+            .chain(TicketColumn::iter().map(|column| match column {
+                TicketColumn::Title => ticket.title.clone(),
+                TicketColumn::Description => ticket.description.clone(),
+                TicketColumn::Status => ticket.status.to_string(),
+                TicketColumn::Priority => ticket.priority.to_string(),
+                TicketColumn::Project => {
+                    ticket.project_id.map_or(String::new(), |id| id.to_string())
+                }
+                TicketColumn::Assignee => assignee.unwrap_or_default().to_owned(),
+                TicketColumn::Labels => ticket.labels.join(&LABEL_SEPARATOR.to_string()),
+            }))
+            .chain([created_at.to_rfc3339(), updated_at.to_rfc3339()])
+            .collect()
+    }
+
+    pub fn write<I: IntoIterator<Item = Vec<String>>>(records: I) -> Result<Vec<u8>, csv::Error> {
+        let mut writer = Writer::from_writer(Vec::new());
+        for record in records {
+            writer.write_record(record)?;
+        }
+        writer.into_inner().map_err(|e| e.into_error().into())
+    }
+
+    /// Reads the rows of the file. Rows the ticket does not accept are reported by line, a file
+    /// which cannot be imported at all fails with the reasons.
+    pub fn read(import: &TicketImportDto) -> Result<(Vec<ImportRow>, RowErrors), Vec<String>> {
+        let mut reader = ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(import.csv.as_bytes());
+        let headers = reader.headers().map_err(|e| vec![e.to_string()])?.clone();
+        let columns = Self::columns(
+            &headers.iter().map(String::from).collect::<Vec<String>>(),
+            &import.mapping,
+        )?;
+
+        let mut rows = Vec::new();
+        let mut errors = RowErrors::new();
+        for result in reader.records() {
+            let record = match result {
+                Ok(record) => record,
+                Err(e) => {
+                    let line = e.position().map_or(0, |p| p.line());
+                    errors.entry(line).or_default().push(e.to_string());
+                    continue;
+                }
+            };
+            let line = record.position().map_or(0, |p| p.line());
+            let value = |column: TicketColumn| {
+                columns
+                    .get(&column)
+                    .and_then(|i| record.get(*i))
+                    .filter(|v| !v.is_empty())
+            };
+            let mut messages = Vec::new();
+            let status = match value(TicketColumn::Status) {
+                Some(status) => TicketStatus::from_str(status).unwrap_or_else(|_| {
+                    messages.push(format!("Unknown status: '{}'", status));
+                    TicketStatus::default()
+                }),
+                None => TicketStatus::default(),
+            };
+            let priority = match value(TicketColumn::Priority) {
+                Some(priority) => TicketPriority::try_from(priority).map_err(|e| messages.push(e)),
+                None => Ok(TicketDto::default().priority),
+            }
+            .unwrap_or(TicketDto::default().priority);
+            let project_id = match value(TicketColumn::Project) {
+                Some(project) => project
+                    .parse::<u64>()
+                    .map_err(|_| messages.push(format!("Project should be an id: '{}'", project)))
+                    .ok(),
+                None => import.project_id,
+            };
+            let ticket = TicketDto {
+                title: value(TicketColumn::Title).unwrap_or_default().to_owned(),
+                description: value(TicketColumn::Description)
+                    .unwrap_or_default()
+                    .to_owned(),
+                status,
+                priority,
+                project_id,
+                labels: value(TicketColumn::Labels)
+                    .map(|labels| {
+                        labels
+                            .split(LABEL_SEPARATOR)
+                            .map(str::trim)
+                            .filter(|label| !label.is_empty())
+                            .map(String::from)
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                ..Default::default()
+            };
+            if let Err(e) = ticket.validate() {
+                messages.extend(ErrorDetail::from(e).messages());
+            }
+            if let Err(e) = TicketValidation::transition_validation(
+                TicketStatus::default(),
+                ticket.status,
+                ticket.project_id.is_some(),
+            ) {
+                messages.push(e);
+            }
+            match messages.is_empty() {
+                true => rows.push(ImportRow {
+                    line,
+                    ticket,
+                    assignee: value(TicketColumn::Assignee).map(String::from),
+                }),
+                false => errors.entry(line).or_default().extend(messages),
+            }
+        }
+        Ok((rows, errors))
+    }
+
+    /// Index of the header imported into each column.
+    fn columns(
+        headers: &[String],
+        mapping: &BTreeMap<String, TicketColumn>,
+    ) -> Result<BTreeMap<TicketColumn, usize>, Vec<String>> {
+        let mut errors = mapping
+            .keys()
+            .filter(|header| !headers.contains(header))
+            .map(|header| format!("The file has no column '{}'.", header))
+            .collect::<Vec<String>>();
+        let mut columns = BTreeMap::new();
+        for (i, header) in headers.iter().enumerate() {
+            let column = match mapping.is_empty() {
+                true => TicketColumn::from_str(header).ok(),
+                false => mapping.get(header).copied(),
+            };
+            if let Some(column) = column {
+                if columns.insert(column, i).is_some() {
+                    errors.push(format!("More than one column is imported as {}.", column));
+                }
+            }
+        }
+        for required in [TicketColumn::Title, TicketColumn::Description] {
+            if !columns.contains_key(&required) {
+                errors.push(format!("No column is imported as {}.", required));
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(columns),
+            false => Err(errors),
+        }
+    }
+
+    /// Errors of the file, and of the rows under the `row.<line>` properties.
+    pub fn error_detail(errors: Vec<String>, rows: RowErrors) -> ErrorDetail {
+        ErrorDetail {
+            errors: (!errors.is_empty()).then_some(errors),
+            properties: (!rows.is_empty()).then(|| {
+                rows.into_iter()
+                    .map(|(line, messages)| {
+                        (format!("row.{}", line), PropertyError::from(messages))
+                    })
+                    .collect::<HashMap<String, PropertyError>>()
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import(csv: &str) -> TicketImportDto {
+        TicketImportDto {
+            csv: String::from(csv),
+            project_id: Some(1),
+            mapping: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_read() {
+        let (rows, errors) = CsvService::read(&import(
+            "Title,Description,Priority,Labels,Comment\n\
+             \"Login page crashes\",\"Error 500, every time\",High,ui; auth,ignored\n\
+             \"Export invoices\",\"As PDF for the accounting\",,,\n",
+        ))
+        .unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].line, 2);
+        assert_eq!(rows[0].ticket.description, "Error 500, every time");
+        assert_eq!(rows[0].ticket.priority.to_string(), "High");
+        assert_eq!(rows[0].ticket.labels, vec!["ui", "auth"]);
+        assert_eq!(rows[0].ticket.project_id, Some(1));
+        assert_eq!(rows[1].ticket.priority, TicketDto::default().priority);
+    }
+
+    #[test]
+    fn test_read_errors() {
+        assert!(
+            CsvService::read(&import("Summary,Details\nLogin page crashes,Every time\n")).is_err(),
+            "Files without title and description columns should be rejected."
+        );
+        let (rows, errors) = CsvService::read(&import(
+            "Title,Description,Status\nShort,Error 500 every time,Started\nLogin page crashes,Error 500 every time,Sleeping\n",
+        ))
+        .unwrap();
+        assert!(rows.is_empty());
+        assert_eq!(errors.keys().copied().collect::<Vec<u64>>(), vec![2, 3]);
+        assert!(errors[&2].iter().any(|m| m.starts_with("title: ")));
+        assert_eq!(errors[&3], vec!["Unknown status: 'Sleeping'"]);
+    }
+
+    #[test]
+    fn test_mapping() {
+        let mut import =
+            import("Summary,Details,Notes\nLogin page crashes,Error 500 every time,x\n");
+        import.mapping = BTreeMap::from([
+            (String::from("Summary"), TicketColumn::Title),
+            (String::from("Details"), TicketColumn::Description),
+        ]);
+        let (rows, _) = CsvService::read(&import).unwrap();
+        assert_eq!(rows[0].ticket.title, "Login page crashes");
+        import
+            .mapping
+            .insert(String::from("Missing"), TicketColumn::Labels);
+        assert_eq!(
+            CsvService::read(&import).err(),
+            Some(vec![String::from("The file has no column 'Missing'.")])
+        );
+    }
+
+    #[test]
+    fn test_export_imports() {
+        let ticket = TicketDto {
+            id: Some(7),
+            title: String::from("Login page crashes"),
+            description: String::from("Error 500, \"every\" time"),
+            status: TicketStatus::Started,
+            project_id: Some(3),
+            labels: vec![String::from("auth"), String::from("ui")],
+            ..Default::default()
+        };
+        let now = Utc::now();
+        let csv = CsvService::write([
+            CsvService::header(),
+            CsvService::record(&ticket, Some("dev@example.com"), now, now),
+        ])
+        .unwrap();
+        let (rows, errors) = CsvService::read(&import(&String::from_utf8(csv).unwrap())).unwrap();
+        assert!(errors.is_empty());
+        assert_eq!(rows[0].ticket.description, ticket.description);
+        assert_eq!(rows[0].ticket.status, TicketStatus::Started);
+        assert_eq!(rows[0].ticket.project_id, Some(3));
+        assert_eq!(rows[0].ticket.labels, ticket.labels);
+        assert_eq!(rows[0].assignee.as_deref(), Some("dev@example.com"));
+    }
+}
@@ -0,0 +1,199 @@
+use crate::api::error::{ApiError, JsonError};
+use axum::http::StatusCode;
+use entity::{
+    custom_fields::{self, Entity as CustomField},
+    ticket_field_values::{self, Entity as TicketFieldValue},
+    tickets,
+    users::{self, Entity as User},
+};
+use sea_orm::{
+    sea_query::{Expr, Func, Query, SimpleExpr, SubQueryStatement},
+    ColumnTrait, ConnectionTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter, QueryOrder, Set,
+};
+use shared::{
+    api::error::{error_detail::ErrorDetail, property_error::PropertyError},
+    dtos::{
+        custom_field_dto::{CustomFieldDto, CustomFieldType},
+        ticket_dto::TicketDto,
+    },
+    validation::custom_field_validation::CustomFieldValidation,
+};
+use std::collections::{BTreeMap, HashMap};
+use uuid::Uuid;
+
+/// Values of the custom fields of a ticket, by field id.
+pub type FieldValues = BTreeMap<u64, Vec<String>>;
+
+pub struct CustomFieldService {}
+
+impl CustomFieldService {
+    /// Fields of the project, in the order they were created.
+    pub async fn definitions<C: ConnectionTrait>(
+        db: &C,
+        project_id: u64,
+    ) -> Result<Vec<CustomFieldDto>, DbErr> {
+        Ok(CustomField::find()
+            .filter(custom_fields::Column::ProjectId.eq(project_id))
+            .order_by_asc(custom_fields::Column::Id)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(CustomFieldDto::from)
+            .collect())
+    }
+
+    /// Checks the values against the fields of the project of the ticket. Only tickets
+    /// belonging to a project can have values.
+    pub async fn validate<C: ConnectionTrait>(
+        db: &C,
+        project_id: Option<u64>,
+        values: &FieldValues,
+    ) -> Result<(), ApiError> {
+        let fields = match project_id {
+            Some(project_id) => Self::definitions(db, project_id).await?,
+            None => Vec::new(),
+        };
+        CustomFieldValidation::values_validation(&fields, values).map_err(invalid_values)?;
+        for field in fields
+            .iter()
+            .filter(|f| f.field_type == CustomFieldType::User)
+        {
+            let public_ids = field
+                .id
+                .and_then(|id| values.get(&id))
+                .map_or(Vec::new(), |v| {
+                    v.iter()
+                        .filter_map(|s| Uuid::parse_str(s).ok())
+                        .collect::<Vec<Uuid>>()
+                });
+            if public_ids.is_empty() {
+                continue;
+            }
+            let found = User::find_active()
+                .filter(users::Column::PublicId.is_in(public_ids.clone()))
+                .count(db)
+                .await?;
+            if found < public_ids.len() as u64 {
+                return Err(invalid_values(vec![format!(
+                    "{}: User not found.",
+                    field.name
+                )]));
+            }
+        }
+        Ok(())
+    }
+
+    /// Replaces the values of the ticket.
+    pub async fn save<C: ConnectionTrait>(
+        db: &C,
+        ticket_id: u64,
+        values: &FieldValues,
+    ) -> Result<(), DbErr> {
+        Self::clear(db, ticket_id).await?;
+        let rows = values
+            .iter()
+            .flat_map(|(field_id, values)| {
+                values.iter().map(|value| ticket_field_values::ActiveModel {
+                    ticket_id: Set(ticket_id),
+                    field_id: Set(*field_id),
+                    value: Set(value.to_owned()),
+                    ..Default::default()
+                })
+            })
+            .collect::<Vec<ticket_field_values::ActiveModel>>();
+        if !rows.is_empty() {
+            TicketFieldValue::insert_many(rows).exec(db).await?;
+        }
+        Ok(())
+    }
+
+    /// Removes the values of the ticket, e.g. when it leaves the project defining the fields.
+    pub async fn clear<C: ConnectionTrait>(db: &C, ticket_id: u64) -> Result<(), DbErr> {
+        TicketFieldValue::delete_many()
+            .filter(ticket_field_values::Column::TicketId.eq(ticket_id))
+            .exec(db)
+            .await?;
+        Ok(())
+    }
+
+    /// Values of the tickets, by ticket id. Tickets without values are left out.
+    pub async fn load<C: ConnectionTrait>(
+        db: &C,
+        ticket_ids: &[u64],
+    ) -> Result<HashMap<u64, FieldValues>, DbErr> {
+        let mut result: HashMap<u64, FieldValues> = HashMap::new();
+        if ticket_ids.is_empty() {
+            return Ok(result);
+        }
+        for value in TicketFieldValue::find()
+            .filter(ticket_field_values::Column::TicketId.is_in(ticket_ids.to_vec()))
+            .order_by_asc(ticket_field_values::Column::Id)
+            .all(db)
+            .await?
+        {
+            result
+                .entry(value.ticket_id)
+                .or_default()
+                .entry(value.field_id)
+                .or_default()
+                .push(value.value);
+        }
+        Ok(result)
+    }
+
+    /// Fills in the values of the tickets.
+    pub async fn attach<C: ConnectionTrait>(
+        db: &C,
+        tickets: &mut [TicketDto],
+    ) -> Result<(), DbErr> {
+        let ids = tickets.iter().filter_map(|t| t.id).collect::<Vec<u64>>();
+        let mut values = Self::load(db, &ids).await?;
+        for ticket in tickets.iter_mut() {
+            if let Some(fields) = ticket.id.and_then(|id| values.remove(&id)) {
+                ticket.fields = fields;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sorts the tickets by their smallest value of the field, numerically for number fields.
+    /// Dates are stored as `YYYY-MM-DD`, so they sort like text.
+    pub fn sort_expr(field: &CustomFieldDto) -> SimpleExpr {
+        let value = Expr::col((TicketFieldValue, ticket_field_values::Column::Value));
+        let smallest: SimpleExpr = match field.field_type {
+            CustomFieldType::Number => {
+                Expr::cust_with_expr("MIN(CAST($1 AS DECIMAL(30, 10)))", value)
+            }
+            _ => Func::min(value).into(),
+        };
+        let query = Query::select()
+            .expr(smallest)
+            .from(TicketFieldValue)
+            .and_where(
+                Expr::col((TicketFieldValue, ticket_field_values::Column::FieldId))
+                    .eq(field.id.unwrap_or_default()),
+            )
+            .and_where(
+                Expr::col((TicketFieldValue, ticket_field_values::Column::TicketId))
+                    .equals((tickets::Entity, tickets::Column::Id)),
+            )
+            .to_owned();
+        SimpleExpr::SubQuery(None, Box::new(SubQueryStatement::SelectStatement(query)))
+    }
+}
+
+/// Same shape as the validation errors of the request body, under the `fields` property.
+fn invalid_values(errors: Vec<String>) -> ApiError {
+    ApiError::from(JsonError::from((
+        StatusCode::UNPROCESSABLE_ENTITY,
+        String::from("Validation Error"),
+        String::from("validation_rejection"),
+        ErrorDetail {
+            errors: None,
+            properties: Some(HashMap::from([(
+                String::from("fields"),
+                PropertyError::from(errors),
+            )])),
+        },
+    )))
+}
@@ -0,0 +1,230 @@
+use chrono::{NaiveDate, Utc};
+use entity::{
+    deadline_reminders::{self, Entity as DeadlineReminder},
+    project_members::{self, Entity as ProjectMember},
+    projects,
+    tickets::{self, Entity as Ticket},
+    users::{self, Entity as User},
+};
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QuerySelect, QueryTrait, Set,
+};
+use shared::validation::ticket_validation::TicketStatus;
+use std::{fmt::Display, str::FromStr};
+use strum::IntoEnumIterator;
+
+/// A reminder of a project deadline, sent once per deadline.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeadlineReminderKind {
+    /// The deadline is at most the lead time of days ahead.
+    Ahead(i64),
+    /// The deadline passed with tickets still open.
+    Overdue,
+}
+
+impl Display for DeadlineReminderKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeadlineReminderKind::Ahead(days) => write!(f, "days:{}", days),
+            DeadlineReminderKind::Overdue => write!(f, "overdue"),
+        }
+    }
+}
+
+pub struct DeadlineService {}
+
+impl DeadlineService {
+    /// The reminder due for a deadline `days_left` days ahead, which is the one of the
+    /// shortest lead time not shorter than the days left. Reminders missed while the worker
+    /// was down are thereby caught up with once, instead of once per lead time.
+    pub fn due(
+        lead_times: &[i64],
+        days_left: i64,
+        open_tickets: u64,
+    ) -> Option<DeadlineReminderKind> {
+        match days_left < 0 {
+            true => (open_tickets > 0).then_some(DeadlineReminderKind::Overdue),
+            false => lead_times
+                .iter()
+                .filter(|days| **days >= days_left)
+                .min()
+                .map(|days| DeadlineReminderKind::Ahead(*days)),
+        }
+    }
+
+    /// Records the reminder of the deadline, `false` if it was sent before.
+    pub async fn record<C: ConnectionTrait>(
+        db: &C,
+        project_id: u64,
+        deadline: NaiveDate,
+        kind: DeadlineReminderKind,
+    ) -> Result<bool, DbErr> {
+        let exists = DeadlineReminder::find()
+            .filter(deadline_reminders::Column::ProjectId.eq(project_id))
+            .filter(deadline_reminders::Column::Deadline.eq(deadline))
+            .filter(deadline_reminders::Column::Kind.eq(kind.to_string()))
+            .count(db)
+            .await?
+            > 0;
+        if !exists {
+            deadline_reminders::ActiveModel {
+                project_id: Set(project_id),
+                deadline: Set(deadline),
+                kind: Set(kind.to_string()),
+                created_at: Set(Utc::now()),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+        }
+        Ok(!exists)
+    }
+
+    /// Unfinished tickets of the project per status, in the order of the workflow.
+    pub async fn open_tickets<C: ConnectionTrait>(
+        db: &C,
+        project_id: u64,
+    ) -> Result<Vec<(TicketStatus, u64)>, DbErr> {
+        let counts: Vec<(String, i64)> = Ticket::find_active()
+            .select_only()
+            .column(tickets::Column::Status)
+            .column_as(tickets::Column::Id.count(), "count")
+            .filter(tickets::Column::ProjectId.eq(project_id))
+            .filter(
+                tickets::Column::Status.is_not_in(
+                    TicketStatus::iter()
+                        .filter(|s| s.is_finished())
+                        .map(|s| s.to_string()),
+                ),
+            )
+            .group_by(tickets::Column::Status)
+            .into_tuple()
+            .all(db)
+            .await?;
+        let mut counts = counts
+            .into_iter()
+            .filter_map(|(status, count)| {
+                TicketStatus::from_str(&status)
+                    .ok()
+                    .map(|status| (status, count as u64))
+            })
+            .collect::<Vec<(TicketStatus, u64)>>();
+        counts.sort_by_key(|(status, _)| TicketStatus::iter().position(|s| s == *status));
+        Ok(counts)
+    }
+
+    /// The owner and the members of the project, users in the trash are left out.
+    pub async fn recipients<C: ConnectionTrait>(
+        db: &C,
+        project: &projects::Model,
+    ) -> Result<Vec<users::Model>, DbErr> {
+        User::find_active()
+            .filter(
+                users::Column::Id
+                    .eq(project.user_id)
+                    .or(users::Column::Id.in_subquery(
+                        ProjectMember::find()
+                            .select_only()
+                            .column(project_members::Column::UserId)
+                            .filter(project_members::Column::ProjectId.eq(project.id))
+                            .into_query(),
+                    )),
+            )
+            .all(db)
+            .await
+    }
+
+    /// Subject and body of the reminder email.
+    pub fn message(
+        project: &projects::Model,
+        deadline: NaiveDate,
+        today: NaiveDate,
+        open_tickets: &[(TicketStatus, u64)],
+    ) -> (String, String) {
+        let days_left = (deadline - today).num_days();
+        let due = match days_left {
+            d if d < 0 => format!("was due {} day(s) ago", -d),
+            0 => String::from("is due today"),
+            d => format!("is due in {} day(s)", d),
+        };
+        let subject = format!("[{}] Deadline {}: {}", project.key, due, project.summary);
+        let total: u64 = open_tickets.iter().map(|(_, count)| count).sum();
+        let mut body = format!(
+            "The deadline of project {} '{}' on {} {}.\n",
+            project.key,
+            project.summary,
+            deadline.format("%Y-%m-%d"),
+            due
+        );
+        match total {
+            0 => body.push_str("All tickets are finished."),
+            _ => {
+                body.push_str(format!("{} ticket(s) remain:\n", total).as_str());
+                for (status, count) in open_tickets {
+                    body.push_str(format!("- {}: {}\n", status, count).as_str());
+                }
+            }
+        }
+        (subject, body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_due() {
+        let lead_times = [7, 1];
+        assert_eq!(DeadlineService::due(&lead_times, 8, 3), None);
+        assert_eq!(
+            DeadlineService::due(&lead_times, 7, 3),
+            Some(DeadlineReminderKind::Ahead(7))
+        );
+        assert_eq!(
+            DeadlineService::due(&lead_times, 4, 0),
+            Some(DeadlineReminderKind::Ahead(7)),
+            "A missed reminder should be caught up with."
+        );
+        assert_eq!(
+            DeadlineService::due(&lead_times, 0, 3),
+            Some(DeadlineReminderKind::Ahead(1))
+        );
+        assert_eq!(
+            DeadlineService::due(&lead_times, -1, 3),
+            Some(DeadlineReminderKind::Overdue)
+        );
+        assert_eq!(
+            DeadlineService::due(&lead_times, -1, 0),
+            None,
+            "Finished projects should not be reported overdue."
+        );
+        assert_eq!(DeadlineService::due(&[], 0, 3), None);
+    }
+
+    #[test]
+    fn test_message() {
+        let deadline = NaiveDate::from_ymd_opt(2024, 4, 10).unwrap();
+        let project = projects::Model {
+            id: 1,
+            summary: String::from("Web shop"),
+            deadline: Some(deadline),
+            user_id: 1,
+            active: 1,
+            key: String::from("SHOP"),
+            ticket_sequence: 3,
+            deleted_at: None,
+            deleted_by: None,
+            version: 1,
+        };
+        let (subject, body) = DeadlineService::message(
+            &project,
+            deadline,
+            NaiveDate::from_ymd_opt(2024, 4, 12).unwrap(),
+            &[(TicketStatus::Created, 2), (TicketStatus::Started, 1)],
+        );
+        assert_eq!(subject, "[SHOP] Deadline was due 2 day(s) ago: Web shop");
+        assert!(body.contains("3 ticket(s) remain:\n- Created: 2\n- Started: 1\n"));
+    }
+}
@@ -0,0 +1,1161 @@
+use super::{
+    label_service::LabelService, rank_service::RankService, ticket_key_service::TicketKeyService,
+};
+use chrono::{DateTime, Utc};
+use entity::{
+    comments, import_jobs,
+    import_references::{self, Entity as ImportReference},
+    sea_orm_active_enums::Priority,
+    ticket_attachments,
+    tickets::{self, Entity as Ticket},
+};
+use object_store::{aws::AmazonS3, ObjectStore, WriteMultipart};
+use sea_orm::{
+    ActiveModelTrait, ActiveValue::NotSet, ColumnTrait, ConnectionTrait, DbErr, EntityTrait,
+    PaginatorTrait, QueryFilter, QuerySelect, Set, TransactionTrait,
+};
+use serde_json::Value;
+use shared::{
+    dtos::import_dto::ImportSource,
+    validation::ticket_validation::{TicketStatus, TicketValidation},
+};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Component, Path, PathBuf},
+};
+
+/// Longest title and description a ticket accepts.
+const TITLE_MAX_LENGTH: usize = 160;
+const DESCRIPTION_MAX_LENGTH: usize = 500;
+
+const KIND_TICKET: &str = "ticket";
+const KIND_COMMENT: &str = "comment";
+const KIND_ATTACHMENT: &str = "attachment";
+
+/// Somebody named in the export, matched to a user by email.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ImportedPerson {
+    pub email: Option<String>,
+    pub name: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportedComment {
+    pub external_id: String,
+    pub author: Option<ImportedPerson>,
+    pub body: String,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImportedAttachment {
+    pub external_id: String,
+    pub file_name: String,
+}
+
+/// An issue of the export, mapped to the values of a ticket.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportedIssue {
+    /// Key of a Jira issue or number of a GitHub issue.
+    pub external_id: String,
+    pub title: String,
+    pub description: String,
+    pub status: TicketStatus,
+    pub priority: Priority,
+    pub assignee: Option<ImportedPerson>,
+    pub reporter: Option<ImportedPerson>,
+    pub labels: Vec<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub comments: Vec<ImportedComment>,
+    pub attachments: Vec<ImportedAttachment>,
+}
+
+/// What importing an issue did.
+#[derive(Debug, Default)]
+pub struct IssueOutcome {
+    /// The ticket is new, otherwise the issue was imported before.
+    pub created: bool,
+    pub warnings: Vec<String>,
+}
+
+/// Everything the issues of a job are imported with.
+pub struct ImportContext<'a> {
+    pub job: &'a import_jobs::Model,
+    pub tracker: &'static str,
+    /// Ids of the users by their lowercase username.
+    pub users: HashMap<String, u64>,
+    /// Directory of the export file.
+    pub dir: PathBuf,
+    pub bucket: Option<&'a AmazonS3>,
+}
+
+impl ImportContext<'_> {
+    fn user_id(&self, person: Option<&ImportedPerson>) -> Option<u64> {
+        person
+            .and_then(|person| person.email.as_ref())
+            .and_then(|email| self.users.get(&email.to_lowercase()))
+            .copied()
+    }
+}
+
+pub struct ImportService {}
+
+impl ImportService {
+    pub fn parse(source: ImportSource, content: &str) -> Result<Vec<ImportedIssue>, String> {
+        match source {
+            ImportSource::JiraXml => Self::parse_jira_xml(content),
+            ImportSource::JiraJson => Self::parse_jira_json(content),
+            ImportSource::GithubJson => Self::parse_github_json(content),
+        }
+        .map_err(|e| format!("The file is not a valid {} export: {}", source, e))
+    }
+
+    /// Path of the export file within the import directory. Absolute paths and paths leaving
+    /// the directory are refused.
+    pub fn resolve(base: &Path, path: &str) -> Result<PathBuf, String> {
+        let relative = Path::new(path.trim());
+        let mut components = relative.components().peekable();
+        match components.peek().is_some() && components.all(|c| matches!(c, Component::Normal(_))) {
+            true => Ok(base.join(relative)),
+            false => Err(format!(
+                "The path '{}' has to be relative to the import directory.",
+                path
+            )),
+        }
+    }
+
+    /// File of an attachment, `attachments/<issue>/<file name>` next to the export file.
+    pub fn attachment_path(dir: &Path, issue_id: &str, file_name: &str) -> Option<PathBuf> {
+        let single = |name: &str| {
+            let mut components = Path::new(name).components();
+            matches!(
+                (components.next(), components.next()),
+                (Some(Component::Normal(_)), None)
+            )
+        };
+        (single(issue_id) && single(file_name))
+            .then(|| dir.join("attachments").join(issue_id).join(file_name))
+    }
+
+    /// Everybody named in the issues, to match them to users at once.
+    pub fn people(issues: &[ImportedIssue]) -> Vec<&ImportedPerson> {
+        issues
+            .iter()
+            .flat_map(|issue| {
+                issue
+                    .assignee
+                    .iter()
+                    .chain(issue.reporter.iter())
+                    .chain(issue.comments.iter().filter_map(|c| c.author.as_ref()))
+            })
+            .collect()
+    }
+
+    /// Imports the issue into the project of the job, or adds the comments and attachments not
+    /// imported yet to its ticket. Issues whose ticket is in the trash are left alone.
+    pub async fn import_issue<C: ConnectionTrait + TransactionTrait>(
+        db: &C,
+        context: &ImportContext<'_>,
+        issue: &ImportedIssue,
+    ) -> Result<IssueOutcome, DbErr> {
+        let mut outcome = IssueOutcome::default();
+        let ticket_id = match Self::reference(db, context, KIND_TICKET, &issue.external_id).await? {
+            Some(ticket_id) => {
+                let active = Ticket::find_active()
+                    .filter(tickets::Column::Id.eq(ticket_id))
+                    .count(db)
+                    .await?
+                    > 0;
+                if !active {
+                    return Ok(outcome);
+                }
+                ticket_id
+            }
+            None => {
+                outcome.created = true;
+                Self::create_ticket(db, context, issue).await?
+            }
+        };
+
+        let imported: HashSet<(String, String)> = ImportReference::find()
+            .select_only()
+            .columns([
+                import_references::Column::Kind,
+                import_references::Column::ExternalId,
+            ])
+            .filter(import_references::Column::TicketId.eq(ticket_id))
+            .into_tuple()
+            .all(db)
+            .await?
+            .into_iter()
+            .collect();
+        let is_imported = |kind: &str, external_id: &str| {
+            imported.contains(&(kind.to_owned(), external_id.to_owned()))
+        };
+
+        for comment in issue
+            .comments
+            .iter()
+            .filter(|c| !is_imported(KIND_COMMENT, &c.external_id))
+        {
+            let author_id = context.user_id(comment.author.as_ref());
+            let Some(user_id) = author_id.or(context.job.user_id) else {
+                outcome.warnings.push(format!(
+                    "Comment {} of {} has no author.",
+                    comment.external_id, issue.external_id
+                ));
+                continue;
+            };
+            // Comments of unknown users are kept under the importing user
+            let text = match (author_id, &comment.author) {
+                (None, Some(author)) => format!("{} wrote:\n\n{}", author.name, comment.body),
+                _ => comment.body.to_owned(),
+            };
+            let txn = db.begin().await?;
+            let saved = comments::ActiveModel {
+                text: Set(text),
+                ticket_id: Set(ticket_id),
+                user_id: Set(user_id),
+                timestamp: comment
+                    .created_at
+                    .map_or(NotSet, |created_at| Set(created_at.naive_utc())),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+            Self::save_reference(
+                &txn,
+                context,
+                KIND_COMMENT,
+                &comment.external_id,
+                ticket_id,
+                saved.id,
+            )
+            .await?;
+            txn.commit().await?;
+        }
+
+        for attachment in issue
+            .attachments
+            .iter()
+            .filter(|a| !is_imported(KIND_ATTACHMENT, &a.external_id))
+        {
+            match Self::upload(context, issue, attachment, ticket_id).await {
+                Ok(raw_path) => {
+                    let txn = db.begin().await?;
+                    let saved = ticket_attachments::ActiveModel {
+                        ticket_id: Set(ticket_id),
+                        local_path: Set(None),
+                        path: Set(raw_path),
+                        ..Default::default()
+                    }
+                    .insert(&txn)
+                    .await?;
+                    Self::save_reference(
+                        &txn,
+                        context,
+                        KIND_ATTACHMENT,
+                        &attachment.external_id,
+                        ticket_id,
+                        saved.id,
+                    )
+                    .await?;
+                    txn.commit().await?;
+                }
+                Err(warning) => outcome.warnings.push(warning),
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Creates the ticket and claims the issue in one transaction. A description longer than a
+    /// ticket accepts is shortened, and kept in full as the first comment.
+    async fn create_ticket<C: ConnectionTrait + TransactionTrait>(
+        db: &C,
+        context: &ImportContext<'_>,
+        issue: &ImportedIssue,
+    ) -> Result<u64, DbErr> {
+        let project_id = context.job.project_id;
+        let reporter_id = context
+            .user_id(issue.reporter.as_ref())
+            .or(context.job.user_id);
+        let description = Self::shorten(&issue.description, DESCRIPTION_MAX_LENGTH);
+        let txn = db.begin().await?;
+        let ticket = tickets::ActiveModel {
+            title: Set(Self::shorten(&issue.title, TITLE_MAX_LENGTH)),
+            description: Set(description.to_owned()),
+            project_id: Set(Some(project_id)),
+            status: Set(issue.status.to_string()),
+            user_id: Set(context.user_id(issue.assignee.as_ref())),
+            priority: Set(Some(issue.priority.clone())),
+            reporter_id: Set(reporter_id),
+            created_at: issue.created_at.map_or(NotSet, Set),
+            board_rank: Set(RankService::last(&txn, project_id).await?),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        TicketKeyService::assign(&txn, ticket.id, project_id).await?;
+        let labels = issue
+            .labels
+            .iter()
+            .filter(|label| TicketValidation::labels_validation(&[label.to_string()]).is_ok())
+            .cloned()
+            .collect::<Vec<String>>();
+        LabelService::save(&txn, ticket.id, &labels).await?;
+        if let (true, Some(user_id)) = (description != issue.description, reporter_id) {
+            comments::ActiveModel {
+                text: Set(format!("Description as imported:\n\n{}", issue.description)),
+                ticket_id: Set(ticket.id),
+                user_id: Set(user_id),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await?;
+        }
+        Self::save_reference(
+            &txn,
+            context,
+            KIND_TICKET,
+            &issue.external_id,
+            ticket.id,
+            ticket.id,
+        )
+        .await?;
+        txn.commit().await?;
+        Ok(ticket.id)
+    }
+
+    /// Uploads the file of the attachment, or says why it could not be imported.
+    async fn upload(
+        context: &ImportContext<'_>,
+        issue: &ImportedIssue,
+        attachment: &ImportedAttachment,
+        ticket_id: u64,
+    ) -> Result<String, String> {
+        let not_imported = |reason: String| {
+            format!(
+                "Attachment '{}' of {} was not imported: {}",
+                attachment.file_name, issue.external_id, reason
+            )
+        };
+        let path = Self::attachment_path(&context.dir, &issue.external_id, &attachment.file_name)
+            .ok_or(not_imported(String::from("the file name is not valid")))?;
+        let bucket = context.bucket.ok_or(not_imported(String::from(
+            "the object store is not available",
+        )))?;
+        let bytes = tokio::fs::read(&path)
+            .await
+            .map_err(|e| not_imported(format!("{} ({})", e, path.display())))?;
+        let raw_path = format!("tickets/{}/attachments/{}", ticket_id, attachment.file_name);
+        let upload = bucket
+            .put_multipart(&object_store::path::Path::from(raw_path.clone()))
+            .await
+            .map_err(|e| not_imported(e.to_string()))?;
+        let mut writer = WriteMultipart::new(upload);
+        writer.write(&bytes);
+        writer
+            .finish()
+            .await
+            .map_err(|e| not_imported(e.to_string()))?;
+        Ok(raw_path)
+    }
+
+    async fn reference<C: ConnectionTrait>(
+        db: &C,
+        context: &ImportContext<'_>,
+        kind: &str,
+        external_id: &str,
+    ) -> Result<Option<u64>, DbErr> {
+        ImportReference::find()
+            .select_only()
+            .column(import_references::Column::TargetId)
+            .filter(import_references::Column::ProjectId.eq(context.job.project_id))
+            .filter(import_references::Column::Tracker.eq(context.tracker))
+            .filter(import_references::Column::Kind.eq(kind))
+            .filter(import_references::Column::ExternalId.eq(external_id))
+            .into_tuple()
+            .one(db)
+            .await
+    }
+
+    async fn save_reference<C: ConnectionTrait>(
+        db: &C,
+        context: &ImportContext<'_>,
+        kind: &str,
+        external_id: &str,
+        ticket_id: u64,
+        target_id: u64,
+    ) -> Result<(), DbErr> {
+        import_references::ActiveModel {
+            project_id: Set(context.job.project_id),
+            tracker: Set(context.tracker.to_owned()),
+            kind: Set(kind.to_owned()),
+            external_id: Set(external_id.to_owned()),
+            ticket_id: Set(ticket_id),
+            target_id: Set(target_id),
+            created_at: Set(Utc::now()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await
+        .map(|_| ())
+    }
+
+    fn shorten(text: &str, max_length: usize) -> String {
+        match text.chars().count() > max_length {
+            true => text
+                .chars()
+                .take(max_length - 1)
+                .chain(std::iter::once('…'))
+                .collect(),
+            false => text.to_owned(),
+        }
+    }
+
+    /// Jira statuses by their default names. Finished issues are done, unless their resolution
+    /// says they were dropped.
+    pub fn jira_status(status: &str, resolution: Option<&str>) -> TicketStatus {
+        let dropped = resolution.is_some_and(|resolution| {
+            matches!(
+                resolution.trim().to_lowercase().as_str(),
+                "won't do"
+                    | "won't fix"
+                    | "duplicate"
+                    | "cannot reproduce"
+                    | "incomplete"
+                    | "declined"
+                    | "rejected"
+            )
+        });
+        match status.trim().to_lowercase().as_str() {
+            "selected for development" | "ready for development" | "ready" | "selected" => {
+                TicketStatus::Selected
+            }
+            "in progress" | "in development" => TicketStatus::Started,
+            "in review" | "code review" | "review" | "under review" => TicketStatus::Reviewing,
+            "testing" | "in testing" | "qa" | "in qa" => TicketStatus::Testing,
+            "done" | "resolved" | "fixed" if !dropped => TicketStatus::Done,
+            "closed" if resolution.is_some() && !dropped => TicketStatus::Done,
+            "done" | "resolved" | "fixed" | "closed" | "won't do" | "rejected" | "declined"
+            | "cancelled" | "canceled" => TicketStatus::Closed,
+            _ => TicketStatus::Created,
+        }
+    }
+
+    /// Jira priorities by their default names, of the current and of older schemes.
+    pub fn jira_priority(priority: &str) -> Priority {
+        match priority.trim().to_lowercase().as_str() {
+            "highest" | "blocker" | "critical" | "urgent" => Priority::Critical,
+            "high" | "major" => Priority::High,
+            "low" | "lowest" | "minor" | "trivial" => Priority::Low,
+            _ => Priority::Normal,
+        }
+    }
+
+    /// Open issues are new, closed ones done unless they were closed as not planned.
+    pub fn github_status(state: &str, state_reason: Option<&str>) -> TicketStatus {
+        match (
+            state.to_lowercase().as_str(),
+            state_reason.map(str::to_lowercase).as_deref(),
+        ) {
+            ("closed", Some("not_planned")) => TicketStatus::Closed,
+            ("closed", _) => TicketStatus::Done,
+            _ => TicketStatus::Created,
+        }
+    }
+
+    /// GitHub has no priorities, labels like `priority: high` or `P1` are used instead. The
+    /// highest one wins.
+    pub fn github_priority(labels: &[String]) -> Priority {
+        let rank = |priority: &Priority| match priority {
+            Priority::Low => 0,
+            Priority::Normal => 1,
+            Priority::High => 2,
+            Priority::Critical => 3,
+        };
+        labels
+            .iter()
+            .filter_map(|label| {
+                let label = label.trim().to_lowercase();
+                let name = label
+                    .strip_prefix("priority")
+                    .map(|rest| rest.trim_start_matches([':', '/', '-', ' ']))
+                    .unwrap_or(label.as_str())
+                    .trim();
+                match name {
+                    "p0" => Some(Priority::Critical),
+                    "p1" => Some(Priority::High),
+                    "p2" => Some(Priority::Normal),
+                    "p3" | "p4" => Some(Priority::Low),
+                    _ if label.starts_with("priority") => Some(Self::jira_priority(name)),
+                    _ => None,
+                }
+            })
+            .max_by_key(rank)
+            .unwrap_or(Priority::Normal)
+    }
+
+    fn parse_jira_xml(content: &str) -> Result<Vec<ImportedIssue>, String> {
+        let rss: jira::Rss = quick_xml::de::from_str(content).map_err(|e| e.to_string())?;
+        Ok(rss
+            .channel
+            .items
+            .into_iter()
+            .map(|item| {
+                let resolution = item.resolution.map(|r| r.value);
+                ImportedIssue {
+                    title: item.summary,
+                    description: Self::html_text(&item.description.unwrap_or_default()),
+                    status: Self::jira_status(&item.status.value, resolution.as_deref()),
+                    priority: item
+                        .priority
+                        .map_or(Priority::Normal, |p| Self::jira_priority(&p.value)),
+                    assignee: item.assignee.and_then(jira::XmlUser::person),
+                    reporter: item.reporter.and_then(jira::XmlUser::person),
+                    labels: item.labels.map(|l| l.label).unwrap_or_default(),
+                    created_at: item.created.as_deref().and_then(Self::date),
+                    comments: item
+                        .comments
+                        .map(|c| c.comment)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|comment| ImportedComment {
+                            external_id: comment.id,
+                            author: comment.author.map(|author| ImportedPerson {
+                                email: author.contains('@').then(|| author.to_owned()),
+                                name: author,
+                            }),
+                            body: Self::html_text(&comment.body),
+                            created_at: comment.created.as_deref().and_then(Self::date),
+                        })
+                        .collect(),
+                    attachments: item
+                        .attachments
+                        .map(|a| a.attachment)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|attachment| ImportedAttachment {
+                            external_id: attachment.id,
+                            file_name: attachment.name,
+                        })
+                        .collect(),
+                    external_id: item.key.value,
+                }
+            })
+            .collect())
+    }
+
+    fn parse_jira_json(content: &str) -> Result<Vec<ImportedIssue>, String> {
+        let issues = match serde_json::from_str(content).map_err(|e| e.to_string())? {
+            jira::Export::Search { issues } => issues,
+            jira::Export::Issues(issues) => issues,
+        };
+        Ok(issues
+            .into_iter()
+            .map(|issue| {
+                let fields = issue.fields;
+                ImportedIssue {
+                    external_id: issue.key,
+                    title: fields.summary,
+                    description: Self::document_text(&fields.description),
+                    status: Self::jira_status(
+                        fields.status.as_ref().map_or("", |s| s.name.as_str()),
+                        fields.resolution.as_ref().map(|r| r.name.as_str()),
+                    ),
+                    priority: fields
+                        .priority
+                        .map_or(Priority::Normal, |p| Self::jira_priority(&p.name)),
+                    assignee: fields.assignee.map(jira::JsonUser::person),
+                    reporter: fields.reporter.map(jira::JsonUser::person),
+                    labels: fields.labels,
+                    created_at: fields.created.as_deref().and_then(Self::date),
+                    comments: fields
+                        .comment
+                        .map(|c| c.comments)
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|comment| ImportedComment {
+                            external_id: comment.id,
+                            author: comment.author.map(jira::JsonUser::person),
+                            body: Self::document_text(&comment.body),
+                            created_at: comment.created.as_deref().and_then(Self::date),
+                        })
+                        .collect(),
+                    attachments: fields
+                        .attachment
+                        .into_iter()
+                        .map(|attachment| ImportedAttachment {
+                            external_id: attachment.id,
+                            file_name: attachment.filename,
+                        })
+                        .collect(),
+                }
+            })
+            .collect())
+    }
+
+    /// Pull requests listed among the issues are left out.
+    fn parse_github_json(content: &str) -> Result<Vec<ImportedIssue>, String> {
+        let issues: Vec<github::Issue> =
+            serde_json::from_str(content).map_err(|e| e.to_string())?;
+        Ok(issues
+            .into_iter()
+            .filter(|issue| issue.pull_request.is_none())
+            .map(|issue| {
+                let labels = issue
+                    .labels
+                    .into_iter()
+                    .map(|label| label.name)
+                    .collect::<Vec<String>>();
+                // The API lists the comments separately, `gh` has them with the issue
+                let comments = serde_json::from_value::<Vec<github::Comment>>(issue.comments)
+                    .unwrap_or_default();
+                ImportedIssue {
+                    external_id: issue.number.to_string(),
+                    title: issue.title,
+                    description: issue.body.unwrap_or_default(),
+                    status: Self::github_status(&issue.state, issue.state_reason.as_deref()),
+                    priority: Self::github_priority(&labels),
+                    assignee: issue
+                        .assignee
+                        .or(issue.assignees.into_iter().next())
+                        .map(github::User::person),
+                    reporter: issue.user.map(github::User::person),
+                    labels,
+                    created_at: issue.created_at.as_deref().and_then(Self::date),
+                    comments: comments
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, comment)| ImportedComment {
+                            external_id: match comment.id {
+                                Value::String(id) => id,
+                                Value::Number(id) => id.to_string(),
+                                _ => format!("{}#{}", issue.number, i + 1),
+                            },
+                            author: comment.user.map(github::User::person),
+                            body: comment.body,
+                            created_at: comment.created_at.as_deref().and_then(Self::date),
+                        })
+                        .collect(),
+                    attachments: Vec::new(),
+                }
+            })
+            .collect())
+    }
+
+    /// Dates of the exports: RFC 3339, RFC 2822 in the XML and Jira's offset without colon.
+    fn date(value: &str) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(value)
+            .or_else(|_| DateTime::parse_from_rfc2822(value))
+            .or_else(|_| DateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f%z"))
+            .map(|date| date.with_timezone(&Utc))
+            .ok()
+    }
+
+    /// Text of a description, plain or in the document format of the newer Jira API.
+    fn document_text(value: &Value) -> String {
+        match value {
+            Value::String(text) => text.to_owned(),
+            Value::Object(node) => match node.get("text").and_then(Value::as_str) {
+                Some(text) => text.to_owned(),
+                None => {
+                    let separator = match node.get("type").and_then(Value::as_str) {
+                        Some("doc") | Some("bulletList") | Some("orderedList") => "\n",
+                        _ => "",
+                    };
+                    node.get("content")
+                        .and_then(Value::as_array)
+                        .map(|children| {
+                            children
+                                .iter()
+                                .map(Self::document_text)
+                                .collect::<Vec<String>>()
+                                .join(separator)
+                        })
+                        .unwrap_or_default()
+                }
+            },
+            _ => String::new(),
+        }
+    }
+
+    /// Text of the HTML the XML export renders descriptions and comments to.
+    fn html_text(html: &str) -> String {
+        let mut text = String::new();
+        let mut tag: Option<String> = None;
+        for c in html.chars() {
+            match (&mut tag, c) {
+                (None, '<') => tag = Some(String::new()),
+                (Some(name), '>') => {
+                    let name = name.trim_start_matches('/').to_lowercase();
+                    if ["br", "br/", "p", "li", "div"]
+                        .contains(&name.split_whitespace().next().unwrap_or_default())
+                        && !text.ends_with('\n')
+                        && !text.is_empty()
+                    {
+                        text.push('\n');
+                    }
+                    tag = None;
+                }
+                (Some(name), c) => name.push(c),
+                (None, c) => text.push(c),
+            }
+        }
+        text.replace("&nbsp;", " ")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&amp;", "&")
+            .trim()
+            .to_owned()
+    }
+}
+
+/// Shapes of the Jira exports, with only the fields imported.
+mod jira {
+    use super::ImportedPerson;
+    use serde::Deserialize;
+    use serde_json::Value;
+
+    #[derive(Deserialize)]
+    pub struct Rss {
+        pub channel: Channel,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Channel {
+        #[serde(default, rename = "item")]
+        pub items: Vec<Item>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Text {
+        #[serde(default, rename = "$text")]
+        pub value: String,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Item {
+        pub key: Text,
+        pub summary: String,
+        pub description: Option<String>,
+        pub status: Text,
+        pub priority: Option<Text>,
+        pub resolution: Option<Text>,
+        pub assignee: Option<XmlUser>,
+        pub reporter: Option<XmlUser>,
+        pub labels: Option<Labels>,
+        pub created: Option<String>,
+        pub comments: Option<Comments>,
+        pub attachments: Option<Attachments>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct XmlUser {
+        #[serde(rename = "@username")]
+        pub username: Option<String>,
+        #[serde(default, rename = "$text")]
+        pub name: String,
+    }
+
+    impl XmlUser {
+        /// Unassigned issues name `-1` as the user.
+        pub fn person(self) -> Option<ImportedPerson> {
+            let username = self.username.filter(|username| username != "-1")?;
+            Some(ImportedPerson {
+                email: username.contains('@').then(|| username.to_owned()),
+                name: match self.name.trim().is_empty() {
+                    true => username,
+                    false => self.name,
+                },
+            })
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct Labels {
+        #[serde(default)]
+        pub label: Vec<String>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Comments {
+        #[serde(default)]
+        pub comment: Vec<XmlComment>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct XmlComment {
+        #[serde(rename = "@id")]
+        pub id: String,
+        #[serde(rename = "@author")]
+        pub author: Option<String>,
+        #[serde(rename = "@created")]
+        pub created: Option<String>,
+        #[serde(default, rename = "$text")]
+        pub body: String,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Attachments {
+        #[serde(default)]
+        pub attachment: Vec<XmlAttachment>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct XmlAttachment {
+        #[serde(rename = "@id")]
+        pub id: String,
+        #[serde(rename = "@name")]
+        pub name: String,
+    }
+
+    /// A page of the issue search, or just its issues.
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    pub enum Export {
+        Search { issues: Vec<Issue> },
+        Issues(Vec<Issue>),
+    }
+
+    #[derive(Deserialize)]
+    pub struct Issue {
+        pub key: String,
+        pub fields: Fields,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Fields {
+        pub summary: String,
+        #[serde(default)]
+        pub description: Value,
+        pub status: Option<Named>,
+        pub priority: Option<Named>,
+        pub resolution: Option<Named>,
+        pub assignee: Option<JsonUser>,
+        pub reporter: Option<JsonUser>,
+        #[serde(default)]
+        pub labels: Vec<String>,
+        pub created: Option<String>,
+        pub comment: Option<JsonComments>,
+        #[serde(default)]
+        pub attachment: Vec<JsonAttachment>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Named {
+        pub name: String,
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct JsonUser {
+        pub email_address: Option<String>,
+        pub display_name: Option<String>,
+    }
+
+    impl JsonUser {
+        pub fn person(self) -> ImportedPerson {
+            ImportedPerson {
+                name: self
+                    .display_name
+                    .or(self.email_address.clone())
+                    .unwrap_or_default(),
+                email: self.email_address,
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct JsonComments {
+        #[serde(default)]
+        pub comments: Vec<JsonComment>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct JsonComment {
+        pub id: String,
+        pub author: Option<JsonUser>,
+        #[serde(default)]
+        pub body: Value,
+        pub created: Option<String>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct JsonAttachment {
+        pub id: String,
+        pub filename: String,
+    }
+}
+
+/// Shape of the GitHub issues, as listed by the API or by `gh issue list --json`.
+mod github {
+    use super::ImportedPerson;
+    use serde::Deserialize;
+    use serde_json::Value;
+
+    #[derive(Deserialize)]
+    pub struct Issue {
+        pub number: u64,
+        pub title: String,
+        pub body: Option<String>,
+        pub state: String,
+        #[serde(alias = "stateReason")]
+        pub state_reason: Option<String>,
+        #[serde(default)]
+        pub labels: Vec<Label>,
+        pub assignee: Option<User>,
+        #[serde(default)]
+        pub assignees: Vec<User>,
+        #[serde(alias = "author")]
+        pub user: Option<User>,
+        #[serde(alias = "createdAt")]
+        pub created_at: Option<String>,
+        /// A count from the API, the comments themselves from `gh`.
+        #[serde(default)]
+        pub comments: Value,
+        pub pull_request: Option<Value>,
+    }
+
+    #[derive(Deserialize)]
+    pub struct Label {
+        pub name: String,
+    }
+
+    #[derive(Deserialize)]
+    pub struct User {
+        pub login: Option<String>,
+        pub email: Option<String>,
+    }
+
+    impl User {
+        pub fn person(self) -> ImportedPerson {
+            ImportedPerson {
+                name: self.login.or(self.email.clone()).unwrap_or_default(),
+                email: self.email,
+            }
+        }
+    }
+
+    #[derive(Deserialize)]
+    pub struct Comment {
+        #[serde(default)]
+        pub id: Value,
+        #[serde(alias = "author")]
+        pub user: Option<User>,
+        #[serde(default)]
+        pub body: String,
+        #[serde(alias = "createdAt")]
+        pub created_at: Option<String>,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_jira_xml() {
+        let issues = ImportService::parse(
+            ImportSource::JiraXml,
+            r#"<rss version="0.92"><channel><title>Jira</title>
+            <item>
+                <title>[OPS-7] Login page crashes</title>
+                <key id="10007">OPS-7</key>
+                <summary>Login page crashes</summary>
+                <description>&lt;p&gt;Error 500 &amp;amp; a blank page&lt;/p&gt;</description>
+                <priority id="2">Highest</priority>
+                <status id="5">Resolved</status>
+                <resolution id="1">Fixed</resolution>
+                <assignee username="dev@example.com">Dev</assignee>
+                <reporter username="-1">Anonymous</reporter>
+                <labels><label>auth</label><label>ui</label></labels>
+                <created>Mon, 1 Apr 2024 10:00:00 +0000</created>
+                <comments>
+                    <comment id="301" author="qa@example.com" created="Tue, 2 Apr 2024 09:30:00 +0000">&lt;p&gt;Still there&lt;/p&gt;</comment>
+                </comments>
+                <attachments><attachment id="501" name="trace.log" size="12"/></attachments>
+            </item>
+            </channel></rss>"#,
+        )
+        .unwrap();
+        assert_eq!(issues.len(), 1);
+        let issue = &issues[0];
+        assert_eq!(issue.external_id, "OPS-7");
+        assert_eq!(issue.description, "Error 500 & a blank page");
+        assert_eq!(issue.status, TicketStatus::Done);
+        assert_eq!(issue.priority, Priority::Critical);
+        assert_eq!(
+            issue.assignee.as_ref().and_then(|a| a.email.as_deref()),
+            Some("dev@example.com")
+        );
+        assert_eq!(issue.reporter, None);
+        assert_eq!(issue.labels, vec!["auth", "ui"]);
+        assert!(issue.created_at.is_some());
+        assert_eq!(issue.comments[0].external_id, "301");
+        assert_eq!(issue.comments[0].body, "Still there");
+        assert_eq!(issue.attachments[0].file_name, "trace.log");
+    }
+
+    #[test]
+    fn test_parse_jira_json() {
+        let issues = ImportService::parse(
+            ImportSource::JiraJson,
+            r#"{"startAt": 0, "total": 1, "issues": [{"id": "10007", "key": "OPS-7", "fields": {
+                "summary": "Login page crashes",
+                "description": {"type": "doc", "content": [
+                    {"type": "paragraph", "content": [{"type": "text", "text": "Error 500 "}, {"type": "text", "text": "every time"}]},
+                    {"type": "paragraph", "content": [{"type": "text", "text": "Since Monday"}]}
+                ]},
+                "status": {"name": "In Review"},
+                "priority": {"name": "Minor"},
+                "resolution": null,
+                "assignee": {"displayName": "Dev", "emailAddress": "dev@example.com"},
+                "reporter": null,
+                "labels": ["auth"],
+                "created": "2024-04-01T10:00:00.000+0200",
+                "comment": {"comments": [{"id": "301", "author": {"displayName": "QA"}, "body": "Still there", "created": "2024-04-02T09:30:00.000+0000"}]},
+                "attachment": [{"id": "501", "filename": "trace.log"}]
+            }}]}"#,
+        )
+        .unwrap();
+        let issue = &issues[0];
+        assert_eq!(issue.description, "Error 500 every time\nSince Monday");
+        assert_eq!(issue.status, TicketStatus::Reviewing);
+        assert_eq!(issue.priority, Priority::Low);
+        assert_eq!(
+            issue.created_at.map(|d| d.to_rfc3339()),
+            Some(String::from("2024-04-01T08:00:00+00:00"))
+        );
+        assert_eq!(
+            issue.comments[0].author,
+            Some(ImportedPerson {
+                email: None,
+                name: String::from("QA")
+            })
+        );
+        assert_eq!(issue.attachments[0].external_id, "501");
+        assert!(ImportService::parse(ImportSource::JiraJson, "{\"total\": 0}").is_err());
+    }
+
+    #[test]
+    fn test_parse_github_json() {
+        let issues = ImportService::parse(
+            ImportSource::GithubJson,
+            r#"[
+                {"number": 12, "title": "Login page crashes", "body": "Error 500", "state": "closed",
+                 "state_reason": "not_planned", "labels": [{"name": "bug"}, {"name": "priority: high"}],
+                 "assignee": null, "assignees": [{"login": "dev", "email": "dev@example.com"}],
+                 "user": {"login": "qa"}, "created_at": "2024-04-01T10:00:00Z", "comments": 3},
+                {"number": 13, "title": "Fix the login", "state": "open", "comments": 0,
+                 "pull_request": {"url": "https://api.github.com/repos/o/r/pulls/13"}},
+                {"number": 14, "title": "Export invoices", "body": null, "state": "OPEN",
+                 "labels": [], "author": {"login": "pm"}, "createdAt": "2024-04-03T08:00:00Z",
+                 "comments": [{"id": "IC_1", "author": {"login": "dev"}, "body": "On it", "createdAt": "2024-04-03T09:00:00Z"}]}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(issues.len(), 2, "Pull requests should be left out.");
+        assert_eq!(issues[0].external_id, "12");
+        assert_eq!(issues[0].status, TicketStatus::Closed);
+        assert_eq!(issues[0].priority, Priority::High);
+        assert!(issues[0].comments.is_empty());
+        assert_eq!(
+            issues[0].assignee.as_ref().and_then(|a| a.email.as_deref()),
+            Some("dev@example.com")
+        );
+        assert_eq!(issues[1].status, TicketStatus::Created);
+        assert_eq!(
+            issues[1].reporter.as_ref().map(|r| r.name.as_str()),
+            Some("pm")
+        );
+        assert_eq!(issues[1].comments[0].external_id, "IC_1");
+        assert!(issues[1].comments[0].created_at.is_some());
+    }
+
+    #[test]
+    fn test_jira_status() {
+        assert_eq!(
+            ImportService::jira_status("To Do", None),
+            TicketStatus::Created
+        );
+        assert_eq!(
+            ImportService::jira_status("Selected for Development", None),
+            TicketStatus::Selected
+        );
+        assert_eq!(
+            ImportService::jira_status("In Progress", None),
+            TicketStatus::Started
+        );
+        assert_eq!(ImportService::jira_status("Done", None), TicketStatus::Done);
+        assert_eq!(
+            ImportService::jira_status("Done", Some("Won't Do")),
+            TicketStatus::Closed
+        );
+        assert_eq!(
+            ImportService::jira_status("Closed", Some("Fixed")),
+            TicketStatus::Done
+        );
+        assert_eq!(
+            ImportService::jira_status("Closed", None),
+            TicketStatus::Closed
+        );
+        assert_eq!(
+            ImportService::jira_status("Waiting", None),
+            TicketStatus::Created
+        );
+    }
+
+    #[test]
+    fn test_github_priority() {
+        let labels = |names: &[&str]| names.iter().map(|n| n.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            ImportService::github_priority(&labels(&["bug"])),
+            Priority::Normal
+        );
+        assert_eq!(
+            ImportService::github_priority(&labels(&["P0"])),
+            Priority::Critical
+        );
+        assert_eq!(
+            ImportService::github_priority(&labels(&["priority/low", "priority: critical"])),
+            Priority::Critical
+        );
+        assert_eq!(
+            ImportService::github_priority(&labels(&["p3"])),
+            Priority::Low
+        );
+    }
+
+    #[test]
+    fn test_paths() {
+        let base = Path::new("/srv/imports");
+        assert_eq!(
+            ImportService::resolve(base, "jira/export.xml"),
+            Ok(PathBuf::from("/srv/imports/jira/export.xml"))
+        );
+        assert!(ImportService::resolve(base, "/etc/passwd").is_err());
+        assert!(ImportService::resolve(base, "../secrets.json").is_err());
+        assert!(ImportService::resolve(base, "").is_err());
+        assert_eq!(
+            ImportService::attachment_path(base, "OPS-7", "trace.log"),
+            Some(PathBuf::from("/srv/imports/attachments/OPS-7/trace.log"))
+        );
+        assert_eq!(
+            ImportService::attachment_path(base, "OPS-7", "../../x"),
+            None
+        );
+        assert_eq!(
+            ImportService::attachment_path(base, "..", "trace.log"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_shorten() {
+        assert_eq!(ImportService::shorten("Short", 8), "Short");
+        assert_eq!(ImportService::shorten("Much too long", 8), "Much to…");
+    }
+}
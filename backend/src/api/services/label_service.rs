@@ -0,0 +1,82 @@
+use crate::api::error::ApiError;
+use axum::http::StatusCode;
+use entity::ticket_labels::{self, Entity as TicketLabel};
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder, Set};
+use shared::{dtos::ticket_dto::TicketDto, validation::ticket_validation::TicketValidation};
+use std::collections::{BTreeSet, HashMap};
+
+pub struct LabelService {}
+
+impl LabelService {
+    pub fn validate(labels: &[String]) -> Result<(), ApiError> {
+        TicketValidation::labels_validation(labels)
+            .map_err(|e| ApiError::new(StatusCode::BAD_REQUEST, e.to_string()))
+    }
+
+    /// Replaces the labels of the ticket. Labels are trimmed and kept once, the saved ones are
+    /// returned in alphabetical order.
+    pub async fn save<C: ConnectionTrait>(
+        db: &C,
+        ticket_id: u64,
+        labels: &[String],
+    ) -> Result<Vec<String>, DbErr> {
+        TicketLabel::delete_many()
+            .filter(ticket_labels::Column::TicketId.eq(ticket_id))
+            .exec(db)
+            .await?;
+        let names = labels
+            .iter()
+            .map(|label| label.trim().to_owned())
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect::<Vec<String>>();
+        let rows = names
+            .iter()
+            .map(|name| ticket_labels::ActiveModel {
+                ticket_id: Set(ticket_id),
+                name: Set(name.to_owned()),
+                ..Default::default()
+            })
+            .collect::<Vec<ticket_labels::ActiveModel>>();
+        if !rows.is_empty() {
+            TicketLabel::insert_many(rows).exec(db).await?;
+        }
+        Ok(names)
+    }
+
+    /// Labels of the tickets in alphabetical order, by ticket id. Tickets without labels are
+    /// left out.
+    pub async fn load<C: ConnectionTrait>(
+        db: &C,
+        ticket_ids: &[u64],
+    ) -> Result<HashMap<u64, Vec<String>>, DbErr> {
+        let mut result: HashMap<u64, Vec<String>> = HashMap::new();
+        if ticket_ids.is_empty() {
+            return Ok(result);
+        }
+        for label in TicketLabel::find()
+            .filter(ticket_labels::Column::TicketId.is_in(ticket_ids.to_vec()))
+            .order_by_asc(ticket_labels::Column::Name)
+            .all(db)
+            .await?
+        {
+            result.entry(label.ticket_id).or_default().push(label.name);
+        }
+        Ok(result)
+    }
+
+    /// Fills in the labels of the tickets.
+    pub async fn attach<C: ConnectionTrait>(
+        db: &C,
+        tickets: &mut [TicketDto],
+    ) -> Result<(), DbErr> {
+        let ids = tickets.iter().filter_map(|t| t.id).collect::<Vec<u64>>();
+        let mut labels = Self::load(db, &ids).await?;
+        for ticket in tickets.iter_mut() {
+            if let Some(names) = ticket.id.and_then(|id| labels.remove(&id)) {
+                ticket.labels = names;
+            }
+        }
+        Ok(())
+    }
+}
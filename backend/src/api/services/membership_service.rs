@@ -0,0 +1,152 @@
+use crate::api::{
+    auth_utils::has_role,
+    error::{ApiError, AuthError},
+};
+use axum::http::StatusCode;
+use entity::{
+    project_members::{self, Entity as ProjectMember},
+    projects, tickets,
+    tickets::Entity as Ticket,
+    users,
+};
+use sea_orm::{
+    sea_query::{OnConflict, Query, SelectStatement},
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, PaginatorTrait, QueryFilter,
+    QuerySelect, Set,
+};
+use shared::{dtos::project_member_dto::ProjectRole, validation::user_validation::UserRole};
+use std::str::FromStr;
+
+pub struct MembershipService {}
+
+impl MembershipService {
+    /// The role of the user in the project, `None` if the user is no member. Managers
+    /// administer every project without being a member.
+    pub async fn role<C: ConnectionTrait>(
+        db: &C,
+        user: &users::Model,
+        project_id: u64,
+    ) -> Result<Option<ProjectRole>, DbErr> {
+        if has_role(user, UserRole::Manager) {
+            return Ok(Some(ProjectRole::Admin));
+        }
+        let role: Option<String> = ProjectMember::find()
+            .select_only()
+            .column(project_members::Column::Role)
+            .filter(project_members::Column::ProjectId.eq(project_id))
+            .filter(project_members::Column::UserId.eq(user.id))
+            .into_tuple()
+            .one(db)
+            .await?;
+        Ok(role.and_then(|role| ProjectRole::from_str(&role).ok()))
+    }
+
+    /// Fails unless the user has at least the role in the project. The project is not found
+    /// for users who are no member, so that they do not learn about it.
+    pub async fn require<C: ConnectionTrait>(
+        db: &C,
+        user: &users::Model,
+        project_id: u64,
+        role: ProjectRole,
+    ) -> Result<ProjectRole, ApiError> {
+        match Self::role(db, user, project_id).await? {
+            Some(member_role) if member_role >= role => Ok(member_role),
+            Some(_) => Err(AuthError {
+                status: StatusCode::FORBIDDEN,
+                message: format!("Only project members with the {} role may do this", role),
+                code: None,
+            }
+            .into()),
+            None => Err(ApiError::new(
+                StatusCode::NOT_FOUND,
+                String::from("Not found"),
+            )),
+        }
+    }
+
+    /// Same as `require` for the project of the ticket. Tickets without a project, and the ones
+    /// which do not exist, are left to the handler.
+    pub async fn require_ticket<C: ConnectionTrait>(
+        db: &C,
+        user: &users::Model,
+        ticket_id: u64,
+        role: ProjectRole,
+    ) -> Result<(), ApiError> {
+        let project_id: Option<Option<u64>> = Ticket::find_by_id(ticket_id)
+            .select_only()
+            .column(tickets::Column::ProjectId)
+            .into_tuple()
+            .one(db)
+            .await?;
+        match project_id.flatten() {
+            Some(project_id) => Self::require(db, user, project_id, role).await.map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    /// Ids of the projects the user is a member of.
+    fn projects_of(user: &users::Model) -> SelectStatement {
+        Query::select()
+            .column(project_members::Column::ProjectId)
+            .from(ProjectMember)
+            .and_where(project_members::Column::UserId.eq(user.id))
+            .to_owned()
+    }
+
+    /// Condition on projects limiting them to the ones the user may see.
+    pub fn visible_projects(user: &users::Model) -> Condition {
+        match has_role(user, UserRole::Manager) {
+            true => Condition::all(),
+            false => {
+                Condition::all().add(projects::Column::Id.in_subquery(Self::projects_of(user)))
+            }
+        }
+    }
+
+    /// Condition on tickets limiting them to the ones the user may see, which are the tickets
+    /// of the user's projects and the ones without a project.
+    pub fn visible_tickets(user: &users::Model) -> Condition {
+        match has_role(user, UserRole::Manager) {
+            true => Condition::all(),
+            false => Condition::any()
+                .add(tickets::Column::ProjectId.is_null())
+                .add(tickets::Column::ProjectId.in_subquery(Self::projects_of(user))),
+        }
+    }
+
+    /// Gives the user the role in the project, adding the user to the members if needed.
+    pub async fn set_role<C: ConnectionTrait>(
+        db: &C,
+        project_id: u64,
+        user_id: u64,
+        role: ProjectRole,
+    ) -> Result<(), DbErr> {
+        ProjectMember::insert(project_members::ActiveModel {
+            project_id: Set(project_id),
+            user_id: Set(user_id),
+            role: Set(role.to_string()),
+            created_at: Set(chrono::Utc::now()),
+            ..Default::default()
+        })
+        .on_conflict(
+            OnConflict::columns([
+                project_members::Column::ProjectId,
+                project_members::Column::UserId,
+            ])
+            .update_column(project_members::Column::Role)
+            .to_owned(),
+        )
+        .exec_without_returning(db)
+        .await?;
+        Ok(())
+    }
+
+    /// Number of members administering the project.
+    pub async fn admins<C: ConnectionTrait>(db: &C, project_id: u64) -> Result<u64, DbErr> {
+        ProjectMember::find()
+            .filter(project_members::Column::ProjectId.eq(project_id))
+            .filter(project_members::Column::Role.eq(ProjectRole::Admin.to_string()))
+            .count(db)
+            .await
+    }
+}
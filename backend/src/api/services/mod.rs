@@ -1,2 +1,20 @@
+pub mod archive_service;
+pub mod board_service;
+pub mod csv_service;
+pub mod custom_field_service;
+pub mod deadline_service;
+pub mod import_service;
+pub mod label_service;
+pub mod membership_service;
 pub mod notification_service;
+pub mod parent_service;
 pub mod preferences_service;
+pub mod rank_service;
+pub mod recurring_ticket_service;
+pub mod report_service;
+pub mod similarity_service;
+pub mod sla_service;
+pub mod sprint_service;
+pub mod ticket_key_service;
+pub mod ticket_update_service;
+pub mod watcher_service;
@@ -0,0 +1,74 @@
+use crate::api::error::ApiError;
+use axum::http::StatusCode;
+use entity::tickets::{self, Entity as Ticket};
+use sea_orm::{sea_query::Expr, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+
+pub struct ParentService {}
+
+impl ParentService {
+    /// The parent has to be a ticket of the same project, and the ticket cannot end up among
+    /// its own ancestors. A parent kept from before is accepted even if it was deleted since.
+    pub async fn validate<C: ConnectionTrait>(
+        db: &C,
+        project_id: Option<u64>,
+        ticket_id: Option<u64>,
+        parent_id: Option<u64>,
+        previous: Option<u64>,
+    ) -> Result<(), ApiError> {
+        let Some(parent_id) = parent_id.filter(|id| previous != Some(*id)) else {
+            return Ok(());
+        };
+        let parent = match project_id {
+            Some(project_id) => {
+                Ticket::find_active()
+                    .filter(tickets::Column::Id.eq(parent_id))
+                    .filter(tickets::Column::ProjectId.eq(project_id))
+                    .one(db)
+                    .await?
+            }
+            None => None,
+        };
+        let Some(parent) = parent else {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                String::from("Parent not found in the project of the ticket"),
+            ));
+        };
+        let mut visited = vec![parent.id];
+        let mut next = Some(parent);
+        while let Some(ancestor) = next {
+            if Some(ancestor.id) == ticket_id {
+                return Err(ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    String::from("A ticket cannot be a parent of itself"),
+                ));
+            }
+            next = match ancestor.parent_id.filter(|id| !visited.contains(id)) {
+                Some(id) => {
+                    visited.push(id);
+                    Ticket::find_by_id(id).one(db).await?
+                }
+                None => None,
+            };
+        }
+        Ok(())
+    }
+
+    /// Unlinks the tickets from their parents and children, e.g. when they leave the project.
+    pub async fn detach<C: ConnectionTrait>(db: &C, ticket_ids: &[u64]) -> Result<(), DbErr> {
+        if ticket_ids.is_empty() {
+            return Ok(());
+        }
+        Ticket::update_many()
+            .col_expr(tickets::Column::ParentId, Expr::value(None::<u64>))
+            .filter(tickets::Column::Id.is_in(ticket_ids.to_vec()))
+            .exec(db)
+            .await?;
+        Ticket::update_many()
+            .col_expr(tickets::Column::ParentId, Expr::value(None::<u64>))
+            .filter(tickets::Column::ParentId.is_in(ticket_ids.to_vec()))
+            .exec(db)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,205 @@
+use crate::api::error::ApiError;
+use axum::http::StatusCode;
+use entity::tickets::{self, Entity as Ticket};
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DbErr,
+    EntityTrait, QueryFilter, QueryOrder, QuerySelect, Set,
+};
+
+/// Digits of the ranks, in ascending order. Ranks compare as plain strings.
+const DIGITS: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const BASE: usize = DIGITS.len();
+/// Length of the ranks given out when a project is renumbered, without the last digit.
+const REBALANCED_LENGTH: usize = 8;
+/// Last digit of the ranks given out when a project is renumbered, the middle one, as ranks
+/// never end with the smallest digit. The migration adding the ranks appends it as well.
+const REBALANCED_SUFFIX: u8 = b'I';
+
+pub struct RankService {}
+
+impl RankService {
+    /// Rank placing a ticket at the end of the board of the project.
+    pub async fn last<C: ConnectionTrait>(db: &C, project_id: u64) -> Result<String, DbErr> {
+        let last: Option<String> = Ticket::find()
+            .select_only()
+            .column(tickets::Column::BoardRank)
+            .filter(tickets::Column::ProjectId.eq(project_id))
+            .order_by_desc(tickets::Column::BoardRank)
+            .into_tuple()
+            .one(db)
+            .await?;
+        Ok(between(last.as_deref().unwrap_or_default(), None)
+            .expect("there is always a rank after another one"))
+    }
+
+    /// Moves the ticket between its neighbours, `after` being the one above it and `before`
+    /// the one below. The project is renumbered if there is no room left between them, the
+    /// neighbours are refused if they are not in this order even then.
+    pub async fn move_between<C: ConnectionTrait>(
+        db: &C,
+        ticket: &tickets::Model,
+        after: Option<&tickets::Model>,
+        before: Option<&tickets::Model>,
+    ) -> Result<String, ApiError> {
+        let lower = after.map_or(String::new(), |t| t.board_rank.clone());
+        let upper = before.map(|t| t.board_rank.clone());
+        let rank = match between(&lower, upper.as_deref()) {
+            Some(rank) => rank,
+            None => {
+                let ranks = Self::rebalance(db, ticket.project_id.unwrap_or_default()).await?;
+                let rank_of = |neighbour: Option<&tickets::Model>| {
+                    neighbour.and_then(|n| ranks.iter().find(|(id, _)| *id == n.id))
+                };
+                let lower = rank_of(after).map_or(String::new(), |(_, r)| r.clone());
+                let upper = rank_of(before).map(|(_, r)| r.clone());
+                between(&lower, upper.as_deref()).ok_or(ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    String::from("The ticket after should be above the ticket before"),
+                ))?
+            }
+        };
+        tickets::ActiveModel {
+            id: ActiveValue::Unchanged(ticket.id),
+            board_rank: Set(rank.clone()),
+            ..Default::default()
+        }
+        .update(db)
+        .await?;
+        Ok(rank)
+    }
+
+    /// Gives the tickets of the project evenly spaced ranks in their current order. Only
+    /// needed when ranks collide, e.g. after tickets were added at the same time.
+    async fn rebalance<C: ConnectionTrait>(
+        db: &C,
+        project_id: u64,
+    ) -> Result<Vec<(u64, String)>, DbErr> {
+        let ids: Vec<u64> = Ticket::find()
+            .select_only()
+            .column(tickets::Column::Id)
+            .filter(tickets::Column::ProjectId.eq(project_id))
+            .order_by_asc(tickets::Column::BoardRank)
+            .order_by_asc(tickets::Column::Id)
+            .into_tuple()
+            .all(db)
+            .await?;
+        let step = BASE.pow(REBALANCED_LENGTH as u32) / (ids.len() + 1);
+        let mut ranks = Vec::with_capacity(ids.len());
+        for (i, id) in ids.into_iter().enumerate() {
+            let mut rank = encode((i + 1) * step, REBALANCED_LENGTH);
+            rank.push(char::from(REBALANCED_SUFFIX));
+            Ticket::update_many()
+                .col_expr(tickets::Column::BoardRank, Expr::value(rank.clone()))
+                .filter(tickets::Column::Id.eq(id))
+                .exec(db)
+                .await?;
+            ranks.push((id, rank));
+        }
+        Ok(ranks)
+    }
+}
+
+/// Rank sorting after `lower` and before `upper`, or after `lower` only if there is no upper
+/// bound. An empty `lower` is the start. Ranks never end with the smallest digit, so there is
+/// always room between two different ranks given out here; `None` means the bounds are equal
+/// or out of order.
+pub fn between(lower: &str, upper: Option<&str>) -> Option<String> {
+    let lower = lower.as_bytes();
+    let upper = upper.map(str::as_bytes);
+    if upper.is_some_and(|upper| upper <= lower) {
+        return None;
+    }
+    let digit =
+        |rank: &[u8], i: usize| rank.get(i).and_then(|c| DIGITS.iter().position(|d| d == c));
+    let mut result = Vec::new();
+    let mut bounded = upper.is_some();
+    for i in 0.. {
+        let low = digit(lower, i).unwrap_or(0);
+        let high = match (bounded, upper) {
+            (true, Some(upper)) => digit(upper, i)?,
+            _ => BASE,
+        };
+        if high > low + 1 {
+            result.push(DIGITS[(low + high) / 2]);
+            break;
+        }
+        result.push(DIGITS[low]);
+        if high == low + 1 {
+            // Anything starting with the lower digit sorts before the upper bound
+            bounded = false;
+        }
+    }
+    String::from_utf8(result).ok()
+}
+
+fn encode(mut value: usize, length: usize) -> String {
+    let mut digits = vec![DIGITS[0]; length];
+    for digit in digits.iter_mut().rev() {
+        *digit = DIGITS[value % BASE];
+        value /= BASE;
+    }
+    String::from_utf8(digits).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_between(lower: &str, upper: Option<&str>) {
+        let rank = between(lower, upper).unwrap();
+        assert!(rank.as_str() > lower, "{} > {}", rank, lower);
+        if let Some(upper) = upper {
+            assert!(rank.as_str() < upper, "{} < {}", rank, upper);
+        }
+        assert!(!rank.ends_with('0'), "{}", rank);
+    }
+
+    #[test]
+    fn test_between() {
+        assert_between("", None);
+        assert_between("", Some("0000001A"));
+        assert_between("0000001A", None);
+        assert_between("0000001A", Some("0000001B"));
+        assert_between("00000010", Some("00000011"));
+        assert_between("Z", None);
+        assert_between("ZZZ", None);
+        assert_between("1", Some("2"));
+        assert_between("1", Some("11"));
+        assert_between("1Z", Some("2"));
+        assert!(between("A", Some("A")).is_none());
+        assert!(between("B", Some("A")).is_none());
+        assert!(
+            between("1", Some("10")).is_none(),
+            "No rank fits in between"
+        );
+    }
+
+    #[test]
+    fn test_repeated_inserts() {
+        // Always inserting right after the same rank keeps finding room
+        let lower = String::from("0000001A");
+        let mut upper = String::from("0000001B");
+        for _ in 0..100 {
+            let rank = between(&lower, Some(&upper)).unwrap();
+            assert!(rank > lower && rank < upper);
+            upper = rank;
+        }
+    }
+
+    #[test]
+    fn test_rebalanced_ranks() {
+        // The ranks of a renumbered project still leave room in between and around them
+        let mut rank = encode(36, REBALANCED_LENGTH);
+        rank.push(char::from(REBALANCED_SUFFIX));
+        assert_eq!(rank, "00000010I");
+        assert_between("", Some(&rank));
+        assert_between(&rank, None);
+        assert_between(&rank, Some("00000011I"));
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode(36, 8), "00000010");
+        assert!(encode(1, 8) < encode(2, 8));
+    }
+}
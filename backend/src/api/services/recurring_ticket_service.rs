@@ -0,0 +1,230 @@
+use super::{rank_service::RankService, ticket_key_service::TicketKeyService};
+use crate::api::error::ApiError;
+use axum::http::StatusCode;
+use chrono::{DateTime, Utc};
+use cron::Schedule;
+use entity::{
+    recurring_tickets::{self, Entity as RecurringTicket},
+    tickets,
+};
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait,
+    QueryFilter, Set, TransactionTrait,
+};
+use shared::validation::ticket_validation::TicketStatus;
+use std::str::FromStr;
+
+pub struct RecurringTicketService {}
+
+impl RecurringTicketService {
+    /// Parses the expression. Five fields are read like crontab, they start at the minutes, so
+    /// a seconds field is added, and their numbered weekdays are converted.
+    pub fn schedule(expression: &str) -> Result<Schedule, cron::error::Error> {
+        let fields = expression.split_whitespace().collect::<Vec<&str>>();
+        match fields.as_slice() {
+            [minutes, hours, days, months, weekdays] => Schedule::from_str(
+                format!(
+                    "0 {} {} {} {} {}",
+                    minutes,
+                    hours,
+                    days,
+                    months,
+                    Self::weekdays(weekdays)
+                )
+                .as_str(),
+            ),
+            _ => Schedule::from_str(expression.trim()),
+        }
+    }
+
+    /// Converts the numbered weekdays of crontab, from 0 or 7 for Sunday, to the ones of the
+    /// cron crate, from 1 for Sunday to 7 for Saturday. Numbers and ranges are written out as
+    /// lists, names and anything not understood are left to the parser.
+    fn weekdays(field: &str) -> String {
+        field
+            .split(',')
+            .map(|item| {
+                let (range, step) = match item.split_once('/') {
+                    Some((range, step)) => (range, step.parse::<usize>().ok().filter(|s| *s > 0)),
+                    None => (item, Some(1)),
+                };
+                let bounds = match range {
+                    "*" => Some((0, 6)),
+                    _ => match range.split_once('-') {
+                        Some((first, last)) => first.parse::<usize>().ok().zip(last.parse().ok()),
+                        None => range
+                            .parse::<usize>()
+                            .ok()
+                            .map(|day| match item.contains('/') {
+                                true => (day, 7),
+                                false => (day, day),
+                            }),
+                    },
+                };
+                match (bounds, step) {
+                    (Some((first, last)), Some(step)) if first <= last && last <= 7 => {
+                        let mut days = (first..=last)
+                            .step_by(step)
+                            .map(|day| day % 7 + 1)
+                            .collect::<Vec<usize>>();
+                        days.sort_unstable();
+                        days.dedup();
+                        days.iter()
+                            .map(usize::to_string)
+                            .collect::<Vec<String>>()
+                            .join(",")
+                    }
+                    _ => item.to_string(),
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    pub fn validate(expression: &str) -> Result<Schedule, ApiError> {
+        Self::schedule(expression).map_err(|e| {
+            ApiError::new(
+                StatusCode::BAD_REQUEST,
+                format!("The cron expression is not valid: {}", e),
+            )
+        })
+    }
+
+    /// The latest occurrence after `since` and not after `now`. Occurrences missed while
+    /// the scheduler was down collapse into one.
+    pub fn due(
+        schedule: &Schedule,
+        since: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        schedule
+            .after(&since)
+            .take_while(|occurrence| *occurrence <= now)
+            .last()
+    }
+
+    /// Creates the ticket of the occurrence, unless it was created already. Claiming the
+    /// occurrence and creating the ticket share a transaction, so a retried or concurrent run
+    /// creates no duplicate.
+    pub async fn materialize<C: ConnectionTrait + TransactionTrait>(
+        db: &C,
+        definition: &recurring_tickets::Model,
+        occurrence: DateTime<Utc>,
+    ) -> Result<Option<tickets::Model>, DbErr> {
+        let txn = db.begin().await?;
+        let claimed = RecurringTicket::update_many()
+            .col_expr(
+                recurring_tickets::Column::LastRunAt,
+                Expr::value(Some(occurrence)),
+            )
+            .filter(recurring_tickets::Column::Id.eq(definition.id))
+            .filter(
+                Condition::any()
+                    .add(recurring_tickets::Column::LastRunAt.is_null())
+                    .add(recurring_tickets::Column::LastRunAt.lt(occurrence)),
+            )
+            .exec(&txn)
+            .await?
+            .rows_affected
+            > 0;
+        if !claimed {
+            return Ok(None);
+        }
+        let mut ticket = tickets::ActiveModel {
+            title: Set(definition.title.to_owned()),
+            description: Set(definition.description.to_owned()),
+            project_id: Set(Some(definition.project_id)),
+            status: Set(TicketStatus::default().to_string()),
+            user_id: Set(definition.user_id),
+            priority: Set(Some(definition.priority.clone())),
+            reporter_id: Set(definition.reporter_id),
+            board_rank: Set(RankService::last(&txn, definition.project_id).await?),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await?;
+        ticket.ticket_key =
+            Some(TicketKeyService::assign(&txn, ticket.id, definition.project_id).await?);
+        txn.commit().await?;
+        Ok(Some(ticket))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 4, day, hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_schedule() {
+        assert!(RecurringTicketService::schedule("0 6 * * Mon").is_ok());
+        assert!(RecurringTicketService::schedule("0 0 6 * * Mon").is_ok());
+        assert!(RecurringTicketService::schedule("* * * * 1").is_ok());
+        assert!(RecurringTicketService::schedule("* * * * 0").is_ok());
+        assert!(RecurringTicketService::schedule("* * * * 0-6").is_ok());
+        assert!(RecurringTicketService::schedule("* * * * 8").is_err());
+        assert!(RecurringTicketService::schedule("every monday").is_err());
+        assert!(RecurringTicketService::schedule("").is_err());
+    }
+
+    #[test]
+    fn test_weekdays() {
+        assert_eq!(RecurringTicketService::weekdays("1"), "2");
+        assert_eq!(RecurringTicketService::weekdays("0"), "1");
+        assert_eq!(RecurringTicketService::weekdays("7"), "1");
+        assert_eq!(RecurringTicketService::weekdays("1-5"), "2,3,4,5,6");
+        assert_eq!(RecurringTicketService::weekdays("5-7"), "1,6,7");
+        assert_eq!(RecurringTicketService::weekdays("*/2"), "1,3,5,7");
+        assert_eq!(RecurringTicketService::weekdays("1,3"), "2,4");
+        assert_eq!(RecurringTicketService::weekdays("*"), "1,2,3,4,5,6,7");
+        assert_eq!(RecurringTicketService::weekdays("Mon-Fri"), "Mon-Fri");
+    }
+
+    #[test]
+    fn test_monday_in_crontab() {
+        // April 2024 starts on a Monday
+        let schedule = RecurringTicketService::schedule("0 6 * * 1").unwrap();
+        assert_eq!(
+            RecurringTicketService::due(&schedule, at(1, 0, 0), at(1, 6, 0)),
+            Some(at(1, 6, 0))
+        );
+        let schedule = RecurringTicketService::schedule("0 6 * * 0").unwrap();
+        assert_eq!(
+            RecurringTicketService::due(&schedule, at(1, 0, 0), at(7, 6, 0)),
+            Some(at(7, 6, 0)),
+            "Sunday should be day 0."
+        );
+        assert_eq!(
+            RecurringTicketService::due(&schedule, at(1, 0, 0), at(6, 12, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_due() {
+        // Every Monday at 06:00, April 2024 starts on a Monday
+        let schedule = RecurringTicketService::schedule("0 6 * * Mon").unwrap();
+        assert_eq!(
+            RecurringTicketService::due(&schedule, at(1, 0, 0), at(1, 5, 59)),
+            None
+        );
+        assert_eq!(
+            RecurringTicketService::due(&schedule, at(1, 0, 0), at(1, 6, 0)),
+            Some(at(1, 6, 0))
+        );
+        assert_eq!(
+            RecurringTicketService::due(&schedule, at(1, 6, 0), at(3, 12, 0)),
+            None,
+            "The occurrence created last should not be due again."
+        );
+        assert_eq!(
+            RecurringTicketService::due(&schedule, at(1, 6, 0), at(20, 12, 0)),
+            Some(at(15, 6, 0)),
+            "Missed occurrences should collapse into the latest."
+        );
+    }
+}
@@ -0,0 +1,478 @@
+use chrono::{DateTime, Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use entity::{
+    sea_orm_active_enums::Priority,
+    ticket_updates::{self, Entity as TicketUpdate},
+    tickets::{self, Entity as Ticket},
+};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use shared::{
+    dtos::report_dto::{
+        CumulativeFlowPointDto, PercentilesDto, ProgressPointDto, StatusDurationDto,
+        TimeInStatusDto,
+    },
+    validation::ticket_validation::TicketStatus,
+};
+use std::str::FromStr;
+use strum::IntoEnumIterator;
+
+/// Longest series served by the progress report, in days.
+pub const MAX_REPORT_DAYS: i64 = 366;
+/// Completed sprints the velocity forecast is averaged over.
+pub const VELOCITY_SPRINTS: usize = 3;
+/// Statuses of work in progress, the cycle time starts when a ticket first enters one of them.
+pub const IN_PROGRESS: [TicketStatus; 3] = [
+    TicketStatus::Started,
+    TicketStatus::Reviewing,
+    TicketStatus::Testing,
+];
+
+/// A ticket in the scope of a report, with its status changes from oldest to newest.
+#[derive(Clone, Debug)]
+pub struct TicketHistory {
+    pub ticket_id: u64,
+    pub priority: Option<Priority>,
+    /// Internal id of the assignee.
+    pub user_id: Option<u64>,
+    pub created_at: NaiveDateTime,
+    pub status: TicketStatus,
+    /// Time, previous and next status of every change.
+    pub changes: Vec<(NaiveDateTime, TicketStatus, TicketStatus)>,
+}
+
+impl TicketHistory {
+    /// Status of the ticket at the given time, `None` if it did not exist yet.
+    fn status_at(&self, at: NaiveDateTime) -> Option<TicketStatus> {
+        if self.created_at >= at {
+            return None;
+        }
+        match self.changes.iter().rev().find(|(time, _, _)| *time < at) {
+            Some((_, _, next)) => Some(*next),
+            // Before the first change the ticket had the status it was changed from
+            None => Some(
+                self.changes
+                    .first()
+                    .map_or(self.status, |(_, previous, _)| *previous),
+            ),
+        }
+    }
+
+    /// Status the ticket was created with.
+    fn initial_status(&self) -> TicketStatus {
+        self.changes
+            .first()
+            .map_or(self.status, |(_, previous, _)| *previous)
+    }
+
+    /// When the ticket was last finished, `None` if it is not finished now.
+    pub fn finished_at(&self) -> Option<NaiveDateTime> {
+        if !self.status.is_finished() {
+            return None;
+        }
+        Some(
+            self.changes
+                .iter()
+                .rev()
+                .find(|(_, previous, next)| next.is_finished() && !previous.is_finished())
+                .map_or(self.created_at, |(time, _, _)| *time),
+        )
+    }
+
+    /// When work on the ticket first started, `None` if it never did.
+    fn started_at(&self) -> Option<NaiveDateTime> {
+        if IN_PROGRESS.contains(&self.initial_status()) {
+            return Some(self.created_at);
+        }
+        self.changes
+            .iter()
+            .find(|(_, _, next)| IN_PROGRESS.contains(next))
+            .map(|(time, _, _)| *time)
+    }
+
+    /// When the ticket was first started or finished, `None` if it is still waiting.
+    pub fn picked_up_at(&self) -> Option<NaiveDateTime> {
+        let picked_up =
+            |status: &TicketStatus| IN_PROGRESS.contains(status) || status.is_finished();
+        if picked_up(&self.initial_status()) {
+            return Some(self.created_at);
+        }
+        self.changes
+            .iter()
+            .find(|(_, _, next)| picked_up(next))
+            .map(|(time, _, _)| *time)
+    }
+
+    /// From creation until finished.
+    pub fn lead_time(&self) -> Option<Duration> {
+        self.finished_at().map(|end| end - self.created_at)
+    }
+
+    /// From the start of the work until finished.
+    pub fn cycle_time(&self) -> Option<Duration> {
+        match (self.started_at(), self.finished_at()) {
+            (Some(start), Some(end)) if start <= end => Some(end - start),
+            _ => None,
+        }
+    }
+
+    /// Time spent in every status until the ticket was finished, or until `now`. Statuses the
+    /// ticket went through more than once add up.
+    pub fn time_in_status(&self, now: NaiveDateTime) -> Vec<(TicketStatus, Duration)> {
+        let end = self.finished_at().unwrap_or(now);
+        let mut durations = TicketStatus::iter()
+            .map(|status| (status, Duration::zero()))
+            .collect::<Vec<(TicketStatus, Duration)>>();
+        let mut since = self.created_at;
+        let mut status = self.initial_status();
+        for (time, _, next) in self.changes.iter().filter(|(time, _, _)| *time <= end) {
+            if let Some((_, duration)) = durations.iter_mut().find(|(s, _)| *s == status) {
+                *duration += *time - since;
+            }
+            since = *time;
+            status = *next;
+        }
+        if let Some((_, duration)) = durations.iter_mut().find(|(s, _)| *s == status) {
+            *duration += end - since;
+        }
+        durations
+    }
+}
+
+type HistoryRow = (u64, DateTime<Utc>, String, Option<Priority>, Option<u64>);
+
+pub struct ReportService {}
+
+impl ReportService {
+    /// Histories of the tickets matching the condition, deleted tickets left out.
+    pub async fn histories<C: ConnectionTrait>(
+        db: &C,
+        condition: Condition,
+    ) -> Result<Vec<TicketHistory>, DbErr> {
+        let tickets: Vec<HistoryRow> = Ticket::find_active()
+            .select_only()
+            .columns([
+                tickets::Column::Id,
+                tickets::Column::CreatedAt,
+                tickets::Column::Status,
+                tickets::Column::Priority,
+                tickets::Column::UserId,
+            ])
+            .filter(condition)
+            .into_tuple()
+            .all(db)
+            .await?;
+        if tickets.is_empty() {
+            return Ok(Vec::new());
+        }
+        let updates = TicketUpdate::find()
+            .filter(
+                ticket_updates::Column::TicketId
+                    .is_in(tickets.iter().map(|(id, ..)| *id).collect::<Vec<u64>>()),
+            )
+            .order_by_asc(ticket_updates::Column::Timestamp)
+            .order_by_asc(ticket_updates::Column::Id)
+            .all(db)
+            .await?;
+        Ok(tickets
+            .into_iter()
+            .map(
+                |(id, created_at, status, priority, user_id)| TicketHistory {
+                    ticket_id: id,
+                    priority,
+                    user_id,
+                    created_at: created_at.naive_utc(),
+                    status: TicketStatus::from_str(status.as_str()).unwrap_or_default(),
+                    changes: updates
+                        .iter()
+                        .filter(|u| u.ticket_id == id)
+                        .map(|u| {
+                            (
+                                u.timestamp,
+                                TicketStatus::from_str(u.previous_state.as_str())
+                                    .unwrap_or_default(),
+                                TicketStatus::from_str(u.next_state.as_str()).unwrap_or_default(),
+                            )
+                        })
+                        .collect(),
+                },
+            )
+            .collect())
+    }
+
+    /// One point per day from `from` to `to`, both included, counted at the end of the day.
+    pub fn progress(
+        histories: &[TicketHistory],
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Vec<ProgressPointDto> {
+        from.iter_days()
+            .take_while(|date| *date <= to)
+            .map(|date| {
+                let end = date.and_time(NaiveTime::MIN) + Duration::days(1);
+                let statuses = histories
+                    .iter()
+                    .filter_map(|h| h.status_at(end))
+                    .collect::<Vec<TicketStatus>>();
+                ProgressPointDto {
+                    date,
+                    scope: statuses.len() as u64,
+                    done: statuses.iter().filter(|s| s.is_finished()).count() as u64,
+                }
+            })
+            .collect()
+    }
+
+    /// Tickets in every status at the end of each day from `from` to `to`, in the order of
+    /// the statuses.
+    pub fn cumulative_flow(
+        histories: &[TicketHistory],
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Vec<CumulativeFlowPointDto> {
+        from.iter_days()
+            .take_while(|date| *date <= to)
+            .map(|date| {
+                let end = date.and_time(NaiveTime::MIN) + Duration::days(1);
+                let statuses = histories
+                    .iter()
+                    .filter_map(|h| h.status_at(end))
+                    .collect::<Vec<TicketStatus>>();
+                CumulativeFlowPointDto {
+                    date,
+                    counts: TicketStatus::iter()
+                        .map(|status| statuses.iter().filter(|s| **s == status).count() as u64)
+                        .collect(),
+                }
+            })
+            .collect()
+    }
+
+    pub fn time_in_status(history: &TicketHistory, now: NaiveDateTime) -> TimeInStatusDto {
+        TimeInStatusDto {
+            ticket_id: history.ticket_id,
+            statuses: history
+                .time_in_status(now)
+                .into_iter()
+                .map(|(status, duration)| StatusDurationDto {
+                    status,
+                    hours: hours(duration),
+                })
+                .collect(),
+            lead_hours: history.lead_time().map(hours),
+            cycle_hours: history.cycle_time().map(hours),
+        }
+    }
+
+    /// Nearest-rank percentiles of the durations, `None` without any.
+    pub fn percentiles(durations: &[Duration]) -> Option<PercentilesDto> {
+        if durations.is_empty() {
+            return None;
+        }
+        let mut sorted = durations.to_vec();
+        sorted.sort();
+        let rank = |p: usize| hours(sorted[(p * sorted.len()).div_ceil(100).max(1) - 1]);
+        Some(PercentilesDto {
+            p50: rank(50),
+            p85: rank(85),
+            p95: rank(95),
+        })
+    }
+
+    /// Tickets finished at the given time.
+    pub fn finished_at(histories: &[TicketHistory], at: NaiveDateTime) -> u64 {
+        histories
+            .iter()
+            .filter(|h| h.status_at(at).is_some_and(|s| s.is_finished()))
+            .count() as u64
+    }
+
+    /// Average of the last sprints, zero without any.
+    pub fn average(completed: &[u64]) -> f64 {
+        let last = &completed[completed.len().saturating_sub(VELOCITY_SPRINTS)..];
+        match last.len() {
+            0 => 0.0,
+            count => last.iter().sum::<u64>() as f64 / count as f64,
+        }
+    }
+}
+
+/// Hours with a precision of a minute.
+fn hours(duration: Duration) -> f64 {
+    (duration.num_minutes() as f64 / 60.0 * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 4, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn history(
+        created: NaiveDateTime,
+        status: TicketStatus,
+        changes: &[(NaiveDateTime, TicketStatus, TicketStatus)],
+    ) -> TicketHistory {
+        TicketHistory {
+            ticket_id: 1,
+            priority: None,
+            user_id: None,
+            created_at: created,
+            status,
+            changes: changes.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_progress() {
+        let histories = vec![
+            history(
+                at(1, 9),
+                TicketStatus::Done,
+                &[
+                    (at(2, 10), TicketStatus::Selected, TicketStatus::Started),
+                    (at(3, 10), TicketStatus::Started, TicketStatus::Done),
+                ],
+            ),
+            history(at(2, 12), TicketStatus::Selected, &[]),
+        ];
+        let points = ReportService::progress(
+            &histories,
+            NaiveDate::from_ymd_opt(2024, 3, 31).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 4, 3).unwrap(),
+        );
+        assert_eq!(
+            points
+                .iter()
+                .map(|p| (p.scope, p.done))
+                .collect::<Vec<(u64, u64)>>(),
+            vec![(0, 0), (1, 0), (2, 0), (2, 1)],
+            "Tickets should count from their creation and be done after the change."
+        );
+        assert_eq!(points[3].remaining(), 1);
+    }
+
+    #[test]
+    fn test_finished_at_reopened() {
+        let histories = vec![history(
+            at(1, 9),
+            TicketStatus::Started,
+            &[
+                (at(2, 10), TicketStatus::Started, TicketStatus::Done),
+                (at(4, 10), TicketStatus::Done, TicketStatus::Started),
+            ],
+        )];
+        assert_eq!(ReportService::finished_at(&histories, at(3, 0)), 1);
+        assert_eq!(
+            ReportService::finished_at(&histories, at(5, 0)),
+            0,
+            "Reopened tickets should not count as finished."
+        );
+    }
+
+    #[test]
+    fn test_cycle_and_lead_time() {
+        let ticket = history(
+            at(1, 9),
+            TicketStatus::Done,
+            &[
+                (at(1, 12), TicketStatus::Created, TicketStatus::Selected),
+                (at(2, 10), TicketStatus::Selected, TicketStatus::Started),
+                (at(2, 16), TicketStatus::Started, TicketStatus::Reviewing),
+                (at(3, 10), TicketStatus::Reviewing, TicketStatus::Started),
+                (at(3, 12), TicketStatus::Started, TicketStatus::Done),
+            ],
+        );
+        assert_eq!(ticket.lead_time(), Some(Duration::hours(51)));
+        assert_eq!(ticket.cycle_time(), Some(Duration::hours(26)));
+        let durations = ticket.time_in_status(at(10, 0));
+        let time_in = |status: TicketStatus| {
+            durations
+                .iter()
+                .find(|(s, _)| *s == status)
+                .map(|(_, d)| *d)
+                .unwrap()
+        };
+        assert_eq!(time_in(TicketStatus::Created), Duration::hours(3));
+        assert_eq!(
+            time_in(TicketStatus::Started),
+            Duration::hours(8),
+            "Time should add up over every stay in the status."
+        );
+        assert_eq!(time_in(TicketStatus::Reviewing), Duration::hours(18));
+        assert_eq!(
+            time_in(TicketStatus::Done),
+            Duration::zero(),
+            "Time after finishing should not count."
+        );
+    }
+
+    #[test]
+    fn test_never_started() {
+        let ticket = history(
+            at(1, 9),
+            TicketStatus::Closed,
+            &[(at(2, 9), TicketStatus::Selected, TicketStatus::Closed)],
+        );
+        assert_eq!(ticket.lead_time(), Some(Duration::hours(24)));
+        assert_eq!(ticket.cycle_time(), None);
+    }
+
+    #[test]
+    fn test_percentiles() {
+        assert_eq!(ReportService::percentiles(&[]), None);
+        let durations = (1..=20).map(Duration::hours).collect::<Vec<Duration>>();
+        assert_eq!(
+            ReportService::percentiles(&durations),
+            Some(PercentilesDto {
+                p50: 10.0,
+                p85: 17.0,
+                p95: 19.0
+            })
+        );
+        assert_eq!(
+            ReportService::percentiles(&[Duration::minutes(90)]),
+            Some(PercentilesDto {
+                p50: 1.5,
+                p85: 1.5,
+                p95: 1.5
+            })
+        );
+    }
+
+    #[test]
+    fn test_cumulative_flow() {
+        let histories = vec![
+            history(
+                at(1, 9),
+                TicketStatus::Started,
+                &[(at(2, 10), TicketStatus::Selected, TicketStatus::Started)],
+            ),
+            history(at(2, 12), TicketStatus::Selected, &[]),
+        ];
+        let points = ReportService::cumulative_flow(
+            &histories,
+            NaiveDate::from_ymd_opt(2024, 4, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 4, 2).unwrap(),
+        );
+        assert_eq!(points[0].counts, vec![0, 1, 0, 0, 0, 0, 0]);
+        assert_eq!(points[1].counts, vec![0, 1, 1, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_average() {
+        assert_eq!(ReportService::average(&[]), 0.0);
+        assert_eq!(ReportService::average(&[2, 4]), 3.0);
+        assert_eq!(
+            ReportService::average(&[100, 3, 6, 9]),
+            6.0,
+            "Only the last sprints should count."
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use entity::tickets::{self, Entity as Ticket};
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, QueryFilter, QuerySelect};
+use shared::{
+    dtos::ticket_dto::{SimilarTicketDto, TicketDraftDto},
+    validation::ticket_validation::TicketStatus,
+};
+use std::{collections::HashSet, str::FromStr};
+use strum::IntoEnumIterator;
+
+/// Score from which a ticket is suggested as a duplicate.
+pub const MIN_SCORE: u8 = 30;
+/// Suggestions returned at most.
+pub const MAX_SUGGESTIONS: usize = 5;
+
+/// Id, key, title, description and status of a ticket.
+type CandidateRow = (u64, Option<String>, String, String, String);
+
+pub struct SimilarityService {}
+
+impl SimilarityService {
+    /// Open tickets of the project of the draft, or without a project, most similar to the
+    /// draft first.
+    pub async fn similar<C: ConnectionTrait>(
+        db: &C,
+        draft: &TicketDraftDto,
+    ) -> Result<Vec<SimilarTicketDto>, DbErr> {
+        let finished = TicketStatus::iter()
+            .filter(|status| status.is_finished())
+            .map(|status| status.to_string())
+            .collect::<Vec<String>>();
+        let candidates: Vec<CandidateRow> = Ticket::find_active()
+            .select_only()
+            .columns([
+                tickets::Column::Id,
+                tickets::Column::TicketKey,
+                tickets::Column::Title,
+                tickets::Column::Description,
+                tickets::Column::Status,
+            ])
+            .filter(match draft.project_id {
+                Some(project_id) => tickets::Column::ProjectId.eq(project_id),
+                None => tickets::Column::ProjectId.is_null(),
+            })
+            .filter(tickets::Column::Status.is_not_in(finished))
+            .into_tuple()
+            .all(db)
+            .await?;
+        let mut similar = candidates
+            .into_iter()
+            .map(|(id, key, title, description, status)| SimilarTicketDto {
+                score: Self::score(&draft.title, &draft.description, &title, &description),
+                id,
+                key,
+                title,
+                status: TicketStatus::from_str(status.as_str()).unwrap_or_default(),
+            })
+            .filter(|ticket| ticket.score >= MIN_SCORE)
+            .collect::<Vec<SimilarTicketDto>>();
+        // The newer of two equally similar tickets first
+        similar.sort_by(|a, b| b.score.cmp(&a.score).then(b.id.cmp(&a.id)));
+        similar.truncate(MAX_SUGGESTIONS);
+        Ok(similar)
+    }
+
+    /// Similarity of two tickets from 0 to 100. Trigrams of the titles weigh most, as they
+    /// also match typos and other forms of a word. Shared words of title and description add
+    /// to it once the draft has a description.
+    pub fn score(title: &str, description: &str, other_title: &str, other_description: &str) -> u8 {
+        let titles = Self::jaccard(&Self::trigrams(title), &Self::trigrams(other_title));
+        let score = match description.trim().is_empty() {
+            true => titles,
+            false => {
+                let words = Self::jaccard(
+                    &Self::words(format!("{} {}", title, description).as_str()),
+                    &Self::words(format!("{} {}", other_title, other_description).as_str()),
+                );
+                titles * 0.7 + words * 0.3
+            }
+        };
+        (score * 100.0).round() as u8
+    }
+
+    /// Lowercase words of at least three letters or digits.
+    pub fn words(text: &str) -> HashSet<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| word.chars().count() >= 3)
+            .map(|word| word.to_lowercase())
+            .collect()
+    }
+
+    /// Trigrams of the words, padded to also match the start and the end of a word.
+    pub fn trigrams(text: &str) -> HashSet<String> {
+        Self::words(text)
+            .iter()
+            .flat_map(|word| {
+                let padded = format!("  {} ", word).chars().collect::<Vec<char>>();
+                padded
+                    .windows(3)
+                    .map(|w| w.iter().collect::<String>())
+                    .collect::<Vec<String>>()
+            })
+            .collect()
+    }
+
+    fn jaccard(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+        let union = a.union(b).count();
+        match union {
+            0 => 0.0,
+            _ => a.intersection(b).count() as f64 / union as f64,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_words() {
+        assert_eq!(
+            SimilarityService::words("Login fails on the UI, error 500!"),
+            HashSet::from(["login", "fails", "the", "error", "500"].map(String::from))
+        );
+        assert!(SimilarityService::words("a b c").is_empty());
+    }
+
+    #[test]
+    fn test_score() {
+        assert_eq!(
+            SimilarityService::score("Login page crashes", "", "Login page crashes", "Anything"),
+            100
+        );
+        assert!(
+            SimilarityService::score("Loggin page crash", "", "Login page crashes", "")
+                >= MIN_SCORE,
+            "A typo should still match."
+        );
+        assert!(
+            SimilarityService::score("Export invoices as PDF", "", "Login page crashes", "")
+                < MIN_SCORE
+        );
+        assert!(
+            SimilarityService::score(
+                "Crash after signing in",
+                "The login page crashes with error 500 for every user",
+                "Login page crashes",
+                "Error 500 for every user on the login page"
+            ) > SimilarityService::score(
+                "Crash after signing in",
+                "",
+                "Login page crashes",
+                "Error 500 for every user on the login page"
+            ),
+            "Shared words of the descriptions should add to the score."
+        );
+        assert_eq!(SimilarityService::score("", "", "", ""), 0);
+    }
+}
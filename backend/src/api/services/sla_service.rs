@@ -0,0 +1,267 @@
+use super::report_service::ReportService;
+use chrono::{Datelike, Duration, NaiveDateTime};
+use entity::{
+    comments::{self, Entity as Comment},
+    sea_orm_active_enums::Priority,
+    sla_policies::{self, Entity as SlaPolicy},
+    tickets::{self, Entity as Ticket},
+};
+use sea_orm::{
+    ColumnTrait, Condition, ConnectionTrait, DbErr, EntityTrait, QueryFilter, QueryOrder,
+    QuerySelect,
+};
+use shared::dtos::sla_dto::{
+    BusinessHoursDto, SlaClock, SlaClockDto, SlaPolicyDto, SlaState, TicketSlaDto,
+};
+
+/// Share of the target in percent after which a running clock is at risk.
+pub const AT_RISK_PERCENT: i64 = 80;
+
+/// Id, project, priority and reporter of a ticket.
+type SlaRow = (u64, Option<u64>, Option<Priority>, Option<u64>);
+
+pub struct SlaService {}
+
+impl SlaService {
+    /// Policies of the project, from the lowest priority to the highest.
+    pub async fn policies<C: ConnectionTrait>(
+        db: &C,
+        project_id: u64,
+    ) -> Result<Vec<SlaPolicyDto>, DbErr> {
+        Ok(SlaPolicy::find()
+            .filter(sla_policies::Column::ProjectId.eq(project_id))
+            .order_by_asc(sla_policies::Column::Priority)
+            .all(db)
+            .await?
+            .into_iter()
+            .map(SlaPolicyDto::from)
+            .collect())
+    }
+
+    /// Clocks of the tickets matching the condition. Tickets without a policy for their
+    /// project and priority are left out.
+    pub async fn ticket_slas<C: ConnectionTrait>(
+        db: &C,
+        condition: Condition,
+        now: NaiveDateTime,
+    ) -> Result<Vec<TicketSlaDto>, DbErr> {
+        let tickets: Vec<SlaRow> = Ticket::find_active()
+            .select_only()
+            .columns([
+                tickets::Column::Id,
+                tickets::Column::ProjectId,
+                tickets::Column::Priority,
+                tickets::Column::ReporterId,
+            ])
+            .filter(condition.clone())
+            .into_tuple()
+            .all(db)
+            .await?;
+        let mut project_ids = tickets
+            .iter()
+            .filter_map(|(_, project_id, ..)| *project_id)
+            .collect::<Vec<u64>>();
+        project_ids.sort();
+        project_ids.dedup();
+        if project_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let policies = SlaPolicy::find()
+            .filter(sla_policies::Column::ProjectId.is_in(project_ids))
+            .all(db)
+            .await?;
+        let tickets = tickets
+            .into_iter()
+            .filter_map(|(id, project_id, priority, reporter_id)| {
+                policies
+                    .iter()
+                    .find(|p| {
+                        Some(p.project_id) == project_id && Some(&p.priority) == priority.as_ref()
+                    })
+                    .map(|policy| (id, reporter_id, SlaPolicyDto::from(policy)))
+            })
+            .collect::<Vec<(u64, Option<u64>, SlaPolicyDto)>>();
+        if tickets.is_empty() {
+            return Ok(Vec::new());
+        }
+        let ids = tickets.iter().map(|(id, ..)| *id).collect::<Vec<u64>>();
+        let histories = ReportService::histories(
+            db,
+            Condition::all().add(tickets::Column::Id.is_in(ids.clone())),
+        )
+        .await?;
+        let comments: Vec<(u64, u64, NaiveDateTime)> = Comment::find()
+            .select_only()
+            .columns([
+                comments::Column::TicketId,
+                comments::Column::UserId,
+                comments::Column::Timestamp,
+            ])
+            .filter(comments::Column::TicketId.is_in(ids))
+            .order_by_asc(comments::Column::Timestamp)
+            .into_tuple()
+            .all(db)
+            .await?;
+
+        Ok(tickets
+            .into_iter()
+            .filter_map(|(id, reporter_id, policy)| {
+                let history = histories.iter().find(|h| h.ticket_id == id)?;
+                let answered_at = comments
+                    .iter()
+                    .find(|(ticket_id, user_id, _)| {
+                        *ticket_id == id && Some(*user_id) != reporter_id
+                    })
+                    .map(|(_, _, timestamp)| *timestamp);
+                let responded_at = match (history.picked_up_at(), answered_at) {
+                    (Some(a), Some(b)) => Some(a.min(b)),
+                    (a, b) => a.or(b),
+                };
+                let hours = policy.business_hours.as_ref();
+                let mut clocks = Vec::new();
+                if let Some(target) = policy.response_minutes {
+                    clocks.push(Self::clock(
+                        SlaClock::Response,
+                        target,
+                        history.created_at,
+                        responded_at,
+                        now,
+                        hours,
+                    ));
+                }
+                if let Some(target) = policy.resolution_minutes {
+                    clocks.push(Self::clock(
+                        SlaClock::Resolution,
+                        target,
+                        history.created_at,
+                        history.finished_at(),
+                        now,
+                        hours,
+                    ));
+                }
+                Some(TicketSlaDto {
+                    ticket_id: id,
+                    clocks,
+                })
+            })
+            .collect())
+    }
+
+    /// A clock started at `start`, running until `stop` or `now`.
+    pub fn clock(
+        clock: SlaClock,
+        target_minutes: u32,
+        start: NaiveDateTime,
+        stop: Option<NaiveDateTime>,
+        now: NaiveDateTime,
+        hours: Option<&BusinessHoursDto>,
+    ) -> SlaClockDto {
+        let elapsed = Self::working_time(start, stop.unwrap_or(now), hours).num_minutes();
+        let target = target_minutes as i64;
+        let state = if elapsed > target {
+            SlaState::Breached
+        } else if stop.is_some() {
+            SlaState::Met
+        } else if elapsed * 100 >= target * AT_RISK_PERCENT {
+            SlaState::AtRisk
+        } else {
+            SlaState::Running
+        };
+        SlaClockDto {
+            clock,
+            target_minutes,
+            elapsed_minutes: elapsed.clamp(0, u32::MAX as i64) as u32,
+            state,
+        }
+    }
+
+    /// Time between two UTC times that falls within the business hours, or all of it without
+    /// business hours.
+    pub fn working_time(
+        start: NaiveDateTime,
+        end: NaiveDateTime,
+        hours: Option<&BusinessHoursDto>,
+    ) -> Duration {
+        if end <= start {
+            return Duration::zero();
+        }
+        let Some(hours) = hours else {
+            return end - start;
+        };
+        let offset = Duration::minutes(hours.utc_offset_minutes as i64);
+        let (start, end) = (start + offset, end + offset);
+        start
+            .date()
+            .iter_days()
+            .take_while(|date| *date <= end.date())
+            .filter(|date| hours.days.contains(&date.weekday()))
+            .map(|date| {
+                let from = start.max(date.and_time(hours.start));
+                let to = end.min(date.and_time(hours.end));
+                (to - from).max(Duration::zero())
+            })
+            .sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{NaiveDate, NaiveTime, Weekday};
+
+    /// April 2024 starts on a Monday.
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 4, day)
+            .unwrap()
+            .and_time(NaiveTime::from_hms_opt(hour, minute, 0).unwrap())
+    }
+
+    #[test]
+    fn test_working_time() {
+        assert_eq!(
+            SlaService::working_time(at(1, 8, 0), at(1, 12, 30), None),
+            Duration::minutes(270)
+        );
+        let hours = BusinessHoursDto::default();
+        assert_eq!(
+            SlaService::working_time(at(1, 8, 0), at(1, 12, 30), Some(&hours)),
+            Duration::minutes(210),
+            "Time before the start of the day should not count."
+        );
+        assert_eq!(
+            SlaService::working_time(at(5, 16, 0), at(8, 10, 0), Some(&hours)),
+            Duration::hours(2),
+            "The weekend should not count."
+        );
+        assert_eq!(
+            SlaService::working_time(at(1, 10, 0), at(1, 9, 0), Some(&hours)),
+            Duration::zero()
+        );
+        let shifted = BusinessHoursDto {
+            days: vec![Weekday::Mon],
+            utc_offset_minutes: 120,
+            ..Default::default()
+        };
+        assert_eq!(
+            SlaService::working_time(at(1, 6, 0), at(1, 20, 0), Some(&shifted)),
+            Duration::hours(8),
+            "Business hours are local, 07:00 to 15:00 in UTC."
+        );
+    }
+
+    #[test]
+    fn test_clock() {
+        let clock = |stop: Option<NaiveDateTime>, now: NaiveDateTime| {
+            SlaService::clock(SlaClock::Response, 240, at(1, 9, 0), stop, now, None).state
+        };
+        assert_eq!(clock(None, at(1, 11, 0)), SlaState::Running);
+        assert_eq!(clock(None, at(1, 12, 15)), SlaState::AtRisk);
+        assert_eq!(clock(None, at(1, 13, 1)), SlaState::Breached);
+        assert_eq!(clock(Some(at(1, 12, 59)), at(2, 9, 0)), SlaState::Met);
+        assert_eq!(
+            clock(Some(at(1, 14, 0)), at(2, 9, 0)),
+            SlaState::Breached,
+            "A clock stopped late stays breached."
+        );
+    }
+}
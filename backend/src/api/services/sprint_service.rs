@@ -0,0 +1,100 @@
+use crate::api::error::ApiError;
+use axum::http::StatusCode;
+use entity::{
+    sprints::{self, Entity as Sprint},
+    tickets::{self, Entity as Ticket},
+};
+use sea_orm::{sea_query::Expr, ColumnTrait, ConnectionTrait, DbErr, EntityTrait, QueryFilter};
+use shared::{dtos::sprint_dto::SprintState, validation::ticket_validation::TicketStatus};
+use strum::IntoEnumIterator;
+
+pub struct SprintService {}
+
+impl SprintService {
+    /// The sprint currently worked on in the project, there is at most one.
+    pub async fn active<C: ConnectionTrait>(
+        db: &C,
+        project_id: u64,
+    ) -> Result<Option<sprints::Model>, DbErr> {
+        Sprint::find()
+            .filter(sprints::Column::ProjectId.eq(project_id))
+            .filter(sprints::Column::State.eq(SprintState::Active.to_string()))
+            .one(db)
+            .await
+    }
+
+    /// Tickets can only be planned for sprints of their own project. Completed sprints take no
+    /// more tickets, but the tickets finished in them stay there.
+    pub async fn validate<C: ConnectionTrait>(
+        db: &C,
+        project_id: Option<u64>,
+        sprint_id: Option<u64>,
+        previous: Option<u64>,
+    ) -> Result<(), ApiError> {
+        let Some(sprint_id) = sprint_id else {
+            return Ok(());
+        };
+        let sprint = match project_id {
+            Some(project_id) => {
+                Sprint::find_by_id(sprint_id)
+                    .filter(sprints::Column::ProjectId.eq(project_id))
+                    .one(db)
+                    .await?
+            }
+            None => None,
+        };
+        match sprint {
+            None => Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                String::from("Sprint not found in the project of the ticket"),
+            )),
+            Some(sprint)
+                if sprint.state == SprintState::Completed.to_string()
+                    && previous != Some(sprint_id) =>
+            {
+                Err(ApiError::new(
+                    StatusCode::BAD_REQUEST,
+                    String::from("Completed sprints cannot take more tickets"),
+                ))
+            }
+            Some(_) => Ok(()),
+        }
+    }
+
+    /// Moves the tickets of the sprint which are not done yet to another sprint, or to the
+    /// backlog if there is none. Returns the number of moved tickets.
+    pub async fn move_unfinished<C: ConnectionTrait>(
+        db: &C,
+        sprint_id: u64,
+        target: Option<u64>,
+    ) -> Result<u64, DbErr> {
+        let unfinished = TicketStatus::iter()
+            .filter(|status| !status.is_finished())
+            .map(|status| status.to_string());
+        Ok(Ticket::update_many()
+            .col_expr(tickets::Column::SprintId, Expr::value(target))
+            .col_expr(
+                tickets::Column::Version,
+                Expr::col(tickets::Column::Version).add(1),
+            )
+            .filter(tickets::Column::SprintId.eq(sprint_id))
+            .filter(tickets::Column::Status.is_in(unfinished))
+            .exec(db)
+            .await?
+            .rows_affected)
+    }
+
+    /// Sends every ticket of the sprint back to the backlog, e.g. when the sprint is deleted.
+    pub async fn clear<C: ConnectionTrait>(db: &C, sprint_id: u64) -> Result<(), DbErr> {
+        Ticket::update_many()
+            .col_expr(tickets::Column::SprintId, Expr::value(None::<u64>))
+            .col_expr(
+                tickets::Column::Version,
+                Expr::col(tickets::Column::Version).add(1),
+            )
+            .filter(tickets::Column::SprintId.eq(sprint_id))
+            .exec(db)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use entity::{
+    projects::{self, Entity as Project},
+    ticket_keys::{self, Entity as TicketKey},
+    tickets,
+};
+use sea_orm::{
+    sea_query::Expr, ActiveModelTrait, ActiveValue, ColumnTrait, ConnectionTrait, DbErr,
+    EntityTrait, QueryFilter, QuerySelect, QueryTrait, Select, Set,
+};
+
+pub struct TicketKeyService {}
+
+impl TicketKeyService {
+    /// Gives the ticket the next key of the project, e.g. `TCK-142`. Has to run in the
+    /// transaction creating or moving the ticket: the increment keeps the project row locked
+    /// until it is committed, so concurrent requests never get the same number.
+    pub async fn assign<C: ConnectionTrait>(
+        db: &C,
+        ticket_id: u64,
+        project_id: u64,
+    ) -> Result<String, DbErr> {
+        Project::update_many()
+            .col_expr(
+                projects::Column::TicketSequence,
+                Expr::col(projects::Column::TicketSequence).add(1),
+            )
+            .filter(projects::Column::Id.eq(project_id))
+            .exec(db)
+            .await?;
+        let (key, sequence): (String, u64) = Project::find_by_id(project_id)
+            .select_only()
+            .column(projects::Column::Key)
+            .column(projects::Column::TicketSequence)
+            .into_tuple()
+            .one(db)
+            .await?
+            .ok_or(DbErr::RecordNotFound(format!("Project {}", project_id)))?;
+        let ticket_key = format!("{}-{}", key, sequence);
+
+        tickets::ActiveModel {
+            id: ActiveValue::Unchanged(ticket_id),
+            ticket_key: Set(Some(ticket_key.clone())),
+            ..Default::default()
+        }
+        .update(db)
+        .await?;
+        ticket_keys::ActiveModel {
+            ticket_key: Set(ticket_key.clone()),
+            ticket_id: Set(ticket_id),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        Ok(ticket_key)
+    }
+
+    /// Keys with the prefix given to tickets which are not in the project, e.g. the ones of a
+    /// project which had the key before. A project taking the prefix over would give their
+    /// keys again.
+    pub fn foreign_keys(prefix: &str, project_id: Option<u64>) -> Select<TicketKey> {
+        TicketKey::find()
+            .filter(ticket_keys::Column::TicketKey.starts_with(format!("{}-", prefix)))
+            .apply_if(project_id, |query, project_id| {
+                query.filter(
+                    ticket_keys::Column::TicketId.not_in_subquery(
+                        tickets::Entity::find()
+                            .select_only()
+                            .column(tickets::Column::Id)
+                            .filter(tickets::Column::ProjectId.eq(project_id))
+                            .into_query(),
+                    ),
+                )
+            })
+    }
+
+    /// Resolves a ticket reference, either a database id or any key the ticket ever had.
+    pub async fn resolve<C: ConnectionTrait>(
+        db: &C,
+        reference: &str,
+    ) -> Result<Option<u64>, DbErr> {
+        if let Ok(id) = reference.parse::<u64>() {
+            return Ok(Some(id));
+        }
+        TicketKey::find()
+            .select_only()
+            .column(ticket_keys::Column::TicketId)
+            .filter(ticket_keys::Column::TicketKey.eq(reference.to_uppercase()))
+            .into_tuple()
+            .one(db)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sea_orm::DbBackend;
+
+    #[test]
+    fn test_foreign_keys() {
+        let sql = TicketKeyService::foreign_keys("TCK", Some(4))
+            .build(DbBackend::MySql)
+            .to_string();
+        assert!(sql.contains("`ticket_keys`.`ticket_key` LIKE 'TCK-%'"));
+        assert!(sql.contains(
+            "`ticket_keys`.`ticket_id` NOT IN (SELECT `tickets`.`id` FROM `tickets` WHERE `tickets`.`project_id` = 4)"
+        ));
+        let sql = TicketKeyService::foreign_keys("TCK", None)
+            .build(DbBackend::MySql)
+            .to_string();
+        assert!(
+            !sql.contains("NOT IN"),
+            "Every key counts for a new project."
+        );
+    }
+}
@@ -0,0 +1,30 @@
+use entity::ticket_updates;
+use sea_orm::{ActiveModelTrait, ConnectionTrait, DbErr, Set};
+use shared::validation::ticket_validation::TicketStatus;
+
+pub struct TicketUpdateService {}
+
+impl TicketUpdateService {
+    /// Records a change of the status in the history of the ticket, nothing is recorded if
+    /// the status stays the same.
+    pub async fn record<C: ConnectionTrait>(
+        db: &C,
+        ticket_id: u64,
+        previous: TicketStatus,
+        next: TicketStatus,
+        user_id: u64,
+    ) -> Result<(), DbErr> {
+        if previous != next {
+            ticket_updates::ActiveModel {
+                ticket_id: Set(ticket_id),
+                previous_state: Set(previous.to_string()),
+                next_state: Set(next.to_string()),
+                user_id: Set(user_id),
+                ..Default::default()
+            }
+            .insert(db)
+            .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use crate::api::tasks::queue_mailer::ticket_id_subscriber_set;
+use entity::users::{self, Entity as User};
+use redis::{Client, Commands, RedisResult};
+use sea_orm::{ColumnTrait, ConnectionTrait, DbErr, QueryFilter, QuerySelect};
+
+/// Watchers of a ticket are the usernames in its subscriber set, the same set the subscribe
+/// toggle and the update emails use.
+pub struct WatcherService {}
+
+impl WatcherService {
+    pub fn usernames(store: &Client, ticket_id: u64) -> RedisResult<Vec<String>> {
+        store
+            .get_connection()?
+            .smembers(ticket_id_subscriber_set(ticket_id))
+    }
+
+    pub fn watch(store: &Client, ticket_id: u64, usernames: &[String]) -> RedisResult<()> {
+        if usernames.is_empty() {
+            return Ok(());
+        }
+        store
+            .get_connection()?
+            .sadd(ticket_id_subscriber_set(ticket_id), usernames)
+    }
+
+    pub fn unwatch(store: &Client, ticket_id: u64, username: &str) -> RedisResult<()> {
+        store
+            .get_connection()?
+            .srem(ticket_id_subscriber_set(ticket_id), username)
+    }
+
+    /// Adds the users to the watchers of the tickets. A failing store only gets logged, the
+    /// change of the ticket is saved already.
+    pub async fn auto_watch<C: ConnectionTrait>(
+        store: &Client,
+        db: &C,
+        ticket_ids: &[u64],
+        user_ids: &[u64],
+    ) -> Result<(), DbErr> {
+        if ticket_ids.is_empty() || user_ids.is_empty() {
+            return Ok(());
+        }
+        let usernames: Vec<String> = User::find_active()
+            .select_only()
+            .column(users::Column::Username)
+            .filter(users::Column::Id.is_in(user_ids.to_vec()))
+            .into_tuple()
+            .all(db)
+            .await?;
+        for ticket_id in ticket_ids {
+            if let Err(e) = Self::watch(store, *ticket_id, &usernames) {
+                tracing::error!("Watching ticket({}) failed: '{}'", ticket_id, e);
+            }
+        }
+        Ok(())
+    }
+}
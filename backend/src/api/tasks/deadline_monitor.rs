@@ -0,0 +1,108 @@
+use crate::api::config::MailConfig;
+use crate::api::consts::{DATABASE_URL, DEADLINE_REMINDER_DAYS};
+use crate::api::services::deadline_service::DeadlineService;
+use crate::api::services::notification_service::NotificationService;
+use crate::api::tasks::to_fang_error;
+use chrono::{NaiveDate, Utc};
+use entity::projects::{self, Entity as Project};
+use fang::async_trait;
+use fang::typetag;
+use fang::AsyncQueueable;
+use fang::AsyncRunnable;
+use fang::FangError;
+use fang::Scheduled;
+use lettre::Message;
+use sea_orm::{ColumnTrait, Database, DatabaseConnection, DbErr, QueryFilter};
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Reminds the owner and the members of active projects of approaching deadlines, and of
+/// passed ones while tickets are open. Each reminder of a deadline is sent once.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(crate = "fang::serde")]
+pub struct DeadlineMonitor {}
+
+#[async_trait]
+#[typetag::serde]
+impl AsyncRunnable for DeadlineMonitor {
+    async fn run(&self, _queue: &mut dyn AsyncQueueable) -> Result<(), FangError> {
+        tracing::info!("RUNNING DeadlineMonitor");
+
+        let db = Database::connect(DATABASE_URL.clone())
+            .await
+            .map_err(to_fang_error)?;
+        let today = Utc::now().date_naive();
+        let projects = Project::find_active()
+            .filter(projects::Column::Active.eq(1))
+            .filter(projects::Column::Deadline.is_not_null())
+            .all(&db)
+            .await
+            .map_err(to_fang_error)?;
+
+        let mail_config = MailConfig::default();
+        let notification_service = NotificationService::new(mail_config);
+        let mut reminded = 0_u32;
+        for project in projects {
+            if remind(&db, &notification_service, &project, today)
+                .await
+                .map_err(to_fang_error)?
+            {
+                reminded += 1;
+            }
+        }
+
+        tracing::info!("Reminded {} project deadlines", reminded);
+        Ok(())
+    }
+
+    fn cron(&self) -> Option<Scheduled> {
+        let expression = "0 0 * * * *";
+        Some(Scheduled::CronPattern(expression.to_string()))
+    }
+
+    fn uniq(&self) -> bool {
+        true
+    }
+}
+
+/// Emails the reminder due for the project, `false` if none is due or it was sent before.
+/// The reminder is recorded first, a failed email is only logged.
+async fn remind(
+    db: &DatabaseConnection,
+    notification_service: &NotificationService,
+    project: &projects::Model,
+    today: NaiveDate,
+) -> Result<bool, DbErr> {
+    let Some(deadline) = project.deadline else {
+        return Ok(false);
+    };
+    let open_tickets = DeadlineService::open_tickets(db, project.id).await?;
+    let total = open_tickets.iter().map(|(_, count)| count).sum();
+    let Some(kind) = DeadlineService::due(
+        DEADLINE_REMINDER_DAYS.as_slice(),
+        (deadline - today).num_days(),
+        total,
+    ) else {
+        return Ok(false);
+    };
+    if !DeadlineService::record(db, project.id, deadline, kind).await? {
+        return Ok(false);
+    }
+
+    let (subject, body) = DeadlineService::message(project, deadline, today, &open_tickets);
+    for user in DeadlineService::recipients(db, project).await? {
+        if let Some(message) = user.username.to_string().parse().ok().and_then(|mailbox| {
+            Message::builder()
+                .from("System <system@example.com>".parse().unwrap())
+                .to(mailbox)
+                .subject(subject.clone())
+                .body(body.clone())
+                .ok()
+        }) {
+            if let Err(e) = notification_service.send_email(message) {
+                tracing::warn!("Unable to send deadline reminder. Reason: {}", e);
+            }
+        }
+    }
+    Ok(true)
+}
@@ -1 +1,15 @@
+pub mod deadline_monitor;
 pub mod queue_mailer;
+pub mod recurring_ticket_scheduler;
+pub mod sla_monitor;
+pub mod ticket_importer;
+pub mod trash_purger;
+
+use fang::FangError;
+
+/// Wraps errors of the database, the mailer or the store for the queue.
+pub fn to_fang_error(e: impl ToString) -> FangError {
+    FangError {
+        description: e.to_string(),
+    }
+}
@@ -49,6 +49,8 @@ const BATCH_SIZE: u32 = 5;
 const TICKET_UPDATES_QUEUE: &str = "ticket:updates";
 pub const TICKET_SUBSCRIBER_SET: &str = "ticket:subscribers";
 const TICKET_UPDATE_ID: &str = "ticket:update:?";
+const TICKET_BULK_SUBSCRIBER_SET: &str = "ticket:bulk:?:subscribers";
+const TICKET_BULK_SUBSCRIBER_SET_TTL: i64 = 60 * 60 * 24;
 pub const PROJECT_SUBSCRIBER_SET: &str = "project:subscribers";
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -145,3 +147,35 @@ pub fn ticket_update_id(id: u64) -> String {
     let ts = Utc::now().timestamp_millis();
     TICKET_UPDATE_ID.replace('?', format!("{}:{}", id, ts).as_str())
 }
+
+/// Queues a single message for a bulk update. The recipients are the union of the subscribers
+/// of every changed ticket, so that nobody receives one email per ticket.
+pub fn push_bulk_to_queue<RV>(
+    con: &mut Connection,
+    ids: &[u64],
+    updater: &str,
+    subject: String,
+    body: String,
+) -> RedisResult<RV>
+where
+    RV: FromRedisValue,
+{
+    let ts = Utc::now().timestamp_millis();
+    let bulk_set = TICKET_BULK_SUBSCRIBER_SET.replace('?', ts.to_string().as_str());
+    let ticket_sets = ids
+        .iter()
+        .map(|id| ticket_id_subscriber_set(*id))
+        .collect::<Vec<String>>();
+    con.sunionstore::<&String, Vec<String>, ()>(&bulk_set, ticket_sets)?;
+    con.srem::<&String, &str, ()>(&bulk_set, updater)?;
+    con.expire::<&String, ()>(&bulk_set, TICKET_BULK_SUBSCRIBER_SET_TTL)?;
+    push_to_queue(
+        con,
+        TICKET_UPDATE_ID.replace('?', format!("bulk:{}", ts).as_str()),
+        &[
+            (UpdateMessageKey::SubscriberSet, bulk_set),
+            (UpdateMessageKey::Subject, subject),
+            (UpdateMessageKey::Body, body),
+        ],
+    )
+}
@@ -0,0 +1,86 @@
+use crate::api::consts::DATABASE_URL;
+use crate::api::services::recurring_ticket_service::RecurringTicketService;
+use crate::api::tasks::to_fang_error;
+use chrono::Utc;
+use entity::{
+    projects::{self, Entity as Project},
+    recurring_tickets::{self, Entity as RecurringTicket},
+};
+use fang::async_trait;
+use fang::typetag;
+use fang::AsyncQueueable;
+use fang::AsyncRunnable;
+use fang::FangError;
+use fang::Scheduled;
+use sea_orm::{ColumnTrait, Database, EntityTrait, QueryFilter, QuerySelect, QueryTrait};
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Creates the tickets of the recurring definitions whose next occurrence has come.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(crate = "fang::serde")]
+pub struct RecurringTicketScheduler {}
+
+#[async_trait]
+#[typetag::serde]
+impl AsyncRunnable for RecurringTicketScheduler {
+    async fn run(&self, _queue: &mut dyn AsyncQueueable) -> Result<(), FangError> {
+        tracing::info!("RUNNING RecurringTicketScheduler");
+
+        let db = Database::connect(DATABASE_URL.clone())
+            .await
+            .map_err(to_fang_error)?;
+        let now = Utc::now();
+        let definitions = RecurringTicket::find()
+            .filter(recurring_tickets::Column::Active.eq(true))
+            .filter(
+                recurring_tickets::Column::ProjectId.in_subquery(
+                    Project::find_active()
+                        .select_only()
+                        .column(projects::Column::Id)
+                        .into_query(),
+                ),
+            )
+            .all(&db)
+            .await
+            .map_err(to_fang_error)?;
+
+        let mut created = 0_u32;
+        for definition in definitions {
+            let schedule = match RecurringTicketService::schedule(&definition.cron) {
+                Ok(schedule) => schedule,
+                Err(e) => {
+                    tracing::warn!(
+                        "Recurring ticket {} has an invalid schedule. Reason: {}",
+                        definition.id,
+                        e
+                    );
+                    continue;
+                }
+            };
+            let since = definition.last_run_at.unwrap_or(definition.created_at);
+            let Some(occurrence) = RecurringTicketService::due(&schedule, since, now) else {
+                continue;
+            };
+            if RecurringTicketService::materialize(&db, &definition, occurrence)
+                .await
+                .map_err(to_fang_error)?
+                .is_some()
+            {
+                created += 1;
+            }
+        }
+
+        tracing::info!("Created {} recurring tickets", created);
+        Ok(())
+    }
+
+    fn cron(&self) -> Option<Scheduled> {
+        let expression = "0 * * * * *";
+        Some(Scheduled::CronPattern(expression.to_string()))
+    }
+
+    fn uniq(&self) -> bool {
+        true
+    }
+}
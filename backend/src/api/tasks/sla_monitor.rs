@@ -0,0 +1,190 @@
+use crate::api::config::MailConfig;
+use crate::api::consts::DATABASE_URL;
+use crate::api::services::notification_service::NotificationService;
+use crate::api::services::sla_service::SlaService;
+use crate::api::tasks::to_fang_error;
+use chrono::Utc;
+use entity::{
+    projects::{self, Entity as Project},
+    sla_alerts::{self, Entity as SlaAlert},
+    sla_policies::{self, Entity as SlaPolicy},
+    tickets::{self, Entity as Ticket},
+    users::{self, Entity as User},
+};
+use fang::async_trait;
+use fang::typetag;
+use fang::AsyncQueueable;
+use fang::AsyncRunnable;
+use fang::FangError;
+use fang::Scheduled;
+use lettre::Message;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Condition, Database, DatabaseConnection, DbErr, EntityTrait,
+    PaginatorTrait, QueryFilter, QuerySelect, Set,
+};
+use serde::Deserialize;
+use serde::Serialize;
+use shared::dtos::sla_dto::{SlaClockDto, SlaState};
+use shared::validation::ticket_validation::TicketStatus;
+use strum::IntoEnumIterator;
+
+/// Flags unfinished tickets whose SLA clocks are at risk or breached, and notifies their
+/// assignee and the owner of their project once per clock and state.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(crate = "fang::serde")]
+pub struct SlaMonitor {}
+
+#[async_trait]
+#[typetag::serde]
+impl AsyncRunnable for SlaMonitor {
+    async fn run(&self, _queue: &mut dyn AsyncQueueable) -> Result<(), FangError> {
+        tracing::info!("RUNNING SlaMonitor");
+
+        let db = Database::connect(DATABASE_URL.clone())
+            .await
+            .map_err(to_fang_error)?;
+        let project_ids: Vec<u64> = SlaPolicy::find()
+            .select_only()
+            .column(sla_policies::Column::ProjectId)
+            .distinct()
+            .into_tuple()
+            .all(&db)
+            .await
+            .map_err(to_fang_error)?;
+        if project_ids.is_empty() {
+            return Ok(());
+        }
+        let slas = SlaService::ticket_slas(
+            &db,
+            Condition::all()
+                .add(tickets::Column::ProjectId.is_in(project_ids))
+                .add(
+                    tickets::Column::Status.is_not_in(
+                        TicketStatus::iter()
+                            .filter(|s| s.is_finished())
+                            .map(|s| s.to_string()),
+                    ),
+                ),
+            Utc::now().naive_utc(),
+        )
+        .await
+        .map_err(to_fang_error)?;
+
+        let mail_config = MailConfig::default();
+        let notification_service = NotificationService::new(mail_config);
+        let mut flagged = 0_u32;
+        for sla in slas {
+            for clock in sla
+                .clocks
+                .iter()
+                .filter(|c| matches!(c.state, SlaState::AtRisk | SlaState::Breached))
+            {
+                if flag(&db, sla.ticket_id, clock)
+                    .await
+                    .map_err(to_fang_error)?
+                {
+                    notify(&db, &notification_service, sla.ticket_id, clock)
+                        .await
+                        .map_err(to_fang_error)?;
+                    flagged += 1;
+                }
+            }
+        }
+
+        tracing::info!("Flagged {} SLA clocks", flagged);
+        Ok(())
+    }
+
+    fn cron(&self) -> Option<Scheduled> {
+        let expression = "0 */5 * * * *";
+        Some(Scheduled::CronPattern(expression.to_string()))
+    }
+
+    fn uniq(&self) -> bool {
+        true
+    }
+}
+
+/// Records the state of the clock, `false` if it was flagged before.
+async fn flag(db: &DatabaseConnection, ticket_id: u64, clock: &SlaClockDto) -> Result<bool, DbErr> {
+    let exists = SlaAlert::find()
+        .filter(sla_alerts::Column::TicketId.eq(ticket_id))
+        .filter(sla_alerts::Column::Clock.eq(clock.clock.to_string()))
+        .filter(sla_alerts::Column::State.eq(clock.state.to_string()))
+        .count(db)
+        .await?
+        > 0;
+    if !exists {
+        sla_alerts::ActiveModel {
+            ticket_id: Set(ticket_id),
+            clock: Set(clock.clock.to_string()),
+            state: Set(clock.state.to_string()),
+            created_at: Set(Utc::now()),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+    }
+    Ok(!exists)
+}
+
+/// Emails the assignee and the project owner, a failed email is only logged.
+async fn notify(
+    db: &DatabaseConnection,
+    notification_service: &NotificationService,
+    ticket_id: u64,
+    clock: &SlaClockDto,
+) -> Result<(), DbErr> {
+    let Some(ticket) = Ticket::find_by_id(ticket_id).one(db).await? else {
+        return Ok(());
+    };
+    let owner_id: Option<u64> = match ticket.project_id {
+        Some(project_id) => {
+            Project::find_by_id(project_id)
+                .select_only()
+                .column(projects::Column::UserId)
+                .into_tuple()
+                .one(db)
+                .await?
+        }
+        None => None,
+    };
+    let recipients = User::find_active()
+        .filter(users::Column::Id.is_in(ticket.user_id.into_iter().chain(owner_id)))
+        .all(db)
+        .await?;
+
+    let reference = ticket.ticket_key.unwrap_or(format!("#{}", ticket.id));
+    let state = match clock.state {
+        SlaState::Breached => "breached",
+        _ => "at risk",
+    };
+    let subject = format!(
+        "[{}] {} SLA {}: {}",
+        reference, clock.clock, state, ticket.title
+    );
+    let body = format!(
+        "The {} SLA of ticket {} '{}' is {}.\n{} of {} minutes are used up.",
+        clock.clock.to_string().to_lowercase(),
+        reference,
+        ticket.title,
+        state,
+        clock.elapsed_minutes,
+        clock.target_minutes
+    );
+    for user in recipients {
+        if let Some(message) = user.username.to_string().parse().ok().and_then(|mailbox| {
+            Message::builder()
+                .from("System <system@example.com>".parse().unwrap())
+                .to(mailbox)
+                .subject(subject.clone())
+                .body(body.clone())
+                .ok()
+        }) {
+            if let Err(e) = notification_service.send_email(message) {
+                tracing::warn!("Unable to send SLA notification. Reason: {}", e);
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,181 @@
+use crate::api::consts::{BUCKET_NAME, DATABASE_URL, IMPORT_DIR};
+use crate::api::services::import_service::{ImportContext, ImportService};
+use crate::api::tasks::to_fang_error;
+use chrono::Utc;
+use entity::{
+    import_jobs::{self, Entity as ImportJob},
+    users::{self, Entity as User},
+};
+use fang::async_trait;
+use fang::typetag;
+use fang::AsyncQueueable;
+use fang::AsyncRunnable;
+use fang::FangError;
+use object_store::aws::AmazonS3Builder;
+use sea_orm::{
+    ActiveModelTrait, ColumnTrait, Database, DatabaseConnection, EntityTrait, QueryFilter,
+    QuerySelect, Set,
+};
+use serde::Deserialize;
+use serde::Serialize;
+use shared::dtos::import_dto::{ImportSource, ImportState};
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+    str::FromStr,
+};
+
+/// Warnings kept on a job, the rest are only counted.
+const MAX_WARNINGS: usize = 100;
+
+/// Imports the export file of a job into its project, saving the progress after each issue.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(crate = "fang::serde")]
+pub struct TicketImporter {
+    pub job_id: u64,
+}
+
+#[async_trait]
+#[typetag::serde]
+impl AsyncRunnable for TicketImporter {
+    async fn run(&self, _queue: &mut dyn AsyncQueueable) -> Result<(), FangError> {
+        tracing::info!("RUNNING TicketImporter for job {}", self.job_id);
+
+        let db = Database::connect(DATABASE_URL.clone())
+            .await
+            .map_err(to_fang_error)?;
+        let Some(job) = ImportJob::find_by_id(self.job_id)
+            .one(&db)
+            .await
+            .map_err(to_fang_error)?
+        else {
+            return Ok(());
+        };
+        if ImportState::from_str(&job.state).is_ok_and(|state| state.is_finished()) {
+            return Ok(());
+        }
+        let mut running: import_jobs::ActiveModel = job.clone().into();
+        running.state = Set(ImportState::Running.to_string());
+        let job = running.update(&db).await.map_err(to_fang_error)?;
+
+        let mut warnings = Vec::new();
+        let result = import(&db, &job, &mut warnings).await;
+        let dropped = warnings.len().saturating_sub(MAX_WARNINGS);
+        warnings.truncate(MAX_WARNINGS);
+        if dropped > 0 {
+            warnings.push(format!("{} more warnings", dropped));
+        }
+
+        // The failure is kept on the job, importing the file again continues where it stopped
+        let mut finished: import_jobs::ActiveModel = ImportJob::find_by_id(job.id)
+            .one(&db)
+            .await
+            .map_err(to_fang_error)?
+            .unwrap_or(job)
+            .into();
+        finished.state = Set(match &result {
+            Ok(()) => ImportState::Completed,
+            Err(_) => ImportState::Failed,
+        }
+        .to_string());
+        finished.error = Set(result.err());
+        finished.warnings = Set((!warnings.is_empty()).then(|| warnings.join("\n")));
+        finished.finished_at = Set(Some(Utc::now()));
+        let job = finished.update(&db).await.map_err(to_fang_error)?;
+
+        tracing::info!(
+            "Import job {} {}: {} tickets created, {} skipped",
+            job.id,
+            job.state,
+            job.created,
+            job.skipped
+        );
+        Ok(())
+    }
+
+    fn max_retries(&self) -> i32 {
+        0
+    }
+}
+
+async fn import(
+    db: &DatabaseConnection,
+    job: &import_jobs::Model,
+    warnings: &mut Vec<String>,
+) -> Result<(), String> {
+    let source = ImportSource::from_str(&job.source).map_err(|e| e.to_string())?;
+    let path = ImportService::resolve(Path::new(IMPORT_DIR.as_str()), &job.path)?;
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|e| format!("The file could not be read: {}", e))?;
+    let issues = ImportService::parse(source, &content)?;
+
+    let mut progress: import_jobs::ActiveModel = job.clone().into();
+    progress.total = Set(issues.len() as u32);
+    progress.update(db).await.map_err(|e| e.to_string())?;
+
+    let people = ImportService::people(&issues);
+    let emails = people
+        .iter()
+        .filter_map(|person| person.email.as_ref().map(|email| email.to_lowercase()))
+        .collect::<BTreeSet<String>>();
+    let users: HashMap<String, u64> = User::find_active()
+        .select_only()
+        .columns([users::Column::Username, users::Column::Id])
+        .filter(users::Column::Username.is_in(emails))
+        .into_tuple::<(String, u64)>()
+        .all(db)
+        .await
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .map(|(username, id)| (username.to_lowercase(), id))
+        .collect();
+    warnings.extend(
+        people
+            .iter()
+            .filter(|person| {
+                person
+                    .email
+                    .as_ref()
+                    .is_none_or(|email| !users.contains_key(&email.to_lowercase()))
+            })
+            .map(|person| person.name.to_owned())
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .map(|name| format!("No user with the email of '{}'.", name)),
+    );
+
+    let bucket = match issues.iter().any(|issue| !issue.attachments.is_empty()) {
+        true => AmazonS3Builder::from_env()
+            .with_bucket_name(BUCKET_NAME.clone())
+            .build()
+            .map_err(|e| warnings.push(format!("Attachments are not imported: {}", e)))
+            .ok(),
+        false => None,
+    };
+    let context = ImportContext {
+        job,
+        tracker: source.tracker(),
+        users,
+        dir: path.parent().map(Path::to_path_buf).unwrap_or_default(),
+        bucket: bucket.as_ref(),
+    };
+
+    let (mut created, mut skipped) = (0_u32, 0_u32);
+    for (i, issue) in issues.iter().enumerate() {
+        let outcome = ImportService::import_issue(db, &context, issue)
+            .await
+            .map_err(|e| format!("Importing {} failed: {}", issue.external_id, e))?;
+        match outcome.created {
+            true => created += 1,
+            false => skipped += 1,
+        }
+        warnings.extend(outcome.warnings);
+        let mut progress: import_jobs::ActiveModel = job.clone().into();
+        progress.processed = Set(i as u32 + 1);
+        progress.created = Set(created);
+        progress.skipped = Set(skipped);
+        progress.update(db).await.map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
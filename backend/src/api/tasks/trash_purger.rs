@@ -0,0 +1,342 @@
+use crate::api::consts::{BUCKET_NAME, DATABASE_URL, TRASH_RETENTION_DAYS};
+use crate::api::tasks::to_fang_error;
+use chrono::{Duration, Utc};
+use entity::{
+    board_columns::{self, Entity as BoardColumn},
+    comments::{self, Entity as Comment},
+    custom_fields::{self, Entity as CustomField},
+    deadline_reminders::{self, Entity as DeadlineReminder},
+    import_jobs::{self, Entity as ImportJob},
+    import_references::{self, Entity as ImportReference},
+    preferences::{self, Entity as Preferences},
+    project_members::{self, Entity as ProjectMember},
+    projects::{self, Entity as Project},
+    recurring_tickets::{self, Entity as RecurringTicket},
+    saved_views::{self, Entity as SavedView},
+    sla_alerts::{self, Entity as SlaAlert},
+    sla_policies::{self, Entity as SlaPolicy},
+    sprints::{self, Entity as Sprint},
+    ticket_attachments::{self, Entity as TicketAttachment},
+    ticket_field_values::{self, Entity as TicketFieldValue},
+    ticket_keys::{self, Entity as TicketKey},
+    ticket_labels::{self, Entity as TicketLabel},
+    ticket_updates::{self, Entity as TicketUpdate},
+    tickets::{self, Entity as Ticket},
+    users::{self, Entity as User},
+};
+use fang::async_trait;
+use fang::typetag;
+use fang::AsyncQueueable;
+use fang::AsyncRunnable;
+use fang::FangError;
+use fang::Scheduled;
+use object_store::aws::AmazonS3Builder;
+use object_store::ObjectStore;
+use sea_orm::sea_query::Expr;
+use sea_orm::{
+    ColumnTrait, ConnectionTrait, Database, DatabaseConnection, DbErr, EntityTrait, PaginatorTrait,
+    QueryFilter, QuerySelect, QueryTrait, TransactionTrait,
+};
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Removes the entries that have been in the trash for longer than the retention period.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(crate = "fang::serde")]
+pub struct TrashPurger {}
+
+#[async_trait]
+#[typetag::serde]
+impl AsyncRunnable for TrashPurger {
+    async fn run(&self, _queue: &mut dyn AsyncQueueable) -> Result<(), FangError> {
+        tracing::info!("RUNNING TrashPurger");
+
+        let db = Database::connect(DATABASE_URL.clone())
+            .await
+            .map_err(to_fang_error)?;
+        let expired_before = Utc::now() - Duration::days(*TRASH_RETENTION_DAYS);
+
+        let ticket_ids: Vec<u64> = Ticket::find()
+            .select_only()
+            .column(tickets::Column::Id)
+            .filter(tickets::Column::DeletedAt.lt(expired_before))
+            .into_tuple()
+            .all(&db)
+            .await
+            .map_err(to_fang_error)?;
+        let ticket_count = purge_tickets(&db, ticket_ids)
+            .await
+            .map_err(to_fang_error)?;
+
+        let project_ids: Vec<u64> = Project::find()
+            .select_only()
+            .column(projects::Column::Id)
+            .filter(projects::Column::DeletedAt.lt(expired_before))
+            .into_tuple()
+            .all(&db)
+            .await
+            .map_err(to_fang_error)?;
+        let project_count = purge_projects(&db, project_ids)
+            .await
+            .map_err(to_fang_error)?;
+
+        let user_ids: Vec<u64> = User::find()
+            .select_only()
+            .column(users::Column::Id)
+            .filter(users::Column::DeletedAt.lt(expired_before))
+            .into_tuple()
+            .all(&db)
+            .await
+            .map_err(to_fang_error)?;
+        let mut user_count = 0_u64;
+        for user_id in user_ids {
+            if purge_user(&db, user_id).await.map_err(to_fang_error)? {
+                user_count += 1;
+            } else {
+                tracing::warn!(
+                    "User {} is still referenced by comments, history or projects, skipping purge",
+                    user_id
+                );
+            }
+        }
+
+        tracing::info!(
+            "Purged {} tickets, {} projects and {} users from the trash",
+            ticket_count,
+            project_count,
+            user_count
+        );
+        Ok(())
+    }
+
+    fn cron(&self) -> Option<Scheduled> {
+        let expression = "0 0 3 * * *";
+        Some(Scheduled::CronPattern(expression.to_string()))
+    }
+
+    fn uniq(&self) -> bool {
+        true
+    }
+}
+
+async fn purge_tickets(db: &DatabaseConnection, ids: Vec<u64>) -> Result<u64, DbErr> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let paths: Vec<String> = TicketAttachment::find()
+        .select_only()
+        .column(ticket_attachments::Column::Path)
+        .filter(ticket_attachments::Column::TicketId.is_in(ids.clone()))
+        .into_tuple()
+        .all(db)
+        .await?;
+
+    let txn = db.begin().await?;
+    Comment::delete_many()
+        .filter(comments::Column::TicketId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    TicketAttachment::delete_many()
+        .filter(ticket_attachments::Column::TicketId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    TicketLabel::delete_many()
+        .filter(ticket_labels::Column::TicketId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    TicketFieldValue::delete_many()
+        .filter(ticket_field_values::Column::TicketId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    TicketKey::delete_many()
+        .filter(ticket_keys::Column::TicketId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    SlaAlert::delete_many()
+        .filter(sla_alerts::Column::TicketId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    // Importing the issue again creates a new ticket
+    ImportReference::delete_many()
+        .filter(import_references::Column::TicketId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    TicketUpdate::delete_many()
+        .filter(ticket_updates::Column::TicketId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    // Children in the trash or elsewhere lose their parent
+    Ticket::update_many()
+        .col_expr(tickets::Column::ParentId, Expr::value(None::<u64>))
+        .filter(tickets::Column::ParentId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    let purged = Ticket::delete_many()
+        .filter(tickets::Column::Id.is_in(ids))
+        .exec(&txn)
+        .await?
+        .rows_affected;
+    txn.commit().await?;
+
+    // The rows are gone already, a leftover object is only logged
+    if !paths.is_empty() {
+        match AmazonS3Builder::from_env()
+            .with_bucket_name(BUCKET_NAME.clone())
+            .build()
+        {
+            Ok(bucket) => {
+                for raw_path in paths {
+                    let obj_path = object_store::path::Path::from(raw_path.clone());
+                    if let Err(e) = bucket.delete(&obj_path).await {
+                        tracing::warn!("Unable to delete attachment {}. Reason: {}", raw_path, e);
+                    }
+                }
+            }
+            Err(e) => tracing::warn!("Amazon bucket could not be built. Reason: {}", e),
+        }
+    }
+
+    Ok(purged)
+}
+
+/// Tickets restored or deleted separately from the project are kept, just without a project.
+async fn purge_projects(db: &DatabaseConnection, ids: Vec<u64>) -> Result<u64, DbErr> {
+    if ids.is_empty() {
+        return Ok(0);
+    }
+    let txn = db.begin().await?;
+    Ticket::update_many()
+        .col_expr(tickets::Column::ProjectId, Expr::value(None::<u64>))
+        .col_expr(tickets::Column::SprintId, Expr::value(None::<u64>))
+        .col_expr(tickets::Column::ParentId, Expr::value(None::<u64>))
+        .filter(tickets::Column::ProjectId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    SavedView::delete_many()
+        .filter(saved_views::Column::ProjectId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    // The values only make sense within the project, they go away with its fields
+    TicketFieldValue::delete_many()
+        .filter(
+            ticket_field_values::Column::FieldId.in_subquery(
+                CustomField::find()
+                    .select_only()
+                    .column(custom_fields::Column::Id)
+                    .filter(custom_fields::Column::ProjectId.is_in(ids.clone()))
+                    .into_query(),
+            ),
+        )
+        .exec(&txn)
+        .await?;
+    CustomField::delete_many()
+        .filter(custom_fields::Column::ProjectId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    Sprint::delete_many()
+        .filter(sprints::Column::ProjectId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    BoardColumn::delete_many()
+        .filter(board_columns::Column::ProjectId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    SlaPolicy::delete_many()
+        .filter(sla_policies::Column::ProjectId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    RecurringTicket::delete_many()
+        .filter(recurring_tickets::Column::ProjectId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    ImportReference::delete_many()
+        .filter(import_references::Column::ProjectId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    ImportJob::delete_many()
+        .filter(import_jobs::Column::ProjectId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    ProjectMember::delete_many()
+        .filter(project_members::Column::ProjectId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    DeadlineReminder::delete_many()
+        .filter(deadline_reminders::Column::ProjectId.is_in(ids.clone()))
+        .exec(&txn)
+        .await?;
+    let purged = Project::delete_many()
+        .filter(projects::Column::Id.is_in(ids))
+        .exec(&txn)
+        .await?
+        .rows_affected;
+    txn.commit().await?;
+    Ok(purged)
+}
+
+/// Comments, history and projects keep their author, so such users are not purged.
+async fn purge_user(db: &DatabaseConnection, id: u64) -> Result<bool, DbErr> {
+    if is_referenced(db, id).await? {
+        return Ok(false);
+    }
+    let txn = db.begin().await?;
+    Preferences::delete_many()
+        .filter(preferences::Column::UserId.eq(id))
+        .exec(&txn)
+        .await?;
+    SavedView::delete_many()
+        .filter(saved_views::Column::UserId.eq(id))
+        .exec(&txn)
+        .await?;
+    Ticket::update_many()
+        .col_expr(tickets::Column::UserId, Expr::value(None::<u64>))
+        .filter(tickets::Column::UserId.eq(id))
+        .exec(&txn)
+        .await?;
+    Ticket::update_many()
+        .col_expr(tickets::Column::ReporterId, Expr::value(None::<u64>))
+        .filter(tickets::Column::ReporterId.eq(id))
+        .exec(&txn)
+        .await?;
+    RecurringTicket::update_many()
+        .col_expr(recurring_tickets::Column::UserId, Expr::value(None::<u64>))
+        .filter(recurring_tickets::Column::UserId.eq(id))
+        .exec(&txn)
+        .await?;
+    RecurringTicket::update_many()
+        .col_expr(
+            recurring_tickets::Column::ReporterId,
+            Expr::value(None::<u64>),
+        )
+        .filter(recurring_tickets::Column::ReporterId.eq(id))
+        .exec(&txn)
+        .await?;
+    ImportJob::update_many()
+        .col_expr(import_jobs::Column::UserId, Expr::value(None::<u64>))
+        .filter(import_jobs::Column::UserId.eq(id))
+        .exec(&txn)
+        .await?;
+    ProjectMember::delete_many()
+        .filter(project_members::Column::UserId.eq(id))
+        .exec(&txn)
+        .await?;
+    User::delete_by_id(id).exec(&txn).await?;
+    txn.commit().await?;
+    Ok(true)
+}
+
+async fn is_referenced<C: ConnectionTrait>(db: &C, id: u64) -> Result<bool, DbErr> {
+    Ok(Comment::find()
+        .filter(comments::Column::UserId.eq(id))
+        .count(db)
+        .await?
+        + TicketUpdate::find()
+            .filter(ticket_updates::Column::UserId.eq(id))
+            .count(db)
+            .await?
+        + Project::find()
+            .filter(projects::Column::UserId.eq(id))
+            .count(db)
+            .await?
+        > 0)
+}
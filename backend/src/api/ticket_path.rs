@@ -0,0 +1,39 @@
+use super::{error::ApiError, services::ticket_key_service::TicketKeyService};
+use axum::{
+    extract::{FromRequestParts, Path},
+    http::{request::Parts, StatusCode},
+};
+use sea_orm::DatabaseConnection;
+use std::collections::HashMap;
+
+/// The `{id}` path parameter of ticket routes, given either as a database id or as a
+/// ticket key like `TCK-142`.
+#[derive(Debug, Clone, Copy)]
+pub struct TicketPath(pub u64);
+
+impl<S> FromRequestParts<S> for TicketPath
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Path(params) =
+            Path::<HashMap<String, String>>::from_request_parts(parts, state).await?;
+        let not_found = || ApiError::new(StatusCode::NOT_FOUND, String::from("Not found"));
+        let reference = params.get("id").ok_or_else(not_found)?;
+        let db = parts
+            .extensions
+            .get::<DatabaseConnection>()
+            .ok_or_else(|| {
+                ApiError::new(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    String::from("Database connection missing"),
+                )
+            })?;
+        TicketKeyService::resolve(db, reference)
+            .await?
+            .map(TicketPath)
+            .ok_or_else(not_found)
+    }
+}
@@ -11,7 +11,11 @@ pub async fn setup_schema(db: &DbConn) -> Result<ExecResult, DbErr> {
                 .col(ColumnDef::new(users::Column::Id).integer().primary_key())
                 .col(ColumnDef::new(users::Column::Name).string())
                 .col(ColumnDef::new(users::Column::Password).string())
-                .col(ColumnDef::new(users::Column::Role).string()),
+                .col(ColumnDef::new(users::Column::Role).string())
+                .col(ColumnDef::new(users::Column::Username).string())
+                .col(ColumnDef::new(users::Column::PublicId).uuid())
+                .col(ColumnDef::new(users::Column::DeletedAt).timestamp_with_time_zone())
+                .col(ColumnDef::new(users::Column::DeletedBy).integer()),
         ),
     )
     .await
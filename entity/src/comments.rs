@@ -14,6 +14,8 @@ pub struct Model {
     pub ticket_id: u64,
     pub timestamp: DateTime,
     pub user_id: u64,
+    #[serde(skip_deserializing)]
+    pub version: u32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -2,11 +2,25 @@
 
 pub mod prelude;
 
+pub mod board_columns;
 pub mod comments;
+pub mod custom_fields;
+pub mod deadline_reminders;
+pub mod import_jobs;
+pub mod import_references;
 pub mod preferences;
+pub mod project_members;
 pub mod projects;
+pub mod recurring_tickets;
+pub mod saved_views;
 pub mod sea_orm_active_enums;
+pub mod sla_alerts;
+pub mod sla_policies;
+pub mod sprints;
 pub mod ticket_attachments;
+pub mod ticket_field_values;
+pub mod ticket_keys;
+pub mod ticket_labels;
 pub mod ticket_updates;
 pub mod tickets;
 pub mod users;
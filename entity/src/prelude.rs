@@ -1,9 +1,23 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.11.3
 
+pub use super::board_columns::Entity as BoardColumns;
 pub use super::comments::Entity as Comments;
+pub use super::custom_fields::Entity as CustomFields;
+pub use super::deadline_reminders::Entity as DeadlineReminders;
+pub use super::import_jobs::Entity as ImportJobs;
+pub use super::import_references::Entity as ImportReferences;
 pub use super::preferences::Entity as Preferences;
+pub use super::project_members::Entity as ProjectMembers;
 pub use super::projects::Entity as Projects;
+pub use super::recurring_tickets::Entity as RecurringTickets;
+pub use super::saved_views::Entity as SavedViews;
+pub use super::sla_alerts::Entity as SlaAlerts;
+pub use super::sla_policies::Entity as SlaPolicies;
+pub use super::sprints::Entity as Sprints;
 pub use super::ticket_attachments::Entity as TicketAttachments;
+pub use super::ticket_field_values::Entity as TicketFieldValues;
+pub use super::ticket_keys::Entity as TicketKeys;
+pub use super::ticket_labels::Entity as TicketLabels;
 pub use super::ticket_updates::Entity as TicketUpdates;
 pub use super::tickets::Entity as Tickets;
 pub use super::users::Entity as Users;
@@ -1,6 +1,7 @@
 //! `SeaORM` Entity. Generated by sea-orm-codegen 0.11.3
 
 use sea_orm::entity::prelude::*;
+use sea_orm::{QueryFilter, Select};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
@@ -13,6 +14,14 @@ pub struct Model {
     pub deadline: Option<Date>,
     pub user_id: u64,
     pub active: i8,
+    #[sea_orm(unique)]
+    pub key: String,
+    #[serde(skip_deserializing)]
+    pub ticket_sequence: u64,
+    pub deleted_at: Option<DateTimeUtc>,
+    pub deleted_by: Option<u64>,
+    #[serde(skip_deserializing)]
+    pub version: u32,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -41,4 +50,11 @@ impl Related<super::users::Entity> for Entity {
     }
 }
 
+impl Entity {
+    /// Rows not moved to the trash. Use it instead of `find` unless deleted rows are needed.
+    pub fn find_active() -> Select<Entity> {
+        Self::find().filter(Column::DeletedAt.is_null())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
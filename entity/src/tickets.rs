@@ -2,6 +2,7 @@
 
 use super::sea_orm_active_enums::Priority;
 use sea_orm::entity::prelude::*;
+use sea_orm::{QueryFilter, Select};
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize, Deserialize)]
@@ -18,12 +19,32 @@ pub struct Model {
     pub user_id: Option<u64>,
     pub priority: Option<Priority>,
     pub created_at: DateTimeUtc,
+    pub reporter_id: Option<u64>,
+    pub updated_at: DateTimeUtc,
+    #[sea_orm(unique)]
+    pub ticket_key: Option<String>,
+    pub deleted_at: Option<DateTimeUtc>,
+    pub deleted_by: Option<u64>,
+    #[serde(skip_deserializing)]
+    pub version: u32,
+    pub sprint_id: Option<u64>,
+    #[serde(skip_deserializing)]
+    pub board_rank: String,
+    pub parent_id: Option<u64>,
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
 pub enum Relation {
     #[sea_orm(has_many = "super::comments::Entity")]
     Comments,
+    #[sea_orm(
+        belongs_to = "Entity",
+        from = "Column::ParentId",
+        to = "Column::Id",
+        on_update = "Restrict",
+        on_delete = "Restrict"
+    )]
+    SelfRef,
     #[sea_orm(
         belongs_to = "super::projects::Entity",
         from = "Column::ProjectId",
@@ -32,10 +53,32 @@ pub enum Relation {
         on_delete = "Restrict"
     )]
     Projects,
+    #[sea_orm(
+        belongs_to = "super::sprints::Entity",
+        from = "Column::SprintId",
+        to = "super::sprints::Column::Id",
+        on_update = "Restrict",
+        on_delete = "Restrict"
+    )]
+    Sprints,
     #[sea_orm(has_many = "super::ticket_attachments::Entity")]
     TicketAttachments,
+    #[sea_orm(has_many = "super::ticket_field_values::Entity")]
+    TicketFieldValues,
+    #[sea_orm(has_many = "super::ticket_keys::Entity")]
+    TicketKeys,
+    #[sea_orm(has_many = "super::ticket_labels::Entity")]
+    TicketLabels,
     #[sea_orm(has_many = "super::ticket_updates::Entity")]
     TicketUpdates,
+    #[sea_orm(
+        belongs_to = "super::users::Entity",
+        from = "Column::ReporterId",
+        to = "super::users::Column::Id",
+        on_update = "Restrict",
+        on_delete = "Restrict"
+    )]
+    Reporter,
     #[sea_orm(
         belongs_to = "super::users::Entity",
         from = "Column::UserId",
@@ -58,12 +101,36 @@ impl Related<super::projects::Entity> for Entity {
     }
 }
 
+impl Related<super::sprints::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Sprints.def()
+    }
+}
+
 impl Related<super::ticket_attachments::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::TicketAttachments.def()
     }
 }
 
+impl Related<super::ticket_field_values::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TicketFieldValues.def()
+    }
+}
+
+impl Related<super::ticket_keys::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TicketKeys.def()
+    }
+}
+
+impl Related<super::ticket_labels::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::TicketLabels.def()
+    }
+}
+
 impl Related<super::ticket_updates::Entity> for Entity {
     fn to() -> RelationDef {
         Relation::TicketUpdates.def()
@@ -76,4 +143,11 @@ impl Related<super::users::Entity> for Entity {
     }
 }
 
+impl Entity {
+    /// Rows not moved to the trash. Use it instead of `find` unless deleted rows are needed.
+    pub fn find_active() -> Select<Entity> {
+        Self::find().filter(Column::DeletedAt.is_null())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {}
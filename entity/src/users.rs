@@ -3,7 +3,8 @@
 use axum_login::AuthUser;
 use sea_orm::entity::prelude::*;
 use sea_orm::{
-    ActiveModelBehavior, ActiveModelTrait, DeriveRelation, Related, RelationDef, RelationTrait, Set,
+    ActiveModelBehavior, ActiveModelTrait, DeriveRelation, QueryFilter, Related, RelationDef,
+    RelationTrait, Select, Set,
 };
 use serde::{Deserialize, Serialize};
 use serde_email::Email;
@@ -23,6 +24,8 @@ pub struct Model {
     pub username: Email,
     #[sea_orm(unique)]
     pub public_id: Uuid,
+    pub deleted_at: Option<DateTimeUtc>,
+    pub deleted_by: Option<u64>,
 }
 
 impl std::fmt::Debug for Model {
@@ -102,6 +105,13 @@ impl Related<super::tickets::Entity> for Entity {
     }
 }
 
+impl Entity {
+    /// Rows not moved to the trash. Use it instead of `find` unless deleted rows are needed.
+    pub fn find_active() -> Select<Entity> {
+        Self::find().filter(Column::DeletedAt.is_null())
+    }
+}
+
 impl ActiveModelBehavior for ActiveModel {
     fn new() -> Self {
         Self {
@@ -0,0 +1,192 @@
+use crate::{
+    components::html::{select::Select, text_input::TextInput},
+    helpers::event_helper::get_value_from_input_event,
+};
+use entity::sea_orm_active_enums::Priority;
+use implicit_clone::unsync::{IArray, IString};
+use shared::{
+    dtos::bulk_dto::BulkAction,
+    validation::ticket_validation::{TicketPriority, TicketStatus},
+};
+use std::str::FromStr;
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
+use uuid::Uuid;
+use yew::prelude::*;
+
+#[derive(Clone, Copy, Default, Display, EnumIter, EnumString, PartialEq)]
+enum BulkActionKind {
+    #[default]
+    Status,
+    Priority,
+    Assignee,
+    Project,
+    #[strum(serialize = "Add label")]
+    AddLabel,
+    #[strum(serialize = "Remove label")]
+    RemoveLabel,
+    Delete,
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub count: usize,
+    pub on_apply: Callback<BulkAction>,
+    pub on_clear: Callback<()>,
+}
+
+pub enum Msg {
+    SelectKind(AttrValue),
+    UpdateValue(AttrValue),
+    Apply,
+}
+
+pub struct BulkActionBar {
+    kind: BulkActionKind,
+    value: AttrValue,
+    error: Option<AttrValue>,
+}
+
+impl Component for BulkActionBar {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            kind: BulkActionKind::default(),
+            value: AttrValue::default(),
+            error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::SelectKind(kind) => {
+                self.kind = BulkActionKind::from_str(kind.as_str()).unwrap_or_default();
+                self.value = AttrValue::default();
+                self.error = None;
+            }
+            Msg::UpdateValue(value) => {
+                self.value = value;
+            }
+            Msg::Apply => match self.to_action() {
+                Ok(action) => {
+                    self.error = None;
+                    ctx.props().on_apply.emit(action);
+                }
+                Err(e) => self.error = Some(AttrValue::from(e)),
+            },
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let on_kind = ctx
+            .link()
+            .callback(|e: Event| Msg::SelectKind(get_value_from_input_event(e)));
+        let on_apply = ctx.link().callback(|_: MouseEvent| Msg::Apply);
+        let on_clear = ctx.props().on_clear.reform(|_: MouseEvent| ());
+
+        html! {
+            <div class="notification is-info is-light">
+                <div class="field is-grouped is-grouped-multiline">
+                    <p class="control">
+                        <span class="tag is-info is-medium">{ format!("{} selected", ctx.props().count) }</span>
+                    </p>
+                    <div class="control">
+                        <div class="select">
+                            <select onchange={on_kind}>
+                                {
+                                    BulkActionKind::iter().map(|kind| html! {
+                                        <option selected={kind == self.kind}>{ kind.to_string() }</option>
+                                    }).collect::<Html>()
+                                }
+                            </select>
+                        </div>
+                    </div>
+                    <div class="control">
+                        { self.value_input(ctx) }
+                    </div>
+                    <p class="control">
+                        <button
+                            class={classes!("button", if self.kind == BulkActionKind::Delete { "is-danger" } else { "is-link" })}
+                            onclick={on_apply}
+                        >
+                            { "Apply" }
+                        </button>
+                    </p>
+                    <p class="control">
+                        <button class="button is-text" onclick={on_clear}>{ "Clear selection" }</button>
+                    </p>
+                </div>
+                if let Some(error) = &self.error {
+                    <p class="help is-danger">{ error }</p>
+                }
+            </div>
+        }
+    }
+}
+
+impl BulkActionBar {
+    fn value_input(&self, ctx: &Context<Self>) -> Html {
+        let on_change = ctx.link().callback(Msg::UpdateValue);
+        match self.kind {
+            BulkActionKind::Status => html! {
+                <Select
+                    value={self.value.clone()}
+                    options={IArray::from(TicketStatus::iter().map(|s| IString::from(s.to_string())).collect::<Vec<IString>>())}
+                    placeholder="Status"
+                    {on_change}
+                />
+            },
+            BulkActionKind::Priority => html! {
+                <Select
+                    value={self.value.clone()}
+                    options={IArray::from(
+                        [Priority::Low, Priority::Normal, Priority::High, Priority::Critical]
+                            .map(|p| IString::from(TicketPriority(p).to_string()))
+                            .to_vec()
+                    )}
+                    placeholder="Priority"
+                    {on_change}
+                />
+            },
+            BulkActionKind::Assignee => html! {
+                <TextInput value={self.value.clone()} {on_change} placeholder="User id (empty to unassign)" />
+            },
+            BulkActionKind::Project => html! {
+                <TextInput value={self.value.clone()} {on_change} placeholder="Project id (empty to remove)" />
+            },
+            BulkActionKind::AddLabel | BulkActionKind::RemoveLabel => html! {
+                <TextInput value={self.value.clone()} {on_change} placeholder="Label" />
+            },
+            BulkActionKind::Delete => html! {},
+        }
+    }
+
+    fn to_action(&self) -> Result<BulkAction, String> {
+        let value = self.value.trim();
+        match self.kind {
+            BulkActionKind::Status => TicketStatus::from_str(value)
+                .map(BulkAction::Status)
+                .map_err(|_| String::from("Select a status.")),
+            BulkActionKind::Priority => TicketPriority::from_str(value)
+                .map(BulkAction::Priority)
+                .map_err(|_| String::from("Select a priority.")),
+            BulkActionKind::Assignee if value.is_empty() => Ok(BulkAction::Assignee(None)),
+            BulkActionKind::Assignee => Uuid::parse_str(value)
+                .map(|id| BulkAction::Assignee(Some(id)))
+                .map_err(|_| String::from("Invalid user id.")),
+            BulkActionKind::Project if value.is_empty() => Ok(BulkAction::Project(None)),
+            BulkActionKind::Project => value
+                .parse::<u64>()
+                .map(|id| BulkAction::Project(Some(id)))
+                .map_err(|_| String::from("Invalid project id.")),
+            BulkActionKind::AddLabel | BulkActionKind::RemoveLabel if value.is_empty() => {
+                Err(String::from("Enter a label."))
+            }
+            BulkActionKind::AddLabel => Ok(BulkAction::AddLabel(value.to_owned())),
+            BulkActionKind::RemoveLabel => Ok(BulkAction::RemoveLabel(value.to_owned())),
+            BulkActionKind::Delete => Ok(BulkAction::Delete),
+        }
+    }
+}
@@ -9,23 +9,48 @@ use crate::{
     route::Route,
 };
 use implicit_clone::unsync::{IArray, IString};
-use shared::dtos::ticket_dto::{ITicketDto, TicketDto, TicketField, TicketValue};
+use shared::dtos::{
+    custom_field_dto::CustomFieldDto,
+    ticket_dto::{ITicketDto, TicketDto, TicketField, TicketValue},
+};
 use std::rc::Rc;
 use yew::{classes, html, Callback};
 use yew_router::prelude::Link;
 
 pub struct TicketDataSource(ITableDataSource<TicketField, ITicketDto, TicketValue>);
 
+pub const DEFAULT_TICKET_FIELDSET: [TicketField; 4] = [
+    TicketField::Id,
+    TicketField::Title,
+    TicketField::Priority,
+    TicketField::Status,
+];
+
 impl From<&Vec<TicketDto>> for TicketDataSource {
     fn from(source: &Vec<TicketDto>) -> Self {
+        Self::new(source, DEFAULT_TICKET_FIELDSET.to_vec())
+    }
+}
+
+impl TicketDataSource {
+    /// Data source showing the given columns only, e.g. the ones of a saved view.
+    pub fn new(source: &[TicketDto], fieldset: Vec<TicketField>) -> Self {
+        Self::with_custom_fields(source, fieldset, &[])
+    }
+
+    /// Data source whose custom field columns are titled with the names of the fields.
+    pub fn with_custom_fields(
+        source: &[TicketDto],
+        fieldset: Vec<TicketField>,
+        custom_fields: &[CustomFieldDto],
+    ) -> Self {
+        let names = custom_fields
+            .iter()
+            .filter_map(|f| f.id.map(|id| (id, IString::from(f.name.clone()))))
+            .collect::<Vec<(u64, IString)>>();
         Self(Rc::new(TableDataSource {
             empty_label: IString::from("No tickets selected for this project"),
-            fieldset: IArray::from(vec![
-                TicketField::Id,
-                TicketField::Title,
-                TicketField::Priority,
-                TicketField::Status,
-            ]),
+            fieldset: IArray::from(fieldset),
             data: IArray::from(
                 source
                     .iter()
@@ -51,11 +76,39 @@ impl From<&Vec<TicketDto>> for TicketDataSource {
                         TicketField::Status => Some(html! {
                             <span class="tag">{ html! {celldata.data.status}}</span>
                         }),
-                        _ => None,
+                        TicketField::Description => Some(html! {
+                            { celldata.data.description.chars().take(80).collect::<String>() }
+                        }),
+                        TicketField::Project => Some(match celldata.data.project_id {
+                            Some(project_id) => html! {
+                                <Link<Route> to={Route::Project { id: project_id }}>
+                                    { project_id }
+                                </Link<Route>>
+                            },
+                            None => html! {},
+                        }),
+                        TicketField::User => Some(match celldata.data.user_id {
+                            Some(user_id) => html! {
+                                <Link<Route> to={Route::User { id: user_id }}>
+                                    <span class="icon"><i class="fas fa-user"></i></span>
+                                </Link<Route>>
+                            },
+                            None => html! {},
+                        }),
+                        TicketField::Custom(field_id) => Some(html! {
+                            { celldata.data.fields.get(&field_id).map(|values| values.join(", ")).unwrap_or_default() }
+                        }),
                     },
                     None => None,
                 }
             }),
+            head_title: Callback::from(move |field: TicketField| match field {
+                TicketField::Custom(field_id) => names
+                    .iter()
+                    .find(|(id, _)| *id == field_id)
+                    .map(|(_, name)| name.clone()),
+                _ => None,
+            }),
             ..Default::default()
         }))
     }
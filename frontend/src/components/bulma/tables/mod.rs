@@ -10,3 +10,4 @@ pub mod table_head_renderer;
 pub mod table_head_sort;
 pub mod table_head_sort_manager;
 pub mod table_head_sort_order;
+pub mod table_row_selection;
@@ -1,11 +1,11 @@
 use super::{
     table_data_source::ITableDataSource, table_head_data::TableHeadData,
-    table_head_sort_manager::TableHeadSortManager,
+    table_head_sort_manager::TableHeadSortManager, table_row_selection::TableRowSelection,
 };
 use crate::components::bulma::tables::{
     composite_cell_data::CompositeCellData, table_head::TableHead,
 };
-use implicit_clone::ImplicitClone;
+use implicit_clone::{unsync::IArray, ImplicitClone};
 use shared::dtos::getter::Getter;
 use std::str::FromStr;
 use yew::{classes, html, AttrValue, Callback, Component, Context, Properties};
@@ -24,6 +24,11 @@ where
     pub sorthandler: Option<Callback<TableHeadData>>,
     #[prop_or_default]
     pub class: Option<AttrValue>,
+    /// Indexes of the selected rows. Checkboxes are only shown if there is a `selecthandler`.
+    #[prop_or_default]
+    pub selected: IArray<usize>,
+    #[prop_or_default]
+    pub selecthandler: Option<Callback<TableRowSelection>>,
 }
 
 pub enum TableMsg {
@@ -51,12 +56,14 @@ where
     type Properties = Props<F, T, V>;
 
     fn create(ctx: &Context<Self>) -> Self {
-        let mut sortmanager = TableHeadSortManager::from(ctx.props().datasource.fieldset.clone());
+        let datasource = ctx.props().datasource.clone();
+        let mut sortmanager = TableHeadSortManager::from(datasource.fieldset.clone());
+        sortmanager.update_titles(&datasource.fieldset, &datasource.head_title);
         if let Some(data) = ctx.props().sort.clone() {
             sortmanager.update(data);
         }
         Self {
-            datasource: ctx.props().datasource.clone(),
+            datasource,
             sortmanager,
             sorthandler: ctx.props().sorthandler.clone(),
         }
@@ -74,8 +81,13 @@ where
         true
     }
 
-    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().datasource.fieldset != old_props.datasource.fieldset {
+            self.sortmanager = TableHeadSortManager::from(ctx.props().datasource.fieldset.clone());
+        }
         self.datasource = ctx.props().datasource.clone();
+        self.sortmanager
+            .update_titles(&self.datasource.fieldset, &self.datasource.head_title);
         if let Some(data) = ctx.props().sort.clone() {
             self.sortmanager.update(data);
         }
@@ -94,6 +106,7 @@ where
                 html! {
                     <thead>
                         <tr>
+                            { self.select_all_view(ctx) }
                             { for heads }
                         </tr>
                     </thead>
@@ -102,31 +115,40 @@ where
             false => html!(),
         };
 
-        let rows = self.datasource.data.iter().map(|entry| {
-            let cols = self.datasource.fieldset.iter().map(|field| {
-                let render = self.datasource.cellrenderer.emit(CompositeCellData {
-                    column: field.clone(),
-                    data: entry.clone(),
-                });
-                if let Some(cell) = render {
-                    match self.datasource.has_row_head
-                        && <F as Into<usize>>::into(field.clone()) == 0usize
-                    {
-                        true => html! {
-                            <th>{ cell }</th>
-                        },
-                        false => html! {
-                            <td>{ cell }</td>
-                        },
+        let rows = self
+            .datasource
+            .data
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                let cols = self.datasource.fieldset.iter().map(|field| {
+                    let render = self.datasource.cellrenderer.emit(CompositeCellData {
+                        column: field.clone(),
+                        data: entry.clone(),
+                    });
+                    if let Some(cell) = render {
+                        match self.datasource.has_row_head
+                            && <F as Into<usize>>::into(field.clone()) == 0usize
+                        {
+                            true => html! {
+                                <th>{ cell }</th>
+                            },
+                            false => html! {
+                                <td>{ cell }</td>
+                            },
+                        }
+                    } else {
+                        html!()
                     }
-                } else {
-                    html!()
+                });
+                let is_selected = ctx.props().selected.iter().any(|i| i == index);
+                html! {
+                    <tr class={classes!(is_selected.then_some("is-selected"))}>
+                        { self.select_row_view(ctx, index, is_selected) }
+                        { for cols }
+                    </tr>
                 }
             });
-            html! {
-                <tr>{ for cols }</tr>
-            }
-        });
 
         match self.datasource.data.is_empty() {
             true => {
@@ -159,4 +181,31 @@ where
         }
         classes.join(" ")
     }
+
+    fn select_all_view(&self, ctx: &Context<Self>) -> yew::Html {
+        match &ctx.props().selecthandler {
+            Some(handler) => {
+                let checked = !self.datasource.data.is_empty()
+                    && ctx.props().selected.len() == self.datasource.data.len();
+                let onchange = handler.reform(move |_: Event| TableRowSelection::All(!checked));
+                html! {
+                    <th><input type="checkbox" {checked} {onchange} /></th>
+                }
+            }
+            None => html!(),
+        }
+    }
+
+    fn select_row_view(&self, ctx: &Context<Self>, index: usize, checked: bool) -> yew::Html {
+        match &ctx.props().selecthandler {
+            Some(handler) => {
+                let onchange =
+                    handler.reform(move |_: Event| TableRowSelection::Row(index, !checked));
+                html! {
+                    <td><input type="checkbox" {checked} {onchange} /></td>
+                }
+            }
+            None => html!(),
+        }
+    }
 }
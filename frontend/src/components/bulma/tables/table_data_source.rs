@@ -27,6 +27,8 @@ where
     pub has_column_head: bool,
     pub has_row_head: bool,
     pub cellrenderer: CellRendererFn<F, T>,
+    /// Title of a column head, if it should not show the name of the field.
+    pub head_title: Callback<F, Option<IString>>,
     pub phantom: PhantomData<V>,
 }
 
@@ -46,6 +48,7 @@ where
             has_column_head: Default::default(),
             has_row_head: Default::default(),
             cellrenderer: SimpleCellRenderer::create(),
+            head_title: Callback::from(|_| None),
             phantom: PhantomData,
         }
     }
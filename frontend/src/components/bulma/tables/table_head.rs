@@ -72,7 +72,7 @@ impl Component for TableHead {
 
 impl TableHead {
     fn head_view(&self, ctx: &Context<Self>) -> Html {
-        let label = self.data.label.clone();
+        let label = self.title();
         match self.sorthandler {
             Some(_) => {
                 let on_click = |_| TableHeadMsg::SortClicked();
@@ -85,7 +85,7 @@ impl TableHead {
     }
 
     fn label_view(&self) -> Html {
-        let label = self.data.label.clone();
+        let label = self.title();
         match self.data.sort.clone() {
             Some(TableHeadSort { sort: _, order }) => html! {
                 <span class="icon-text">
@@ -97,6 +97,13 @@ impl TableHead {
         }
     }
 
+    fn title(&self) -> IString {
+        self.data
+            .title
+            .clone()
+            .unwrap_or_else(|| self.data.label.clone())
+    }
+
     fn icon_view(&self, order: TableHeadSortOrder) -> Html {
         let icon = match order {
             TableHeadSortOrder::Asc => "fa-sort-up",
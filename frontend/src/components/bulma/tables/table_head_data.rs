@@ -4,6 +4,8 @@ use implicit_clone::{unsync::IString, ImplicitClone};
 #[derive(Clone, Debug, PartialEq)]
 pub struct TableHeadData {
     pub label: IString,
+    /// Shown instead of the label if set, the label still identifies the column.
+    pub title: Option<IString>,
     pub sort: Option<TableHeadSort>,
 }
 
@@ -16,6 +18,7 @@ where
     fn from(value: T) -> Self {
         Self {
             label: IString::from(value.to_string()),
+            title: None,
             sort: None,
         }
     }
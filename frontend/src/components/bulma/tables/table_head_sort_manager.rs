@@ -1,6 +1,7 @@
 use super::table_head_data::TableHeadData;
 use implicit_clone::{unsync::IArray, unsync::IString, ImplicitClone};
 use std::str::FromStr;
+use yew::Callback;
 
 pub struct TableHeadSortManager {
     pub header: IArray<TableHeadData>,
@@ -12,15 +13,39 @@ impl TableHeadSortManager {
             .header
             .iter()
             .map(|old_data| match old_data.label == data.label {
-                true => data.clone(),
+                true => TableHeadData {
+                    title: old_data.title.clone(),
+                    ..data.clone()
+                },
                 false => TableHeadData {
                     label: old_data.label.clone(),
+                    title: old_data.title.clone(),
                     sort: None,
                 },
             })
             .collect();
         self.header = new_header;
     }
+
+    /// Sets the titles of the columns, they can change without the columns changing.
+    pub fn update_titles<F>(
+        &mut self,
+        fieldset: &IArray<F>,
+        head_title: &Callback<F, Option<IString>>,
+    ) where
+        F: Clone + ImplicitClone + 'static,
+    {
+        let new_header = self
+            .header
+            .iter()
+            .zip(fieldset.iter())
+            .map(|(data, field)| TableHeadData {
+                title: head_title.emit(field),
+                ..data
+            })
+            .collect();
+        self.header = new_header;
+    }
 }
 
 impl<F> From<IArray<F>> for TableHeadSortManager
@@ -32,6 +57,7 @@ where
             .iter()
             .map(|f| TableHeadData {
                 label: IString::from(f.to_string()),
+                title: None,
                 sort: None,
             })
             .collect();
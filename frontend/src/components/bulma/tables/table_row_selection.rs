@@ -0,0 +1,6 @@
+/// Change of the selected rows, by row index in the data source.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TableRowSelection {
+    Row(usize, bool),
+    All(bool),
+}
@@ -0,0 +1,73 @@
+use super::{axes_view, legend_view, x_label_view, y_max, y_of, HEIGHT, LEFT, RIGHT, WIDTH};
+use yew::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub labels: Vec<AttrValue>,
+    pub values: Vec<f64>,
+    pub color: AttrValue,
+    pub name: AttrValue,
+    /// Drawn as a dashed line across the bars.
+    #[prop_or_default]
+    pub average: Option<f64>,
+}
+
+/// One bar per label, drawn as plain SVG.
+#[function_component(BarChart)]
+pub fn bar_chart(props: &Props) -> Html {
+    let Props {
+        labels,
+        values,
+        color,
+        name,
+        average,
+    } = props;
+    if values.is_empty() {
+        return html! { <p class="has-text-grey">{ "No data yet." }</p> };
+    }
+    let max = y_max(values.iter().copied().chain(*average));
+    let slot = (WIDTH - LEFT - RIGHT) / values.len() as f64;
+    let width = slot * 0.6;
+
+    let bars = values
+        .iter()
+        .zip(labels.iter())
+        .enumerate()
+        .map(|(i, (v, label))| {
+            let center = LEFT + slot * (i as f64 + 0.5);
+            let y = y_of(*v, max);
+            html! {
+                <>
+                    <rect
+                        x={(center - width / 2.0).to_string()}
+                        y={y.to_string()}
+                        width={width.to_string()}
+                        height={(y_of(0.0, max) - y).to_string()}
+                        fill={color.clone()}
+                    >
+                        <title>{ format!("{}: {}", label, v) }</title>
+                    </rect>
+                    { x_label_view(center, label) }
+                </>
+            }
+        });
+    let mut legend = vec![(name.clone(), color.clone())];
+    let average_line = average.map(|average| {
+        let y = y_of(average, max);
+        legend.push((AttrValue::from(format!("Average: {:.1}", average)), AttrValue::from("#7a7a7a")));
+        html! {
+            <line x1={LEFT.to_string()} y1={y.to_string()} x2={(WIDTH - RIGHT).to_string()} y2={y.to_string()} stroke="#7a7a7a" stroke-width="2" stroke-dasharray="6 4" />
+        }
+    });
+
+    html! {
+        <div>
+            <svg viewBox={format!("0 0 {} {}", WIDTH, HEIGHT)} width="100%" role="img">
+                { axes_view(max) }
+                { for bars }
+                { average_line.unwrap_or_default() }
+            </svg>
+            { legend_view(legend) }
+        </div>
+    }
+}
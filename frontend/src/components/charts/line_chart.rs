@@ -0,0 +1,72 @@
+use super::{axes_view, legend_view, x_label_view, y_max, y_of, HEIGHT, LEFT, RIGHT, WIDTH};
+use yew::prelude::*;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChartSeries {
+    pub name: AttrValue,
+    pub color: AttrValue,
+    pub values: Vec<f64>,
+    /// Drawn dashed, e.g. for a target instead of a measure.
+    pub dashed: bool,
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    /// One label per point of the series, only some of them are shown.
+    pub labels: Vec<AttrValue>,
+    pub series: Vec<ChartSeries>,
+}
+
+/// Lines over a shared horizontal axis, drawn as plain SVG.
+#[function_component(LineChart)]
+pub fn line_chart(props: &Props) -> Html {
+    let Props { labels, series } = props;
+    let count = labels.len();
+    if count == 0 {
+        return html! { <p class="has-text-grey">{ "No data yet." }</p> };
+    }
+    let max = y_max(series.iter().flat_map(|s| s.values.iter().copied()));
+    let step = (WIDTH - LEFT - RIGHT) / (count.max(2) - 1) as f64;
+    let x_of = |index: usize| LEFT + index as f64 * step;
+
+    let lines = series.iter().map(|s| {
+        let points = s
+            .values
+            .iter()
+            .enumerate()
+            .map(|(i, v)| format!("{:.1},{:.1}", x_of(i), y_of(*v, max)))
+            .collect::<Vec<String>>()
+            .join(" ");
+        html! {
+            <polyline
+                {points}
+                fill="none"
+                stroke={s.color.clone()}
+                stroke-width="2"
+                stroke-dasharray={if s.dashed { "6 4" } else { "none" }}
+            />
+        }
+    });
+    // The first, middle and last labels are enough to read the axis
+    let shown = [0, count / 2, count - 1];
+    let x_labels = labels
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| shown.contains(i))
+        .map(|(i, label)| x_label_view(x_of(i), label));
+    let legend = series
+        .iter()
+        .map(|s| (s.name.clone(), s.color.clone()))
+        .collect();
+
+    html! {
+        <div>
+            <svg viewBox={format!("0 0 {} {}", WIDTH, HEIGHT)} width="100%" role="img">
+                { axes_view(max) }
+                { for lines }
+                { for x_labels }
+            </svg>
+            { legend_view(legend) }
+        </div>
+    }
+}
@@ -0,0 +1,69 @@
+pub mod bar_chart;
+pub mod line_chart;
+
+use yew::{html, AttrValue, Html};
+
+/// Size of the drawing in SVG units, it scales with the width of its container.
+const WIDTH: f64 = 600.0;
+const HEIGHT: f64 = 240.0;
+/// Room for the labels of the axes.
+const LEFT: f64 = 40.0;
+const BOTTOM: f64 = 30.0;
+const TOP: f64 = 10.0;
+const RIGHT: f64 = 10.0;
+
+/// Vertical position of the value, for values from zero to `max`.
+fn y_of(value: f64, max: f64) -> f64 {
+    HEIGHT - BOTTOM - value / max * (HEIGHT - BOTTOM - TOP)
+}
+
+/// Upper bound of the vertical axis, at least one so empty charts still draw.
+fn y_max(values: impl Iterator<Item = f64>) -> f64 {
+    values.fold(1.0, f64::max).ceil()
+}
+
+/// Both axes with the scale of the vertical one at zero, half and top.
+fn axes_view(max: f64) -> Html {
+    let ticks = [0.0, max / 2.0, max].map(|value| {
+        let y = y_of(value, max);
+        html! {
+            <>
+                <line x1={LEFT.to_string()} y1={y.to_string()} x2={(WIDTH - RIGHT).to_string()} y2={y.to_string()} stroke="#ededed" />
+                <text x={(LEFT - 6.0).to_string()} y={(y + 4.0).to_string()} text-anchor="end" font-size="11" fill="#7a7a7a">
+                    { format!("{}", (value * 10.0).round() / 10.0) }
+                </text>
+            </>
+        }
+    });
+    html! {
+        <>
+            { for ticks }
+            <line x1={LEFT.to_string()} y1={TOP.to_string()} x2={LEFT.to_string()} y2={(HEIGHT - BOTTOM).to_string()} stroke="#b5b5b5" />
+            <line x1={LEFT.to_string()} y1={(HEIGHT - BOTTOM).to_string()} x2={(WIDTH - RIGHT).to_string()} y2={(HEIGHT - BOTTOM).to_string()} stroke="#b5b5b5" />
+        </>
+    }
+}
+
+fn x_label_view(x: f64, label: &AttrValue) -> Html {
+    html! {
+        <text x={x.to_string()} y={(HEIGHT - BOTTOM + 18.0).to_string()} text-anchor="middle" font-size="11" fill="#7a7a7a">
+            { label.clone() }
+        </text>
+    }
+}
+
+/// Names of the series next to a swatch of their colour.
+fn legend_view(entries: Vec<(AttrValue, AttrValue)>) -> Html {
+    html! {
+        <div class="tags mt-2">
+            {
+                entries.into_iter().map(|(name, color)| html! {
+                    <span class="tag is-white">
+                        <span class="mr-1" style={format!("display: inline-block; width: 10px; height: 10px; background-color: {};", color)}></span>
+                        { name }
+                    </span>
+                }).collect::<Html>()
+            }
+        </div>
+    }
+}
@@ -0,0 +1,296 @@
+use crate::app_state::AppStateContext;
+use crate::components::bulma::field::Field;
+use crate::components::html::checkbox::Checkbox;
+use crate::components::html::text_input::TextInput;
+use crate::services::board_service::BoardService;
+use implicit_clone::sync::{IArray, IString};
+use shared::api::error::error_response::ErrorResponse;
+use shared::dtos::board_dto::{BoardColumnDto, BoardDto};
+use shared::dtos::identity::Identity;
+use shared::validation::ticket_validation::TicketStatus;
+use shared::validation::validation_messages::{IValidationMessages, ValidationMessagesTrait};
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+#[derive(Clone, Debug, PartialEq, Properties)]
+pub struct Props {
+    pub projectid: u64,
+    pub onsubmit: Callback<(BoardDto, Callback<ErrorResponse>)>,
+}
+
+pub enum BoardMsg {
+    ContextChanged(AppStateContext),
+    FetchedBoard(BoardDto),
+    UpdateName((usize, AttrValue)),
+    ToggleStatus((usize, TicketStatus, bool)),
+    UpdateWipLimit((usize, AttrValue)),
+    ToggleHidden((usize, bool)),
+    ToggleWipEnforced((usize, bool)),
+    MoveUp(usize),
+    MoveDown(usize),
+    AddColumn(),
+    RemoveColumn(usize),
+    Submit(),
+    UpdateErrors(ErrorResponse),
+}
+
+/// Edits the columns of the board of a project: their statuses, order, visibility and
+/// work-in-progress limits.
+pub struct BoardForm {
+    app_state: AppStateContext,
+    _listener: ContextHandle<AppStateContext>,
+    /// Columns with a key that stays the same while they are reordered.
+    columns: Vec<(u32, BoardColumnDto)>,
+    next_key: u32,
+    on_submit: Callback<(BoardDto, Callback<ErrorResponse>)>,
+    common_error: IValidationMessages,
+    columns_error: IValidationMessages,
+}
+
+impl Component for BoardForm {
+    type Message = BoardMsg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (app_state, _listener) = ctx
+            .link()
+            .context::<AppStateContext>(ctx.link().callback(BoardMsg::ContextChanged))
+            .expect("context to be set");
+        if let Some(Identity { token, .. }) = &app_state.identity {
+            BoardService::fetch(
+                token.to_string(),
+                ctx.props().projectid,
+                ctx.link().callback(BoardMsg::FetchedBoard),
+            );
+        }
+        Self {
+            app_state,
+            _listener,
+            columns: Vec::new(),
+            next_key: 0,
+            on_submit: ctx.props().onsubmit.to_owned(),
+            common_error: None,
+            columns_error: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            BoardMsg::ContextChanged(state) => {
+                self.app_state = state;
+            }
+            BoardMsg::FetchedBoard(board) => {
+                self.columns = Vec::new();
+                for column in board.columns {
+                    self.push_column(column);
+                }
+            }
+            BoardMsg::UpdateName((index, name)) => {
+                if let Some((_, column)) = self.columns.get_mut(index) {
+                    column.name = name.to_string();
+                }
+            }
+            BoardMsg::ToggleStatus((index, status, checked)) => {
+                if let Some((_, column)) = self.columns.get_mut(index) {
+                    column.statuses.retain(|s| *s != status);
+                    if checked {
+                        column.statuses.push(status);
+                        // Dropping a ticket on the column moves it to its earliest status
+                        column
+                            .statuses
+                            .sort_by_key(|s| TicketStatus::iter().position(|other| other == *s));
+                    }
+                }
+            }
+            BoardMsg::UpdateWipLimit((index, value)) => {
+                if let Some((_, column)) = self.columns.get_mut(index) {
+                    column.wip_limit = value.as_str().trim().parse::<u32>().ok();
+                }
+            }
+            BoardMsg::ToggleHidden((index, hidden)) => {
+                if let Some((_, column)) = self.columns.get_mut(index) {
+                    column.hidden = hidden;
+                }
+            }
+            BoardMsg::ToggleWipEnforced((index, enforced)) => {
+                if let Some((_, column)) = self.columns.get_mut(index) {
+                    column.wip_enforced = enforced;
+                }
+            }
+            BoardMsg::MoveUp(index) => {
+                if index > 0 && index < self.columns.len() {
+                    self.columns.swap(index - 1, index);
+                }
+            }
+            BoardMsg::MoveDown(index) => {
+                if index + 1 < self.columns.len() {
+                    self.columns.swap(index, index + 1);
+                }
+            }
+            BoardMsg::AddColumn() => {
+                self.push_column(BoardColumnDto::default());
+            }
+            BoardMsg::RemoveColumn(index) => {
+                if index < self.columns.len() {
+                    self.columns.remove(index);
+                }
+            }
+            BoardMsg::Submit() => {
+                self.common_error = None;
+                self.columns_error = None;
+                self.on_submit.emit((
+                    BoardDto {
+                        columns: self.columns.iter().map(|(_, c)| c.clone()).collect(),
+                    },
+                    ctx.link().callback(BoardMsg::UpdateErrors),
+                ));
+            }
+            BoardMsg::UpdateErrors(error_response) => {
+                log::debug!("Error response: {}", error_response);
+                match error_response.details {
+                    Some(errors) => self.update_errors(errors),
+                    None => {
+                        self.common_error =
+                            Some(IArray::from(vec![IString::from(error_response.message)]))
+                    }
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let on_submit_pressed = |_: MouseEvent| BoardMsg::Submit();
+        let on_add_pressed = |_: MouseEvent| BoardMsg::AddColumn();
+
+        html! {
+            <div class="card">
+                <div class="card-content">
+                    { Self::errors_view(&self.common_error) }
+                    { Self::errors_view(&self.columns_error) }
+                    {
+                        self.columns.iter().enumerate().map(|(index, (key, column))| {
+                            self.column_view(ctx, index, *key, column)
+                        }).collect::<Html>()
+                    }
+                    <button class="button is-small" onclick={ctx.link().callback(on_add_pressed)}>
+                        <span class="icon is-small">
+                            <i class="fas fa-plus"></i>
+                        </span>
+                        <span>{ "Add a column" }</span>
+                    </button>
+                </div>
+                <footer class="card-footer">
+                    <div class="card-content">
+                        <div class="field is-grouped">
+                            <div class="control">
+                                <button class="button is-link" onmouseup={ctx.link().callback(on_submit_pressed)}>{ "Save" }</button>
+                            </div>
+                        </div>
+                    </div>
+                </footer>
+            </div>
+        }
+    }
+}
+
+impl BoardForm {
+    fn push_column(&mut self, column: BoardColumnDto) {
+        self.columns.push((self.next_key, column));
+        self.next_key += 1;
+    }
+
+    fn column_view(
+        &self,
+        ctx: &Context<Self>,
+        index: usize,
+        key: u32,
+        column: &BoardColumnDto,
+    ) -> Html {
+        let statuses = TicketStatus::iter().map(|status| {
+            let on_change = ctx
+                .link()
+                .callback(move |checked: bool| BoardMsg::ToggleStatus((index, status, checked)));
+            html! {
+                <label class="checkbox mr-3">
+                    <Checkbox checked={column.contains(&status)} {on_change} />
+                    { " " }{ status.to_string() }
+                </label>
+            }
+        });
+        let wip_limit = column
+            .wip_limit
+            .map_or(String::new(), |limit| format!("{}", limit));
+
+        html! {
+            <div class="box" key={key}>
+                <div class="columns">
+                    <div class="column is-half">
+                        <Field label="Name">
+                            <TextInput value={column.name.clone()} on_change={ctx.link().callback(move |name| BoardMsg::UpdateName((index, name)))} />
+                        </Field>
+                    </div>
+                    <div class="column">
+                        <Field label="WIP limit">
+                            <TextInput value={wip_limit} placeholder="None" on_change={ctx.link().callback(move |value| BoardMsg::UpdateWipLimit((index, value)))} />
+                        </Field>
+                    </div>
+                    <div class="column is-narrow">
+                        <div class="buttons are-small mt-5">
+                            <button class="button" title="Move left" disabled={index == 0} onclick={ctx.link().callback(move |_| BoardMsg::MoveUp(index))}>
+                                <span class="icon is-small"><i class="fas fa-arrow-left"></i></span>
+                            </button>
+                            <button class="button" title="Move right" disabled={index + 1 == self.columns.len()} onclick={ctx.link().callback(move |_| BoardMsg::MoveDown(index))}>
+                                <span class="icon is-small"><i class="fas fa-arrow-right"></i></span>
+                            </button>
+                            <button class="button is-danger is-light" title="Remove" onclick={ctx.link().callback(move |_| BoardMsg::RemoveColumn(index))}>
+                                <span class="icon is-small"><i class="fas fa-trash"></i></span>
+                            </button>
+                        </div>
+                    </div>
+                </div>
+                <div class="field">
+                    <label class="label">{ "Statuses" }</label>
+                    <div class="control">
+                        { for statuses }
+                    </div>
+                </div>
+                <div class="field is-grouped">
+                    <label class="checkbox control">
+                        <Checkbox checked={column.hidden} on_change={ctx.link().callback(move |hidden| BoardMsg::ToggleHidden((index, hidden)))} />
+                        { " Hidden" }
+                    </label>
+                    <label class="checkbox control">
+                        <Checkbox checked={column.wip_enforced} on_change={ctx.link().callback(move |enforced| BoardMsg::ToggleWipEnforced((index, enforced)))} />
+                        { " Reject moves over the limit" }
+                    </label>
+                </div>
+            </div>
+        }
+    }
+
+    fn errors_view(errors: &IValidationMessages) -> Html {
+        match errors {
+            Some(errors) => html! {
+                <p class="help is-danger">
+                    <ul>
+                    {
+                        errors.iter().map(|message| {
+                            html!{<li>{ html! {message}}</li>}
+                        }).collect::<Html>()
+                    }
+                    </ul>
+                </p>
+            },
+            None => html! { <></> },
+        }
+    }
+
+    fn update_errors<E>(&mut self, errors: E)
+    where
+        E: ValidationMessagesTrait,
+    {
+        self.common_error = errors.get_common_messages();
+        self.columns_error = errors.get_property_messages("columns");
+    }
+}
@@ -1,3 +1,4 @@
+pub mod board_form;
 pub mod login_form;
 pub mod preferences_form;
 pub mod project_form;
@@ -34,6 +34,7 @@ pub enum ProjectMsg {
     ContextChanged(AppStateContext),
     DialogContextChanged(Rc<DialogContext>),
     UpdateSummary(AttrValue),
+    UpdateKey(AttrValue),
     UpdateDeadline(AttrValue),
     UpdateOwner(AttrValue),
     UpdateUserId((IString, IString)),
@@ -61,6 +62,7 @@ pub struct ProjectForm {
     on_submit: Callback<(ProjectDto, Callback<ErrorResponse>)>,
     common_error: IValidationMessages,
     summary_error: IValidationMessages,
+    key_error: IValidationMessages,
     deadline_error: IValidationMessages,
     owner_error: IValidationMessages,
 }
@@ -91,6 +93,7 @@ impl Component for ProjectForm {
             on_submit: ctx.props().onsubmit.to_owned(),
             common_error: None,
             summary_error: None,
+            key_error: None,
             deadline_error: None,
             owner_error: None,
         }
@@ -111,6 +114,9 @@ impl Component for ProjectForm {
             ProjectMsg::UpdateSummary(summary) => {
                 self.project.summary = String::from(summary.as_str());
             }
+            ProjectMsg::UpdateKey(key) => {
+                self.project.key = key.trim().to_uppercase();
+            }
             ProjectMsg::UpdateDeadline(deadline) => {
                 self.deadline = deadline;
                 log::debug!("Trying to parse '{}'", self.deadline.as_str());
@@ -258,6 +264,9 @@ impl Component for ProjectForm {
                     <Field label="Summary" help={&self.summary_error}>
                         <TextInput value={self.project.summary.clone()} on_change={ctx.link().callback(ProjectMsg::UpdateSummary)} valid={self.summary_error.is_empty()} />
                     </Field>
+                    <Field label="Key" help={&self.key_error}>
+                        <TextInput value={self.project.key.clone()} on_change={ctx.link().callback(ProjectMsg::UpdateKey)} valid={self.key_error.is_empty()} placeholder="e.g. TCK, prefix of the ticket keys" />
+                    </Field>
                     <Field label="Deadline" help={&self.deadline_error}>
                         <div class="field">
                             <div class="control">
@@ -320,6 +329,7 @@ impl ProjectForm {
     {
         self.common_error = errors.get_common_messages();
         self.summary_error = errors.get_property_messages("summary");
+        self.key_error = errors.get_property_messages("key");
         self.deadline_error = errors.get_property_messages("ts_seconds_option");
         self.owner_error = errors.get_property_messages("user_id");
     }
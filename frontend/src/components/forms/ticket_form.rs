@@ -1,12 +1,16 @@
 use crate::app_state::AppStateContext;
 use crate::components::button_link::ButtonLinkData;
 use crate::components::dialogs::dialog_context::DialogContext;
+use crate::components::html::checkbox::Checkbox;
+use crate::components::html::date_input::DateInput;
 use crate::components::html::select::Select;
 use crate::components::html::text_input::TextInput;
 use crate::components::icon_link::{IconLink, IconLinkData};
 use crate::components::priority_tag::PriorityTag;
 use crate::route::Route;
+use crate::services::custom_field_service::CustomFieldService;
 use crate::services::project_service::ProjectService;
+use crate::services::sprint_service::SprintService;
 use crate::services::user_service::UserService;
 use crate::{components::bulma::field::Field, services::ticket_service::TicketService};
 use entity::sea_orm_active_enums::Priority;
@@ -17,9 +21,11 @@ use implicit_clone::{
 };
 use serde_valid::Validate;
 use shared::api::error::error_response::ErrorResponse;
+use shared::dtos::custom_field_dto::{CustomFieldDto, CustomFieldType};
 use shared::dtos::identity::Identity;
 use shared::dtos::project_dto::ProjectDto;
-use shared::dtos::ticket_dto::{TicketDto, TicketField};
+use shared::dtos::sprint_dto::{SprintDto, SprintState};
+use shared::dtos::ticket_dto::{SimilarTicketDto, TicketDraftDto, TicketDto, TicketField};
 use shared::dtos::user_dto::UserDto;
 use shared::validation::is_empty::IsEmpty;
 use shared::validation::ticket_validation::{TicketPriority, TicketStatus};
@@ -31,6 +37,7 @@ use std::str::FromStr;
 use strum::{EnumCount, IntoEnumIterator};
 use uuid::Uuid;
 use yew::prelude::*;
+use yew_router::prelude::Link;
 use yew_router::scope_ext::RouterScopeExt;
 
 const SEARCH_DELAY_MS: u32 = 300;
@@ -49,9 +56,13 @@ pub enum TicketMsg {
     ContextChanged(AppStateContext),
     DialogContextChanged(Rc<DialogContext>),
     FetchedTicket(TicketDto),
+    FetchedLatest(TicketDto),
     FetchedProject(ProjectDto),
     FetchedUser(UserDto),
     FetchedUsers(Vec<UserDto>),
+    FetchedCustomFields(Vec<CustomFieldDto>),
+    FetchedSprints(Vec<SprintDto>),
+    FetchedSimilar(Vec<SimilarTicketDto>),
     UpdateTitle(AttrValue),
     UpdateDescription(AttrValue),
     UpdateProjectId(AttrValue),
@@ -59,12 +70,19 @@ pub enum TicketMsg {
     UpdateStatus(AttrValue),
     UpdateOwner(AttrValue),
     UpdateUserId((Uuid, IString)),
+    UpdateCustomField((u64, AttrValue)),
+    UpdateSprint(AttrValue),
+    ToggleCustomOption((u64, IString, bool)),
     SearchUser(AttrValue),
     ToggleSearchDropdownDelayed(bool),
     ToggleSearchDropdown(bool),
     ToggleField(TicketField),
     Submit(),
     UpdateErrors(ErrorResponse),
+    ReloadLatest(),
+    KeepChanges(),
+    ReportDuplicate(u64),
+    ReportedDuplicate(TicketDto),
     Cancel(),
 }
 
@@ -73,6 +91,8 @@ pub struct TicketForm {
     _listener: ContextHandle<AppStateContext>,
     dialog_context: Option<Rc<DialogContext>>,
     ticket: TicketDto,
+    /// Version saved by somebody else while this form was open.
+    latest: Option<TicketDto>,
     project: Option<ButtonLinkData<Route>>,
     user: Option<ButtonLinkData<Route>>,
     project_id: Option<u64>,
@@ -81,6 +101,13 @@ pub struct TicketForm {
     search_timeout: Option<Timeout>,
     dropdown_enabled: bool,
     user_list: IArray<(IString, IString)>,
+    /// Custom fields of the project of the ticket.
+    custom_fields: Vec<CustomFieldDto>,
+    /// Sprints of the project of the ticket.
+    sprints: Vec<SprintDto>,
+    /// Open tickets resembling a new ticket, which might report the same.
+    similar: Vec<SimilarTicketDto>,
+    similar_timeout: Option<Timeout>,
     on_submit: Callback<(TicketDto, Callback<ErrorResponse>)>,
     common_error: IValidationMessages,
     title_error: IValidationMessages,
@@ -89,6 +116,7 @@ pub struct TicketForm {
     owner_error: IValidationMessages,
     priority_error: IValidationMessages,
     status_error: IValidationMessages,
+    fields_error: IValidationMessages,
     field_visibility_flags: Vec<bool>,
 }
 impl Component for TicketForm {
@@ -119,6 +147,7 @@ impl Component for TicketForm {
                 status,
                 ..Default::default()
             },
+            latest: None,
             project: None,
             user: None,
             project_id: ctx.props().projectid,
@@ -127,6 +156,10 @@ impl Component for TicketForm {
             search_timeout: None,
             dropdown_enabled: false,
             user_list: IArray::from(vec![]),
+            custom_fields: Vec::new(),
+            sprints: Vec::new(),
+            similar: Vec::new(),
+            similar_timeout: None,
             on_submit: ctx.props().onsubmit.to_owned(),
             common_error: None,
             title_error: None,
@@ -135,6 +168,7 @@ impl Component for TicketForm {
             owner_error: None,
             priority_error: None,
             status_error: None,
+            fields_error: None,
             field_visibility_flags: vec![false; TicketField::COUNT],
         }
     }
@@ -156,6 +190,16 @@ impl Component for TicketForm {
                             project_id,
                             ctx.link().callback(TicketMsg::FetchedProject),
                         );
+                        CustomFieldService::fetch_all(
+                            token.clone(),
+                            project_id,
+                            ctx.link().callback(TicketMsg::FetchedCustomFields),
+                        );
+                        SprintService::fetch_all(
+                            token.clone(),
+                            project_id,
+                            ctx.link().callback(TicketMsg::FetchedSprints),
+                        );
                     }
                     if let Some(user_id) = &self.ticket.user_id {
                         UserService::fetch(
@@ -195,14 +239,65 @@ impl Component for TicketForm {
                 }
                 self.user_list = IArray::from(v);
             }
+            TicketMsg::FetchedCustomFields(fields) => {
+                // Answers for a project typed before the current one are dropped
+                if fields
+                    .first()
+                    .is_none_or(|f| Some(f.project_id) == self.ticket.project_id)
+                {
+                    self.custom_fields = fields;
+                }
+            }
+            TicketMsg::FetchedSprints(sprints) => {
+                if sprints
+                    .first()
+                    .is_none_or(|s| Some(s.project_id) == self.ticket.project_id)
+                {
+                    self.sprints = sprints;
+                }
+            }
+            TicketMsg::FetchedSimilar(similar) => {
+                self.similar = similar;
+            }
             TicketMsg::UpdateTitle(title) => {
                 self.ticket.title = String::from(title.as_str());
+                self.find_similar(ctx);
             }
             TicketMsg::UpdateDescription(description) => {
                 self.ticket.description = String::from(description.as_str());
+                self.find_similar(ctx);
             }
             TicketMsg::UpdateProjectId(value) => {
-                self.ticket.project_id = value.as_str().parse::<u64>().ok();
+                let project_id = value.as_str().parse::<u64>().ok();
+                if project_id != self.ticket.project_id {
+                    // Sprints belong to a single project
+                    self.custom_fields = Vec::new();
+                    self.sprints = Vec::new();
+                    self.ticket.sprint_id = None;
+                    if let (Some(Identity { token, .. }), Some(project_id)) =
+                        (&self.app_state.identity, project_id)
+                    {
+                        CustomFieldService::fetch_all(
+                            token.clone(),
+                            project_id,
+                            ctx.link().callback(TicketMsg::FetchedCustomFields),
+                        );
+                        SprintService::fetch_all(
+                            token.clone(),
+                            project_id,
+                            ctx.link().callback(TicketMsg::FetchedSprints),
+                        );
+                    }
+                }
+                self.ticket.project_id = project_id;
+                self.find_similar(ctx);
+            }
+            TicketMsg::UpdateSprint(value) => {
+                self.ticket.sprint_id = self
+                    .sprints
+                    .iter()
+                    .find(|s| s.name == value.as_str())
+                    .and_then(|s| s.id);
             }
             TicketMsg::UpdatePriority(value) => {
                 if let Ok(priority) = TicketPriority::try_from(value.as_str()) {
@@ -223,6 +318,22 @@ impl Component for TicketForm {
                 self.ticket.user_id = Some(id);
                 self.user_search = name;
             }
+            TicketMsg::UpdateCustomField((field_id, value)) => {
+                match value.trim().is_empty() {
+                    true => self.ticket.fields.remove(&field_id),
+                    false => self.ticket.fields.insert(field_id, vec![value.to_string()]),
+                };
+            }
+            TicketMsg::ToggleCustomOption((field_id, option, checked)) => {
+                let values = self.ticket.fields.entry(field_id).or_default();
+                values.retain(|v| v.as_str() != option.as_str());
+                if checked {
+                    values.push(option.to_string());
+                }
+                if values.is_empty() {
+                    self.ticket.fields.remove(&field_id);
+                }
+            }
             TicketMsg::SearchUser(value) => {
                 self.user_search = value;
                 // We need to throttle the API call to prevent superfluous calls
@@ -271,12 +382,57 @@ impl Component for TicketForm {
                     Err(e) => self.update_errors(ErrorsWrapper(e)),
                 }
             }
+            TicketMsg::FetchedLatest(ticket) => {
+                self.latest = Some(ticket);
+            }
             TicketMsg::UpdateErrors(error_response) => {
                 log::debug!("Error response: {}", error_response);
-                if let Some(errors) = error_response.details {
+                if error_response.is_version_conflict() {
+                    if let (Some(Identity { token, .. }), Some(ticket_id)) =
+                        (&self.app_state.identity, self.ticket.id)
+                    {
+                        TicketService::fetch(
+                            token.clone(),
+                            ticket_id,
+                            ctx.link().callback(TicketMsg::FetchedLatest),
+                        );
+                    }
+                } else if let Some(errors) = error_response.details {
                     self.update_errors(errors);
                 }
             }
+            TicketMsg::ReloadLatest() => {
+                if let Some(latest) = self.latest.take() {
+                    ctx.link().send_message(TicketMsg::FetchedTicket(latest));
+                }
+            }
+            TicketMsg::KeepChanges() => {
+                // Saving on top of the latest version overwrites the fields changed there
+                if let Some(latest) = self.latest.take() {
+                    self.ticket.version = latest.version;
+                    ctx.link().send_message(TicketMsg::Submit());
+                }
+            }
+            TicketMsg::ReportDuplicate(id) => {
+                if let Some(Identity { token, .. }) = &self.app_state.identity {
+                    TicketService::report_duplicate(
+                        token.clone(),
+                        id,
+                        self.draft(),
+                        ctx.link().callback(TicketMsg::ReportedDuplicate),
+                        ctx.link().callback(TicketMsg::UpdateErrors),
+                    );
+                }
+            }
+            TicketMsg::ReportedDuplicate(ticket) => {
+                if let Some(context) = self.dialog_context.clone() {
+                    context.closehandler.emit(());
+                }
+                if let Some(id) = ticket.id {
+                    let navigator = ctx.link().navigator().unwrap();
+                    navigator.replace(&Route::Ticket { id });
+                }
+            }
             TicketMsg::Cancel() => match self.dialog_context.clone() {
                 Some(context) => {
                     context.closehandler.emit(());
@@ -340,6 +496,17 @@ impl TicketForm {
                     ticket_id,
                     ctx.link().callback(TicketMsg::FetchedTicket),
                 );
+            } else if let Some(project_id) = ctx.props().projectid {
+                CustomFieldService::fetch_all(
+                    token.to_string(),
+                    project_id,
+                    ctx.link().callback(TicketMsg::FetchedCustomFields),
+                );
+                SprintService::fetch_all(
+                    token.to_string(),
+                    project_id,
+                    ctx.link().callback(TicketMsg::FetchedSprints),
+                );
             }
         }
     }
@@ -361,6 +528,7 @@ impl TicketForm {
 
         html! {
             <div class="content">
+                { self.conflict_prompt(ctx) }
                 if let Some(common_error) = &self.common_error {
                     <div class="columns">
                         <div class="column">
@@ -457,6 +625,35 @@ impl TicketForm {
                         </Field>
                     </div>
                 </div>
+                if !self.sprints.is_empty() {
+                    <div class="columns">
+                        <div class="column is-one-quarter"><h6 class="title is-6">{ "Sprint" }</h6></div>
+                        <div class="column">
+                            { self.sprint_input(ctx) }
+                        </div>
+                    </div>
+                }
+                {
+                    self.custom_fields.iter().map(|field| html! {
+                        <div class="columns">
+                            <div class="column is-one-quarter"><h6 class="title is-6">{ field.name.clone() }</h6></div>
+                            <div class="column">
+                                { self.custom_field_input(ctx, field) }
+                            </div>
+                        </div>
+                    }).collect::<Html>()
+                }
+                if let Some(fields_error) = &self.fields_error {
+                    <p class="help is-danger">
+                        <ul>
+                        {
+                            fields_error.iter().map(|message| {
+                                html!{<li>{ html! {message}}</li>}
+                            }).collect::<Html>()
+                        }
+                        </ul>
+                    </p>
+                }
             </div>
         }
     }
@@ -477,6 +674,7 @@ impl TicketForm {
 
         html! {
             <>
+                { self.conflict_prompt(ctx) }
                 if let Some(common_error) = &self.common_error {
                     <p class="help is-danger">
                         <ul>
@@ -494,6 +692,7 @@ impl TicketForm {
                 <Field label="Description" help={&self.description_error}>
                     <TextInput value={self.ticket.description.clone()} on_change={ctx.link().callback(TicketMsg::UpdateDescription)} valid={self.description_error.is_empty()} />
                 </Field>
+                { self.similar_tickets(ctx) }
                 <Field label="Project" help={&self.project_error}>
                     <TextInput value={self.get_project_id()} on_change={ctx.link().callback(TicketMsg::UpdateProjectId)} valid={self.project_error.is_empty()} />
                 </Field>
@@ -517,10 +716,144 @@ impl TicketForm {
                         </div>
                     </div>
                 </Field>
+                if !self.sprints.is_empty() {
+                    <Field label="Sprint" class={classes!("is-one-third")}>
+                        { self.sprint_input(ctx) }
+                    </Field>
+                }
+                {
+                    self.custom_fields.iter().map(|field| html! {
+                        <Field label={AttrValue::from(field.name.clone())}>
+                            { self.custom_field_input(ctx, field) }
+                        </Field>
+                    }).collect::<Html>()
+                }
+                if let Some(fields_error) = &self.fields_error {
+                    <p class="help is-danger">
+                        <ul>
+                        {
+                            fields_error.iter().map(|message| {
+                                html!{<li>{ html! {message}}</li>}
+                            }).collect::<Html>()
+                        }
+                        </ul>
+                    </p>
+                }
             </>
         }
     }
 
+    /// Completed sprints are only listed if the ticket was finished in them, tickets without a
+    /// sprint are in the backlog.
+    fn sprint_input(&self, ctx: &Context<Self>) -> Html {
+        let value = IString::from(
+            self.sprints
+                .iter()
+                .find(|s| s.id.is_some() && s.id == self.ticket.sprint_id)
+                .map_or(String::new(), |s| s.name.clone()),
+        );
+        let options = self
+            .sprints
+            .iter()
+            .filter(|s| s.state != SprintState::Completed || s.id == self.ticket.sprint_id)
+            .map(|s| IString::from(s.name.clone()))
+            .collect::<IArray<IString>>();
+        html! {
+            <Select {value} {options} on_change={ctx.link().callback(TicketMsg::UpdateSprint)} placeholder={AttrValue::from("Backlog")} />
+        }
+    }
+
+    /// Input matching the type of the custom field. Users are entered by their public id.
+    fn custom_field_input(&self, ctx: &Context<Self>, field: &CustomFieldDto) -> Html {
+        let field_id = field.id.unwrap_or_default();
+        let values = self
+            .ticket
+            .fields
+            .get(&field_id)
+            .cloned()
+            .unwrap_or_default();
+        let value = IString::from(values.first().cloned().unwrap_or_default());
+        let on_change = ctx
+            .link()
+            .callback(move |value: AttrValue| TicketMsg::UpdateCustomField((field_id, value)));
+        let valid = self.fields_error.is_empty();
+        match field.field_type {
+            CustomFieldType::Date => html! {
+                <DateInput {value} {on_change} {valid} />
+            },
+            CustomFieldType::Select => html! {
+                <Select {value} options={field.options.iter().map(|o| IString::from(o.clone())).collect::<IArray<IString>>()} {on_change} placeholder={AttrValue::from("None")} {valid} />
+            },
+            CustomFieldType::MultiSelect => html! {
+                <div class="checkboxes">
+                {
+                    field.options.iter().map(|option| {
+                        let option = IString::from(option.clone());
+                        let checked = values.iter().any(|v| v.as_str() == option.as_str());
+                        let label = option.clone();
+                        let on_toggle = ctx.link().callback(move |checked: bool| {
+                            TicketMsg::ToggleCustomOption((field_id, option.clone(), checked))
+                        });
+                        html! {
+                            <label class="checkbox">
+                                <Checkbox {checked} on_change={on_toggle} {valid} />
+                                { " " }{ label }
+                            </label>
+                        }
+                    }).collect::<Html>()
+                }
+                </div>
+            },
+            _ => html! {
+                <TextInput {value} {on_change} {valid} />
+            },
+        }
+    }
+
+    /// Suggests reporting a new ticket on an open one resembling it instead.
+    fn similar_tickets(&self, ctx: &Context<Self>) -> Html {
+        if self.ticket.id.is_some() || self.similar.is_empty() {
+            return html! {};
+        }
+        html! {
+            <div class="notification is-info is-light">
+                <p>{ "Similar open tickets exist already. If one of them reports the same, add yours to it instead of creating a new ticket." }</p>
+                <ul>
+                {
+                    self.similar.iter().map(|similar| {
+                        let id = similar.id;
+                        html! {
+                            <li>
+                                <Link<Route> to={Route::Ticket { id }}>
+                                    { similar.key.clone().unwrap_or_else(|| format!("#{}", id)) }{ " " }{ similar.title.clone() }
+                                </Link<Route>>
+                                { " " }<span class="tag is-light">{ similar.status.to_string() }</span>
+                                <button class="button is-small is-link is-light ml-2" onclick={ctx.link().callback(move |_| TicketMsg::ReportDuplicate(id))}>{ "Link as duplicate" }</button>
+                            </li>
+                        }
+                    }).collect::<Html>()
+                }
+                </ul>
+            </div>
+        }
+    }
+
+    fn conflict_prompt(&self, ctx: &Context<Self>) -> Html {
+        match &self.latest {
+            Some(latest) => html! {
+                <div class="notification is-warning is-light">
+                    <p>{ "This ticket has been modified in the meantime. Reload it to see the changes, or save your version over them." }</p>
+                    <p><strong>{ latest.title.clone() }</strong>{ " " }<span class="tag is-light">{ latest.status.to_string() }</span></p>
+                    <div class="buttons mt-3">
+                        <button class="button is-small is-warning" onclick={ctx.link().callback(|_| TicketMsg::ReloadLatest())}>{ "Reload" }</button>
+                        <button class="button is-small is-light" onclick={ctx.link().callback(|_| TicketMsg::KeepChanges())}>{ "Keep my changes" }</button>
+                    </div>
+                </div>
+            },
+            None => html! {},
+        }
+    }
+
     fn submit_button(&self, ctx: &Context<Self>) -> Html {
         let on_submit_pressed = |_: MouseEvent| TicketMsg::Submit();
         html! {
@@ -535,6 +868,36 @@ impl TicketForm {
         }
     }
 
+    /// Looks for similar tickets once the user stops typing. Only new tickets are compared.
+    fn find_similar(&mut self, ctx: &Context<Self>) {
+        if let Some(timeout) = self.similar_timeout.take() {
+            timeout.cancel();
+        }
+        if self.ticket.id.is_some() || self.ticket.title.trim().chars().count() < 3 {
+            self.similar = Vec::new();
+            return;
+        }
+        let draft = self.draft();
+        let fetch_callback = ctx.link().callback(TicketMsg::FetchedSimilar);
+        self.similar_timeout = self
+            .app_state
+            .identity
+            .clone()
+            .map(|Identity { token, .. }| {
+                Timeout::new(SEARCH_DELAY_MS, || {
+                    TicketService::fetch_similar(token, draft, fetch_callback)
+                })
+            })
+    }
+
+    fn draft(&self) -> TicketDraftDto {
+        TicketDraftDto {
+            project_id: self.ticket.project_id,
+            title: self.ticket.title.clone(),
+            description: self.ticket.description.clone(),
+        }
+    }
+
     fn get_project_id(&self) -> IString {
         self.ticket
             .project_id
@@ -580,6 +943,7 @@ impl TicketForm {
         self.priority_error = errors.get_property_messages("priority");
         self.status_error = errors.get_property_messages("status");
         self.owner_error = errors.get_property_messages("user_id");
+        self.fields_error = errors.get_property_messages("fields");
     }
 
     fn span_class(&self, field: TicketField) -> &'static str {
@@ -1,6 +1,8 @@
 pub mod app_modal;
+pub mod bulk_action_bar;
 pub mod bulma;
 pub mod button_link;
+pub mod charts;
 pub mod check_tag;
 pub mod consts;
 pub mod dialogs;
@@ -11,6 +13,8 @@ pub mod navbar;
 pub mod option_data;
 pub mod priority_tag;
 pub mod project_card;
+pub mod sla_badge;
 pub mod theme_icon;
 pub mod theme_switcher;
 pub mod user_card;
+pub mod view_picker;
@@ -89,9 +89,9 @@ impl Component for Navbar {
                             if self.app_state.identity.is_some() {
                                 html! {
                                     <>
-                                        /*<Link<Route> classes={classes!("navbar-item")} to={Route::Tickets}>
+                                        <Link<Route> classes={classes!("navbar-item")} to={Route::Tickets}>
                                             { "Tickets" }
-                                        </Link<Route>>*/
+                                        </Link<Route>>
 
                                         <div class="navbar-item has-dropdown is-hoverable">
                                             <div class="navbar-link">
@@ -107,6 +107,9 @@ impl Component for Navbar {
                                                     <Link<Route> classes={classes!("navbar-item")} to={Route::ProjectNew}>
                                                     { "Create new project" }
                                                 </Link<Route>>
+                                                <Link<Route> classes={classes!("navbar-item")} to={Route::Trash}>
+                                                    { "Trash" }
+                                                </Link<Route>>
                                             </div>
                                         </div>
 
@@ -0,0 +1,37 @@
+use shared::dtos::sla_dto::{SlaClockDto, SlaState};
+use yew::{classes, function_component, html, Html, Properties};
+
+#[derive(Properties, PartialEq)]
+pub struct Props {
+    pub clock: SlaClockDto,
+}
+
+#[function_component(SlaBadge)]
+pub fn sla_badge(props: &Props) -> Html {
+    let clock = &props.clock;
+    let left = format!("{} left", duration_text(clock.remaining_minutes()));
+    let (state_class, text) = match clock.state {
+        SlaState::Running => ("is-success", left),
+        SlaState::AtRisk => ("is-warning", left),
+        SlaState::Breached => ("is-danger", String::from("breached")),
+        SlaState::Met => ("is-info", String::from("met")),
+    };
+    let title = format!(
+        "{} of {} used",
+        duration_text(clock.elapsed_minutes as i64),
+        duration_text(clock.target_minutes as i64)
+    );
+    html! {
+        <span class={classes!("tag", "is-light", state_class)} {title}>
+            <span class="icon is-small"><i class="fas fa-stopwatch"></i></span>
+            <span>{ format!("{} {}", clock.clock, text) }</span>
+        </span>
+    }
+}
+
+fn duration_text(minutes: i64) -> String {
+    match minutes {
+        m if m >= 60 => format!("{}h {:02}m", m / 60, m % 60),
+        m => format!("{}m", m.max(0)),
+    }
+}
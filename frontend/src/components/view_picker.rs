@@ -0,0 +1,186 @@
+use crate::{
+    components::html::text_input::TextInput, helpers::event_helper::get_value_from_input_event,
+};
+use implicit_clone::unsync::IArray;
+use shared::dtos::{saved_view_dto::SavedViewDto, ticket_dto::TicketField};
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub views: IArray<SavedViewDto>,
+    pub selected: Option<u64>,
+    pub default_view: Option<u64>,
+    pub columns: IArray<TicketField>,
+    pub on_select: Callback<Option<u64>>,
+    /// Saves the current state as a new view with the given name, optionally shared with a project.
+    pub on_save: Callback<(AttrValue, Option<u64>)>,
+    /// Overwrites the selected view with the current state.
+    pub on_update: Callback<u64>,
+    pub on_delete: Callback<u64>,
+    pub on_set_default: Callback<Option<u64>>,
+    pub on_toggle_column: Callback<TicketField>,
+}
+
+pub enum Msg {
+    Select(AttrValue),
+    UpdateName(AttrValue),
+    UpdateProject(AttrValue),
+    Save,
+}
+
+pub struct ViewPicker {
+    name: AttrValue,
+    project: AttrValue,
+}
+
+impl Component for ViewPicker {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self {
+            name: AttrValue::default(),
+            project: AttrValue::default(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Select(value) => {
+                ctx.props().on_select.emit(value.parse::<u64>().ok());
+                false
+            }
+            Msg::UpdateName(name) => {
+                self.name = name;
+                true
+            }
+            Msg::UpdateProject(project) => {
+                self.project = project;
+                false
+            }
+            Msg::Save => {
+                let name = AttrValue::from(self.name.trim().to_owned());
+                if !name.is_empty() {
+                    ctx.props()
+                        .on_save
+                        .emit((name, self.project.trim().parse::<u64>().ok()));
+                    self.name = AttrValue::default();
+                    self.project = AttrValue::default();
+                }
+                true
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let Props {
+            views,
+            selected,
+            default_view,
+            columns,
+            ..
+        } = ctx.props();
+        let selected_view = views.iter().find(|v| v.id == *selected);
+        let onchange = ctx
+            .link()
+            .callback(|e: Event| Msg::Select(get_value_from_input_event(e)));
+        let onsubmit = ctx.link().callback(|e: SubmitEvent| {
+            e.prevent_default();
+            Msg::Save
+        });
+
+        html! {
+            <div class="box">
+                <div class="field is-grouped is-grouped-multiline">
+                    <div class="control">
+                        <div class="select">
+                            <select {onchange}>
+                                <option value="" selected={selected.is_none()}>{ "All tickets" }</option>
+                                {
+                                    views.iter().map(|view| {
+                                        let label = format!(
+                                            "{}{}{}",
+                                            view.name,
+                                            if view.project_id.is_some() { " (shared)" } else { "" },
+                                            if view.id == *default_view { " ★" } else { "" },
+                                        );
+                                        html! {
+                                            <option value={view.id.unwrap_or_default().to_string()} selected={view.id == *selected}>
+                                                { label }
+                                            </option>
+                                        }
+                                    }).collect::<Html>()
+                                }
+                            </select>
+                        </div>
+                    </div>
+                    if let Some(view) = selected_view {
+                        { self.view_actions(ctx, &view) }
+                    }
+                </div>
+                <div class="field is-grouped is-grouped-multiline">
+                    {
+                        TicketField::iter().map(|field| {
+                            let visible = columns.iter().any(|c| c == field);
+                            let onclick = ctx.props().on_toggle_column.reform(move |_: MouseEvent| field);
+                            html! {
+                                <p class="control">
+                                    <button class={classes!("button", "is-small", visible.then_some("is-info"))} {onclick}>
+                                        { field.to_string() }
+                                    </button>
+                                </p>
+                            }
+                        }).collect::<Html>()
+                    }
+                </div>
+                <form {onsubmit}>
+                    <div class="field has-addons">
+                        <div class="control">
+                            <TextInput value={self.name.clone()} on_change={ctx.link().callback(Msg::UpdateName)} placeholder="Name of the view" />
+                        </div>
+                        <div class="control">
+                            <TextInput value={self.project.clone()} on_change={ctx.link().callback(Msg::UpdateProject)} placeholder="Share with project id (optional)" />
+                        </div>
+                        <div class="control">
+                            <button type="submit" class="button" disabled={self.name.trim().is_empty()}>
+                                <span class="icon is-small"><i class="fas fa-floppy-disk"></i></span>
+                                <span>{ "Save as new view" }</span>
+                            </button>
+                        </div>
+                    </div>
+                </form>
+            </div>
+        }
+    }
+}
+
+impl ViewPicker {
+    fn view_actions(&self, ctx: &Context<Self>, view: &SavedViewDto) -> Html {
+        let id = view.id.unwrap_or_default();
+        let is_default = ctx.props().default_view == view.id;
+        let on_default = ctx
+            .props()
+            .on_set_default
+            .reform(move |_: MouseEvent| (!is_default).then_some(id));
+        let on_update = ctx.props().on_update.reform(move |_: MouseEvent| id);
+        let on_delete = ctx.props().on_delete.reform(move |_: MouseEvent| id);
+        html! {
+            <>
+                <p class="control">
+                    <button class="button" onclick={on_default}>
+                        { if is_default { "Unset default" } else { "Set as default" } }
+                    </button>
+                </p>
+                if view.owned {
+                    <p class="control">
+                        <button class="button" onclick={on_update}>{ "Update view" }</button>
+                    </p>
+                    <p class="control">
+                        <button class="button is-danger is-outlined" onclick={on_delete}>{ "Delete" }</button>
+                    </p>
+                }
+            </>
+        }
+    }
+}
@@ -6,7 +6,8 @@ use frontend::pages::{
     home_page::HomePage, login_page::LoginPage, page_not_found::PageNotFound,
     project_board_page::ProjectBoardPage, project_list_page::ProjectListPage,
     project_new_page::ProjectNewPage, project_page::ProjectPage,
-    registration_page::RegistrationPage, ticket_new_page::TicketNewPage, ticket_page::TicketPage,
+    registration_page::RegistrationPage, ticket_list_page::TicketListPage,
+    ticket_new_page::TicketNewPage, ticket_page::TicketPage, trash_page::TrashPage,
     user_list_page::UserListPage, user_page::UserPage,
 };
 use frontend::route::Route;
@@ -81,12 +82,18 @@ fn switch(routes: Route) -> Html {
         Route::Users => {
             html! { <UserListPage /> }
         }
+        Route::Tickets => {
+            html! { <TicketListPage /> }
+        }
         Route::TicketNew => {
             html! { <TicketNewPage /> }
         }
         Route::Ticket { id } => {
             html! { <TicketPage id={id} /> }
         }
+        Route::Trash => {
+            html! { <TrashPage /> }
+        }
         Route::Login => {
             html! { <LoginPage />}
         }
@@ -175,7 +175,7 @@ impl HomePage {
     }
 
     fn view_info_tiles(&self) -> Html {
-        let projects = self.list.iter().map(|ProjectDto { id, summary, deadline: _, user_id: _, active: _ }| {
+        let projects = self.list.iter().map(|ProjectDto { id, summary, deadline: _, user_id: _, active: _, key: _ }| {
             match id {
                 Some(id) => html! {
                     <tr>
@@ -6,7 +6,9 @@ pub mod project_list_page;
 pub mod project_new_page;
 pub mod project_page;
 pub mod registration_page;
+pub mod ticket_list_page;
 pub mod ticket_new_page;
 pub mod ticket_page;
+pub mod trash_page;
 pub mod user_list_page;
 pub mod user_page;
@@ -3,21 +3,36 @@ use crate::components::button_link::{ButtonLink, ButtonLinkData};
 use crate::components::check_tag::CheckTag;
 use crate::components::dialogs::form_dialog::FormDialog;
 use crate::components::dialogs::select_dialog::SelectDialog;
+use crate::components::forms::board_form::BoardForm;
 use crate::components::forms::ticket_form::TicketForm;
-use crate::helpers::event_helper::{get_transfer_data, set_transfer_data};
+use crate::components::sla_badge::SlaBadge;
+use crate::helpers::event_helper::{
+    get_transfer_data, get_value_from_input_event, set_transfer_data,
+};
+use crate::services::board_service::BoardService;
 use crate::services::project_service::ProjectService;
+use crate::services::sla_service::SlaService;
+use crate::services::sprint_service::SprintService;
 use crate::services::ticket_service::TicketService;
 use crate::services::user_service::UserService;
 use crate::{app_state::AppState, dialog::Dialog, route::Route};
+use entity::sea_orm_active_enums::Priority;
 use implicit_clone::sync::{IArray, IString};
+use serde_json::{json, Map, Value};
 use shared::api::error::error_response::ErrorResponse;
+use shared::dtos::board_dto::{BoardColumnDto, BoardDto};
 use shared::dtos::identity::Identity;
 use shared::dtos::project_dto::ProjectDto;
-use shared::dtos::ticket_dto::TicketDto;
+use shared::dtos::sla_dto::TicketSlaDto;
+use shared::dtos::sprint_dto::{SprintDto, SprintState};
+use shared::dtos::ticket_dto::{MoveTicketDto, TicketDto};
 use shared::dtos::user_dto::UserDto;
-use shared::validation::ticket_validation::TicketStatus;
+use shared::validation::ticket_validation::{TicketPriority, TicketStatus};
+use std::collections::BTreeSet;
 use std::rc::Rc;
-use strum::IntoEnumIterator;
+use std::str::FromStr;
+use strum::{Display, EnumIter, EnumString, IntoEnumIterator};
+use uuid::Uuid;
 use web_sys::DragEvent;
 use yew::prelude::*;
 use yew_router::prelude::Link;
@@ -27,27 +42,119 @@ pub struct Props {
     pub id: u64,
 }
 
+/// Field grouping the tickets of the board into horizontal lanes.
+#[derive(Clone, Copy, Default, Display, EnumIter, EnumString, PartialEq)]
+enum Swimlanes {
+    #[default]
+    #[strum(serialize = "No swimlanes")]
+    None,
+    Assignee,
+    Priority,
+    Label,
+    #[strum(serialize = "Parent ticket")]
+    Parent,
+}
+
+/// Value of the grouping field shared by the tickets of a lane. Tickets are grouped by their
+/// first label.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Lane {
+    All,
+    Assignee(Option<Uuid>),
+    Priority(Priority),
+    Label(Option<String>),
+    Parent(Option<u64>),
+}
+
+impl Lane {
+    fn contains(&self, ticket: &TicketDto) -> bool {
+        match self {
+            Lane::All => true,
+            Lane::Assignee(user_id) => ticket.user_id == *user_id,
+            Lane::Priority(priority) => ticket.priority.0 == *priority,
+            Lane::Label(label) => ticket.labels.first() == label.as_ref(),
+            Lane::Parent(parent_id) => ticket.parent_id == *parent_id,
+        }
+    }
+
+    /// Field of the patch moving the ticket into the lane, if it is not there yet. Moving
+    /// between label lanes swaps the label of the previous lane for the one of the next.
+    fn change(&self, ticket: &TicketDto) -> Option<(&'static str, Value)> {
+        if self.contains(ticket) {
+            return None;
+        }
+        match self {
+            Lane::All => None,
+            Lane::Assignee(user_id) => Some(("user_id", json!(user_id.map(|u| u.to_string())))),
+            Lane::Priority(priority) => Some(("priority", json!(TicketPriority(priority.clone())))),
+            Lane::Label(label) => {
+                let mut labels = ticket
+                    .labels
+                    .iter()
+                    .skip(1)
+                    .cloned()
+                    .collect::<Vec<String>>();
+                match label {
+                    Some(label) => labels.push(label.clone()),
+                    None => labels.clear(),
+                }
+                Some(("labels", json!(labels)))
+            }
+            Lane::Parent(parent_id) => Some(("parent_id", json!(parent_id))),
+        }
+    }
+}
+
 pub enum ProjectBoardPageMsg {
     ContextChanged(AppStateContext),
     FetchedProject(ProjectDto),
     FetchedUser(UserDto),
     FetchedTickets(Vec<TicketDto>),
+    FetchedSlas(Vec<TicketSlaDto>),
+    FetchedSprints(Vec<SprintDto>),
+    FetchedBoard(BoardDto),
+    ToggleActiveSprintOnly,
+    SelectSwimlanes(AttrValue),
+    FetchedUsers(Vec<UserDto>),
+    ToggleLane(Lane),
+    StartSprint(SprintDto),
+    CompleteSprint(SprintDto),
+    SprintChanged(SprintDto),
     FetchUnassigned(Callback<Vec<TicketDto>>),
     OpenSelectDialog(),
     OpenFormDialog(),
     OpenTicketDialog(u64),
+    OpenBoardDialog(),
+    SubmittedBoard((BoardDto, Callback<ErrorResponse>)),
+    BoardSaved(BoardDto),
+    MoveRejected(ErrorResponse),
+    DismissNotice,
     SelectedTickets(IArray<u64>),
     SubmittedForm((TicketDto, Callback<ErrorResponse>)),
     TicketCreated(TicketDto),
     TicketUpdated(TicketDto),
     DragStart(DragEvent, u64),
-    Drop(DragEvent, TicketStatus),
+    /// Dropped on a column of a lane, before the given ticket or at the end of the column.
+    Drop(DragEvent, usize, Lane, Option<u64>),
+    Reposition(u64, MoveTicketDto),
 }
 
 pub struct ProjectBoardPage {
     project: ProjectDto,
     user: Option<ButtonLinkData<Route>>,
     ticket_list: Vec<TicketDto>,
+    sprints: Vec<SprintDto>,
+    /// Shows only the tickets of the active sprint, if there is one.
+    active_sprint_only: bool,
+    board: BoardDto,
+    /// Warning about a work-in-progress limit, shown above the columns.
+    notice: Option<String>,
+    swimlanes: Swimlanes,
+    collapsed: Vec<Lane>,
+    /// Names of the assignee lanes, fetched once grouping by assignee.
+    users: Vec<UserDto>,
+    /// SLA clocks of the unfinished tickets, refreshed with the tickets.
+    slas: Vec<TicketSlaDto>,
     app_state: AppStateContext,
     _listener: ContextHandle<AppStateContext>,
 }
@@ -67,6 +174,14 @@ impl Component for ProjectBoardPage {
             project: ProjectDto::default(),
             user: None,
             ticket_list: vec![],
+            sprints: vec![],
+            active_sprint_only: true,
+            board: BoardDto::standard(),
+            notice: None,
+            swimlanes: Swimlanes::default(),
+            collapsed: vec![],
+            users: vec![],
+            slas: vec![],
             app_state,
             _listener,
         }
@@ -105,6 +220,110 @@ impl Component for ProjectBoardPage {
             }
             ProjectBoardPageMsg::FetchedTickets(tickets) => {
                 self.ticket_list = tickets;
+                if let Some(Identity { token, .. }) = &self.app_state.identity {
+                    SlaService::fetch_project(
+                        token.to_string(),
+                        ctx.props().id,
+                        ctx.link().callback(ProjectBoardPageMsg::FetchedSlas),
+                    );
+                }
+            }
+            ProjectBoardPageMsg::FetchedSlas(slas) => {
+                self.slas = slas;
+            }
+            ProjectBoardPageMsg::FetchedSprints(sprints) => {
+                self.sprints = sprints;
+            }
+            ProjectBoardPageMsg::FetchedBoard(board) => {
+                self.board = board;
+            }
+            ProjectBoardPageMsg::OpenBoardDialog() => {
+                let dialog = Rc::new(Dialog {
+                    active: true,
+                    content: html! {
+                        <FormDialog title="Board columns">
+                            <BoardForm projectid={ctx.props().id} onsubmit={ctx.link().callback(ProjectBoardPageMsg::SubmittedBoard)} />
+                        </FormDialog>
+                    },
+                });
+                AppState::update_dialog(&self.app_state, dialog);
+            }
+            ProjectBoardPageMsg::SubmittedBoard((board, callback_error)) => {
+                if let Some(Identity { token, .. }) = &self.app_state.identity {
+                    BoardService::update(
+                        token.to_string(),
+                        ctx.props().id,
+                        board,
+                        ctx.link().callback(ProjectBoardPageMsg::BoardSaved),
+                        callback_error,
+                    );
+                }
+            }
+            ProjectBoardPageMsg::BoardSaved(board) => {
+                AppState::close_dialog(&self.app_state);
+                self.board = board;
+            }
+            ProjectBoardPageMsg::MoveRejected(error) => {
+                self.notice = Some(error.message);
+            }
+            ProjectBoardPageMsg::DismissNotice => {
+                self.notice = None;
+            }
+            ProjectBoardPageMsg::ToggleActiveSprintOnly => {
+                self.active_sprint_only = !self.active_sprint_only;
+            }
+            ProjectBoardPageMsg::SelectSwimlanes(value) => {
+                self.swimlanes = Swimlanes::from_str(value.as_str()).unwrap_or_default();
+                self.collapsed = vec![];
+                if self.swimlanes == Swimlanes::Assignee && self.users.is_empty() {
+                    if let Some(Identity { token, .. }) = &self.app_state.identity {
+                        UserService::fetch_all(
+                            token.to_string(),
+                            None,
+                            None,
+                            None,
+                            ctx.link().callback(ProjectBoardPageMsg::FetchedUsers),
+                        );
+                    }
+                }
+            }
+            ProjectBoardPageMsg::FetchedUsers(users) => {
+                self.users = users;
+            }
+            ProjectBoardPageMsg::ToggleLane(lane) => {
+                match self.collapsed.iter().position(|l| *l == lane) {
+                    Some(i) => {
+                        self.collapsed.remove(i);
+                    }
+                    None => self.collapsed.push(lane),
+                }
+            }
+            ProjectBoardPageMsg::StartSprint(sprint) => {
+                if let Some(Identity { token, .. }) = &self.app_state.identity {
+                    SprintService::start(
+                        token.to_string(),
+                        &sprint,
+                        ctx.link().callback(ProjectBoardPageMsg::SprintChanged),
+                        Callback::from(|e: ErrorResponse| log::debug!("{}", e.message)),
+                    );
+                }
+            }
+            ProjectBoardPageMsg::CompleteSprint(sprint) => {
+                // Unfinished tickets go to the next planned sprint, or back to the backlog
+                let next_sprint_id = self.next_sprint().and_then(|s| s.id);
+                if let Some(Identity { token, .. }) = &self.app_state.identity {
+                    SprintService::complete(
+                        token.to_string(),
+                        &sprint,
+                        next_sprint_id,
+                        ctx.link().callback(ProjectBoardPageMsg::SprintChanged),
+                        Callback::from(|e: ErrorResponse| log::debug!("{}", e.message)),
+                    );
+                }
+            }
+            ProjectBoardPageMsg::SprintChanged(sprint) => {
+                log::debug!("Sprint changed: {}", sprint);
+                ProjectBoardPage::init(&self.app_state, ctx);
             }
             ProjectBoardPageMsg::ContextChanged(state) => {
                 self.app_state = state;
@@ -203,38 +422,84 @@ impl Component for ProjectBoardPage {
                 log::debug!("Drag started. Id: {}", id);
                 let _ = set_transfer_data(e, format!("{}", id).as_str());
             }
-            ProjectBoardPageMsg::Drop(e, status) => {
+            ProjectBoardPageMsg::Drop(e, index, lane, target) => {
                 e.prevent_default();
-                if let Ok(id_s) = get_transfer_data(e) {
-                    if let Ok(id) = id_s.as_str().parse::<u64>() {
-                        log::debug!("Dropped. Status: id({}) -> {}", id, status);
-                        if let Some(Identity { token, .. }) = &self.app_state.identity {
-                            if let Some(ticket) = self
-                                .ticket_list
-                                .iter()
-                                .filter(|t| t.id == Some(id))
-                                .collect::<Vec<&TicketDto>>()
-                                .first()
-                            {
-                                if ticket.status != status {
-                                    TicketService::update(
-                                        token.to_string(),
-                                        TicketDto {
-                                            id: Some(id),
-                                            title: ticket.title.clone(),
-                                            status,
-                                            description: ticket.description.clone(),
-                                            project_id: ticket.project_id,
-                                            user_id: ticket.user_id,
-                                            priority: ticket.priority.clone(),
-                                        },
-                                        ctx.link().callback(ProjectBoardPageMsg::TicketCreated),
-                                        Callback::noop(),
-                                    );
-                                }
-                            }
+                let Some(column) = self.board.columns.get(index).cloned() else {
+                    return false;
+                };
+                let Some(id) = get_transfer_data(e)
+                    .ok()
+                    .and_then(|id_s| id_s.as_str().parse::<u64>().ok())
+                else {
+                    return false;
+                };
+                if target == Some(id) {
+                    return false;
+                }
+                let Some(ticket) = self.ticket_list.iter().find(|t| t.id == Some(id)).cloned()
+                else {
+                    return false;
+                };
+                let position = self.position(&column, &lane, id, target);
+                let Some(Identity { token, .. }) = &self.app_state.identity else {
+                    return false;
+                };
+                let mut changes = Map::new();
+                if let Some((field, value)) = lane.change(&ticket) {
+                    changes.insert(String::from(field), value);
+                }
+                // Tickets moving within their column keep their status
+                if !column.contains(&ticket.status) {
+                    let Some(status) = column.statuses.first().copied() else {
+                        return false;
+                    };
+                    log::debug!("Dropped. Status: id({}) -> {}", id, status);
+                    if column.is_full(self.count(&column)) {
+                        let limit = column.wip_limit.unwrap_or_default();
+                        if column.wip_enforced {
+                            self.notice = Some(format!(
+                                "The column {} has reached its limit of {} tickets.",
+                                column.name, limit
+                            ));
+                            return true;
                         }
+                        self.notice = Some(format!(
+                            "The column {} is over its limit of {} tickets.",
+                            column.name, limit
+                        ));
                     }
+                    changes.insert(String::from("status"), json!(status));
+                }
+                if changes.is_empty() {
+                    TicketService::move_to(
+                        token.to_string(),
+                        id,
+                        position,
+                        ctx.link().callback(ProjectBoardPageMsg::TicketCreated),
+                        ctx.link().callback(ProjectBoardPageMsg::MoveRejected),
+                    );
+                    return false;
+                }
+                // The position is set once the changes went through
+                TicketService::patch(
+                    token.to_string(),
+                    id,
+                    ticket.version,
+                    Value::Object(changes),
+                    ctx.link()
+                        .callback(move |_| ProjectBoardPageMsg::Reposition(id, position.clone())),
+                    ctx.link().callback(ProjectBoardPageMsg::MoveRejected),
+                );
+            }
+            ProjectBoardPageMsg::Reposition(id, position) => {
+                if let Some(Identity { token, .. }) = &self.app_state.identity {
+                    TicketService::move_to(
+                        token.to_string(),
+                        id,
+                        position,
+                        ctx.link().callback(ProjectBoardPageMsg::TicketCreated),
+                        ctx.link().callback(ProjectBoardPageMsg::MoveRejected),
+                    );
                 }
             }
             ProjectBoardPageMsg::FetchUnassigned(consumer) => {
@@ -251,33 +516,66 @@ impl Component for ProjectBoardPage {
             project,
             user,
             ticket_list,
+            sprints: _,
+            active_sprint_only,
+            board,
+            notice,
+            swimlanes: _,
+            collapsed: _,
+            users: _,
             app_state: _,
             _listener,
         } = self;
 
+        let active_sprint = self.active_sprint();
+        let ticket_list = match active_sprint {
+            Some(SprintDto { id, .. }) if *active_sprint_only => ticket_list
+                .iter()
+                .filter(|t| t.sprint_id == *id)
+                .cloned()
+                .collect::<Vec<TicketDto>>(),
+            _ => ticket_list.clone(),
+        };
+        let ticket_list = &ticket_list;
+
         let on_assign_click = |_| ProjectBoardPageMsg::OpenSelectDialog();
         let on_add_click = |_| ProjectBoardPageMsg::OpenFormDialog();
+        let on_configure_click = |_| ProjectBoardPageMsg::OpenBoardDialog();
+        let on_swimlanes_change = ctx.link().callback(|e: Event| {
+            ProjectBoardPageMsg::SelectSwimlanes(get_value_from_input_event(e))
+        });
 
-        let status_headers = TicketStatus::iter()
-            .filter(Self::is_status_managable)
-            .map(|status| html! {
-                <div class="tile col"><div class="tile notification is-light is-vertical"><p class="tile is-uppercase is-size-7"><b>{ html! {status}}</b></p></div></div>
-            });
-
-        let statuses = TicketStatus::iter()
-            .filter(Self::is_status_managable)
-            .map(
-            |status| {
-                let ondrop = Self::drop_callback(ctx, status);
-                html! {
-                    <div class="tile col">
-                        <div class="tile notification is-light is-vertical pt-3 pr-3 pb-3 pl-3" {ondrop} ondragover={|e: DragEvent| e.prevent_default()}>
-                            { Self::ticket_view(ctx, &status, ticket_list) }
-                        </div>
+        let columns = board
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(_, column)| !column.hidden)
+            .collect::<Vec<(usize, &BoardColumnDto)>>();
+
+        let column_headers = columns.iter().map(|(_, column)| {
+            let count = self.count(column);
+            let class = match column.is_over_limit(count) {
+                true => "is-danger",
+                false => "is-light",
+            };
+            html! {
+                <div class="tile col">
+                    <div class={classes!("tile", "notification", "is-vertical", class)}>
+                        <p class="tile is-uppercase is-size-7">
+                            <b>{ &column.name }</b>
+                            <span class="ml-2">
+                                { column.wip_limit.map_or(format!("{}", count), |limit| format!("{} / {}", count, limit)) }
+                            </span>
+                        </p>
                     </div>
-                }
+                </div>
             }
-        );
+        });
+
+        let lanes = self
+            .lanes(ticket_list)
+            .into_iter()
+            .map(|(lane, title)| self.lane_view(ctx, &columns, lane, title, ticket_list));
 
         html! {
             <div class="section container">
@@ -287,6 +585,15 @@ impl Component for ProjectBoardPage {
                             <div class="columns">
                                 <div class="column is-two-thirds">
                                     <p class="title">{ &project.summary }</p>
+                                    if let Some(sprint) = active_sprint {
+                                        <p class="subtitle is-6">
+                                            <span class="tag is-info is-light mr-2">{ &sprint.name }</span>
+                                            { format!("{} - {}", sprint.start_date.format("%F"), sprint.end_date.format("%F")) }
+                                            if !sprint.goal.is_empty() {
+                                                <span class="ml-2 has-text-grey">{ &sprint.goal }</span>
+                                            }
+                                        </p>
+                                    }
                                     <div class="field is-grouped mt-6">
                                         <div class="field has-addons">
                                             <p class="control">
@@ -306,6 +613,33 @@ impl Component for ProjectBoardPage {
                                                 </button>
                                             </p>
                                         </div>
+                                        { self.sprint_actions_view(ctx) }
+                                        <div class="field ml-3">
+                                            <p class="control">
+                                                <button class="button" onclick={ctx.link().callback(on_configure_click)}>
+                                                    <span class="icon is-small">
+                                                        <i class="fas fa-table-columns"></i>
+                                                    </span>
+                                                    <span>{ "Columns" }</span>
+                                                </button>
+                                            </p>
+                                        </div>
+                                        <div class="field ml-3">
+                                            <div class="control has-icons-left">
+                                                <div class="select">
+                                                    <select onchange={on_swimlanes_change}>
+                                                        {
+                                                            Swimlanes::iter().map(|swimlanes| html! {
+                                                                <option selected={swimlanes == self.swimlanes}>{ swimlanes.to_string() }</option>
+                                                            }).collect::<Html>()
+                                                        }
+                                                    </select>
+                                                </div>
+                                                <span class="icon is-left">
+                                                    <i class="fas fa-layer-group"></i>
+                                                </span>
+                                            </div>
+                                        </div>
                                         <div class="field ml-3">
                                             <p class="control">
                                                 <Link<Route> classes={classes!("button")} to={Route::Project { id: project.id.unwrap_or(0) }}>
@@ -342,12 +676,18 @@ impl Component for ProjectBoardPage {
                             </div>
                         </article>
                     </div>
+                    if let Some(notice) = notice {
+                        <div class="tile is-parent">
+                            <div class="tile is-child notification is-warning is-light">
+                                <button class="delete" onclick={ctx.link().callback(|_| ProjectBoardPageMsg::DismissNotice)}></button>
+                                { notice }
+                            </div>
+                        </div>
+                    }
                     <div class="tile is-parent">
-                        { for status_headers }
-                    </div>
-                    <div class="tile is-parent">
-                        { for statuses }
+                        { for column_headers }
                     </div>
+                    { for lanes }
                 </div>
             </div>
         }
@@ -367,15 +707,264 @@ impl ProjectBoardPage {
                 ctx.props().id,
                 ctx.link().callback(ProjectBoardPageMsg::FetchedTickets),
             );
+            SprintService::fetch_all(
+                token.to_string(),
+                ctx.props().id,
+                ctx.link().callback(ProjectBoardPageMsg::FetchedSprints),
+            );
+            BoardService::fetch(
+                token.to_string(),
+                ctx.props().id,
+                ctx.link().callback(ProjectBoardPageMsg::FetchedBoard),
+            );
         }
     }
 
-    fn is_status_managable(status: &TicketStatus) -> bool {
-        status != &TicketStatus::Created && status != &TicketStatus::Closed
+    /// Tickets of the project in the column, whichever sprint they belong to.
+    fn count(&self, column: &BoardColumnDto) -> usize {
+        self.ticket_list
+            .iter()
+            .filter(|t| column.contains(&t.status))
+            .count()
     }
 
-    fn ticket_view(ctx: &Context<Self>, column: &TicketStatus, ticket_list: &[TicketDto]) -> Html {
-        let tickets = ticket_list.iter().map(
+    fn active_sprint(&self) -> Option<&SprintDto> {
+        self.sprints.iter().find(|s| s.state == SprintState::Active)
+    }
+
+    /// The planned sprint starting first, it takes over the unfinished tickets.
+    fn next_sprint(&self) -> Option<&SprintDto> {
+        self.sprints
+            .iter()
+            .find(|s| s.state == SprintState::Planned)
+    }
+
+    /// Completes the active sprint, or starts the next one if none is active.
+    fn sprint_actions_view(&self, ctx: &Context<Self>) -> Html {
+        match (self.active_sprint(), self.next_sprint()) {
+            (Some(active), _) => {
+                let sprint = active.clone();
+                let onclick = ctx
+                    .link()
+                    .callback(move |_| ProjectBoardPageMsg::CompleteSprint(sprint.clone()));
+                let onchange = ctx
+                    .link()
+                    .callback(|_| ProjectBoardPageMsg::ToggleActiveSprintOnly);
+                html! {
+                    <div class="field is-grouped ml-3">
+                        <p class="control">
+                            <button class="button" {onclick}>
+                                <span class="icon is-small">
+                                    <i class="fas fa-flag-checkered"></i>
+                                </span>
+                                <span>{ "Complete sprint" }</span>
+                            </button>
+                        </p>
+                        <label class="checkbox control pt-2">
+                            <input type="checkbox" class="mr-1" checked={self.active_sprint_only} {onchange} />
+                            { "Active sprint only" }
+                        </label>
+                    </div>
+                }
+            }
+            (None, Some(next)) => {
+                let sprint = next.clone();
+                let label = format!("Start {}", sprint.name);
+                let onclick = ctx
+                    .link()
+                    .callback(move |_| ProjectBoardPageMsg::StartSprint(sprint.clone()));
+                html! {
+                    <div class="field ml-3">
+                        <p class="control">
+                            <button class="button" {onclick}>
+                                <span class="icon is-small">
+                                    <i class="fas fa-play"></i>
+                                </span>
+                                <span>{ label }</span>
+                            </button>
+                        </p>
+                    </div>
+                }
+            }
+            (None, None) => html! { <></> },
+        }
+    }
+
+    /// Lanes of the grouping with their titles. Lanes for tickets without a value come last.
+    fn lanes(&self, ticket_list: &[TicketDto]) -> Vec<(Lane, String)> {
+        let mut lanes: Vec<Lane> = vec![];
+        let mut push = |lane: Lane| {
+            if !lanes.contains(&lane) {
+                lanes.push(lane);
+            }
+        };
+        match self.swimlanes {
+            Swimlanes::None => push(Lane::All),
+            Swimlanes::Assignee => {
+                ticket_list
+                    .iter()
+                    .filter_map(|t| t.user_id)
+                    .for_each(|user_id| push(Lane::Assignee(Some(user_id))));
+                push(Lane::Assignee(None));
+            }
+            Swimlanes::Priority => {
+                [
+                    Priority::Critical,
+                    Priority::High,
+                    Priority::Normal,
+                    Priority::Low,
+                ]
+                .into_iter()
+                .for_each(|priority| push(Lane::Priority(priority)));
+            }
+            Swimlanes::Label => {
+                ticket_list
+                    .iter()
+                    .filter_map(|t| t.labels.first().cloned())
+                    .collect::<BTreeSet<String>>()
+                    .into_iter()
+                    .for_each(|label| push(Lane::Label(Some(label))));
+                push(Lane::Label(None));
+            }
+            Swimlanes::Parent => {
+                ticket_list
+                    .iter()
+                    .filter_map(|t| t.parent_id)
+                    .for_each(|parent_id| push(Lane::Parent(Some(parent_id))));
+                push(Lane::Parent(None));
+            }
+        }
+        lanes
+            .into_iter()
+            .map(|lane| {
+                let title = self.lane_title(&lane);
+                (lane, title)
+            })
+            .collect()
+    }
+
+    fn lane_title(&self, lane: &Lane) -> String {
+        match lane {
+            Lane::All => String::new(),
+            Lane::Assignee(Some(user_id)) => self
+                .users
+                .iter()
+                .find(|u| u.public_id == Some(*user_id))
+                .map_or(user_id.to_string(), |u| u.name.clone()),
+            Lane::Assignee(None) => String::from("Unassigned"),
+            Lane::Priority(priority) => TicketPriority(priority.clone()).to_string(),
+            Lane::Label(Some(label)) => label.clone(),
+            Lane::Label(None) => String::from("No label"),
+            Lane::Parent(Some(parent_id)) => self
+                .ticket_list
+                .iter()
+                .find(|t| t.id == Some(*parent_id))
+                .map_or(format!("#{}", parent_id), |t| match &t.key {
+                    Some(key) => format!("{} {}", key, t.title),
+                    None => t.title.clone(),
+                }),
+            Lane::Parent(None) => String::from("No parent"),
+        }
+    }
+
+    /// Columns of the tickets in the lane, under a header with the lane count unless the
+    /// board is not grouped.
+    fn lane_view(
+        &self,
+        ctx: &Context<Self>,
+        columns: &[(usize, &BoardColumnDto)],
+        lane: Lane,
+        title: String,
+        ticket_list: &[TicketDto],
+    ) -> Html {
+        let tickets = ticket_list
+            .iter()
+            .filter(|t| lane.contains(t))
+            .cloned()
+            .collect::<Vec<TicketDto>>();
+        let collapsed = self.collapsed.contains(&lane);
+        let column_tiles = columns.iter().map(|(index, column)| {
+            let ondrop = Self::drop_callback(ctx, *index, lane.clone(), None);
+            let class = match column.is_over_limit(self.count(column)) {
+                true => "is-danger",
+                false => "is-light",
+            };
+            html! {
+                <div class="tile col">
+                    <div class={classes!("tile", "notification", "is-vertical", "pt-3", "pr-3", "pb-3", "pl-3", class)} {ondrop} ondragover={|e: DragEvent| e.prevent_default()}>
+                        { Self::ticket_view(ctx, *index, &lane, column, &tickets, &self.slas) }
+                    </div>
+                </div>
+            }
+        });
+        let count = tickets.len();
+        let icon = match collapsed {
+            true => "fa-chevron-right",
+            false => "fa-chevron-down",
+        };
+        let toggled = lane.clone();
+        let onclick = ctx
+            .link()
+            .callback(move |_| ProjectBoardPageMsg::ToggleLane(toggled.clone()));
+
+        html! {
+            <>
+                if lane != Lane::All {
+                    <div class="tile is-parent pb-0">
+                        <p class="is-clickable" {onclick}>
+                            <span class="icon">
+                                <i class={classes!("fas", icon)}></i>
+                            </span>
+                            <b>{ title }</b>
+                            <span class="tag is-light ml-2">{ count }</span>
+                        </p>
+                    </div>
+                }
+                if !collapsed {
+                    <div class="tile is-parent">
+                        { for column_tiles }
+                    </div>
+                }
+            </>
+        }
+    }
+
+    /// Neighbours of the ticket dropped before the target, or at the end of the column in the
+    /// lane. The tickets hidden by the sprint filter keep their place as well.
+    fn position(
+        &self,
+        column: &BoardColumnDto,
+        lane: &Lane,
+        id: u64,
+        target: Option<u64>,
+    ) -> MoveTicketDto {
+        let others = self
+            .ticket_list
+            .iter()
+            .filter(|t| column.contains(&t.status) && lane.contains(t) && t.id != Some(id))
+            .filter_map(|t| t.id)
+            .collect::<Vec<u64>>();
+        match target.and_then(|target| others.iter().position(|other| *other == target)) {
+            Some(i) => MoveTicketDto {
+                after: i.checked_sub(1).map(|j| others[j]),
+                before: Some(others[i]),
+            },
+            None => MoveTicketDto {
+                after: others.last().copied(),
+                before: None,
+            },
+        }
+    }
+
+    fn ticket_view(
+        ctx: &Context<Self>,
+        index: usize,
+        lane: &Lane,
+        column: &BoardColumnDto,
+        ticket_list: &[TicketDto],
+        slas: &[TicketSlaDto],
+    ) -> Html {
+        let tickets = ticket_list.iter().filter(|t| column.contains(&t.status)).map(
             |TicketDto {
                  id,
                  title,
@@ -384,16 +973,34 @@ impl ProjectBoardPage {
                  status,
                  user_id: _,
                  priority: _,
+                 key,
+                 version: _,
+                 sprint_id: _,
+                 parent_id: _,
+                 labels: _,
+                 fields: _,
              }| {
                 match id {
                     Some(id) => {
                         let ondragstart = Self::dragstart_callback(ctx, *id);
+                        let ondrop = Self::drop_callback(ctx, index, lane.clone(), Some(*id));
                         let onclick = Self::ticket_click_callback(ctx, *id);
+                        let sla_clock = slas
+                            .iter()
+                            .find(|s| s.ticket_id == *id)
+                            .and_then(|s| s.worst())
+                            .cloned();
                         html! {
-                            <div class={classes!(Self::ticket_classes(*status, *column))} draggable="true" {ondragstart}>
+                            <div class={classes!(Self::ticket_classes(*status))} draggable="true" {ondragstart} {ondrop}>
                                 <a draggable="true" ondragstart={|e: DragEvent| e.prevent_default()} {onclick}>
+                                    if let Some(key) = key {
+                                        <span class="tag is-light mr-2">{ key.clone() }</span>
+                                    }
                                     {title.clone()}
                                 </a>
+                                if let Some(clock) = sla_clock {
+                                    <div class="mt-2"><SlaBadge {clock} /></div>
+                                }
                             </div>
                         }
                     },
@@ -404,8 +1011,17 @@ impl ProjectBoardPage {
         html! { for tickets }
     }
 
-    fn drop_callback(ctx: &Context<Self>, column: TicketStatus) -> Callback<DragEvent> {
-        let function = move |e: DragEvent| ProjectBoardPageMsg::Drop(e, column);
+    fn drop_callback(
+        ctx: &Context<Self>,
+        column: usize,
+        lane: Lane,
+        target: Option<u64>,
+    ) -> Callback<DragEvent> {
+        let function = move |e: DragEvent| {
+            // Drops on a card are not drops on the end of its column
+            e.stop_propagation();
+            ProjectBoardPageMsg::Drop(e, column, lane.clone(), target)
+        };
         ctx.link().callback(function)
     }
 
@@ -414,7 +1030,7 @@ impl ProjectBoardPage {
         ctx.link().callback(function)
     }
 
-    fn ticket_classes(status: TicketStatus, column: TicketStatus) -> Vec<&'static str> {
+    fn ticket_classes(status: TicketStatus) -> Vec<&'static str> {
         let mut cls = vec![
             "tile",
             "notification",
@@ -427,19 +1043,12 @@ impl ProjectBoardPage {
             "is-clickable",
             "grabable",
         ];
-        if !Self::is_ticket_visible(&status, &column) {
-            cls.push("is-invisible");
-        }
         if status.eq(&TicketStatus::Done) {
             cls.push("done");
         }
         cls
     }
 
-    fn is_ticket_visible(status: &TicketStatus, column: &TicketStatus) -> bool {
-        status == column
-    }
-
     fn ticket_click_callback(ctx: &Context<Self>, id: u64) -> Callback<MouseEvent> {
         let function = move |_: MouseEvent| ProjectBoardPageMsg::OpenTicketDialog(id);
         ctx.link().callback(function)
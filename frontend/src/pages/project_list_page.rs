@@ -1,14 +1,19 @@
 use crate::{
     app_state::AppStateContext,
-    components::bulma::{
-        pagination::Pagination,
-        tables::{
-            data_sources::project_data_source::ProjectDataSource, table::Table,
-            table_data_source::ITableDataSource, table_head_data::TableHeadData,
+    components::{
+        bulma::{
+            field::Field,
+            pagination::Pagination,
+            tables::{
+                data_sources::project_data_source::ProjectDataSource, table::Table,
+                table_data_source::ITableDataSource, table_head_data::TableHeadData,
+            },
         },
+        html::{date_input::DateInput, text_input::TextInput},
     },
+    helpers::event_helper::get_value_from_input_event,
     route::Route,
-    services::project_service::ProjectService,
+    services::project_service::{ProjectFilter, ProjectService},
 };
 use implicit_clone::unsync::IString;
 use shared::dtos::{
@@ -26,11 +31,18 @@ pub enum Msg {
     FetchedProjects(Page<ProjectDto>),
     SortProjects(TableHeadData),
     UpdateOffset(u64),
+    UpdateSearch(AttrValue),
+    SubmitSearch,
+    ToggleOwn,
+    SelectActive(AttrValue),
+    UpdateDeadlineFrom(AttrValue),
+    UpdateDeadlineTo(AttrValue),
 }
 
 pub struct ProjectListPage {
     total: i64,
     list: Vec<ProjectDto>,
+    filter: ProjectFilter,
     sort: Option<IString>,
     order: Option<IString>,
     limit: u64,
@@ -47,28 +59,19 @@ impl Component for ProjectListPage {
             .link()
             .context::<AppStateContext>(ctx.link().callback(Msg::ContextChanged))
             .expect("context to be set");
-        let sort = None;
-        let order = None;
-        let limit = DEFAULT_LIMIT;
-        let offset = DEFAULT_OFFSET;
-        ProjectListPage::init(
-            &app_state,
-            ctx,
-            sort.clone(),
-            order.clone(),
-            Some(limit),
-            Some(offset),
-        );
-        Self {
+        let page = Self {
             total: 0,
             list: Vec::new(),
-            sort,
-            order,
-            limit,
-            offset,
+            filter: ProjectFilter::default(),
+            sort: None,
+            order: None,
+            limit: DEFAULT_LIMIT,
+            offset: DEFAULT_OFFSET,
             app_state,
             _listener,
-        }
+        };
+        page.init(ctx);
+        page
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -88,25 +91,35 @@ impl Component for ProjectListPage {
                     .sort
                     .as_ref()
                     .map(|s| IString::from(s.order.to_string()));
-                ProjectListPage::init(
-                    &self.app_state,
-                    ctx,
-                    self.sort.clone(),
-                    self.order.clone(),
-                    Some(self.limit),
-                    Some(self.offset),
-                )
+                self.init(ctx);
             }
             Msg::UpdateOffset(offset) => {
                 self.offset = offset;
-                ProjectListPage::init(
-                    &self.app_state,
-                    ctx,
-                    self.sort.clone(),
-                    self.order.clone(),
-                    Some(self.limit),
-                    Some(self.offset),
-                )
+                self.init(ctx);
+            }
+            Msg::UpdateSearch(q) => {
+                self.filter.q = q;
+                return false;
+            }
+            Msg::SubmitSearch => self.refetch(ctx),
+            Msg::ToggleOwn => {
+                self.filter.owner = match self.filter.owner {
+                    Some(_) => None,
+                    None => self.app_state.identity.as_ref().map(|i| i.userid),
+                };
+                self.refetch(ctx);
+            }
+            Msg::SelectActive(active) => {
+                self.filter.active = active;
+                self.refetch(ctx);
+            }
+            Msg::UpdateDeadlineFrom(date) => {
+                self.filter.deadline_from = date;
+                self.refetch(ctx);
+            }
+            Msg::UpdateDeadlineTo(date) => {
+                self.filter.deadline_to = date;
+                self.refetch(ctx);
             }
         }
         true
@@ -118,6 +131,14 @@ impl Component for ProjectListPage {
 
         let sorthandler = Some(ctx.link().callback(Msg::SortProjects));
         let paginghandler = ctx.link().callback(Msg::UpdateOffset);
+        let onsubmit = ctx.link().callback(|e: SubmitEvent| {
+            e.prevent_default();
+            Msg::SubmitSearch
+        });
+        let on_own_change = ctx.link().callback(|_| Msg::ToggleOwn);
+        let on_active_change = ctx
+            .link()
+            .callback(|e: Event| Msg::SelectActive(get_value_from_input_event(e)));
 
         html! {
             <div class="container">
@@ -134,6 +155,53 @@ impl Component for ProjectListPage {
                 <p class="section py-0">
                     { "This is the list of all the created projects retrieved from the API in the background." }
                 </p>
+                <div class="section pb-0">
+                    <form {onsubmit}>
+                        <Field label="Search">
+                            <div class="field has-addons">
+                                <div class="control is-expanded">
+                                    <TextInput
+                                        value={self.filter.q.clone()}
+                                        on_change={ctx.link().callback(Msg::UpdateSearch)}
+                                        placeholder="Summary"
+                                    />
+                                </div>
+                                <div class="control">
+                                    <button type="submit" class="button is-link">{ "Search" }</button>
+                                </div>
+                            </div>
+                        </Field>
+                    </form>
+                    <div class="field is-grouped is-grouped-multiline mt-3">
+                        <label class="checkbox control pt-2">
+                            <input type="checkbox" class="mr-1" checked={self.filter.owner.is_some()} onchange={on_own_change} />
+                            { "Only my projects" }
+                        </label>
+                        <div class="control">
+                            <div class="select">
+                                <select onchange={on_active_change}>
+                                    <option value="" selected={self.filter.active.is_empty()}>{ "Active and inactive" }</option>
+                                    <option value="true" selected={self.filter.active.as_str() == "true"}>{ "Active" }</option>
+                                    <option value="false" selected={self.filter.active.as_str() == "false"}>{ "Inactive" }</option>
+                                </select>
+                            </div>
+                        </div>
+                        <div class="control">
+                            <DateInput
+                                value={self.filter.deadline_from.clone()}
+                                on_change={ctx.link().callback(Msg::UpdateDeadlineFrom)}
+                                placeholder="Deadline from"
+                            />
+                        </div>
+                        <div class="control">
+                            <DateInput
+                                value={self.filter.deadline_to.clone()}
+                                on_change={ctx.link().callback(Msg::UpdateDeadlineTo)}
+                                placeholder="Deadline to"
+                            />
+                        </div>
+                    </div>
+                </div>
                 <div class="section">
                     <Table<ProjectField, IProjectDto, ProjectValue> {datasource} {sorthandler} />
                     <Pagination total={self.total} offset={self.offset} limit={self.limit} {paginghandler} />
@@ -156,23 +224,23 @@ impl Component for ProjectListPage {
 }
 
 impl ProjectListPage {
-    fn init(
-        app_state: &AppStateContext,
-        ctx: &Context<Self>,
-        sort: Option<IString>,
-        order: Option<IString>,
-        limit: Option<u64>,
-        offset: Option<u64>,
-    ) {
-        if app_state.identity.is_some() {
+    fn init(&self, ctx: &Context<Self>) {
+        if let Some(identity) = &self.app_state.identity {
             ProjectService::fetch_all(
-                app_state.identity.clone().unwrap().token.clone(),
-                sort,
-                order,
-                limit,
-                offset,
+                identity.token.clone(),
+                self.filter.clone(),
+                self.sort.clone(),
+                self.order.clone(),
+                Some(self.limit),
+                Some(self.offset),
                 ctx.link().callback(Msg::FetchedProjects),
             );
         }
     }
+
+    /// Fetches the first page again, the filters changed.
+    fn refetch(&mut self, ctx: &Context<Self>) {
+        self.offset = DEFAULT_OFFSET;
+        self.init(ctx);
+    }
 }
@@ -1,15 +1,23 @@
 use crate::app_state::AppStateContext;
-use crate::components::bulma::tables::data_sources::ticket_data_source::TicketDataSource;
+use crate::components::bulma::tables::data_sources::ticket_data_source::{
+    TicketDataSource, DEFAULT_TICKET_FIELDSET,
+};
 use crate::components::bulma::tables::table::Table;
 use crate::components::bulma::tables::table_data_source::ITableDataSource;
 use crate::components::bulma::tables::table_head_data::TableHeadData;
 use crate::components::button_link::{ButtonLink, ButtonLinkData};
+use crate::components::charts::bar_chart::BarChart;
+use crate::components::charts::line_chart::{ChartSeries, LineChart};
 use crate::components::check_tag::CheckTag;
 use crate::components::dialogs::form_dialog::FormDialog;
 use crate::components::dialogs::select_dialog::SelectDialog;
 use crate::components::forms::ticket_form::TicketForm;
 use crate::components::option_data::OptionData;
+use crate::helpers::event_helper::get_value_from_input_event;
+use crate::services::custom_field_service::CustomFieldService;
 use crate::services::project_service::ProjectService;
+use crate::services::report_service::ReportService;
+use crate::services::sprint_service::SprintService;
 use crate::services::ticket_service::TicketService;
 use crate::services::user_service::UserService;
 use crate::{app_state::AppState, dialog::Dialog, route::Route};
@@ -18,11 +26,18 @@ use implicit_clone::{
     unsync,
 };
 use shared::api::error::error_response::ErrorResponse;
+use shared::dtos::custom_field_dto::CustomFieldDto;
 use shared::dtos::identity::Identity;
 use shared::dtos::project_dto::ProjectDto;
+use shared::dtos::project_member_dto::{ProjectMemberDto, ProjectRole};
+use shared::dtos::report_dto::{ProgressReportDto, VelocityReportDto};
+use shared::dtos::sprint_dto::{SprintDto, SprintState};
 use shared::dtos::ticket_dto::{ITicketDto, TicketDto, TicketField, TicketValue};
 use shared::dtos::user_dto::UserDto;
 use std::rc::Rc;
+use std::str::FromStr;
+use strum::{Display, EnumIter, IntoEnumIterator};
+use uuid::Uuid;
 use yew::prelude::*;
 use yew_router::prelude::Link;
 
@@ -41,11 +56,23 @@ pub struct Props {
     pub id: u64,
 }
 
+#[derive(Clone, Copy, Debug, Display, EnumIter, Eq, PartialEq)]
+pub enum ProjectTab {
+    Tickets,
+    Members,
+}
+
 pub enum ProjectPageMsg {
     ContextChanged(AppStateContext),
     FetchedProject(ProjectDto),
     FetchedUser(UserDto),
     FetchedTickets(Vec<TicketDto>),
+    FetchedCustomFields(Vec<CustomFieldDto>),
+    FetchedSprints(Vec<SprintDto>),
+    FetchedProgress(ProgressReportDto),
+    FetchedVelocity(VelocityReportDto),
+    SelectReportSprint(AttrValue),
+    ToggleColumn(TicketField),
     FetchUnassigned(Callback<Vec<TicketDto>>),
     OpenSelectDialog(),
     OpenFormDialog(),
@@ -53,12 +80,40 @@ pub enum ProjectPageMsg {
     SubmittedForm((TicketDto, Callback<ErrorResponse>)),
     SortTickets(TableHeadData),
     TicketCreated(TicketDto),
+    SelectTab(ProjectTab),
+    FetchedRole(ProjectRole),
+    FetchedMembers(Vec<ProjectMemberDto>),
+    FetchedUsers(Vec<UserDto>),
+    SelectNewMember(AttrValue),
+    SelectNewRole(AttrValue),
+    AddMember,
+    ChangeRole(Uuid, AttrValue),
+    RemoveMember(Uuid),
+    MemberSaved(ProjectMemberDto),
+    MemberRemoved(Uuid),
+    MemberFailed(ErrorResponse),
 }
 
 pub struct ProjectPage {
     project: ProjectDto,
     user: Option<ButtonLinkData<Route>>,
     ticket_list: Vec<TicketDto>,
+    custom_fields: Vec<CustomFieldDto>,
+    columns: Vec<TicketField>,
+    sprints: Vec<SprintDto>,
+    /// Sprint the burndown and burnup charts are about, the whole project if none.
+    report_sprint_id: Option<u64>,
+    progress: ProgressReportDto,
+    velocity: VelocityReportDto,
+    tab: ProjectTab,
+    /// Role of the signed in user, `None` until it is known.
+    role: Option<ProjectRole>,
+    members: Vec<ProjectMemberDto>,
+    /// Users who can be added to the members.
+    users: Vec<UserDto>,
+    new_member: Option<Uuid>,
+    new_role: ProjectRole,
+    member_error: Option<AttrValue>,
     app_state: AppStateContext,
     _listener: ContextHandle<AppStateContext>,
 }
@@ -77,6 +132,19 @@ impl Component for ProjectPage {
             project: ProjectDto::default(),
             user: None,
             ticket_list: vec![],
+            custom_fields: vec![],
+            columns: DEFAULT_TICKET_FIELDSET.to_vec(),
+            sprints: vec![],
+            report_sprint_id: None,
+            progress: ProgressReportDto::default(),
+            velocity: VelocityReportDto::default(),
+            tab: ProjectTab::Tickets,
+            role: None,
+            members: vec![],
+            users: vec![],
+            new_member: None,
+            new_role: ProjectRole::Contributor,
+            member_error: None,
             app_state,
             _listener,
         }
@@ -116,6 +184,36 @@ impl Component for ProjectPage {
             ProjectPageMsg::FetchedTickets(tickets) => {
                 self.ticket_list = tickets;
             }
+            ProjectPageMsg::FetchedCustomFields(fields) => {
+                self.custom_fields = fields;
+            }
+            ProjectPageMsg::FetchedSprints(sprints) => {
+                // Reports start with the active sprint, if there is one
+                self.report_sprint_id = sprints
+                    .iter()
+                    .find(|s| s.state == SprintState::Active)
+                    .and_then(|s| s.id);
+                self.sprints = sprints;
+                self.fetch_progress(ctx);
+            }
+            ProjectPageMsg::FetchedProgress(progress) => {
+                self.progress = progress;
+            }
+            ProjectPageMsg::FetchedVelocity(velocity) => {
+                self.velocity = velocity;
+            }
+            ProjectPageMsg::SelectReportSprint(value) => {
+                self.report_sprint_id = value.as_str().parse::<u64>().ok();
+                self.fetch_progress(ctx);
+            }
+            ProjectPageMsg::ToggleColumn(field) => {
+                match self.columns.iter().position(|c| *c == field) {
+                    Some(index) => {
+                        self.columns.remove(index);
+                    }
+                    None => self.columns.push(field),
+                }
+            }
             ProjectPageMsg::ContextChanged(state) => {
                 self.app_state = state;
             }
@@ -197,6 +295,73 @@ impl Component for ProjectPage {
                     TicketService::fetch_unassigned(token.to_string(), consumer);
                 }
             }
+            ProjectPageMsg::SelectTab(tab) => {
+                self.tab = tab;
+                self.member_error = None;
+                if tab == ProjectTab::Members {
+                    self.fetch_members(ctx);
+                    if let Some(Identity { token, .. }) = &self.app_state.identity {
+                        UserService::fetch_all(
+                            token.to_string(),
+                            None,
+                            Some(unsync::IString::from("name")),
+                            None,
+                            ctx.link().callback(ProjectPageMsg::FetchedUsers),
+                        );
+                    }
+                }
+            }
+            ProjectPageMsg::FetchedRole(role) => {
+                self.role = Some(role);
+            }
+            ProjectPageMsg::FetchedMembers(members) => {
+                self.members = members;
+            }
+            ProjectPageMsg::FetchedUsers(users) => {
+                self.users = users;
+            }
+            ProjectPageMsg::SelectNewMember(value) => {
+                self.new_member = Uuid::parse_str(value.as_str()).ok();
+            }
+            ProjectPageMsg::SelectNewRole(value) => {
+                if let Ok(role) = ProjectRole::from_str(value.as_str()) {
+                    self.new_role = role;
+                }
+            }
+            ProjectPageMsg::AddMember => {
+                if let Some(user_id) = self.new_member {
+                    self.save_member(ctx, user_id, self.new_role);
+                }
+                return false;
+            }
+            ProjectPageMsg::ChangeRole(user_id, value) => {
+                if let Ok(role) = ProjectRole::from_str(value.as_str()) {
+                    self.save_member(ctx, user_id, role);
+                }
+                return false;
+            }
+            ProjectPageMsg::RemoveMember(user_id) => {
+                if let Some(Identity { token, .. }) = &self.app_state.identity {
+                    ProjectService::remove_member(
+                        token.to_string(),
+                        ctx.props().id,
+                        user_id,
+                        ctx.link().callback(ProjectPageMsg::MemberRemoved),
+                        ctx.link().callback(ProjectPageMsg::MemberFailed),
+                    );
+                }
+                return false;
+            }
+            ProjectPageMsg::MemberSaved(_) | ProjectPageMsg::MemberRemoved(_) => {
+                self.member_error = None;
+                self.new_member = None;
+                self.fetch_members(ctx);
+            }
+            ProjectPageMsg::MemberFailed(error_response) => {
+                self.member_error = Some(AttrValue::from(error_response.message));
+                // The selects show the roles saved before
+                self.fetch_members(ctx);
+            }
         }
         true
     }
@@ -206,15 +371,31 @@ impl Component for ProjectPage {
             project,
             user,
             ticket_list,
+            custom_fields,
+            columns,
+            sprints: _,
+            report_sprint_id: _,
+            progress: _,
+            velocity: _,
+            tab,
+            role,
+            members: _,
+            users: _,
+            new_member: _,
+            new_role: _,
+            member_error: _,
             app_state: _,
             _listener,
         } = self;
+        // Viewers only read the project
+        let read_only = role.is_none_or(|role| role < ProjectRole::Contributor);
 
         let on_assign_click = |_| ProjectPageMsg::OpenSelectDialog();
         let on_add_click = |_| ProjectPageMsg::OpenFormDialog();
 
         let datasource: ITableDataSource<TicketField, ITicketDto, TicketValue> =
-            TicketDataSource::from(ticket_list).into();
+            TicketDataSource::with_custom_fields(ticket_list, columns.clone(), custom_fields)
+                .into();
 
         let sorthandler = Some(ctx.link().callback(ProjectPageMsg::SortTickets));
 
@@ -226,10 +407,11 @@ impl Component for ProjectPage {
                             <div class="columns">
                                 <div class="column is-two-thirds">
                                     <p class="title">{ &project.summary }</p>
+                                    <p class="subtitle"><span class="tag is-medium">{ &project.key }</span></p>
                                     <div class="field is-grouped mt-6">
                                         <div class="field has-addons">
                                             <p class="control">
-                                                <button class="button" onclick={ctx.link().callback(on_assign_click)}>
+                                                <button class="button" disabled={read_only} onclick={ctx.link().callback(on_assign_click)}>
                                                     <span class="icon is-small">
                                                         <i class="fas fa-arrow-up"></i>
                                                     </span>
@@ -237,7 +419,7 @@ impl Component for ProjectPage {
                                                 </button>
                                             </p>
                                             <p class="control">
-                                                <button class="button" onclick={ctx.link().callback(on_add_click)}>
+                                                <button class="button" disabled={read_only} onclick={ctx.link().callback(on_add_click)}>
                                                     <span class="icon is-small">
                                                         <i class="fas fa-plus"></i>
                                                     </span>
@@ -284,13 +466,34 @@ impl Component for ProjectPage {
                     <div class="tile">
                         <div class="tile is-parent">
                             <article class="tile is-child notification is-light">
+                                <div class="tabs">
+                                    <ul>
+                                        {
+                                            ProjectTab::iter().map(|t| {
+                                                let onclick = ctx.link().callback(move |_: MouseEvent| ProjectPageMsg::SelectTab(t));
+                                                html! {
+                                                    <li class={classes!((t == *tab).then_some("is-active"))}>
+                                                        <a {onclick}>{ t.to_string() }</a>
+                                                    </li>
+                                                }
+                                            }).collect::<Html>()
+                                        }
+                                    </ul>
+                                </div>
                                 <div class="content">
-                                    <p class="title">{ "Tickets" }</p>
-                                    <Table<TicketField, ITicketDto, TicketValue> {datasource} {sorthandler} />
+                                    if *tab == ProjectTab::Tickets {
+                                        <>
+                                            { self.custom_columns_view(ctx) }
+                                            <Table<TicketField, ITicketDto, TicketValue> {datasource} {sorthandler} />
+                                        </>
+                                    } else {
+                                        { self.members_view(ctx) }
+                                    }
                                 </div>
                             </article>
                         </div>
                     </div>
+                    { self.reports_view(ctx) }
                 </div>
             </div>
         }
@@ -300,6 +503,11 @@ impl Component for ProjectPage {
 impl ProjectPage {
     fn init(app_state: &AppStateContext, ctx: &Context<Self>) {
         if let Some(Identity { token, .. }) = &app_state.identity {
+            CustomFieldService::fetch_all(
+                token.to_string(),
+                ctx.props().id,
+                ctx.link().callback(ProjectPageMsg::FetchedCustomFields),
+            );
             ProjectService::fetch(
                 token.to_string(),
                 ctx.props().id,
@@ -310,6 +518,284 @@ impl ProjectPage {
                 ctx.props().id,
                 ctx.link().callback(ProjectPageMsg::FetchedTickets),
             );
+            ProjectService::fetch_own_role(
+                token.to_string(),
+                ctx.props().id,
+                ctx.link().callback(ProjectPageMsg::FetchedRole),
+            );
+            SprintService::fetch_all(
+                token.to_string(),
+                ctx.props().id,
+                ctx.link().callback(ProjectPageMsg::FetchedSprints),
+            );
+            ReportService::fetch_velocity(
+                token.to_string(),
+                ctx.props().id,
+                ctx.link().callback(ProjectPageMsg::FetchedVelocity),
+            );
+        }
+    }
+
+    fn fetch_members(&self, ctx: &Context<Self>) {
+        if let Some(Identity { token, .. }) = &self.app_state.identity {
+            ProjectService::fetch_members(
+                token.to_string(),
+                ctx.props().id,
+                ctx.link().callback(ProjectPageMsg::FetchedMembers),
+            );
+        }
+    }
+
+    fn save_member(&self, ctx: &Context<Self>, user_id: Uuid, role: ProjectRole) {
+        if let Some(Identity { token, .. }) = &self.app_state.identity {
+            ProjectService::set_member_role(
+                token.to_string(),
+                ctx.props().id,
+                user_id,
+                role,
+                ctx.link().callback(ProjectPageMsg::MemberSaved),
+                ctx.link().callback(ProjectPageMsg::MemberFailed),
+            );
+        }
+    }
+
+    fn fetch_progress(&self, ctx: &Context<Self>) {
+        if let Some(Identity { token, .. }) = &self.app_state.identity {
+            ReportService::fetch_progress(
+                token.to_string(),
+                ctx.props().id,
+                self.report_sprint_id,
+                ctx.link().callback(ProjectPageMsg::FetchedProgress),
+            );
+        }
+    }
+
+    /// Burndown and burnup of the selected sprint or of the project, and the velocity of the
+    /// completed sprints.
+    fn reports_view(&self, ctx: &Context<Self>) -> Html {
+        let points = &self.progress.points;
+        let labels = points
+            .iter()
+            .map(|p| AttrValue::from(p.date.format("%m-%d").to_string()))
+            .collect::<Vec<AttrValue>>();
+        // The ideal burndown goes from the initial scope down to zero on the last day
+        let initial = points.first().map_or(0.0, |p| p.remaining() as f64);
+        let days = points.len().max(2) - 1;
+        let burndown = vec![
+            ChartSeries {
+                name: AttrValue::from("Remaining"),
+                color: AttrValue::from("#485fc7"),
+                values: points.iter().map(|p| p.remaining() as f64).collect(),
+                dashed: false,
+            },
+            ChartSeries {
+                name: AttrValue::from("Ideal"),
+                color: AttrValue::from("#b5b5b5"),
+                values: (0..points.len())
+                    .map(|i| initial * (days - i.min(days)) as f64 / days as f64)
+                    .collect(),
+                dashed: true,
+            },
+        ];
+        let burnup = vec![
+            ChartSeries {
+                name: AttrValue::from("Scope"),
+                color: AttrValue::from("#b5b5b5"),
+                values: points.iter().map(|p| p.scope as f64).collect(),
+                dashed: true,
+            },
+            ChartSeries {
+                name: AttrValue::from("Done"),
+                color: AttrValue::from("#48c78e"),
+                values: points.iter().map(|p| p.done as f64).collect(),
+                dashed: false,
+            },
+        ];
+        let velocity_labels = self
+            .velocity
+            .sprints
+            .iter()
+            .map(|s| AttrValue::from(s.name.clone()))
+            .collect::<Vec<AttrValue>>();
+        let velocity_values = self
+            .velocity
+            .sprints
+            .iter()
+            .map(|s| s.completed as f64)
+            .collect::<Vec<f64>>();
+        let average = (!velocity_values.is_empty()).then_some(self.velocity.average);
+        let onchange = ctx
+            .link()
+            .callback(|e: Event| ProjectPageMsg::SelectReportSprint(get_value_from_input_event(e)));
+
+        html! {
+            <div class="tile">
+                <div class="tile is-parent">
+                    <article class="tile is-child notification is-light">
+                        <div class="content">
+                            <p class="title">{ "Reports" }</p>
+                            <div class="field">
+                                <div class="control">
+                                    <div class="select is-small">
+                                        <select {onchange}>
+                                            <option value="" selected={self.report_sprint_id.is_none()}>{ "Whole project, last 30 days" }</option>
+                                            {
+                                                self.sprints.iter().filter(|s| s.state != SprintState::Planned).filter_map(|s| s.id.map(|id| (id, s.name.clone()))).map(|(id, name)| html! {
+                                                    <option value={id.to_string()} selected={self.report_sprint_id == Some(id)}>{ name }</option>
+                                                }).collect::<Html>()
+                                            }
+                                        </select>
+                                    </div>
+                                </div>
+                            </div>
+                            <div class="columns">
+                                <div class="column">
+                                    <p class="title is-6">{ "Burndown" }</p>
+                                    <LineChart labels={labels.clone()} series={burndown} />
+                                </div>
+                                <div class="column">
+                                    <p class="title is-6">{ "Burnup" }</p>
+                                    <LineChart {labels} series={burnup} />
+                                </div>
+                            </div>
+                            <p class="title is-6">{ "Velocity" }</p>
+                            <BarChart labels={velocity_labels} values={velocity_values} color="#485fc7" name="Tickets done" {average} />
+                        </div>
+                    </article>
+                </div>
+            </div>
+        }
+    }
+
+    /// Toggles for the optional columns of the custom fields of the project.
+    fn custom_columns_view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div class="field is-grouped is-grouped-multiline">
+                {
+                    self.custom_fields.iter().filter_map(|f| f.id.map(|id| (id, f.name.clone()))).map(|(id, name)| {
+                        let field = TicketField::Custom(id);
+                        let visible = self.columns.contains(&field);
+                        let onclick = ctx.link().callback(move |_: MouseEvent| ProjectPageMsg::ToggleColumn(field));
+                        html! {
+                            <p class="control">
+                                <button class={classes!("button", "is-small", visible.then_some("is-info"))} {onclick}>
+                                    { name }
+                                </button>
+                            </p>
+                        }
+                    }).collect::<Html>()
+                }
+            </div>
+        }
+    }
+
+    /// Members with their roles, which admins of the project change.
+    fn members_view(&self, ctx: &Context<Self>) -> Html {
+        let admin = self.role == Some(ProjectRole::Admin);
+        let role_options = |selected: ProjectRole| {
+            ProjectRole::iter()
+                .map(|role| {
+                    html! {
+                        <option value={role.to_string()} selected={role == selected}>{ role.to_string() }</option>
+                    }
+                })
+                .collect::<Html>()
+        };
+        let candidates = self
+            .users
+            .iter()
+            .filter_map(|u| u.public_id.map(|id| (id, u.name.clone())))
+            .filter(|(id, _)| !self.members.iter().any(|m| m.user_id == *id))
+            .collect::<Vec<(Uuid, String)>>();
+        let on_member_change = ctx
+            .link()
+            .callback(|e: Event| ProjectPageMsg::SelectNewMember(get_value_from_input_event(e)));
+        let on_role_change = ctx
+            .link()
+            .callback(|e: Event| ProjectPageMsg::SelectNewRole(get_value_from_input_event(e)));
+        let on_add_click = ctx
+            .link()
+            .callback(|_: MouseEvent| ProjectPageMsg::AddMember);
+
+        html! {
+            <>
+                if let Some(error) = &self.member_error {
+                    <p class="help is-danger mb-4">{ error }</p>
+                }
+                <table class="table is-fullwidth is-hoverable">
+                    <thead>
+                        <tr>
+                            <th>{ "Name" }</th>
+                            <th>{ "Username" }</th>
+                            <th>{ "Role" }</th>
+                            if admin {
+                                <th></th>
+                            }
+                        </tr>
+                    </thead>
+                    <tbody>
+                        {
+                            self.members.iter().map(|member| {
+                                let user_id = member.user_id;
+                                let onchange = ctx.link().callback(move |e: Event| ProjectPageMsg::ChangeRole(user_id, get_value_from_input_event(e)));
+                                let onclick = ctx.link().callback(move |_: MouseEvent| ProjectPageMsg::RemoveMember(user_id));
+                                html! {
+                                    <tr>
+                                        <td>
+                                            <Link<Route> to={Route::User { id: user_id }}>{ member.name.clone() }</Link<Route>>
+                                        </td>
+                                        <td>{ member.username.clone() }</td>
+                                        if admin {
+                                            <>
+                                                <td>
+                                                    <div class="select is-small">
+                                                        <select {onchange}>{ role_options(member.role) }</select>
+                                                    </div>
+                                                </td>
+                                                <td>
+                                                    <button class="button is-small is-danger is-light" {onclick}>
+                                                        <span class="icon is-small"><i class="fas fa-user-minus"></i></span>
+                                                        <span>{ "Remove" }</span>
+                                                    </button>
+                                                </td>
+                                            </>
+                                        } else {
+                                            <td>{ member.role.to_string() }</td>
+                                        }
+                                    </tr>
+                                }
+                            }).collect::<Html>()
+                        }
+                    </tbody>
+                </table>
+                if admin {
+                    <div class="field has-addons">
+                        <div class="control">
+                            <div class="select is-small">
+                                <select onchange={on_member_change}>
+                                    <option value="" selected={self.new_member.is_none()}>{ "Add a member" }</option>
+                                    {
+                                        candidates.into_iter().map(|(id, name)| html! {
+                                            <option value={id.to_string()} selected={self.new_member == Some(id)}>{ name }</option>
+                                        }).collect::<Html>()
+                                    }
+                                </select>
+                            </div>
+                        </div>
+                        <div class="control">
+                            <div class="select is-small">
+                                <select onchange={on_role_change}>{ role_options(self.new_role) }</select>
+                            </div>
+                        </div>
+                        <div class="control">
+                            <button class="button is-small is-info" disabled={self.new_member.is_none()} onclick={on_add_click}>
+                                <span class="icon is-small"><i class="fas fa-user-plus"></i></span>
+                                <span>{ "Add" }</span>
+                            </button>
+                        </div>
+                    </div>
+                }
+            </>
         }
     }
 }
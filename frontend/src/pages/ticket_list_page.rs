@@ -0,0 +1,628 @@
+use crate::{
+    app_state::{AppState, AppStateContext},
+    components::{
+        bulk_action_bar::BulkActionBar,
+        bulma::{
+            field::Field,
+            pagination::Pagination,
+            tables::{
+                data_sources::ticket_data_source::{TicketDataSource, DEFAULT_TICKET_FIELDSET},
+                table::Table,
+                table_data_source::ITableDataSource,
+                table_head_data::TableHeadData,
+                table_row_selection::TableRowSelection,
+            },
+        },
+        html::text_input::TextInput,
+        view_picker::ViewPicker,
+    },
+    route::Route,
+    services::{
+        saved_view_service::SavedViewService,
+        ticket_service::{TicketPageQuery, TicketService},
+        user_service::UserService,
+    },
+};
+use implicit_clone::{
+    sync,
+    unsync::{IArray, IString},
+};
+use shared::{
+    api::error::error_response::ErrorResponse,
+    dtos::{
+        bulk_dto::{BulkAction, BulkTicketRequest, BulkTicketResponse, BULK_MAX_ITEMS},
+        identity::Identity,
+        page::Page,
+        preferences_dto::PreferencesDto,
+        saved_view_dto::SavedViewDto,
+        ticket_dto::{ITicketDto, TicketDto, TicketField, TicketValue},
+    },
+    validation::validation_messages::{IValidationMessages, ValidationMessagesTrait},
+};
+use std::str::FromStr;
+use yew::prelude::*;
+use yew_router::prelude::Link;
+
+const DEFAULT_LIMIT: u64 = 10;
+const DEFAULT_OFFSET: u64 = 0;
+
+const QUERY_EXAMPLES: [(&str, &str); 5] = [
+    (
+        "status:Started,Reviewing",
+        "Tickets in any of the listed statuses",
+    ),
+    ("priority>=High", "High and Critical tickets"),
+    (
+        "assignee:me",
+        "Tickets assigned to you (also: none, an email address)",
+    ),
+    (
+        "created>-7d",
+        "Created within the last 7 days (h, d, w or YYYY-MM-DD)",
+    ),
+    (
+        "project:12 \"login bug\"",
+        "Tickets of project 12 mentioning \"login bug\"",
+    ),
+];
+
+pub enum Msg {
+    ContextChanged(AppStateContext),
+    FetchedTickets(Page<TicketDto>),
+    FetchFailed(ErrorResponse),
+    SortTickets(TableHeadData),
+    UpdateOffset(u64),
+    UpdateQuery(AttrValue),
+    SubmitQuery,
+    ToggleHelp,
+    FetchedViews(Vec<SavedViewDto>),
+    SelectView(Option<u64>),
+    SaveView((AttrValue, Option<u64>)),
+    UpdateView(u64),
+    SavedView(SavedViewDto),
+    SaveViewFailed(ErrorResponse),
+    DeleteView(u64),
+    DeletedView(u64),
+    SetDefaultView(Option<u64>),
+    UpdatedPreferences(PreferencesDto),
+    ToggleColumn(TicketField),
+    SelectRows(TableRowSelection),
+    ClearSelection,
+    ApplyBulk(BulkAction),
+    BulkApplied(BulkTicketResponse),
+    BulkFailed(ErrorResponse),
+}
+
+pub struct TicketListPage {
+    total: i64,
+    list: Vec<TicketDto>,
+    query: AttrValue,
+    query_error: IValidationMessages,
+    /// Character position of the query error, the rest of the query is highlighted from there.
+    query_error_position: Option<usize>,
+    show_help: bool,
+    views: Vec<SavedViewDto>,
+    selected_view: Option<u64>,
+    default_view_applied: bool,
+    view_error: Option<AttrValue>,
+    columns: Vec<TicketField>,
+    /// Row indexes of the current page selected for a bulk action.
+    selected: Vec<usize>,
+    bulk_result: Option<BulkTicketResponse>,
+    bulk_error: Option<AttrValue>,
+    sort: Option<IString>,
+    order: Option<IString>,
+    limit: u64,
+    offset: u64,
+    app_state: AppStateContext,
+    _listener: ContextHandle<AppStateContext>,
+}
+
+impl Component for TicketListPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (app_state, _listener) = ctx
+            .link()
+            .context::<AppStateContext>(ctx.link().callback(Msg::ContextChanged))
+            .expect("context to be set");
+        let page = Self {
+            total: 0,
+            list: Vec::new(),
+            query: AttrValue::default(),
+            query_error: None,
+            query_error_position: None,
+            show_help: false,
+            views: Vec::new(),
+            selected_view: None,
+            default_view_applied: false,
+            view_error: None,
+            columns: DEFAULT_TICKET_FIELDSET.to_vec(),
+            selected: Vec::new(),
+            bulk_result: None,
+            bulk_error: None,
+            sort: None,
+            order: None,
+            limit: DEFAULT_LIMIT,
+            offset: DEFAULT_OFFSET,
+            app_state,
+            _listener,
+        };
+        page.init(ctx);
+        if let Some(Identity { token, .. }) = &page.app_state.identity {
+            SavedViewService::fetch_all(
+                token.clone(),
+                None,
+                ctx.link().callback(Msg::FetchedViews),
+            );
+        }
+        page
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::ContextChanged(state) => {
+                self.app_state = state;
+                self.apply_default_view(ctx);
+            }
+            Msg::FetchedTickets(ticket_page) => {
+                self.query_error = None;
+                self.query_error_position = None;
+                self.selected.clear();
+                self.total = ticket_page.total;
+                self.list = ticket_page.list;
+                self.limit = ticket_page.limit;
+                self.offset = ticket_page.offset;
+            }
+            Msg::FetchFailed(error_response) => {
+                self.query_error_position = error_response
+                    .details
+                    .as_ref()
+                    .and_then(|details| details.get_property_position("jql"));
+                self.query_error = match &error_response.details {
+                    Some(details) => details.get_property_messages("jql"),
+                    None => None,
+                }
+                .or_else(|| {
+                    Some(sync::IArray::from(vec![sync::IString::from(
+                        error_response.message,
+                    )]))
+                });
+                self.total = 0;
+                self.list = Vec::new();
+            }
+            Msg::SortTickets(sortdata) => {
+                self.sort = sortdata.sort.as_ref().map(|s| s.sort.clone());
+                self.order = sortdata
+                    .sort
+                    .as_ref()
+                    .map(|s| IString::from(s.order.to_string()));
+                self.init(ctx);
+            }
+            Msg::UpdateOffset(offset) => {
+                self.offset = offset;
+                self.init(ctx);
+            }
+            Msg::UpdateQuery(query) => {
+                self.query = query;
+                self.query_error_position = None;
+                return false;
+            }
+            Msg::SubmitQuery => {
+                self.offset = DEFAULT_OFFSET;
+                self.init(ctx);
+                return false;
+            }
+            Msg::ToggleHelp => {
+                self.show_help = !self.show_help;
+            }
+            Msg::FetchedViews(views) => {
+                self.views = views;
+                self.apply_default_view(ctx);
+            }
+            Msg::SelectView(id) => {
+                self.select_view(ctx, id);
+            }
+            Msg::SaveView((name, project_id)) => {
+                let view = SavedViewDto {
+                    name: name.to_string(),
+                    project_id,
+                    ..self.current_view()
+                };
+                if let Some(Identity { token, .. }) = &self.app_state.identity {
+                    SavedViewService::create(
+                        token.clone(),
+                        view,
+                        ctx.link().callback(Msg::SavedView),
+                        ctx.link().callback(Msg::SaveViewFailed),
+                    );
+                }
+                return false;
+            }
+            Msg::UpdateView(id) => {
+                if let (Some(Identity { token, .. }), Some(original)) = (
+                    &self.app_state.identity,
+                    self.views.iter().find(|v| v.id == Some(id)),
+                ) {
+                    let view = SavedViewDto {
+                        id: original.id,
+                        name: original.name.clone(),
+                        project_id: original.project_id,
+                        ..self.current_view()
+                    };
+                    SavedViewService::update(
+                        token.clone(),
+                        view,
+                        ctx.link().callback(Msg::SavedView),
+                        ctx.link().callback(Msg::SaveViewFailed),
+                    );
+                }
+                return false;
+            }
+            Msg::SavedView(view) => {
+                self.view_error = None;
+                self.selected_view = view.id;
+                match self.views.iter_mut().find(|v| v.id == view.id) {
+                    Some(existing) => *existing = view,
+                    None => self.views.push(view),
+                }
+            }
+            Msg::SaveViewFailed(error_response) => {
+                self.view_error = Some(AttrValue::from(error_response.to_string()));
+            }
+            Msg::DeleteView(id) => {
+                if let Some(Identity { token, .. }) = &self.app_state.identity {
+                    SavedViewService::delete(
+                        token.clone(),
+                        id,
+                        ctx.link().batch_callback(move |deleted: bool| {
+                            deleted.then_some(Msg::DeletedView(id))
+                        }),
+                    );
+                }
+                return false;
+            }
+            Msg::DeletedView(id) => {
+                self.views.retain(|v| v.id != Some(id));
+                if self.selected_view == Some(id) {
+                    self.selected_view = None;
+                }
+                if self.default_view() == Some(id) {
+                    ctx.link().send_message(Msg::SetDefaultView(None));
+                }
+            }
+            Msg::SetDefaultView(id) => {
+                if let Some(Identity { token, .. }) = &self.app_state.identity {
+                    UserService::update_preferences(
+                        token.clone(),
+                        PreferencesDto {
+                            default_view: id,
+                            ..self.app_state.preferences.clone().unwrap_or_default()
+                        },
+                        ctx.link().callback(Msg::UpdatedPreferences),
+                        ctx.link().callback(Msg::SaveViewFailed),
+                    );
+                }
+                return false;
+            }
+            Msg::UpdatedPreferences(preferences) => {
+                AppState::update_preferences(&self.app_state, Some(preferences));
+            }
+            Msg::ToggleColumn(field) => match self.columns.iter().position(|c| *c == field) {
+                Some(index) => {
+                    self.columns.remove(index);
+                }
+                None => {
+                    self.columns.push(field);
+                    self.columns.sort_by_key(|c| usize::from(*c));
+                }
+            },
+            Msg::SelectRows(TableRowSelection::Row(index, true)) => {
+                if !self.selected.contains(&index) && self.selected.len() < BULK_MAX_ITEMS {
+                    self.selected.push(index);
+                }
+            }
+            Msg::SelectRows(TableRowSelection::Row(index, false)) => {
+                self.selected.retain(|i| *i != index);
+            }
+            Msg::SelectRows(TableRowSelection::All(all)) => {
+                self.selected = match all {
+                    true => (0..self.list.len().min(BULK_MAX_ITEMS)).collect(),
+                    false => Vec::new(),
+                };
+            }
+            Msg::ClearSelection => {
+                self.selected.clear();
+            }
+            Msg::ApplyBulk(action) => {
+                let ids = self
+                    .selected
+                    .iter()
+                    .filter_map(|i| self.list.get(*i).and_then(|t| t.id))
+                    .collect::<Vec<u64>>();
+                if let Some(Identity { token, .. }) = &self.app_state.identity {
+                    TicketService::bulk(
+                        token.clone(),
+                        BulkTicketRequest { ids, action },
+                        ctx.link().callback(Msg::BulkApplied),
+                        ctx.link().callback(Msg::BulkFailed),
+                    );
+                }
+                return false;
+            }
+            Msg::BulkApplied(response) => {
+                self.bulk_error = None;
+                self.bulk_result = Some(response);
+                self.init(ctx);
+            }
+            Msg::BulkFailed(error_response) => {
+                self.bulk_result = None;
+                self.bulk_error = Some(AttrValue::from(error_response.to_string()));
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let datasource: ITableDataSource<TicketField, ITicketDto, TicketValue> =
+            TicketDataSource::new(&self.list, self.columns.clone()).into();
+
+        let sorthandler = Some(ctx.link().callback(Msg::SortTickets));
+        let paginghandler = ctx.link().callback(Msg::UpdateOffset);
+        let on_change = ctx.link().callback(Msg::UpdateQuery);
+        let onsubmit = ctx.link().callback(|e: SubmitEvent| {
+            e.prevent_default();
+            Msg::SubmitQuery
+        });
+        let on_help = ctx.link().callback(|_: MouseEvent| Msg::ToggleHelp);
+        let selecthandler = Some(ctx.link().callback(Msg::SelectRows));
+
+        html! {
+            <div class="container">
+                <section class="hero">
+                    <div class="hero-body">
+                        <div class="container">
+                            <h1 class="title">{ "Ticket list" }</h1>
+                            <h2 class="subtitle">
+                                { "Here you can search all tickets of the application" }
+                            </h2>
+                        </div>
+                    </div>
+                </section>
+                <div class="section pb-0">
+                    <ViewPicker
+                        views={IArray::from(self.views.clone())}
+                        selected={self.selected_view}
+                        default_view={self.default_view()}
+                        columns={IArray::from(self.columns.clone())}
+                        on_select={ctx.link().callback(Msg::SelectView)}
+                        on_save={ctx.link().callback(Msg::SaveView)}
+                        on_update={ctx.link().callback(Msg::UpdateView)}
+                        on_delete={ctx.link().callback(Msg::DeleteView)}
+                        on_set_default={ctx.link().callback(Msg::SetDefaultView)}
+                        on_toggle_column={ctx.link().callback(Msg::ToggleColumn)}
+                    />
+                    if let Some(error) = &self.view_error {
+                        <p class="help is-danger mb-4">{ error }</p>
+                    }
+                    <form {onsubmit}>
+                        <Field label="Query" help={&self.query_error}>
+                            <TextInput
+                                value={self.query.clone()}
+                                {on_change}
+                                valid={self.query_error.is_none()}
+                                placeholder="status:Started,Reviewing priority>=High assignee:me created>-7d \"login bug\""
+                            />
+                        </Field>
+                        { self.query_error_marker() }
+                        <div class="field is-grouped">
+                            <p class="control">
+                                <button type="submit" class="button is-link">{ "Search" }</button>
+                            </p>
+                            <p class="control">
+                                <button type="button" class="button is-text" onclick={on_help}>
+                                    { if self.show_help { "Hide syntax help" } else { "Syntax help" } }
+                                </button>
+                            </p>
+                        </div>
+                    </form>
+                    if self.show_help {
+                        { self.syntax_help() }
+                    }
+                </div>
+                <div class="section">
+                    if !self.selected.is_empty() {
+                        <BulkActionBar
+                            count={self.selected.len()}
+                            on_apply={ctx.link().callback(Msg::ApplyBulk)}
+                            on_clear={ctx.link().callback(|_| Msg::ClearSelection)}
+                        />
+                    }
+                    { self.bulk_result_view() }
+                    <Table<TicketField, ITicketDto, TicketValue>
+                        {datasource}
+                        {sorthandler}
+                        selected={IArray::from(self.selected.clone())}
+                        {selecthandler}
+                    />
+                    <Pagination total={self.total} offset={self.offset} limit={self.limit} {paginghandler} />
+                </div>
+                <div class="section pt-0">
+                    <div class="field is-grouped">
+                        <p class="control">
+                            <Link<Route> classes={classes!("button", "is-full")} to={Route::TicketNew}>
+                                <span class="icon is-small">
+                                    <i class="fas fa-plus"></i>
+                                </span>
+                                <span>{ "Create a new ticket" }</span>
+                            </Link<Route>>
+                        </p>
+                    </div>
+                </div>
+            </div>
+        }
+    }
+}
+
+impl TicketListPage {
+    fn init(&self, ctx: &Context<Self>) {
+        if let Some(Identity { token, .. }) = &self.app_state.identity {
+            let jql = match self.query.trim() {
+                "" => None,
+                q => Some(IString::from(q.to_owned())),
+            };
+            TicketService::fetch_page(
+                token.clone(),
+                TicketPageQuery {
+                    jql,
+                    sort: self.sort.clone(),
+                    order: self.order.clone(),
+                    limit: Some(self.limit),
+                    offset: Some(self.offset),
+                },
+                ctx.link().callback(Msg::FetchedTickets),
+                ctx.link().callback(Msg::FetchFailed),
+            );
+        }
+    }
+
+    fn default_view(&self) -> Option<u64> {
+        self.app_state
+            .preferences
+            .as_ref()
+            .and_then(|p| p.default_view)
+    }
+
+    /// Applies the default view once both the views and the preferences have arrived.
+    fn apply_default_view(&mut self, ctx: &Context<Self>) {
+        if self.default_view_applied || self.views.is_empty() {
+            return;
+        }
+        if let Some(id) = self.default_view() {
+            self.default_view_applied = true;
+            self.select_view(ctx, Some(id));
+        }
+    }
+
+    fn select_view(&mut self, ctx: &Context<Self>, id: Option<u64>) {
+        let view = id.and_then(|id| self.views.iter().find(|v| v.id == Some(id)).cloned());
+        self.selected_view = view.as_ref().and_then(|v| v.id);
+        let view = view.unwrap_or_default();
+        self.query = AttrValue::from(view.filter);
+        self.sort = view.sort.map(IString::from);
+        self.order = view.order.map(IString::from);
+        self.columns = view
+            .columns
+            .iter()
+            .filter_map(|c| TicketField::from_str(c).ok())
+            .collect();
+        if self.columns.is_empty() {
+            self.columns = DEFAULT_TICKET_FIELDSET.to_vec();
+        }
+        self.offset = DEFAULT_OFFSET;
+        self.init(ctx);
+    }
+
+    /// The current filter, sort and columns as an unnamed view.
+    fn current_view(&self) -> SavedViewDto {
+        SavedViewDto {
+            filter: self.query.trim().to_owned(),
+            sort: self.sort.as_ref().map(|s| s.to_string()),
+            order: self.order.as_ref().map(|o| o.to_string()),
+            columns: self.columns.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    /// Summary of the last bulk action, listing the tickets it failed for.
+    /// The submitted query with the token the error is about highlighted.
+    fn query_error_marker(&self) -> Html {
+        let Some(position) = self.query_error_position else {
+            return html! {};
+        };
+        let before = self.query.chars().take(position).collect::<String>();
+        let token = self
+            .query
+            .chars()
+            .skip(position)
+            .take_while(|c| !c.is_whitespace())
+            .collect::<String>();
+        let after = self
+            .query
+            .chars()
+            .skip(position + token.chars().count())
+            .collect::<String>();
+        html! {
+            <p class="help is-family-monospace mb-3">
+                { before }<mark>{ token }</mark>{ after }
+            </p>
+        }
+    }
+
+    fn bulk_result_view(&self) -> Html {
+        if let Some(error) = &self.bulk_error {
+            return html! { <p class="help is-danger mb-4">{ error }</p> };
+        }
+        match &self.bulk_result {
+            Some(response) => html! {
+                <article class={classes!("message", if response.failed > 0 { "is-warning" } else { "is-success" })}>
+                    <div class="message-body">
+                        <p>{ format!("{} succeeded, {} failed", response.succeeded, response.failed) }</p>
+                        <ul>
+                            {
+                                response.results.iter().filter(|r| !r.success).map(|r| html! {
+                                    <li>
+                                        <Link<Route> to={Route::Ticket { id: r.id }}>{ format!("#{}", r.id) }</Link<Route>>
+                                        { format!(": {}", r.error.clone().unwrap_or_default()) }
+                                    </li>
+                                }).collect::<Html>()
+                            }
+                        </ul>
+                    </div>
+                </article>
+            },
+            None => html! {},
+        }
+    }
+
+    fn syntax_help(&self) -> Html {
+        html! {
+            <article class="message is-info mt-4">
+                <div class="message-body content">
+                    <p>
+                        { "Combine " }<code>{ "field:value" }</code>
+                        { " conditions and free text separated by spaces; all of them must match. " }
+                        { "Lists of values are separated by commas, quoted text is searched in title and description." }
+                    </p>
+                    <p>
+                        <strong>{ "Fields: " }</strong>
+                        <code>{ "project" }</code>{ " (id or none), " }
+                        <code>{ "status" }</code>{ ", " }
+                        <code>{ "priority" }</code>{ ", " }
+                        <code>{ "assignee" }</code>{ " (me, none, email or user id), " }
+                        <code>{ "created" }</code>
+                        <br />
+                        <strong>{ "Operators: " }</strong>
+                        <code>{ ":" }</code>{ " " }<code>{ "!=" }</code>
+                        { ", and for priority and created also " }
+                        <code>{ ">" }</code>{ " " }<code>{ ">=" }</code>{ " " }
+                        <code>{ "<" }</code>{ " " }<code>{ "<=" }</code>
+                    </p>
+                    <table class="table is-narrow">
+                        <tbody>
+                            {
+                                QUERY_EXAMPLES.iter().map(|(example, description)| html! {
+                                    <tr>
+                                        <td><code>{ *example }</code></td>
+                                        <td>{ *description }</td>
+                                    </tr>
+                                }).collect::<Html>()
+                            }
+                        </tbody>
+                    </table>
+                </div>
+            </article>
+        }
+    }
+}
@@ -1,17 +1,22 @@
 use crate::app_state::AppStateContext;
 use crate::components::button_link::{ButtonLink, ButtonLinkData};
 use crate::components::priority_tag::PriorityTag;
+use crate::components::sla_badge::SlaBadge;
 use crate::helpers::event_helper::get_file_from_change_event;
 use crate::route::Route;
 use crate::services::project_service::ProjectService;
+use crate::services::sla_service::SlaService;
 use crate::services::{ticket_service::TicketService, user_service::UserService};
 use implicit_clone::sync::IString;
 use shared::dtos::identity::Identity;
 use shared::dtos::project_dto::ProjectDto;
+use shared::dtos::sla_dto::TicketSlaDto;
 use shared::dtos::ticket_dto::TicketDto;
 use shared::dtos::user_dto::UserDto;
+use shared::dtos::watcher_dto::WatcherDto;
 use std::rc::Rc;
 use yew::prelude::*;
+use yew_router::prelude::Link;
 
 const BUTTON_CLASS: &str = "button is-one-third";
 const UPLOAD_ICON_CLASS: &str = "fas";
@@ -27,8 +32,10 @@ pub enum Msg {
     FetchedTicket(TicketDto),
     FetchedProject(ProjectDto),
     FetchedUser(UserDto),
+    FetchedSla(TicketSlaDto),
     Subscribe,
     Subscribed(bool),
+    FetchedWatchers(Vec<WatcherDto>),
     Upload(Event),
     Uploaded(bool),
 }
@@ -37,7 +44,9 @@ pub struct TicketPage {
     ticket: TicketDto,
     project: Option<ButtonLinkData<Route>>,
     user: Option<ButtonLinkData<Route>>,
+    sla: TicketSlaDto,
     is_subscribed: bool,
+    watchers: Vec<WatcherDto>,
     is_loading: bool,
     is_uploaded: bool,
     file_name: IString,
@@ -65,12 +74,19 @@ impl Component for TicketPage {
                 ctx.props().id,
                 ctx.link().callback(Msg::Subscribed),
             );
+            TicketService::fetch_watchers(
+                token.to_string(),
+                ctx.props().id,
+                ctx.link().callback(Msg::FetchedWatchers),
+            );
         }
         Self {
             ticket: TicketDto::default(),
             project: None,
             user: None,
+            sla: TicketSlaDto::default(),
             is_subscribed: false,
+            watchers: Vec::new(),
             is_loading: false,
             is_uploaded: false,
             file_name: IString::default(),
@@ -86,6 +102,11 @@ impl Component for TicketPage {
                 ctx.props().id,
                 ctx.link().callback(Msg::FetchedTicket),
             );
+            TicketService::fetch_watchers(
+                token.to_string(),
+                ctx.props().id,
+                ctx.link().callback(Msg::FetchedWatchers),
+            );
         }
         true
     }
@@ -112,6 +133,13 @@ impl Component for TicketPage {
                             ctx.link().callback(Msg::FetchedUser),
                         );
                     }
+                    if let Some(id) = self.ticket.id {
+                        SlaService::fetch_ticket(
+                            token.to_string(),
+                            id,
+                            ctx.link().callback(Msg::FetchedSla),
+                        );
+                    }
                 }
             }
             Msg::FetchedProject(project) => {
@@ -130,6 +158,9 @@ impl Component for TicketPage {
                     },
                 });
             }
+            Msg::FetchedSla(sla) => {
+                self.sla = sla;
+            }
             Msg::Subscribe => {
                 if let Some(Identity { token, .. }) = &self.app_state.identity {
                     TicketService::subscribe(
@@ -141,6 +172,17 @@ impl Component for TicketPage {
             }
             Msg::Subscribed(res) => {
                 self.is_subscribed = res;
+                // Subscribing adds or removes the user from the watchers
+                if let Some(Identity { token, .. }) = &self.app_state.identity {
+                    TicketService::fetch_watchers(
+                        token.to_string(),
+                        ctx.props().id,
+                        ctx.link().callback(Msg::FetchedWatchers),
+                    );
+                }
+            }
+            Msg::FetchedWatchers(watchers) => {
+                self.watchers = watchers;
             }
             Msg::Upload(e) => {
                 if let (Some(Identity { token, .. }), Some(file)) =
@@ -169,7 +211,9 @@ impl Component for TicketPage {
             ticket,
             project,
             user,
+            sla,
             is_subscribed: has_subscribed,
+            watchers,
             ..
         } = self;
         let priority = Rc::new(ticket.priority.clone());
@@ -179,7 +223,12 @@ impl Component for TicketPage {
                 <div class="tile is-ancestor is-vertical">
                     <div class="tile is-parent">
                         <article class="tile is-child notification is-light">
-                            <p class="title">{ &ticket.title }</p>
+                            <p class="title">
+                                if let Some(key) = &ticket.key {
+                                    <span class="tag is-medium mr-3">{ key }</span>
+                                }
+                                { &ticket.title }
+                            </p>
                         </article>
                     </div>
                     <div class="tile">
@@ -205,12 +254,38 @@ impl Component for TicketPage {
                                         <div class="column is-one-quarter"><h5 class="title is-5">{ "Status" }</h5></div>
                                         <div class="column"><span class="tag is-white">{ &ticket.status.to_string() }</span></div>
                                     </div>
+                                    if !sla.clocks.is_empty() {
+                                        <div class="columns">
+                                            <div class="column is-one-quarter"><h5 class="title is-5">{ "SLA" }</h5></div>
+                                            <div class="column">
+                                                <div class="tags">
+                                                    { for sla.clocks.iter().map(|clock| html! { <SlaBadge clock={clock.clone()} /> }) }
+                                                </div>
+                                            </div>
+                                        </div>
+                                    }
                                     <div class="columns">
                                         <div class="column is-one-quarter"><h5 class="title is-5">{ "Assigned to" }</h5></div>
                                         <div class="column">
                                             <ButtonLink<Route> data={user.clone()} />
                                         </div>
                                     </div>
+                                    <div class="columns">
+                                        <div class="column is-one-quarter"><h5 class="title is-5">{ "Watchers" }</h5></div>
+                                        <div class="column">
+                                            if watchers.is_empty() {
+                                                <span class="has-text-grey">{ "Nobody is watching this ticket" }</span>
+                                            } else {
+                                                <div class="tags">
+                                                    { for watchers.iter().map(|watcher| html! {
+                                                        <Link<Route> classes={classes!("tag", "is-white")} to={Route::User { id: watcher.user_id }}>
+                                                            { &watcher.name }
+                                                        </Link<Route>>
+                                                    }) }
+                                                </div>
+                                            }
+                                        </div>
+                                    </div>
                                 </div>
                             </article>
                         </div>
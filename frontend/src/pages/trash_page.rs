@@ -0,0 +1,177 @@
+use crate::{app_state::AppStateContext, services::trash_service::TrashService};
+use shared::{
+    api::error::error_response::ErrorResponse,
+    dtos::trash_dto::{TrashEntryDto, TrashKind},
+};
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+pub enum Msg {
+    ContextChanged(AppStateContext),
+    SelectKind(TrashKind),
+    Fetched(Vec<TrashEntryDto>),
+    FetchFailed(ErrorResponse),
+    Restore(u64),
+    Restored(bool),
+}
+
+pub struct TrashPage {
+    kind: TrashKind,
+    list: Vec<TrashEntryDto>,
+    error: Option<AttrValue>,
+    app_state: AppStateContext,
+    _listener: ContextHandle<AppStateContext>,
+}
+
+impl Component for TrashPage {
+    type Message = Msg;
+    type Properties = ();
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let (app_state, _listener) = ctx
+            .link()
+            .context::<AppStateContext>(ctx.link().callback(Msg::ContextChanged))
+            .expect("context to be set");
+        let page = Self {
+            kind: TrashKind::Tickets,
+            list: Vec::new(),
+            error: None,
+            app_state,
+            _listener,
+        };
+        page.fetch(ctx);
+        page
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::ContextChanged(state) => {
+                self.app_state = state;
+            }
+            Msg::SelectKind(kind) => {
+                self.kind = kind;
+                self.list = Vec::new();
+                self.fetch(ctx);
+            }
+            Msg::Fetched(list) => {
+                self.error = None;
+                self.list = list;
+            }
+            Msg::FetchFailed(error_response) => {
+                self.error = Some(AttrValue::from(error_response.message));
+            }
+            Msg::Restore(id) => {
+                if let Some(identity) = &self.app_state.identity {
+                    TrashService::restore(
+                        identity.token.clone(),
+                        self.kind,
+                        id,
+                        ctx.link().callback(Msg::Restored),
+                    );
+                }
+                return false;
+            }
+            Msg::Restored(success) => {
+                if success {
+                    self.fetch(ctx);
+                } else {
+                    self.error = Some(AttrValue::from("The entry could not be restored"));
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <div class="container">
+                <section class="hero">
+                    <div class="hero-body">
+                        <div class="container">
+                            <h1 class="title">{ "Trash" }</h1>
+                            <h2 class="subtitle">
+                                { "Deleted entries are kept here for a while before they are removed for good" }
+                            </h2>
+                        </div>
+                    </div>
+                </section>
+                <div class="section">
+                    <div class="tabs">
+                        <ul>
+                            {
+                                TrashKind::iter().map(|kind| {
+                                    let onclick = ctx.link().callback(move |_: MouseEvent| Msg::SelectKind(kind));
+                                    html! {
+                                        <li class={classes!((kind == self.kind).then_some("is-active"))}>
+                                            <a {onclick}>{ kind.to_string() }</a>
+                                        </li>
+                                    }
+                                }).collect::<Html>()
+                            }
+                        </ul>
+                    </div>
+                    if let Some(error) = &self.error {
+                        <p class="help is-danger mb-4">{ error }</p>
+                    }
+                    { self.table_view(ctx) }
+                </div>
+            </div>
+        }
+    }
+}
+
+impl TrashPage {
+    fn fetch(&self, ctx: &Context<Self>) {
+        if let Some(identity) = &self.app_state.identity {
+            TrashService::fetch_all(
+                identity.token.clone(),
+                self.kind,
+                ctx.link().callback(Msg::Fetched),
+                ctx.link().callback(Msg::FetchFailed),
+            );
+        }
+    }
+
+    fn table_view(&self, ctx: &Context<Self>) -> Html {
+        if self.list.is_empty() {
+            return html! { <p>{ "The trash is empty." }</p> };
+        }
+        html! {
+            <table class="table is-fullwidth is-hoverable">
+                <thead>
+                    <tr>
+                        <th>{ "#" }</th>
+                        <th>{ "Name" }</th>
+                        <th>{ "Deleted at" }</th>
+                        <th>{ "Deleted by" }</th>
+                        <th>{ "Purged at" }</th>
+                        <th></th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        self.list.iter().map(|entry| {
+                            let id = entry.id;
+                            let onclick = ctx.link().callback(move |_: MouseEvent| Msg::Restore(id));
+                            html! {
+                                <tr>
+                                    <td>{ entry.id }</td>
+                                    <td>{ entry.label.clone() }</td>
+                                    <td>{ entry.deleted_at.format("%Y-%m-%d %H:%M").to_string() }</td>
+                                    <td>{ entry.deleted_by.clone().unwrap_or_default() }</td>
+                                    <td>{ entry.purge_at.format("%F").to_string() }</td>
+                                    <td>
+                                        <button class="button is-small is-info is-light" {onclick}>
+                                            <span class="icon is-small"><i class="fas fa-rotate-left"></i></span>
+                                            <span>{ "Restore" }</span>
+                                        </button>
+                                    </td>
+                                </tr>
+                            }
+                        }).collect::<Html>()
+                    }
+                </tbody>
+            </table>
+        }
+    }
+}
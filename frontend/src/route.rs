@@ -22,6 +22,10 @@ pub enum Route {
     TicketNew,
     #[at("/tickets/:id")]
     Ticket { id: u64 },
+    #[at("/tickets")]
+    Tickets,
+    #[at("/trash")]
+    Trash,
     #[at("/login")]
     Login,
     #[at("/verify/:token")]
@@ -46,7 +50,9 @@ impl RouteSelector {
                 | Route::User { id: _ }
                 | Route::Users
                 | Route::TicketNew
+                | Route::Tickets
                 | Route::Ticket { id: _ }
+                | Route::Trash
         )
     }
 
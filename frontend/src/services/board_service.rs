@@ -0,0 +1,72 @@
+use super::get_api_url;
+use gloo_net::http::Request;
+use shared::api::error::error_response::ErrorResponse;
+use shared::dtos::board_dto::BoardDto;
+use yew::{platform::spawn_local, Callback};
+
+const PROJECTS_ENDPOINT: &str = "projects";
+const BOARD_ENDPOINT: &str = "board";
+
+pub struct BoardService;
+
+impl BoardService {
+    /// Columns of the board of the project, the standard ones if it has not configured any.
+    pub fn fetch(jwt: String, project_id: u64, callback: Callback<BoardDto>) {
+        spawn_local(async move {
+            let board: BoardDto = Request::get(Self::url(project_id).as_str())
+                .header("Authorization", format!("Bearer {}", jwt).as_str())
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+
+            callback.emit(board);
+        });
+    }
+
+    pub fn update(
+        jwt: String,
+        project_id: u64,
+        board: BoardDto,
+        callback: Callback<BoardDto>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        spawn_local(async move {
+            let res = Request::put(Self::url(project_id).as_str())
+                .header("Authorization", format!("Bearer {}", jwt).as_str())
+                .json(&board)
+                .unwrap()
+                .send()
+                .await;
+
+            match res {
+                Ok(resp) => match resp.text().await {
+                    Ok(text) => match serde_json::from_str::<BoardDto>(text.as_str()) {
+                        Ok(returned_board) => callback.emit(returned_board),
+                        Err(e) => {
+                            log::debug!("Serde result error: {}", e.to_string());
+                            match serde_json::from_str::<ErrorResponse>(text.as_str()) {
+                                Ok(error_response) => callback_error.emit(error_response),
+                                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                            }
+                        }
+                    },
+                    Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                },
+                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+            }
+        });
+    }
+
+    fn url(project_id: u64) -> String {
+        format!(
+            "{}{}/{}/{}",
+            get_api_url(),
+            PROJECTS_ENDPOINT,
+            project_id,
+            BOARD_ENDPOINT
+        )
+    }
+}
@@ -0,0 +1,36 @@
+use super::get_api_url;
+use gloo_net::http::Request;
+use shared::dtos::custom_field_dto::CustomFieldDto;
+use yew::{platform::spawn_local, Callback};
+
+const PROJECTS_ENDPOINT: &str = "projects";
+const FIELDS_ENDPOINT: &str = "fields";
+
+pub struct CustomFieldService;
+
+impl CustomFieldService {
+    /// Custom fields defined by the project, in the order they were created.
+    pub fn fetch_all(jwt: String, project_id: u64, callback: Callback<Vec<CustomFieldDto>>) {
+        spawn_local(async move {
+            let list: Vec<CustomFieldDto> = Request::get(
+                format!(
+                    "{}{}/{}/{}",
+                    get_api_url(),
+                    PROJECTS_ENDPOINT,
+                    project_id,
+                    FIELDS_ENDPOINT
+                )
+                .as_str(),
+            )
+            .header("Authorization", format!("Bearer {}", jwt).as_str())
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+
+            callback.emit(list);
+        });
+    }
+}
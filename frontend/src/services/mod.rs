@@ -1,6 +1,13 @@
 pub mod auth_service;
+pub mod board_service;
+pub mod custom_field_service;
 pub mod project_service;
+pub mod report_service;
+pub mod saved_view_service;
+pub mod sla_service;
+pub mod sprint_service;
 pub mod ticket_service;
+pub mod trash_service;
 pub mod user_service;
 
 pub fn get_api_url() -> String {
@@ -4,14 +4,45 @@ use implicit_clone::unsync::IString;
 use shared::api::error::error_response::ErrorResponse;
 use shared::dtos::page::Page;
 use shared::dtos::project_dto::{ProjectDto, ProjectTickets};
+use shared::dtos::project_member_dto::{ProjectMemberDto, ProjectMemberRoleDto, ProjectRole};
 use shared::dtos::ticket_dto::TicketDto;
+use uuid::Uuid;
 use yew::{platform::spawn_local, Callback};
 
 const PROJECTS_ENDPOINT: &str = "projects";
 const TICKETS_ENDPOINT: &str = "tickets";
+const MEMBERS_ENDPOINT: &str = "members";
 
 pub struct ProjectService;
 
+/// Search and filters of the project list, empty values are left out.
+#[derive(Clone, Default, PartialEq)]
+pub struct ProjectFilter {
+    pub q: IString,
+    pub owner: Option<Uuid>,
+    pub active: IString,
+    pub deadline_from: IString,
+    pub deadline_to: IString,
+}
+
+impl ProjectFilter {
+    fn params(&self) -> Vec<(&'static str, String)> {
+        [
+            ("q", self.q.trim().to_owned()),
+            (
+                "owner",
+                self.owner.map(|o| o.to_string()).unwrap_or_default(),
+            ),
+            ("active", self.active.to_string()),
+            ("deadline_from", self.deadline_from.to_string()),
+            ("deadline_to", self.deadline_to.to_string()),
+        ]
+        .into_iter()
+        .filter(|(_, value)| !value.is_empty())
+        .collect()
+    }
+}
+
 impl ProjectService {
     pub fn fetch(jwt: String, id: u64, callback: Callback<ProjectDto>) {
         spawn_local(async move {
@@ -31,6 +62,7 @@ impl ProjectService {
 
     pub fn fetch_all(
         jwt: String,
+        filter: ProjectFilter,
         sort: Option<IString>,
         order: Option<IString>,
         limit: Option<u64>,
@@ -39,7 +71,8 @@ impl ProjectService {
     ) {
         spawn_local(async move {
             let mut request_builder =
-                Request::get(format!("{}{}", get_api_url(), PROJECTS_ENDPOINT).as_str());
+                Request::get(format!("{}{}", get_api_url(), PROJECTS_ENDPOINT).as_str())
+                    .query(filter.params());
             if let Some(s) = sort {
                 request_builder = request_builder.query([("sort", s.as_str())]);
             }
@@ -185,4 +218,133 @@ impl ProjectService {
             }
         });
     }
+
+    pub fn fetch_members(jwt: String, project_id: u64, callback: Callback<Vec<ProjectMemberDto>>) {
+        spawn_local(async move {
+            let list: Vec<ProjectMemberDto> = Request::get(
+                format!(
+                    "{}{}/{}/{}",
+                    get_api_url(),
+                    PROJECTS_ENDPOINT,
+                    project_id,
+                    MEMBERS_ENDPOINT
+                )
+                .as_str(),
+            )
+            .header("Authorization", format!("Bearer {}", jwt).as_str())
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+
+            callback.emit(list);
+        });
+    }
+
+    /// Role of the signed in user in the project, nothing is emitted for non-members.
+    pub fn fetch_own_role(jwt: String, project_id: u64, callback: Callback<ProjectRole>) {
+        spawn_local(async move {
+            let res = Request::get(
+                format!(
+                    "{}{}/{}/{}/me",
+                    get_api_url(),
+                    PROJECTS_ENDPOINT,
+                    project_id,
+                    MEMBERS_ENDPOINT
+                )
+                .as_str(),
+            )
+            .header("Authorization", format!("Bearer {}", jwt).as_str())
+            .send()
+            .await;
+
+            if let Ok(resp) = res {
+                if let Ok(ProjectMemberRoleDto { role }) = resp.json().await {
+                    callback.emit(role);
+                }
+            }
+        });
+    }
+
+    /// Gives the user the role in the project, adding the user to the members if needed.
+    pub fn set_member_role(
+        jwt: String,
+        project_id: u64,
+        user_id: Uuid,
+        role: ProjectRole,
+        callback: Callback<ProjectMemberDto>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        spawn_local(async move {
+            let res = Request::put(
+                format!(
+                    "{}{}/{}/{}/{}",
+                    get_api_url(),
+                    PROJECTS_ENDPOINT,
+                    project_id,
+                    MEMBERS_ENDPOINT,
+                    user_id
+                )
+                .as_str(),
+            )
+            .header("Authorization", format!("Bearer {}", jwt).as_str())
+            .json(&ProjectMemberRoleDto { role })
+            .unwrap()
+            .send()
+            .await;
+
+            match res {
+                Ok(resp) => match resp.text().await {
+                    Ok(text) => match serde_json::from_str::<ProjectMemberDto>(text.as_str()) {
+                        Ok(member) => callback.emit(member),
+                        Err(e) => {
+                            log::debug!("Serde result error: {}", e.to_string());
+                            match serde_json::from_str::<ErrorResponse>(text.as_str()) {
+                                Ok(error_response) => callback_error.emit(error_response),
+                                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                            }
+                        }
+                    },
+                    Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                },
+                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+            }
+        });
+    }
+
+    pub fn remove_member(
+        jwt: String,
+        project_id: u64,
+        user_id: Uuid,
+        callback: Callback<Uuid>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        spawn_local(async move {
+            let res = Request::delete(
+                format!(
+                    "{}{}/{}/{}/{}",
+                    get_api_url(),
+                    PROJECTS_ENDPOINT,
+                    project_id,
+                    MEMBERS_ENDPOINT,
+                    user_id
+                )
+                .as_str(),
+            )
+            .header("Authorization", format!("Bearer {}", jwt).as_str())
+            .send()
+            .await;
+
+            match res {
+                Ok(resp) if resp.ok() => callback.emit(user_id),
+                Ok(resp) => match resp.json::<ErrorResponse>().await {
+                    Ok(error_response) => callback_error.emit(error_response),
+                    Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                },
+                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+            }
+        });
+    }
 }
@@ -0,0 +1,63 @@
+use super::get_api_url;
+use gloo_net::http::Request;
+use shared::dtos::report_dto::{ProgressReportDto, VelocityReportDto};
+use yew::{platform::spawn_local, Callback};
+
+const PROJECTS_ENDPOINT: &str = "projects";
+const REPORTS_ENDPOINT: &str = "reports";
+
+pub struct ReportService;
+
+impl ReportService {
+    /// Daily progress of the sprint, or of the project over the last 30 days.
+    pub fn fetch_progress(
+        jwt: String,
+        project_id: u64,
+        sprint_id: Option<u64>,
+        callback: Callback<ProgressReportDto>,
+    ) {
+        spawn_local(async move {
+            let mut request_builder = Request::get(Self::url(project_id, "progress").as_str());
+            if let Some(id) = sprint_id {
+                request_builder = request_builder.query([("sprint_id", format!("{}", id))]);
+            }
+            let report: ProgressReportDto = request_builder
+                .header("Authorization", format!("Bearer {}", jwt).as_str())
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+
+            callback.emit(report);
+        });
+    }
+
+    pub fn fetch_velocity(jwt: String, project_id: u64, callback: Callback<VelocityReportDto>) {
+        spawn_local(async move {
+            let report: VelocityReportDto =
+                Request::get(Self::url(project_id, "velocity").as_str())
+                    .header("Authorization", format!("Bearer {}", jwt).as_str())
+                    .send()
+                    .await
+                    .unwrap()
+                    .json()
+                    .await
+                    .unwrap();
+
+            callback.emit(report);
+        });
+    }
+
+    fn url(project_id: u64, report: &str) -> String {
+        format!(
+            "{}{}/{}/{}/{}",
+            get_api_url(),
+            PROJECTS_ENDPOINT,
+            project_id,
+            REPORTS_ENDPOINT,
+            report
+        )
+    }
+}
@@ -0,0 +1,110 @@
+use super::get_api_url;
+use gloo_net::http::{Request, RequestBuilder};
+use shared::api::error::error_response::ErrorResponse;
+use shared::dtos::saved_view_dto::SavedViewDto;
+use yew::{platform::spawn_local, Callback};
+
+const VIEWS_ENDPOINT: &str = "views";
+
+pub struct SavedViewService;
+
+impl SavedViewService {
+    pub fn fetch_all(jwt: String, project_id: Option<u64>, callback: Callback<Vec<SavedViewDto>>) {
+        spawn_local(async move {
+            let mut request_builder =
+                Request::get(format!("{}{}", get_api_url(), VIEWS_ENDPOINT).as_str());
+            if let Some(p_id) = project_id {
+                request_builder = request_builder.query([("project_id", format!("{}", p_id))]);
+            }
+            let list: Vec<SavedViewDto> = request_builder
+                .header("Authorization", format!("Bearer {}", jwt).as_str())
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+
+            callback.emit(list);
+        });
+    }
+
+    pub fn create(
+        jwt: String,
+        view: SavedViewDto,
+        callback: Callback<SavedViewDto>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        Self::send(
+            Request::post(format!("{}{}", get_api_url(), VIEWS_ENDPOINT).as_str()),
+            jwt,
+            view,
+            callback,
+            callback_error,
+        );
+    }
+
+    pub fn update(
+        jwt: String,
+        view: SavedViewDto,
+        callback: Callback<SavedViewDto>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        Self::send(
+            Request::put(
+                format!("{}{}/{}", get_api_url(), VIEWS_ENDPOINT, view.id.unwrap()).as_str(),
+            ),
+            jwt,
+            view,
+            callback,
+            callback_error,
+        );
+    }
+
+    pub fn delete(jwt: String, id: u64, callback: Callback<bool>) {
+        spawn_local(async move {
+            if let Ok(response) =
+                Request::delete(format!("{}{}/{}", get_api_url(), VIEWS_ENDPOINT, id).as_str())
+                    .header("Authorization", format!("Bearer {}", jwt).as_str())
+                    .send()
+                    .await
+            {
+                callback.emit(response.ok());
+            }
+        });
+    }
+
+    fn send(
+        request_builder: RequestBuilder,
+        jwt: String,
+        view: SavedViewDto,
+        callback: Callback<SavedViewDto>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        spawn_local(async move {
+            let res = request_builder
+                .header("Authorization", format!("Bearer {}", jwt).as_str())
+                .json(&view)
+                .unwrap()
+                .send()
+                .await;
+
+            match res {
+                Ok(resp) => match resp.text().await {
+                    Ok(text) => match serde_json::from_str::<SavedViewDto>(text.as_str()) {
+                        Ok(returned_view) => callback.emit(returned_view),
+                        Err(e) => {
+                            log::debug!("Serde result error: {}", e.to_string());
+                            match serde_json::from_str::<ErrorResponse>(text.as_str()) {
+                                Ok(error_response) => callback_error.emit(error_response),
+                                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                            }
+                        }
+                    },
+                    Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                },
+                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+            }
+        });
+    }
+}
@@ -0,0 +1,62 @@
+use super::get_api_url;
+use gloo_net::http::Request;
+use shared::dtos::sla_dto::TicketSlaDto;
+use yew::{platform::spawn_local, Callback};
+
+const PROJECTS_ENDPOINT: &str = "projects";
+const TICKETS_ENDPOINT: &str = "tickets";
+const SLA_ENDPOINT: &str = "sla";
+
+pub struct SlaService;
+
+impl SlaService {
+    /// Clocks of the ticket, empty if no policy applies to it.
+    pub fn fetch_ticket(jwt: String, ticket_id: u64, callback: Callback<TicketSlaDto>) {
+        spawn_local(async move {
+            let sla: TicketSlaDto = Request::get(
+                format!(
+                    "{}{}/{}/{}",
+                    get_api_url(),
+                    TICKETS_ENDPOINT,
+                    ticket_id,
+                    SLA_ENDPOINT
+                )
+                .as_str(),
+            )
+            .header("Authorization", format!("Bearer {}", jwt).as_str())
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+
+            callback.emit(sla);
+        });
+    }
+
+    /// Clocks of the unfinished tickets of the project which have a policy.
+    pub fn fetch_project(jwt: String, project_id: u64, callback: Callback<Vec<TicketSlaDto>>) {
+        spawn_local(async move {
+            let list: Vec<TicketSlaDto> = Request::get(
+                format!(
+                    "{}{}/{}/{}",
+                    get_api_url(),
+                    PROJECTS_ENDPOINT,
+                    project_id,
+                    SLA_ENDPOINT
+                )
+                .as_str(),
+            )
+            .header("Authorization", format!("Bearer {}", jwt).as_str())
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+
+            callback.emit(list);
+        });
+    }
+}
@@ -0,0 +1,112 @@
+use super::get_api_url;
+use gloo_net::http::{Request, RequestBuilder};
+use serde_json::Value;
+use shared::api::error::error_response::ErrorResponse;
+use shared::dtos::sprint_dto::{CompleteSprintDto, SprintDto};
+use yew::{platform::spawn_local, Callback};
+
+const PROJECTS_ENDPOINT: &str = "projects";
+const SPRINTS_ENDPOINT: &str = "sprints";
+
+pub struct SprintService;
+
+impl SprintService {
+    /// Sprints of the project, by start date.
+    pub fn fetch_all(jwt: String, project_id: u64, callback: Callback<Vec<SprintDto>>) {
+        spawn_local(async move {
+            let list: Vec<SprintDto> = Request::get(Self::url(project_id, None, None).as_str())
+                .header("Authorization", format!("Bearer {}", jwt).as_str())
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+
+            callback.emit(list);
+        });
+    }
+
+    pub fn start(
+        jwt: String,
+        sprint: &SprintDto,
+        callback: Callback<SprintDto>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        Self::send(
+            Request::post(Self::url(sprint.project_id, sprint.id, Some("start")).as_str()),
+            jwt,
+            Value::Null,
+            callback,
+            callback_error,
+        );
+    }
+
+    /// Unfinished tickets move to the next sprint, or to the backlog if there is none.
+    pub fn complete(
+        jwt: String,
+        sprint: &SprintDto,
+        next_sprint_id: Option<u64>,
+        callback: Callback<SprintDto>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        Self::send(
+            Request::post(Self::url(sprint.project_id, sprint.id, Some("complete")).as_str()),
+            jwt,
+            serde_json::to_value(CompleteSprintDto { next_sprint_id }).unwrap(),
+            callback,
+            callback_error,
+        );
+    }
+
+    fn url(project_id: u64, sprint_id: Option<u64>, action: Option<&str>) -> String {
+        let mut url = format!(
+            "{}{}/{}/{}",
+            get_api_url(),
+            PROJECTS_ENDPOINT,
+            project_id,
+            SPRINTS_ENDPOINT
+        );
+        if let Some(id) = sprint_id {
+            url = format!("{}/{}", url, id);
+        }
+        if let Some(action) = action {
+            url = format!("{}/{}", url, action);
+        }
+        url
+    }
+
+    fn send(
+        request_builder: RequestBuilder,
+        jwt: String,
+        body: Value,
+        callback: Callback<SprintDto>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        spawn_local(async move {
+            let res = request_builder
+                .header("Authorization", format!("Bearer {}", jwt).as_str())
+                .json(&body)
+                .unwrap()
+                .send()
+                .await;
+
+            match res {
+                Ok(resp) => match resp.text().await {
+                    Ok(text) => match serde_json::from_str::<SprintDto>(text.as_str()) {
+                        Ok(sprint) => callback.emit(sprint),
+                        Err(e) => {
+                            log::debug!("Serde result error: {}", e.to_string());
+                            match serde_json::from_str::<ErrorResponse>(text.as_str()) {
+                                Ok(error_response) => callback_error.emit(error_response),
+                                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                            }
+                        }
+                    },
+                    Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                },
+                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+            }
+        });
+    }
+}
@@ -1,8 +1,14 @@
 use super::get_api_url;
 use gloo_net::http::Request;
 use implicit_clone::unsync::IString;
+use serde_json::Value;
 use shared::api::error::error_response::ErrorResponse;
-use shared::dtos::ticket_dto::TicketDto;
+use shared::dtos::{
+    bulk_dto::{BulkTicketRequest, BulkTicketResponse},
+    page::Page,
+    ticket_dto::{MoveTicketDto, SimilarTicketDto, TicketDraftDto, TicketDto},
+    watcher_dto::WatcherDto,
+};
 use web_sys::{File, FormData};
 use yew::{platform::spawn_local, Callback};
 
@@ -10,8 +16,23 @@ const TICKETS_ENDPOINT: &str = "tickets";
 const UNASSIGNED_MARKER: &str = "/unassigned";
 const SUBSCRIBE_ENDPOINT: &str = "/subscribe";
 const IS_SUBSCRIBED_ENDPOINT: &str = "/is_subscribed";
+const WATCHERS_ENDPOINT: &str = "/watchers";
 const UPLOAD_ENDPOINT: &str = "/attachments";
 
+/// Query, ordering and page of the ticket list, `None` values are left out.
+#[derive(Clone, Default, PartialEq)]
+pub struct TicketPageQuery {
+    pub jql: Option<IString>,
+    pub sort: Option<IString>,
+    pub order: Option<IString>,
+    pub limit: Option<u64>,
+    pub offset: Option<u64>,
+}
+const BULK_ENDPOINT: &str = "/bulk";
+const MOVE_ENDPOINT: &str = "/move";
+const SIMILAR_ENDPOINT: &str = "/similar";
+const DUPLICATES_ENDPOINT: &str = "/duplicates";
+
 pub struct TicketService;
 
 impl TicketService {
@@ -54,7 +75,7 @@ impl TicketService {
             if let Some(o) = order {
                 request_builder = request_builder.query([("order", o.as_str())]);
             }
-            let list: Vec<TicketDto> = request_builder
+            let page: Page<TicketDto> = request_builder
                 .header("Authorization", format!("Bearer {}", jwt).as_str())
                 .send()
                 .await
@@ -63,7 +84,55 @@ impl TicketService {
                 .await
                 .unwrap();
 
-            callback.emit(list);
+            callback.emit(page.list);
+        });
+    }
+
+    pub fn fetch_page(
+        jwt: String,
+        query: TicketPageQuery,
+        callback: Callback<Page<TicketDto>>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        spawn_local(async move {
+            let mut request_builder =
+                Request::get(format!("{}{}", get_api_url(), TICKETS_ENDPOINT).as_str());
+            if let Some(q) = query.jql {
+                request_builder = request_builder.query([("jql", q.as_str())]);
+            }
+            if let Some(s) = query.sort {
+                request_builder = request_builder.query([("sort", s.as_str())]);
+            }
+            if let Some(o) = query.order {
+                request_builder = request_builder.query([("order", o.as_str())]);
+            }
+            if let Some(l) = query.limit {
+                request_builder = request_builder.query([("limit", format!("{}", l))]);
+            }
+            if let Some(o) = query.offset {
+                request_builder = request_builder.query([("offset", format!("{}", o))]);
+            }
+            let res = request_builder
+                .header("Authorization", format!("Bearer {}", jwt).as_str())
+                .send()
+                .await;
+
+            match res {
+                Ok(resp) => match resp.text().await {
+                    Ok(text) => match serde_json::from_str::<Page<TicketDto>>(text.as_str()) {
+                        Ok(page) => callback.emit(page),
+                        Err(e) => {
+                            log::debug!("Serde result error: {}", e.to_string());
+                            match serde_json::from_str::<ErrorResponse>(text.as_str()) {
+                                Ok(error_response) => callback_error.emit(error_response),
+                                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                            }
+                        }
+                    },
+                    Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                },
+                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+            }
         });
     }
 
@@ -145,6 +214,7 @@ impl TicketService {
                 .as_str(),
             )
             .header("Authorization", format!("Bearer {}", jwt).as_str())
+            .header("If-Match", format!("\"{}\"", ticket.version).as_str())
             .json(&ticket)
             .unwrap()
             .send()
@@ -180,6 +250,189 @@ impl TicketService {
         });
     }
 
+    /// Sends only the changed fields as a JSON merge patch, e.g. `{"status": "Done"}`.
+    pub fn patch(
+        jwt: String,
+        id: u64,
+        version: u32,
+        patch: Value,
+        callback: Callback<TicketDto>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        spawn_local(async move {
+            let res =
+                Request::patch(format!("{}{}/{}", get_api_url(), TICKETS_ENDPOINT, id).as_str())
+                    .header("Authorization", format!("Bearer {}", jwt).as_str())
+                    .header("If-Match", format!("\"{}\"", version).as_str())
+                    .json(&patch)
+                    .unwrap()
+                    .send()
+                    .await;
+
+            match res {
+                Ok(resp) => match resp.text().await {
+                    Ok(text) => match serde_json::from_str::<TicketDto>(text.as_str()) {
+                        Ok(ticket) => callback.emit(ticket),
+                        Err(e) => {
+                            log::debug!("Serde result error: {}", e.to_string());
+                            match serde_json::from_str::<ErrorResponse>(text.as_str()) {
+                                Ok(error_response) => callback_error.emit(error_response),
+                                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                            }
+                        }
+                    },
+                    Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                },
+                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+            }
+        });
+    }
+
+    /// Places the ticket between its new neighbours on the board.
+    pub fn move_to(
+        jwt: String,
+        id: u64,
+        position: MoveTicketDto,
+        callback: Callback<TicketDto>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        spawn_local(async move {
+            let res = Request::post(
+                format!(
+                    "{}{}/{}{}",
+                    get_api_url(),
+                    TICKETS_ENDPOINT,
+                    id,
+                    MOVE_ENDPOINT
+                )
+                .as_str(),
+            )
+            .header("Authorization", format!("Bearer {}", jwt).as_str())
+            .json(&position)
+            .unwrap()
+            .send()
+            .await;
+
+            match res {
+                Ok(resp) => match resp.text().await {
+                    Ok(text) => match serde_json::from_str::<TicketDto>(text.as_str()) {
+                        Ok(ticket) => callback.emit(ticket),
+                        Err(e) => {
+                            log::debug!("Serde result error: {}", e.to_string());
+                            match serde_json::from_str::<ErrorResponse>(text.as_str()) {
+                                Ok(error_response) => callback_error.emit(error_response),
+                                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                            }
+                        }
+                    },
+                    Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                },
+                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+            }
+        });
+    }
+
+    pub fn bulk(
+        jwt: String,
+        request: BulkTicketRequest,
+        callback: Callback<BulkTicketResponse>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        spawn_local(async move {
+            let res = Request::post(
+                format!("{}{}{}", get_api_url(), TICKETS_ENDPOINT, BULK_ENDPOINT).as_str(),
+            )
+            .header("Authorization", format!("Bearer {}", jwt).as_str())
+            .json(&request)
+            .unwrap()
+            .send()
+            .await;
+
+            match res {
+                Ok(resp) => match resp.text().await {
+                    Ok(text) => match serde_json::from_str::<BulkTicketResponse>(text.as_str()) {
+                        Ok(response) => callback.emit(response),
+                        Err(e) => {
+                            log::debug!("Serde result error: {}", e.to_string());
+                            match serde_json::from_str::<ErrorResponse>(text.as_str()) {
+                                Ok(error_response) => callback_error.emit(error_response),
+                                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                            }
+                        }
+                    },
+                    Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                },
+                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+            }
+        });
+    }
+
+    pub fn fetch_similar(
+        jwt: String,
+        draft: TicketDraftDto,
+        callback: Callback<Vec<SimilarTicketDto>>,
+    ) {
+        spawn_local(async move {
+            if let Ok(response) = Request::post(
+                format!("{}{}{}", get_api_url(), TICKETS_ENDPOINT, SIMILAR_ENDPOINT).as_str(),
+            )
+            .header("Authorization", format!("Bearer {}", jwt).as_str())
+            .json(&draft)
+            .unwrap()
+            .send()
+            .await
+            {
+                if let Ok(similar) = response.json::<Vec<SimilarTicketDto>>().await {
+                    callback.emit(similar);
+                }
+            }
+        });
+    }
+
+    /// Reports the draft on the given ticket instead of creating a new one.
+    pub fn report_duplicate(
+        jwt: String,
+        id: u64,
+        draft: TicketDraftDto,
+        callback: Callback<TicketDto>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        spawn_local(async move {
+            let res = Request::post(
+                format!(
+                    "{}{}/{}{}",
+                    get_api_url(),
+                    TICKETS_ENDPOINT,
+                    id,
+                    DUPLICATES_ENDPOINT
+                )
+                .as_str(),
+            )
+            .header("Authorization", format!("Bearer {}", jwt).as_str())
+            .json(&draft)
+            .unwrap()
+            .send()
+            .await;
+
+            match res {
+                Ok(resp) => match resp.text().await {
+                    Ok(text) => match serde_json::from_str::<TicketDto>(text.as_str()) {
+                        Ok(ticket) => callback.emit(ticket),
+                        Err(e) => {
+                            log::debug!("Serde result error: {}", e.to_string());
+                            match serde_json::from_str::<ErrorResponse>(text.as_str()) {
+                                Ok(error_response) => callback_error.emit(error_response),
+                                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                            }
+                        }
+                    },
+                    Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                },
+                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+            }
+        });
+    }
+
     pub fn subscribe(jwt: String, id: u64, callback: Callback<bool>) {
         spawn_local(async move {
             if let Ok(response) = Request::post(
@@ -222,6 +475,29 @@ impl TicketService {
         });
     }
 
+    pub fn fetch_watchers(jwt: String, id: u64, callback: Callback<Vec<WatcherDto>>) {
+        spawn_local(async move {
+            if let Ok(response) = Request::get(
+                format!(
+                    "{}{}/{}{}",
+                    get_api_url(),
+                    TICKETS_ENDPOINT,
+                    id,
+                    WATCHERS_ENDPOINT
+                )
+                .as_str(),
+            )
+            .header("Authorization", format!("Bearer {}", jwt).as_str())
+            .send()
+            .await
+            {
+                if let Ok(watchers) = response.json::<Vec<WatcherDto>>().await {
+                    callback.emit(watchers);
+                }
+            }
+        });
+    }
+
     pub fn upload_attachment(jwt: String, id: u64, file: File, callback: Callback<bool>) {
         spawn_local(async move {
             let payload = FormData::new().unwrap();
@@ -0,0 +1,64 @@
+use super::get_api_url;
+use gloo_net::http::Request;
+use shared::api::error::error_response::ErrorResponse;
+use shared::dtos::trash_dto::{TrashEntryDto, TrashKind};
+use yew::{platform::spawn_local, Callback};
+
+const TRASH_ENDPOINT: &str = "trash";
+
+pub struct TrashService;
+
+impl TrashService {
+    pub fn fetch_all(
+        jwt: String,
+        kind: TrashKind,
+        callback: Callback<Vec<TrashEntryDto>>,
+        callback_error: Callback<ErrorResponse>,
+    ) {
+        spawn_local(async move {
+            let res =
+                Request::get(format!("{}{}/{}", get_api_url(), TRASH_ENDPOINT, kind).as_str())
+                    .header("Authorization", format!("Bearer {}", jwt).as_str())
+                    .send()
+                    .await;
+
+            match res {
+                Ok(resp) => match resp.text().await {
+                    Ok(text) => match serde_json::from_str::<Vec<TrashEntryDto>>(text.as_str()) {
+                        Ok(list) => callback.emit(list),
+                        Err(e) => {
+                            log::debug!("Serde result error: {}", e.to_string());
+                            match serde_json::from_str::<ErrorResponse>(text.as_str()) {
+                                Ok(error_response) => callback_error.emit(error_response),
+                                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                            }
+                        }
+                    },
+                    Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+                },
+                Err(e) => callback_error.emit(ErrorResponse::from(e.to_string())),
+            }
+        });
+    }
+
+    pub fn restore(jwt: String, kind: TrashKind, id: u64, callback: Callback<bool>) {
+        spawn_local(async move {
+            if let Ok(response) = Request::post(
+                format!(
+                    "{}{}/{}/{}/restore",
+                    get_api_url(),
+                    TRASH_ENDPOINT,
+                    kind,
+                    id
+                )
+                .as_str(),
+            )
+            .header("Authorization", format!("Bearer {}", jwt).as_str())
+            .send()
+            .await
+            {
+                callback.emit(response.ok());
+            }
+        });
+    }
+}
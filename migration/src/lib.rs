@@ -17,6 +17,27 @@ mod m20240125_125611_alter_user_table;
 mod m20240125_130304_alter_user_table;
 mod m20240131_154943_create_preferences_table;
 mod m20240215_133641_create_ticket_attachments_table;
+mod m20240305_101500_alter_ticket_table;
+mod m20240308_091200_create_saved_view_table;
+mod m20240312_143000_create_ticket_label_table;
+mod m20240315_090000_alter_project_table;
+mod m20240315_090100_create_ticket_key_table;
+mod m20240318_100000_alter_soft_delete_tables;
+mod m20240320_110000_alter_versioned_tables;
+mod m20240322_090000_create_custom_field_table;
+mod m20240322_090100_create_ticket_field_value_table;
+mod m20240325_090000_create_sprint_table;
+mod m20240325_090100_alter_ticket_table;
+mod m20240327_090000_create_board_column_table;
+mod m20240328_090000_alter_ticket_table;
+mod m20240329_090000_alter_ticket_table;
+mod m20240402_090000_create_sla_policy_table;
+mod m20240402_090100_create_sla_alert_table;
+mod m20240403_090000_create_recurring_ticket_table;
+mod m20240405_090000_create_import_job_table;
+mod m20240405_090100_create_import_reference_table;
+mod m20240408_090000_create_project_member_table;
+mod m20240410_090000_create_deadline_reminder_table;
 
 pub struct Migrator;
 
@@ -41,6 +62,27 @@ impl MigratorTrait for Migrator {
             Box::new(m20240125_130304_alter_user_table::Migration),
             Box::new(m20240131_154943_create_preferences_table::Migration),
             Box::new(m20240215_133641_create_ticket_attachments_table::Migration),
+            Box::new(m20240305_101500_alter_ticket_table::Migration),
+            Box::new(m20240308_091200_create_saved_view_table::Migration),
+            Box::new(m20240312_143000_create_ticket_label_table::Migration),
+            Box::new(m20240315_090000_alter_project_table::Migration),
+            Box::new(m20240315_090100_create_ticket_key_table::Migration),
+            Box::new(m20240318_100000_alter_soft_delete_tables::Migration),
+            Box::new(m20240320_110000_alter_versioned_tables::Migration),
+            Box::new(m20240322_090000_create_custom_field_table::Migration),
+            Box::new(m20240322_090100_create_ticket_field_value_table::Migration),
+            Box::new(m20240325_090000_create_sprint_table::Migration),
+            Box::new(m20240325_090100_alter_ticket_table::Migration),
+            Box::new(m20240327_090000_create_board_column_table::Migration),
+            Box::new(m20240328_090000_alter_ticket_table::Migration),
+            Box::new(m20240329_090000_alter_ticket_table::Migration),
+            Box::new(m20240402_090000_create_sla_policy_table::Migration),
+            Box::new(m20240402_090100_create_sla_alert_table::Migration),
+            Box::new(m20240403_090000_create_recurring_ticket_table::Migration),
+            Box::new(m20240405_090000_create_import_job_table::Migration),
+            Box::new(m20240405_090100_create_import_reference_table::Migration),
+            Box::new(m20240408_090000_create_project_member_table::Migration),
+            Box::new(m20240410_090000_create_deadline_reminder_table::Migration),
         ]
     }
 }
@@ -0,0 +1,72 @@
+use crate::m20230627_000001_create_user_table::User;
+use crate::m20230627_000002_create_project_table::Project;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SavedView::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SavedView::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SavedView::Name).string().not_null())
+                    .col(ColumnDef::new(SavedView::UserId).big_unsigned().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-saved_view-user_id")
+                            .from(SavedView::Table, SavedView::UserId)
+                            .to(User::Table, User::Id),
+                    )
+                    .col(ColumnDef::new(SavedView::ProjectId).big_unsigned().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-saved_view-project_id")
+                            .from(SavedView::Table, SavedView::ProjectId)
+                            .to(Project::Table, Project::Id),
+                    )
+                    .col(ColumnDef::new(SavedView::Filter).text().not_null())
+                    .col(ColumnDef::new(SavedView::Sort).string().null())
+                    .col(ColumnDef::new(SavedView::SortOrder).string().null())
+                    .col(
+                        ColumnDef::new(SavedView::Columns)
+                            .string()
+                            .not_null()
+                            .default(""),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SavedView::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum SavedView {
+    #[iden = "saved_views"]
+    Table,
+    Id,
+    Name,
+    UserId,
+    ProjectId,
+    Filter,
+    Sort,
+    SortOrder,
+    Columns,
+}
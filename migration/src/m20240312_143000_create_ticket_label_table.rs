@@ -0,0 +1,61 @@
+use crate::m20230627_000003_create_ticket_table::Ticket;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TicketLabel::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TicketLabel::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TicketLabel::TicketId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-ticket_labels-ticket_id")
+                            .from(TicketLabel::Table, TicketLabel::TicketId)
+                            .to(Ticket::Table, Ticket::Id),
+                    )
+                    .col(ColumnDef::new(TicketLabel::Name).string_len(64).not_null())
+                    .index(
+                        Index::create()
+                            .name("idx-ticket_labels-ticket_id-name")
+                            .col(TicketLabel::TicketId)
+                            .col(TicketLabel::Name)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TicketLabel::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum TicketLabel {
+    #[iden = "ticket_labels"]
+    Table,
+    Id,
+    TicketId,
+    Name,
+}
@@ -0,0 +1,69 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230627_000002_create_project_table::Project;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Project::Table)
+                    .add_column(ColumnDef::new(Alias::new("key")).string_len(10))
+                    .add_column(
+                        ColumnDef::new(Alias::new("ticket_sequence"))
+                            .big_unsigned()
+                            .not_null()
+                            .default(0),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        // Existing projects get a key derived from their id, it can be changed later on
+        manager
+            .get_connection()
+            .execute_unprepared("UPDATE `projects` SET `key` = CONCAT('P', `id`)")
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Project::Table)
+                    .modify_column(ColumnDef::new(Alias::new("key")).string_len(10).not_null())
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-project-key")
+                    .table(Project::Table)
+                    .col(Alias::new("key"))
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-project-key")
+                    .table(Project::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Project::Table)
+                    .drop_column(Alias::new("key"))
+                    .drop_column(Alias::new("ticket_sequence"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,116 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230627_000003_create_ticket_table::Ticket;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .add_column(ColumnDef::new(Alias::new("ticket_key")).string_len(32))
+                    .to_owned(),
+            )
+            .await?;
+        // Every key a ticket ever had, so that links keep working after moving it
+        manager
+            .create_table(
+                Table::create()
+                    .table(TicketKey::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TicketKey::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TicketKey::Key)
+                            .string_len(32)
+                            .not_null()
+                            .unique_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TicketKey::TicketId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-ticket_key-ticket_id")
+                            .from(TicketKey::Table, TicketKey::TicketId)
+                            .to(Ticket::Table, Ticket::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        // Number the tickets of existing projects in order of creation
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "UPDATE `tickets` t \
+             JOIN (SELECT `id`, ROW_NUMBER() OVER (PARTITION BY `project_id` ORDER BY `id`) AS n \
+                   FROM `tickets` WHERE `project_id` IS NOT NULL) s ON s.`id` = t.`id` \
+             JOIN `projects` p ON p.`id` = t.`project_id` \
+             SET t.`ticket_key` = CONCAT(p.`key`, '-', s.n)",
+        )
+        .await?;
+        db.execute_unprepared(
+            "UPDATE `projects` p SET `ticket_sequence` = \
+             (SELECT COUNT(*) FROM `tickets` t WHERE t.`project_id` = p.`id`)",
+        )
+        .await?;
+        db.execute_unprepared(
+            "INSERT INTO `ticket_keys` (`ticket_key`, `ticket_id`) \
+             SELECT `ticket_key`, `id` FROM `tickets` WHERE `ticket_key` IS NOT NULL",
+        )
+        .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-ticket-ticket_key")
+                    .table(Ticket::Table)
+                    .col(Alias::new("ticket_key"))
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TicketKey::Table).to_owned())
+            .await?;
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-ticket-ticket_key")
+                    .table(Ticket::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .drop_column(Alias::new("ticket_key"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum TicketKey {
+    #[iden = "ticket_keys"]
+    Table,
+    Id,
+    #[iden = "ticket_key"]
+    Key,
+    TicketId,
+}
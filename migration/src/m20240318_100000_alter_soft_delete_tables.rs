@@ -0,0 +1,51 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230627_000001_create_user_table::User;
+use crate::m20230627_000002_create_project_table::Project;
+use crate::m20230627_000003_create_ticket_table::Ticket;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Adds `deleted_at` and `deleted_by` to the soft deletable tables. `deleted_by` has no
+/// foreign key, so that purging a user does not depend on what they deleted.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            Ticket::Table.into_iden(),
+            Project::Table.into_iden(),
+            User::Table.into_iden(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(ColumnDef::new(Alias::new("deleted_at")).timestamp())
+                        .add_column(ColumnDef::new(Alias::new("deleted_by")).big_unsigned())
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            Ticket::Table.into_iden(),
+            Project::Table.into_iden(),
+            User::Table.into_iden(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(Alias::new("deleted_at"))
+                        .drop_column(Alias::new("deleted_by"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
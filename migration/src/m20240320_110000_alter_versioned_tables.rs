@@ -0,0 +1,53 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230627_000002_create_project_table::Project;
+use crate::m20230627_000003_create_ticket_table::Ticket;
+use crate::m20230627_000005_create_comment_table::Comment;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Adds the `version` used for optimistic concurrency control, every update increments it.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            Ticket::Table.into_iden(),
+            Project::Table.into_iden(),
+            Comment::Table.into_iden(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .add_column(
+                            ColumnDef::new(Alias::new("version"))
+                                .unsigned()
+                                .not_null()
+                                .default(1),
+                        )
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for table in [
+            Ticket::Table.into_iden(),
+            Project::Table.into_iden(),
+            Comment::Table.into_iden(),
+        ] {
+            manager
+                .alter_table(
+                    Table::alter()
+                        .table(table)
+                        .drop_column(Alias::new("version"))
+                        .to_owned(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use crate::m20230627_000002_create_project_table::Project;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CustomField::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CustomField::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(CustomField::ProjectId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-custom_fields-project_id")
+                            .from(CustomField::Table, CustomField::ProjectId)
+                            .to(Project::Table, Project::Id),
+                    )
+                    .col(ColumnDef::new(CustomField::Name).string_len(80).not_null())
+                    .col(
+                        ColumnDef::new(CustomField::FieldType)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(CustomField::Options).text().not_null())
+                    .col(
+                        ColumnDef::new(CustomField::Required)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-custom_fields-project_id-name")
+                            .col(CustomField::ProjectId)
+                            .col(CustomField::Name)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CustomField::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum CustomField {
+    #[iden = "custom_fields"]
+    Table,
+    Id,
+    ProjectId,
+    Name,
+    FieldType,
+    Options,
+    Required,
+}
@@ -0,0 +1,78 @@
+use crate::m20230627_000003_create_ticket_table::Ticket;
+use crate::m20240322_090000_create_custom_field_table::CustomField;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Values of the custom fields, one row per value: multi-select fields may have several.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(TicketFieldValue::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(TicketFieldValue::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(TicketFieldValue::TicketId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-ticket_field_values-ticket_id")
+                            .from(TicketFieldValue::Table, TicketFieldValue::TicketId)
+                            .to(Ticket::Table, Ticket::Id),
+                    )
+                    .col(
+                        ColumnDef::new(TicketFieldValue::FieldId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-ticket_field_values-field_id")
+                            .from(TicketFieldValue::Table, TicketFieldValue::FieldId)
+                            .to(CustomField::Table, CustomField::Id),
+                    )
+                    .col(
+                        ColumnDef::new(TicketFieldValue::Value)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-ticket_field_values-field_id-value")
+                            .col(TicketFieldValue::FieldId)
+                            .col(TicketFieldValue::Value),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(TicketFieldValue::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum TicketFieldValue {
+    #[iden = "ticket_field_values"]
+    Table,
+    Id,
+    TicketId,
+    FieldId,
+    Value,
+}
@@ -0,0 +1,71 @@
+use crate::m20230627_000002_create_project_table::Project;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Sprint::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Sprint::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Sprint::ProjectId).big_unsigned().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-sprints-project_id")
+                            .from(Sprint::Table, Sprint::ProjectId)
+                            .to(Project::Table, Project::Id),
+                    )
+                    .col(ColumnDef::new(Sprint::Name).string_len(80).not_null())
+                    .col(ColumnDef::new(Sprint::Goal).text().not_null())
+                    .col(ColumnDef::new(Sprint::StartDate).date().not_null())
+                    .col(ColumnDef::new(Sprint::EndDate).date().not_null())
+                    .col(
+                        ColumnDef::new(Sprint::State)
+                            .string_len(16)
+                            .not_null()
+                            .default("Planned"),
+                    )
+                    .col(ColumnDef::new(Sprint::CompletedAt).timestamp())
+                    .index(
+                        Index::create()
+                            .name("idx-sprints-project_id-state")
+                            .col(Sprint::ProjectId)
+                            .col(Sprint::State),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Sprint::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum Sprint {
+    #[iden = "sprints"]
+    Table,
+    Id,
+    ProjectId,
+    Name,
+    Goal,
+    StartDate,
+    EndDate,
+    State,
+    CompletedAt,
+}
@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230627_000003_create_ticket_table::Ticket;
+use crate::m20240325_090000_create_sprint_table::Sprint;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tickets without a sprint are in the backlog of their project.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .add_column(ColumnDef::new(Alias::new("sprint_id")).big_unsigned())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk-ticket-sprint_id")
+                            .from_tbl(Ticket::Table)
+                            .from_col(Alias::new("sprint_id"))
+                            .to_tbl(Sprint::Table)
+                            .to_col(Sprint::Id),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .drop_foreign_key(Alias::new("fk-ticket-sprint_id"))
+                    .drop_column(Alias::new("sprint_id"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
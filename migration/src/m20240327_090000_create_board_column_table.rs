@@ -0,0 +1,88 @@
+use crate::m20230627_000002_create_project_table::Project;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(BoardColumn::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(BoardColumn::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(BoardColumn::ProjectId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-board_columns-project_id")
+                            .from(BoardColumn::Table, BoardColumn::ProjectId)
+                            .to(Project::Table, Project::Id),
+                    )
+                    .col(ColumnDef::new(BoardColumn::Name).string_len(40).not_null())
+                    .col(
+                        ColumnDef::new(BoardColumn::Statuses)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(BoardColumn::Position)
+                            .unsigned()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(BoardColumn::Hidden)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(ColumnDef::new(BoardColumn::WipLimit).unsigned().null())
+                    .col(
+                        ColumnDef::new(BoardColumn::WipEnforced)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-board_columns-project_id")
+                            .col(BoardColumn::ProjectId),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(BoardColumn::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+pub enum BoardColumn {
+    #[iden = "board_columns"]
+    Table,
+    Id,
+    ProjectId,
+    Name,
+    Statuses,
+    Position,
+    Hidden,
+    WipLimit,
+    WipEnforced,
+}
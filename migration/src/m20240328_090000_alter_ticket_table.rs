@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230627_000003_create_ticket_table::Ticket;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Position of the ticket on the board of its project. Ranks compare as strings, so a ticket
+/// can move between two others without renumbering the rest.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .add_column(
+                        ColumnDef::new(Alias::new("board_rank"))
+                            .string_len(255)
+                            .not_null()
+                            .default(""),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        // Existing tickets keep the order of their ids, the ranks are written like the ones of
+        // a renumbered project: eight base 36 digits followed by the middle digit
+        manager
+            .get_connection()
+            .execute_unprepared(
+                "UPDATE `tickets` SET `board_rank` = CONCAT(LPAD(CONV(`id`, 10, 36), 8, '0'), 'I')",
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-ticket-project_id-board_rank")
+                    .table(Ticket::Table)
+                    .col(Alias::new("project_id"))
+                    .col(Alias::new("board_rank"))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_index(
+                Index::drop()
+                    .name("idx-ticket-project_id-board_rank")
+                    .table(Ticket::Table)
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .drop_column(Alias::new("board_rank"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
@@ -0,0 +1,41 @@
+use sea_orm_migration::prelude::*;
+
+use crate::m20230627_000003_create_ticket_table::Ticket;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+/// Tickets can be broken down into child tickets of the same project.
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .add_column(ColumnDef::new(Alias::new("parent_id")).big_unsigned())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk-ticket-parent_id")
+                            .from_tbl(Ticket::Table)
+                            .from_col(Alias::new("parent_id"))
+                            .to_tbl(Ticket::Table)
+                            .to_col(Ticket::Id),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Ticket::Table)
+                    .drop_foreign_key(Alias::new("fk-ticket-parent_id"))
+                    .drop_column(Alias::new("parent_id"))
+                    .to_owned(),
+            )
+            .await
+    }
+}
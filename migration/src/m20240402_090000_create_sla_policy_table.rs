@@ -0,0 +1,109 @@
+use crate::m20230627_000002_create_project_table::Project;
+use crate::m20230731_083101_alter_ticket_table::Priority;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SlaPolicy::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SlaPolicy::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(SlaPolicy::ProjectId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-sla_policies-project_id")
+                            .from(SlaPolicy::Table, SlaPolicy::ProjectId)
+                            .to(Project::Table, Project::Id),
+                    )
+                    .col(
+                        ColumnDef::new(SlaPolicy::Priority)
+                            .enumeration(Alias::new("Priority"), Priority::iden_values())
+                            .not_null(),
+                    )
+                    .col(ColumnDef::new(SlaPolicy::ResponseMinutes).unsigned().null())
+                    .col(
+                        ColumnDef::new(SlaPolicy::ResolutionMinutes)
+                            .unsigned()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(SlaPolicy::BusinessHours)
+                            .boolean()
+                            .not_null()
+                            .default(false),
+                    )
+                    .col(
+                        ColumnDef::new(SlaPolicy::DayStart)
+                            .time()
+                            .not_null()
+                            .default("09:00:00"),
+                    )
+                    .col(
+                        ColumnDef::new(SlaPolicy::DayEnd)
+                            .time()
+                            .not_null()
+                            .default("17:00:00"),
+                    )
+                    .col(
+                        ColumnDef::new(SlaPolicy::WorkDays)
+                            .string_len(32)
+                            .not_null()
+                            .default("Mon,Tue,Wed,Thu,Fri"),
+                    )
+                    .col(
+                        ColumnDef::new(SlaPolicy::UtcOffsetMinutes)
+                            .integer()
+                            .not_null()
+                            .default(0),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-sla_policies-project_id-priority")
+                            .col(SlaPolicy::ProjectId)
+                            .col(SlaPolicy::Priority)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SlaPolicy::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum SlaPolicy {
+    #[iden = "sla_policies"]
+    Table,
+    Id,
+    ProjectId,
+    Priority,
+    ResponseMinutes,
+    ResolutionMinutes,
+    BusinessHours,
+    DayStart,
+    DayEnd,
+    WorkDays,
+    UtcOffsetMinutes,
+}
@@ -0,0 +1,67 @@
+use crate::m20230627_000003_create_ticket_table::Ticket;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(SlaAlert::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(SlaAlert::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(SlaAlert::TicketId).big_unsigned().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-sla_alerts-ticket_id")
+                            .from(SlaAlert::Table, SlaAlert::TicketId)
+                            .to(Ticket::Table, Ticket::Id),
+                    )
+                    .col(ColumnDef::new(SlaAlert::Clock).string_len(16).not_null())
+                    .col(ColumnDef::new(SlaAlert::State).string_len(16).not_null())
+                    .col(
+                        ColumnDef::new(SlaAlert::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-sla_alerts-ticket_id-clock-state")
+                            .col(SlaAlert::TicketId)
+                            .col(SlaAlert::Clock)
+                            .col(SlaAlert::State)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(SlaAlert::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum SlaAlert {
+    #[iden = "sla_alerts"]
+    Table,
+    Id,
+    TicketId,
+    Clock,
+    State,
+    CreatedAt,
+}
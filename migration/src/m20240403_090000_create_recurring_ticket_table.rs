@@ -0,0 +1,124 @@
+use crate::m20230627_000001_create_user_table::User;
+use crate::m20230627_000002_create_project_table::Project;
+use crate::m20230731_083101_alter_ticket_table::Priority;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(RecurringTicket::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(RecurringTicket::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(RecurringTicket::ProjectId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-recurring_tickets-project_id")
+                            .from(RecurringTicket::Table, RecurringTicket::ProjectId)
+                            .to(Project::Table, Project::Id),
+                    )
+                    .col(ColumnDef::new(RecurringTicket::Title).string().not_null())
+                    .col(
+                        ColumnDef::new(RecurringTicket::Description)
+                            .text()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RecurringTicket::Priority)
+                            .enumeration(Alias::new("Priority"), Priority::iden_values())
+                            .not_null()
+                            .default(Priority::Normal),
+                    )
+                    .col(
+                        ColumnDef::new(RecurringTicket::UserId)
+                            .big_unsigned()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-recurring_tickets-user_id")
+                            .from(RecurringTicket::Table, RecurringTicket::UserId)
+                            .to(User::Table, User::Id),
+                    )
+                    .col(
+                        ColumnDef::new(RecurringTicket::ReporterId)
+                            .big_unsigned()
+                            .null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-recurring_tickets-reporter_id")
+                            .from(RecurringTicket::Table, RecurringTicket::ReporterId)
+                            .to(User::Table, User::Id),
+                    )
+                    .col(
+                        ColumnDef::new(RecurringTicket::Cron)
+                            .string_len(100)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(RecurringTicket::Active)
+                            .boolean()
+                            .not_null()
+                            .default(true),
+                    )
+                    .col(
+                        ColumnDef::new(RecurringTicket::LastRunAt)
+                            .timestamp()
+                            .null(),
+                    )
+                    .col(
+                        ColumnDef::new(RecurringTicket::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-recurring_tickets-project_id")
+                            .col(RecurringTicket::ProjectId),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(RecurringTicket::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum RecurringTicket {
+    #[iden = "recurring_tickets"]
+    Table,
+    Id,
+    ProjectId,
+    Title,
+    Description,
+    Priority,
+    UserId,
+    ReporterId,
+    Cron,
+    Active,
+    LastRunAt,
+    CreatedAt,
+}
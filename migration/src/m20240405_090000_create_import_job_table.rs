@@ -0,0 +1,113 @@
+use crate::m20230627_000001_create_user_table::User;
+use crate::m20230627_000002_create_project_table::Project;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ImportJob::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ImportJob::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportJob::ProjectId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-import_jobs-project_id")
+                            .from(ImportJob::Table, ImportJob::ProjectId)
+                            .to(Project::Table, Project::Id),
+                    )
+                    .col(ColumnDef::new(ImportJob::Source).string_len(16).not_null())
+                    .col(ColumnDef::new(ImportJob::Path).string().not_null())
+                    .col(ColumnDef::new(ImportJob::State).string_len(16).not_null())
+                    .col(
+                        ColumnDef::new(ImportJob::Total)
+                            .unsigned()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ImportJob::Processed)
+                            .unsigned()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ImportJob::Created)
+                            .unsigned()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(
+                        ColumnDef::new(ImportJob::Skipped)
+                            .unsigned()
+                            .not_null()
+                            .default(0),
+                    )
+                    .col(ColumnDef::new(ImportJob::Warnings).text().null())
+                    .col(ColumnDef::new(ImportJob::Error).text().null())
+                    .col(ColumnDef::new(ImportJob::UserId).big_unsigned().null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-import_jobs-user_id")
+                            .from(ImportJob::Table, ImportJob::UserId)
+                            .to(User::Table, User::Id),
+                    )
+                    .col(
+                        ColumnDef::new(ImportJob::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .col(ColumnDef::new(ImportJob::FinishedAt).timestamp().null())
+                    .index(
+                        Index::create()
+                            .name("idx-import_jobs-project_id")
+                            .col(ImportJob::ProjectId),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ImportJob::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum ImportJob {
+    #[iden = "import_jobs"]
+    Table,
+    Id,
+    ProjectId,
+    Source,
+    Path,
+    State,
+    Total,
+    Processed,
+    Created,
+    Skipped,
+    Warnings,
+    Error,
+    UserId,
+    CreatedAt,
+    FinishedAt,
+}
@@ -0,0 +1,105 @@
+use crate::m20230627_000002_create_project_table::Project;
+use crate::m20230627_000003_create_ticket_table::Ticket;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ImportReference::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ImportReference::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportReference::ProjectId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-import_references-project_id")
+                            .from(ImportReference::Table, ImportReference::ProjectId)
+                            .to(Project::Table, Project::Id),
+                    )
+                    .col(
+                        ColumnDef::new(ImportReference::Tracker)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportReference::Kind)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportReference::ExternalId)
+                            .string_len(255)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportReference::TicketId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-import_references-ticket_id")
+                            .from(ImportReference::Table, ImportReference::TicketId)
+                            .to(Ticket::Table, Ticket::Id),
+                    )
+                    .col(
+                        ColumnDef::new(ImportReference::TargetId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ImportReference::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-import_references-project_id-tracker-kind-external_id")
+                            .col(ImportReference::ProjectId)
+                            .col(ImportReference::Tracker)
+                            .col(ImportReference::Kind)
+                            .col(ImportReference::ExternalId)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ImportReference::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum ImportReference {
+    #[iden = "import_references"]
+    Table,
+    Id,
+    ProjectId,
+    Tracker,
+    Kind,
+    ExternalId,
+    TicketId,
+    TargetId,
+    CreatedAt,
+}
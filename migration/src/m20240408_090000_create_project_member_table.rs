@@ -0,0 +1,103 @@
+use crate::m20230627_000001_create_user_table::User;
+use crate::m20230627_000002_create_project_table::Project;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ProjectMember::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ProjectMember::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(ProjectMember::ProjectId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-project_members-project_id")
+                            .from(ProjectMember::Table, ProjectMember::ProjectId)
+                            .to(Project::Table, Project::Id),
+                    )
+                    .col(
+                        ColumnDef::new(ProjectMember::UserId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-project_members-user_id")
+                            .from(ProjectMember::Table, ProjectMember::UserId)
+                            .to(User::Table, User::Id),
+                    )
+                    .col(
+                        ColumnDef::new(ProjectMember::Role)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(ProjectMember::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-project_members-project_id-user_id")
+                            .col(ProjectMember::ProjectId)
+                            .col(ProjectMember::UserId)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        // Nobody loses access to the projects they work on: owners administer their projects,
+        // assignees and reporters of tickets contribute to them
+        let db = manager.get_connection();
+        db.execute_unprepared(
+            "INSERT IGNORE INTO `project_members` (`project_id`, `user_id`, `role`) \
+             SELECT `id`, `user_id`, 'admin' FROM `projects`",
+        )
+        .await?;
+        for column in ["user_id", "reporter_id"] {
+            db.execute_unprepared(&format!(
+                "INSERT IGNORE INTO `project_members` (`project_id`, `user_id`, `role`) \
+                 SELECT DISTINCT `project_id`, `{0}`, 'contributor' FROM `tickets` \
+                 WHERE `project_id` IS NOT NULL AND `{0}` IS NOT NULL",
+                column
+            ))
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ProjectMember::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum ProjectMember {
+    #[iden = "project_members"]
+    Table,
+    Id,
+    ProjectId,
+    UserId,
+    Role,
+    CreatedAt,
+}
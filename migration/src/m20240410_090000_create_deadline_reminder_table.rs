@@ -0,0 +1,75 @@
+use crate::m20230627_000002_create_project_table::Project;
+use sea_orm_migration::prelude::*;
+
+#[derive(DeriveMigrationName)]
+pub struct Migration;
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(DeadlineReminder::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(DeadlineReminder::Id)
+                            .big_unsigned()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(
+                        ColumnDef::new(DeadlineReminder::ProjectId)
+                            .big_unsigned()
+                            .not_null(),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-deadline_reminders-project_id")
+                            .from(DeadlineReminder::Table, DeadlineReminder::ProjectId)
+                            .to(Project::Table, Project::Id),
+                    )
+                    .col(ColumnDef::new(DeadlineReminder::Deadline).date().not_null())
+                    .col(
+                        ColumnDef::new(DeadlineReminder::Kind)
+                            .string_len(16)
+                            .not_null(),
+                    )
+                    .col(
+                        ColumnDef::new(DeadlineReminder::CreatedAt)
+                            .timestamp()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .index(
+                        Index::create()
+                            .name("idx-deadline_reminders-project_id-deadline-kind")
+                            .col(DeadlineReminder::ProjectId)
+                            .col(DeadlineReminder::Deadline)
+                            .col(DeadlineReminder::Kind)
+                            .unique(),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(DeadlineReminder::Table).to_owned())
+            .await
+    }
+}
+
+/// Learn more at https://docs.rs/sea-query#iden
+#[derive(Iden)]
+enum DeadlineReminder {
+    #[iden = "deadline_reminders"]
+    Table,
+    Id,
+    ProjectId,
+    Deadline,
+    Kind,
+    CreatedAt,
+}
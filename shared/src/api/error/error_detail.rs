@@ -13,6 +13,36 @@ pub struct ErrorDetail {
     pub properties: Option<HashMap<String, PropertyError>>,
 }
 
+impl ErrorDetail {
+    /// Position of the error in the value of the property, if the error has one.
+    pub fn get_property_position(&self, property_key: &str) -> Option<usize> {
+        self.properties
+            .as_ref()
+            .and_then(|m| m.get(property_key))
+            .and_then(|p| p.position)
+    }
+
+    /// All the messages as one list, the ones of a property prefixed with it.
+    pub fn messages(self) -> Vec<String> {
+        let mut properties = self
+            .properties
+            .unwrap_or_default()
+            .into_iter()
+            .collect::<Vec<(String, PropertyError)>>();
+        properties.sort_by(|a, b| a.0.cmp(&b.0));
+        self.errors
+            .unwrap_or_default()
+            .into_iter()
+            .chain(properties.into_iter().flat_map(|(property, error)| {
+                error
+                    .errors
+                    .into_iter()
+                    .map(move |message| format!("{}: {}", property, message))
+            }))
+            .collect()
+    }
+}
+
 impl ValidationMessagesTrait for ErrorDetail {
     fn get_common_messages(&self) -> IValidationMessages {
         self.errors.as_ref().map(|v| {
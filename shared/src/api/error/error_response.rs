@@ -27,6 +27,9 @@ use std::fmt::Display;
 }
 */
 
+/// Origin of the error returned when a save is based on an outdated version.
+pub const VERSION_CONFLICT_ORIGIN: &str = "version_conflict";
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ErrorResponse {
     pub code: String,
@@ -35,6 +38,12 @@ pub struct ErrorResponse {
     pub origin: String,
 }
 
+impl ErrorResponse {
+    pub fn is_version_conflict(&self) -> bool {
+        self.origin == VERSION_CONFLICT_ORIGIN
+    }
+}
+
 impl From<String> for ErrorResponse {
     fn from(value: String) -> Self {
         Self {
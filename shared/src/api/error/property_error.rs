@@ -3,10 +3,17 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PropertyError {
     pub errors: Vec<String>,
+    /// Zero based character position in the value of the property where the error is, for
+    /// values which are parsed like queries.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub position: Option<usize>,
 }
 
 impl From<Vec<String>> for PropertyError {
     fn from(value: Vec<String>) -> Self {
-        Self { errors: value }
+        Self {
+            errors: value,
+            position: None,
+        }
     }
 }
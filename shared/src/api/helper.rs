@@ -28,3 +28,21 @@ where
         Some(s) => FromStr::from_str(s).map_err(de::Error::custom).map(Some),
     }
 }
+
+/// Serde deserialization decorator to map comma separated lists to a Vec, empty Strings to None,
+pub fn comma_separated_as_vec<'de, D, T>(de: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    let opt = Option::<String>::deserialize(de)?;
+    match opt.as_deref() {
+        None | Some("") => Ok(None),
+        Some(s) => s
+            .split(',')
+            .map(|item| FromStr::from_str(item.trim()).map_err(de::Error::custom))
+            .collect::<Result<Vec<T>, D::Error>>()
+            .map(Some),
+    }
+}
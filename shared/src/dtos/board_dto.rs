@@ -0,0 +1,115 @@
+use crate::validation::{board_validation::BoardValidation, ticket_validation::TicketStatus};
+use entity::board_columns::Model;
+use implicit_clone::ImplicitClone;
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use std::{fmt::Display, str::FromStr};
+use strum::IntoEnumIterator;
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, Validate)]
+pub struct BoardColumnDto {
+    pub id: Option<u64>,
+    #[validate(min_length = 1)]
+    #[validate(max_length = 40)]
+    pub name: String,
+    /// Tickets with any of these statuses show in the column. Dropping a ticket on the column
+    /// moves it to the first one.
+    #[validate(min_items = 1)]
+    pub statuses: Vec<TicketStatus>,
+    #[serde(default)]
+    pub hidden: bool,
+    /// Maximum number of tickets in the column.
+    #[validate(minimum = 1)]
+    pub wip_limit: Option<u32>,
+    /// Rejects moves exceeding the limit instead of only warning about them.
+    #[serde(default)]
+    pub wip_enforced: bool,
+}
+
+impl BoardColumnDto {
+    pub fn contains(&self, status: &TicketStatus) -> bool {
+        self.statuses.contains(status)
+    }
+
+    /// Whether one more ticket would exceed the limit of the column.
+    pub fn is_full(&self, count: usize) -> bool {
+        self.wip_limit.is_some_and(|limit| count >= limit as usize)
+    }
+
+    pub fn is_over_limit(&self, count: usize) -> bool {
+        self.wip_limit.is_some_and(|limit| count > limit as usize)
+    }
+}
+
+impl Display for BoardColumnDto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "( name: '{}', statuses: {}, limit: {} )",
+            self.name,
+            self.statuses
+                .iter()
+                .map(|s| s.to_string())
+                .collect::<Vec<String>>()
+                .join(","),
+            self.wip_limit
+                .map_or(String::from("-"), |l| format!("{}", l)),
+        )
+    }
+}
+
+impl From<&Model> for BoardColumnDto {
+    fn from(m: &Model) -> Self {
+        Self {
+            id: Some(m.id),
+            name: m.name.to_owned(),
+            statuses: m
+                .statuses
+                .split(',')
+                .filter_map(|s| TicketStatus::from_str(s).ok())
+                .collect(),
+            hidden: m.hidden != 0,
+            wip_limit: m.wip_limit,
+            wip_enforced: m.wip_enforced != 0,
+        }
+    }
+}
+
+impl From<Model> for BoardColumnDto {
+    fn from(m: Model) -> Self {
+        Self::from(&m)
+    }
+}
+
+impl ImplicitClone for BoardColumnDto {}
+
+/// The columns of the board of a project, from left to right.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, Validate)]
+pub struct BoardDto {
+    #[validate]
+    #[validate(min_items = 1)]
+    #[validate(custom(BoardValidation::columns_validation))]
+    pub columns: Vec<BoardColumnDto>,
+}
+
+impl BoardDto {
+    /// Board of the projects which did not configure one: a column for every status a ticket
+    /// passes through while it is worked on.
+    pub fn standard() -> Self {
+        Self {
+            columns: TicketStatus::iter()
+                .filter(|s| *s != TicketStatus::Created && *s != TicketStatus::Closed)
+                .map(|status| BoardColumnDto {
+                    name: status.to_string(),
+                    statuses: vec![status],
+                    ..Default::default()
+                })
+                .collect(),
+        }
+    }
+
+    /// The column showing tickets of the given status, if it is on the board.
+    pub fn column_of(&self, status: &TicketStatus) -> Option<&BoardColumnDto> {
+        self.columns.iter().find(|c| c.contains(status))
+    }
+}
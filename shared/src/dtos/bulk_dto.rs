@@ -0,0 +1,92 @@
+use crate::validation::ticket_validation::{TicketPriority, TicketStatus};
+use serde::{Deserialize, Serialize};
+use serde_valid::{validation::Error, Validate};
+use std::fmt::Display;
+use uuid::Uuid;
+
+pub const BULK_MAX_ITEMS: usize = 200;
+pub const LABEL_MAX_LENGTH: usize = 64;
+
+/// A single change applied to every selected ticket.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "action", content = "value", rename_all = "snake_case")]
+pub enum BulkAction {
+    Status(TicketStatus),
+    Priority(TicketPriority),
+    /// `None` unassigns the tickets.
+    Assignee(Option<Uuid>),
+    /// `None` removes the tickets from their project.
+    Project(Option<u64>),
+    AddLabel(String),
+    RemoveLabel(String),
+    Delete,
+}
+
+impl Display for BulkAction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BulkAction::Status(status) => write!(f, "Status set to {}", status),
+            BulkAction::Priority(priority) => write!(f, "Priority set to {}", priority),
+            BulkAction::Assignee(Some(_)) => write!(f, "Assignee changed"),
+            BulkAction::Assignee(None) => write!(f, "Assignee removed"),
+            BulkAction::Project(Some(id)) => write!(f, "Moved to project {}", id),
+            BulkAction::Project(None) => write!(f, "Removed from project"),
+            BulkAction::AddLabel(label) => write!(f, "Label '{}' added", label),
+            BulkAction::RemoveLabel(label) => write!(f, "Label '{}' removed", label),
+            BulkAction::Delete => write!(f, "Deleted"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize, Validate)]
+pub struct BulkTicketRequest {
+    #[validate(min_items = 1)]
+    #[validate(max_items = 200)]
+    #[validate(unique_items)]
+    pub ids: Vec<u64>,
+    #[serde(flatten)]
+    #[validate(custom(BulkTicketRequest::action_validation))]
+    pub action: BulkAction,
+}
+
+impl BulkTicketRequest {
+    pub fn action_validation(action: &BulkAction) -> Result<(), Error> {
+        match action {
+            BulkAction::AddLabel(label) | BulkAction::RemoveLabel(label)
+                if label.trim().is_empty() || label.trim().chars().count() > LABEL_MAX_LENGTH =>
+            {
+                Err(Error::Custom(format!(
+                    "Labels should be between 1 and {} characters long.",
+                    LABEL_MAX_LENGTH
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Outcome of the action for one ticket. Failed items leave the ticket untouched.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BulkItemResult {
+    pub id: u64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct BulkTicketResponse {
+    pub results: Vec<BulkItemResult>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+impl From<Vec<BulkItemResult>> for BulkTicketResponse {
+    fn from(results: Vec<BulkItemResult>) -> Self {
+        let succeeded = results.iter().filter(|r| r.success).count();
+        Self {
+            failed: results.len() - succeeded,
+            succeeded,
+            results,
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use crate::validation::custom_field_validation::CustomFieldValidation;
+use entity::custom_fields::Model;
+use implicit_clone::ImplicitClone;
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use std::{fmt::Display, str::FromStr};
+use strum::{Display, EnumIter, EnumString};
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumIter,
+    EnumString,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub enum CustomFieldType {
+    #[default]
+    Text,
+    Number,
+    /// Stored as `YYYY-MM-DD`.
+    Date,
+    Select,
+    MultiSelect,
+    /// Public id of a user.
+    User,
+}
+
+impl CustomFieldType {
+    pub fn has_options(&self) -> bool {
+        matches!(self, CustomFieldType::Select | CustomFieldType::MultiSelect)
+    }
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, Validate)]
+#[validate(custom = CustomFieldValidation::definition_validation)]
+pub struct CustomFieldDto {
+    pub id: Option<u64>,
+    /// Set from the path, the fields belong to the project they are created in.
+    #[serde(default)]
+    pub project_id: u64,
+    #[validate(min_length = 1)]
+    #[validate(max_length = 80)]
+    pub name: String,
+    pub field_type: CustomFieldType,
+    /// Choices of the select fields.
+    #[serde(default)]
+    #[validate(custom(CustomFieldValidation::options_validation))]
+    pub options: Vec<String>,
+    #[serde(default)]
+    pub required: bool,
+}
+
+impl Display for CustomFieldDto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "( id: {}, name: '{}', type: {} )",
+            self.id.map_or(String::from("-"), |id| format!("{}", id)),
+            self.name,
+            self.field_type
+        )
+    }
+}
+
+impl From<&Model> for CustomFieldDto {
+    fn from(m: &Model) -> Self {
+        Self {
+            id: Some(m.id),
+            project_id: m.project_id,
+            name: m.name.to_owned(),
+            field_type: CustomFieldType::from_str(m.field_type.as_str()).unwrap_or_default(),
+            options: m
+                .options
+                .split(',')
+                .filter(|o| !o.is_empty())
+                .map(String::from)
+                .collect(),
+            required: m.required != 0,
+        }
+    }
+}
+
+impl From<Model> for CustomFieldDto {
+    fn from(m: Model) -> Self {
+        Self::from(&m)
+    }
+}
+
+impl ImplicitClone for CustomFieldDto {}
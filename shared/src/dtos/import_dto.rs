@@ -0,0 +1,124 @@
+use chrono::{DateTime, Utc};
+use entity::import_jobs::Model;
+use implicit_clone::ImplicitClone;
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use std::str::FromStr;
+use strum::{Display, EnumIter, EnumString};
+
+/// Export formats of the other trackers.
+#[derive(
+    Clone, Copy, Debug, Deserialize, Display, EnumIter, EnumString, Eq, PartialEq, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ImportSource {
+    /// RSS export of a Jira issue search.
+    JiraXml,
+    /// Response of the Jira issue search, with the `issues` of the page.
+    JiraJson,
+    /// Array of the issues as listed by the GitHub API or `gh issue list --json`.
+    GithubJson,
+}
+
+impl ImportSource {
+    /// Tracker the ids of the source come from. Both Jira exports share their ids, so
+    /// importing one after the other creates no duplicates.
+    pub fn tracker(&self) -> &'static str {
+        match self {
+            ImportSource::JiraXml | ImportSource::JiraJson => "jira",
+            ImportSource::GithubJson => "github",
+        }
+    }
+}
+
+impl ImplicitClone for ImportSource {}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Deserialize,
+    Display,
+    EnumIter,
+    EnumString,
+    Eq,
+    PartialEq,
+    Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ImportState {
+    #[default]
+    Queued,
+    Running,
+    Completed,
+    Failed,
+}
+
+impl ImportState {
+    pub fn is_finished(&self) -> bool {
+        matches!(self, ImportState::Completed | ImportState::Failed)
+    }
+}
+
+impl ImplicitClone for ImportState {}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Validate)]
+pub struct ImportRequestDto {
+    pub source: ImportSource,
+    /// Path of the export file within the import directory of the server. Attachments are
+    /// read from `attachments/<issue key or number>/<file name>` next to the file.
+    #[validate(min_length = 1)]
+    #[validate(max_length = 255)]
+    pub path: String,
+}
+
+/// An import into a project and its progress. Importing a file again only adds the issues,
+/// comments and attachments not imported before.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ImportJobDto {
+    pub id: u64,
+    pub project_id: u64,
+    pub source: Option<ImportSource>,
+    pub path: String,
+    pub state: ImportState,
+    /// Issues in the file, known once the file is read.
+    pub total: u32,
+    pub processed: u32,
+    /// Issues imported as new tickets.
+    pub created: u32,
+    /// Issues imported by an earlier run or left out.
+    pub skipped: u32,
+    /// Users, attachments and values which could not be imported.
+    pub warnings: Vec<String>,
+    pub error: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+}
+
+impl ImplicitClone for ImportJobDto {}
+
+impl From<Model> for ImportJobDto {
+    fn from(m: Model) -> Self {
+        Self {
+            id: m.id,
+            project_id: m.project_id,
+            source: ImportSource::from_str(m.source.as_str()).ok(),
+            path: m.path,
+            state: ImportState::from_str(m.state.as_str()).unwrap_or_default(),
+            total: m.total,
+            processed: m.processed,
+            created: m.created,
+            skipped: m.skipped,
+            warnings: m
+                .warnings
+                .map(|warnings| warnings.lines().map(String::from).collect())
+                .unwrap_or_default(),
+            error: m.error,
+            created_at: Some(m.created_at),
+            finished_at: m.finished_at,
+        }
+    }
+}
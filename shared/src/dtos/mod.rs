@@ -1,8 +1,22 @@
+pub mod board_dto;
+pub mod bulk_dto;
+pub mod custom_field_dto;
 pub mod getter;
 pub mod identity;
+pub mod import_dto;
 pub mod login_dto;
 pub mod page;
 pub mod preferences_dto;
+pub mod project_archive_dto;
 pub mod project_dto;
+pub mod project_member_dto;
+pub mod recurring_ticket_dto;
+pub mod report_dto;
+pub mod saved_view_dto;
+pub mod sla_dto;
+pub mod sprint_dto;
+pub mod ticket_csv_dto;
 pub mod ticket_dto;
+pub mod trash_dto;
 pub mod user_dto;
+pub mod watcher_dto;
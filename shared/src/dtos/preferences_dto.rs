@@ -37,4 +37,7 @@ pub struct PreferencesDto {
     pub theme: Option<Theme>,
     pub notifications: Option<Notifications>,
     pub mfa: Option<bool>,
+    /// Saved view applied when opening the ticket list.
+    #[serde(default)]
+    pub default_view: Option<u64>,
 }
@@ -0,0 +1,47 @@
+use implicit_clone::ImplicitClone;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString};
+
+#[derive(
+    Clone, Copy, Debug, Deserialize, Display, EnumIter, EnumString, Eq, PartialEq, Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum RestoreConflictKind {
+    /// The key of the project is taken. Blocks the restore until another key is given.
+    ProjectKey,
+    /// Nobody has the username, the entries of the user are attributed to the one restoring.
+    User,
+    /// The key of a ticket is taken, the ticket gets the next key of the project.
+    TicketKey,
+    /// The file is not in the archive or could not be stored.
+    Attachment,
+}
+
+impl ImplicitClone for RestoreConflictKind {}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct RestoreConflictDto {
+    pub kind: RestoreConflictKind,
+    /// Key, username or file name the conflict is about.
+    pub subject: String,
+    pub message: String,
+}
+
+impl ImplicitClone for RestoreConflictDto {}
+
+/// What restoring an archive did, or would do for a dry run.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ProjectRestoreDto {
+    pub dry_run: bool,
+    /// Id of the restored project, `None` for a dry run.
+    pub project_id: Option<u64>,
+    pub key: String,
+    pub tickets: u32,
+    pub comments: u32,
+    pub history: u32,
+    pub attachments: u32,
+    pub conflicts: Vec<RestoreConflictDto>,
+}
+
+impl ImplicitClone for ProjectRestoreDto {}
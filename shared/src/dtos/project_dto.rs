@@ -62,6 +62,8 @@ pub struct ProjectQueryResult {
     pub deadline: Option<NaiveDate>,
     pub user_id: Uuid,
     pub active: i8,
+    pub key: String,
+    pub version: u32,
 }
 
 #[derive(
@@ -72,12 +74,18 @@ pub struct ProjectDto {
     #[validate(min_length = 8)]
     #[validate(max_length = 160)]
     pub summary: String,
-    #[serde(with = "ts_seconds_option")]
+    #[serde(default, with = "ts_seconds_option")]
     #[validate(custom(ProjectValidation::deadline_validation))]
     pub deadline: Option<DateTime<Utc>>,
     pub user_id: Uuid,
     #[validate(enumerate = [0, 1], message = "Active can be either 0 or 1.")]
     pub active: i8,
+    /// Prefix of the ticket keys, e.g. `TCK` for `TCK-142`.
+    #[validate(custom(ProjectValidation::key_validation))]
+    pub key: String,
+    /// Version the project was read at, sent back as `If-Match` when saving it.
+    #[serde(default)]
+    pub version: u32,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, Validate)]
@@ -100,8 +108,9 @@ impl Display for ProjectDto {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "( id: {}, summary: '{}', deadline: {} )",
+            "( id: {}, key: {}, summary: '{}', deadline: {} )",
             self.id.map_or(String::from("-"), |id| format!("{}", id)),
+            self.key,
             self.summary,
             self.deadline.map_or(String::from("-"), |d| d.to_string())
         )
@@ -120,6 +129,8 @@ impl From<&Model> for ProjectDto {
             }),
             user_id: Uuid::default(),
             active: m.active,
+            key: m.key.to_owned(),
+            version: m.version,
         }
     }
 }
@@ -136,6 +147,8 @@ impl From<Model> for ProjectDto {
             }),
             user_id: Uuid::default(),
             active: m.active,
+            key: m.key.to_owned(),
+            version: m.version,
         }
     }
 }
@@ -152,6 +165,8 @@ impl From<ProjectQueryResult> for ProjectDto {
             }),
             user_id: m.user_id,
             active: m.active,
+            key: m.key.to_owned(),
+            version: m.version,
         }
     }
 }
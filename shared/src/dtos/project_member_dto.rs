@@ -0,0 +1,48 @@
+use implicit_clone::ImplicitClone;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString};
+use uuid::Uuid;
+
+/// What a member may do in a project, each role includes the ones before it.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Display,
+    EnumIter,
+    EnumString,
+    Eq,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum ProjectRole {
+    /// Reads the project, its tickets and their comments.
+    Viewer,
+    /// Creates and changes tickets, comments, attachments and sprints.
+    Contributor,
+    /// Changes the project itself, its settings and its members.
+    Admin,
+}
+
+impl ImplicitClone for ProjectRole {}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ProjectMemberDto {
+    pub user_id: Uuid,
+    pub name: String,
+    pub username: String,
+    pub role: ProjectRole,
+}
+
+impl ImplicitClone for ProjectMemberDto {}
+
+/// The role to give a user in a project, adding the user to the members if needed.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ProjectMemberRoleDto {
+    pub role: ProjectRole,
+}
@@ -0,0 +1,75 @@
+use chrono::{DateTime, Utc};
+use entity::{recurring_tickets::Model, sea_orm_active_enums::Priority};
+use implicit_clone::ImplicitClone;
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use std::fmt::Display;
+use uuid::Uuid;
+
+/// A ticket created in the project again and again on the schedule of a cron expression.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Validate)]
+pub struct RecurringTicketDto {
+    pub id: Option<u64>,
+    /// Set from the path, definitions belong to the project they are created in.
+    #[serde(default)]
+    pub project_id: u64,
+    #[validate(min_length = 8)]
+    #[validate(max_length = 160)]
+    pub title: String,
+    #[validate(min_length = 8)]
+    #[validate(max_length = 500)]
+    pub description: String,
+    pub priority: Priority,
+    /// Assignee of the created tickets.
+    #[serde(default)]
+    pub user_id: Option<Uuid>,
+    /// Cron expression in UTC, with five fields from minutes to weekdays or with seconds
+    /// and years added, e.g. `0 6 * * Mon` for every Monday at 06:00. Numbered weekdays of five
+    /// fields are the ones of crontab, 0 or 7 being Sunday and 1 Monday, with seconds they start
+    /// with Sunday as 1. Names avoid the confusion.
+    #[validate(min_length = 9)]
+    #[validate(max_length = 100)]
+    pub cron: String,
+    /// Paused definitions create no tickets.
+    #[serde(default)]
+    pub paused: bool,
+    /// Occurrence the last ticket was created for. Set by the server.
+    #[serde(default)]
+    pub last_run_at: Option<DateTime<Utc>>,
+    /// Next occurrence, `None` while paused. Set by the server.
+    #[serde(default)]
+    pub next_run_at: Option<DateTime<Utc>>,
+}
+
+impl Display for RecurringTicketDto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "( id: {}, title: '{}', cron: '{}', paused: {} )",
+            self.id.map_or(String::from("-"), |id| format!("{}", id)),
+            self.title,
+            self.cron,
+            self.paused
+        )
+    }
+}
+
+/// The definition with the public id of its assignee.
+impl From<(Model, Option<Uuid>)> for RecurringTicketDto {
+    fn from((m, user_id): (Model, Option<Uuid>)) -> Self {
+        Self {
+            id: Some(m.id),
+            project_id: m.project_id,
+            title: m.title,
+            description: m.description,
+            priority: m.priority,
+            user_id,
+            cron: m.cron,
+            paused: m.active == 0,
+            last_run_at: m.last_run_at,
+            next_run_at: None,
+        }
+    }
+}
+
+impl ImplicitClone for RecurringTicketDto {}
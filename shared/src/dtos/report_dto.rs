@@ -0,0 +1,104 @@
+use crate::validation::ticket_validation::TicketStatus;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// Tickets in scope at the end of a day, and how many of them were finished by then.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ProgressPointDto {
+    pub date: NaiveDate,
+    pub scope: u64,
+    pub done: u64,
+}
+
+impl ProgressPointDto {
+    /// Work left, as shown by a burndown chart.
+    pub fn remaining(&self) -> u64 {
+        self.scope.saturating_sub(self.done)
+    }
+}
+
+/// Daily series of a project or a sprint, feeding both the burndown and the burnup chart.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ProgressReportDto {
+    pub sprint_id: Option<u64>,
+    pub points: Vec<ProgressPointDto>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SprintVelocityDto {
+    pub sprint_id: u64,
+    pub name: String,
+    /// Tickets of the sprint finished by the time it was completed.
+    pub completed: u64,
+}
+
+/// Completed sprints of a project, oldest first.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct VelocityReportDto {
+    pub sprints: Vec<SprintVelocityDto>,
+    /// Average of the last sprints, a forecast for the next one.
+    pub average: f64,
+}
+
+/// Durations in hours below which 50, 85 and 95 percent of the tickets fall.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct PercentilesDto {
+    pub p50: f64,
+    pub p85: f64,
+    pub p95: f64,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct StatusDurationDto {
+    pub status: TicketStatus,
+    pub hours: f64,
+}
+
+/// Time a ticket spent in each status, until it was finished or until now.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct TimeInStatusDto {
+    pub ticket_id: u64,
+    pub statuses: Vec<StatusDurationDto>,
+    /// From creation until finished, `None` while the ticket is not finished.
+    pub lead_hours: Option<f64>,
+    /// From the start of the work until finished, `None` if it never started or finished.
+    pub cycle_hours: Option<f64>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct StatusPercentilesDto {
+    pub status: TicketStatus,
+    pub hours: Option<PercentilesDto>,
+}
+
+/// Percentiles of the tickets of a group finished in the range of the report. They are
+/// `None` when no ticket had a value.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct FlowGroupDto {
+    /// The project, a priority or the name of an assignee.
+    pub group: String,
+    pub count: u64,
+    pub lead_time: Option<PercentilesDto>,
+    pub cycle_time: Option<PercentilesDto>,
+    pub time_in_status: Vec<StatusPercentilesDto>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct FlowReportDto {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub groups: Vec<FlowGroupDto>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CumulativeFlowPointDto {
+    pub date: NaiveDate,
+    /// Tickets per status at the end of the day, in the order of the statuses of the report.
+    pub counts: Vec<u64>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CumulativeFlowDto {
+    pub statuses: Vec<TicketStatus>,
+    pub points: Vec<CumulativeFlowPointDto>,
+}
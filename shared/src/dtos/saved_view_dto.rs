@@ -0,0 +1,63 @@
+use crate::validation::saved_view_validation::SavedViewValidation;
+use entity::saved_views::Model;
+use implicit_clone::ImplicitClone;
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use std::fmt::Display;
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, Validate)]
+pub struct SavedViewDto {
+    pub id: Option<u64>,
+    #[validate(min_length = 1)]
+    #[validate(max_length = 80)]
+    pub name: String,
+    /// Views shared with a project are listed for everyone, not only for their owner.
+    pub project_id: Option<u64>,
+    /// Ticket query expression applied to the list.
+    #[serde(default)]
+    pub filter: String,
+    pub sort: Option<String>,
+    #[validate(custom(SavedViewValidation::order_validation))]
+    pub order: Option<String>,
+    /// Visible columns, by `TicketField` name. Empty means the default columns.
+    #[serde(default)]
+    #[validate(custom(SavedViewValidation::columns_validation))]
+    pub columns: Vec<String>,
+    /// Whether the requesting user owns the view and thus can change it.
+    #[serde(default)]
+    pub owned: bool,
+}
+
+impl Display for SavedViewDto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "( id: {}, name: '{}', filter: '{}' )",
+            self.id.map_or(String::from("-"), |id| format!("{}", id)),
+            self.name,
+            self.filter
+        )
+    }
+}
+
+impl From<&Model> for SavedViewDto {
+    fn from(m: &Model) -> Self {
+        Self {
+            id: Some(m.id),
+            name: m.name.to_owned(),
+            project_id: m.project_id,
+            filter: m.filter.to_owned(),
+            sort: m.sort.to_owned(),
+            order: m.sort_order.to_owned(),
+            columns: m
+                .columns
+                .split(',')
+                .filter(|c| !c.is_empty())
+                .map(String::from)
+                .collect(),
+            owned: false,
+        }
+    }
+}
+
+impl ImplicitClone for SavedViewDto {}
@@ -0,0 +1,182 @@
+use crate::validation::sla_validation::SlaValidation;
+use chrono::{NaiveTime, Weekday};
+use entity::{sea_orm_active_enums::Priority, sla_policies::Model};
+use implicit_clone::ImplicitClone;
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use std::fmt::Display;
+use strum::{Display, EnumIter, EnumString};
+
+/// Working time the SLA clocks count, in a fixed offset from UTC.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Validate)]
+#[validate(custom = SlaValidation::business_hours_validation)]
+pub struct BusinessHoursDto {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+    #[validate(min_items = 1)]
+    #[validate(unique_items)]
+    pub days: Vec<Weekday>,
+    #[serde(default)]
+    #[validate(minimum = -720)]
+    #[validate(maximum = 840)]
+    pub utc_offset_minutes: i32,
+}
+
+impl Default for BusinessHoursDto {
+    fn default() -> Self {
+        Self {
+            start: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            end: NaiveTime::from_hms_opt(17, 0, 0).unwrap(),
+            days: vec![
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri,
+            ],
+            utc_offset_minutes: 0,
+        }
+    }
+}
+
+/// Targets for the tickets of a project with the given priority. Without business hours the
+/// clocks run around the clock.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Validate)]
+pub struct SlaPolicyDto {
+    pub priority: Priority,
+    /// Until the ticket is picked up or commented on by someone else than its reporter.
+    #[validate(minimum = 1)]
+    pub response_minutes: Option<u32>,
+    /// Until the ticket is finished.
+    #[validate(minimum = 1)]
+    pub resolution_minutes: Option<u32>,
+    #[validate]
+    pub business_hours: Option<BusinessHoursDto>,
+}
+
+impl Display for SlaPolicyDto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "( priority: {:?}, response: {}, resolution: {} )",
+            self.priority,
+            self.response_minutes
+                .map_or(String::from("-"), |m| format!("{}m", m)),
+            self.resolution_minutes
+                .map_or(String::from("-"), |m| format!("{}m", m)),
+        )
+    }
+}
+
+impl From<&Model> for SlaPolicyDto {
+    fn from(m: &Model) -> Self {
+        Self {
+            priority: m.priority.clone(),
+            response_minutes: m.response_minutes,
+            resolution_minutes: m.resolution_minutes,
+            business_hours: (m.business_hours != 0).then(|| BusinessHoursDto {
+                start: m.day_start,
+                end: m.day_end,
+                days: m
+                    .work_days
+                    .split(',')
+                    .filter_map(|d| d.parse::<Weekday>().ok())
+                    .collect(),
+                utc_offset_minutes: m.utc_offset_minutes,
+            }),
+        }
+    }
+}
+
+impl From<Model> for SlaPolicyDto {
+    fn from(m: Model) -> Self {
+        Self::from(&m)
+    }
+}
+
+/// The policies of a project, one per priority at most.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, Validate)]
+pub struct SlaPoliciesDto {
+    #[validate]
+    #[validate(custom(SlaValidation::policies_validation))]
+    pub policies: Vec<SlaPolicyDto>,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumIter,
+    EnumString,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub enum SlaClock {
+    #[default]
+    Response,
+    Resolution,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumIter,
+    EnumString,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub enum SlaState {
+    /// Running and within the target.
+    #[default]
+    Running,
+    /// Running with most of the target used up.
+    AtRisk,
+    /// Past the target, running or not.
+    Breached,
+    /// Stopped within the target.
+    Met,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SlaClockDto {
+    pub clock: SlaClock,
+    pub target_minutes: u32,
+    pub elapsed_minutes: u32,
+    pub state: SlaState,
+}
+
+impl SlaClockDto {
+    pub fn remaining_minutes(&self) -> i64 {
+        self.target_minutes as i64 - self.elapsed_minutes as i64
+    }
+}
+
+/// The clocks of a ticket under the policy of its project and priority.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TicketSlaDto {
+    pub ticket_id: u64,
+    pub clocks: Vec<SlaClockDto>,
+}
+
+impl TicketSlaDto {
+    /// The clock needing attention first: breached before at risk before the others.
+    pub fn worst(&self) -> Option<&SlaClockDto> {
+        self.clocks.iter().max_by_key(|c| match c.state {
+            SlaState::Breached => 3,
+            SlaState::AtRisk => 2,
+            SlaState::Running => 1,
+            SlaState::Met => 0,
+        })
+    }
+}
+
+impl ImplicitClone for TicketSlaDto {}
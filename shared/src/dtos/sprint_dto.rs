@@ -0,0 +1,91 @@
+use crate::validation::sprint_validation::SprintValidation;
+use chrono::NaiveDate;
+use entity::sprints::Model;
+use implicit_clone::ImplicitClone;
+use serde::{Deserialize, Serialize};
+use serde_valid::Validate;
+use std::{fmt::Display, str::FromStr};
+use strum::{Display, EnumIter, EnumString};
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Display,
+    EnumIter,
+    EnumString,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+)]
+pub enum SprintState {
+    #[default]
+    Planned,
+    Active,
+    Completed,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, Validate)]
+#[validate(custom = SprintValidation::dates_validation)]
+pub struct SprintDto {
+    pub id: Option<u64>,
+    /// Set from the path, sprints belong to the project they are created in.
+    #[serde(default)]
+    pub project_id: u64,
+    #[validate(min_length = 1)]
+    #[validate(max_length = 80)]
+    pub name: String,
+    #[serde(default)]
+    #[validate(max_length = 1000)]
+    pub goal: String,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+    /// Only changed by starting and completing the sprint.
+    #[serde(default)]
+    pub state: SprintState,
+}
+
+impl Display for SprintDto {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "( id: {}, name: '{}', {} to {}, state: {} )",
+            self.id.map_or(String::from("-"), |id| format!("{}", id)),
+            self.name,
+            self.start_date,
+            self.end_date,
+            self.state
+        )
+    }
+}
+
+impl From<&Model> for SprintDto {
+    fn from(m: &Model) -> Self {
+        Self {
+            id: Some(m.id),
+            project_id: m.project_id,
+            name: m.name.to_owned(),
+            goal: m.goal.to_owned(),
+            start_date: m.start_date,
+            end_date: m.end_date,
+            state: SprintState::from_str(m.state.as_str()).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<Model> for SprintDto {
+    fn from(m: Model) -> Self {
+        Self::from(&m)
+    }
+}
+
+impl ImplicitClone for SprintDto {}
+
+/// Completes the active sprint. Unfinished tickets move to the given planned sprint, or back
+/// to the backlog if there is none.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct CompleteSprintDto {
+    pub next_sprint_id: Option<u64>,
+}
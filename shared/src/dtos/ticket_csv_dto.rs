@@ -0,0 +1,56 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use strum::{Display, EnumIter, EnumString};
+
+/// Ticket columns a CSV file can be imported into. The export names its columns the same,
+/// so an exported file imports without a mapping.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Display,
+    EnumIter,
+    EnumString,
+    Eq,
+    Ord,
+    PartialEq,
+    PartialOrd,
+    Serialize,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case", ascii_case_insensitive)]
+pub enum TicketColumn {
+    Title,
+    Description,
+    Status,
+    Priority,
+    /// Id of the project.
+    Project,
+    /// Username, i.e. the email, of the assignee.
+    Assignee,
+    /// Labels separated by semicolons.
+    Labels,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TicketImportDto {
+    /// Content of the file, with a header row.
+    pub csv: String,
+    /// Project of the rows without a project column or value.
+    #[serde(default)]
+    pub project_id: Option<u64>,
+    /// Ticket column of each header. Without a mapping, headers named like a ticket column are
+    /// imported into it and the others are ignored.
+    #[serde(default)]
+    pub mapping: BTreeMap<String, TicketColumn>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TicketImportResultDto {
+    pub dry_run: bool,
+    /// Rows imported, or which would be imported by a dry run.
+    pub rows: u64,
+    /// Ids of the created tickets, empty for a dry run.
+    pub ids: Vec<u64>,
+}
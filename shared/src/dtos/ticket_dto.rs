@@ -1,15 +1,18 @@
 use super::getter::Getter;
-use crate::validation::ticket_validation::{TicketPriority, TicketStatus};
+use crate::validation::ticket_validation::{TicketPriority, TicketStatus, TicketValidation};
 use entity::{sea_orm_active_enums::Priority, tickets::Model};
 use implicit_clone::ImplicitClone;
 use sea_orm::FromQueryResult;
 use serde::{Deserialize, Serialize};
 use serde_valid::Validate;
-use std::{fmt::Display, rc::Rc, str::FromStr};
-use strum::{Display, EnumCount, EnumIter, EnumString};
+use std::{collections::BTreeMap, fmt::Display, rc::Rc, str::FromStr};
+use strum::{EnumCount, EnumIter, IntoEnumIterator};
 use uuid::Uuid;
 
-#[derive(Copy, Clone, Display, EnumCount, EnumIter, EnumString, PartialEq)]
+/// Prefix of the custom field columns and sort keys, e.g. `cf.12`.
+pub const CUSTOM_FIELD_PREFIX: &str = "cf.";
+
+#[derive(Copy, Clone, EnumCount, EnumIter, PartialEq)]
 pub enum TicketField {
     Id,
     Title,
@@ -18,13 +21,53 @@ pub enum TicketField {
     Status,
     User,
     Priority,
+    /// Custom field of the project, by its id.
+    #[strum(disabled)]
+    Custom(u64),
 }
 
 impl ImplicitClone for TicketField {}
 
+impl Display for TicketField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TicketField::Id => write!(f, "Id"),
+            TicketField::Title => write!(f, "Title"),
+            TicketField::Description => write!(f, "Description"),
+            TicketField::Project => write!(f, "Project"),
+            TicketField::Status => write!(f, "Status"),
+            TicketField::User => write!(f, "User"),
+            TicketField::Priority => write!(f, "Priority"),
+            TicketField::Custom(id) => write!(f, "{}{}", CUSTOM_FIELD_PREFIX, id),
+        }
+    }
+}
+
 impl From<TicketField> for usize {
     fn from(val: TicketField) -> Self {
-        val as usize
+        match val {
+            // Custom fields come after the built-in ones
+            TicketField::Custom(_) => TicketField::COUNT,
+            field => TicketField::iter()
+                .position(|f| f == field)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl FromStr for TicketField {
+    type Err = strum::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix(CUSTOM_FIELD_PREFIX) {
+            Some(id) => id
+                .parse()
+                .map(TicketField::Custom)
+                .map_err(|_| strum::ParseError::VariantNotFound),
+            None => TicketField::iter()
+                .find(|f| f.to_string() == s)
+                .ok_or(strum::ParseError::VariantNotFound),
+        }
     }
 }
 
@@ -37,6 +80,7 @@ pub enum TicketValue {
     Status(Rc<TicketStatus>),
     User(Rc<Option<Uuid>>),
     Priority(Rc<TicketPriority>),
+    Custom(Rc<Vec<String>>),
 }
 
 impl Display for TicketValue {
@@ -58,6 +102,7 @@ impl Display for TicketValue {
                 None => write!(f, ""),
             },
             TicketValue::Priority(prio) => write!(f, "{}", prio),
+            TicketValue::Custom(values) => write!(f, "{}", values.join(", ")),
         }
     }
 }
@@ -73,6 +118,10 @@ pub struct TicketQueryResult {
     pub status: String,
     pub user_id: Option<Uuid>,
     pub priority: Option<Priority>,
+    pub ticket_key: Option<String>,
+    pub version: u32,
+    pub sprint_id: Option<u64>,
+    pub parent_id: Option<u64>,
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Validate)]
@@ -88,6 +137,27 @@ pub struct TicketDto {
     pub status: TicketStatus,
     pub user_id: Option<Uuid>,
     pub priority: TicketPriority,
+    /// Human-readable key within the project, e.g. `TCK-142`. Assigned by the server.
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Version the ticket was read at, sent back as `If-Match` when saving it.
+    #[serde(default)]
+    pub version: u32,
+    /// Sprint of the project the ticket is planned for, `None` while it is in the backlog.
+    #[serde(default)]
+    pub sprint_id: Option<u64>,
+    /// Ticket of the same project this one is part of.
+    #[serde(default)]
+    pub parent_id: Option<u64>,
+    /// Labels in alphabetical order. Saving the ticket replaces them.
+    #[serde(default)]
+    #[validate(unique_items)]
+    #[validate(custom(TicketValidation::labels_validation))]
+    pub labels: Vec<String>,
+    /// Values of the custom fields of the project, by field id. Only multi-select fields can
+    /// have more than one value.
+    #[serde(default)]
+    pub fields: BTreeMap<u64, Vec<String>>,
 }
 
 impl Getter<TicketField, TicketValue> for ITicketDto {
@@ -100,6 +170,9 @@ impl Getter<TicketField, TicketValue> for ITicketDto {
             TicketField::Status => TicketValue::Status(Rc::new(self.status)),
             TicketField::User => TicketValue::User(Rc::new(self.user_id)),
             TicketField::Priority => TicketValue::Priority(Rc::new(self.priority.clone())),
+            TicketField::Custom(id) => {
+                TicketValue::Custom(Rc::new(self.fields.get(&id).cloned().unwrap_or_default()))
+            }
         }
     }
 }
@@ -114,6 +187,12 @@ impl Default for TicketDto {
             status: Default::default(),
             user_id: Default::default(),
             priority: TicketPriority(Priority::Normal),
+            key: Default::default(),
+            version: Default::default(),
+            sprint_id: Default::default(),
+            parent_id: Default::default(),
+            labels: Default::default(),
+            fields: Default::default(),
         }
     }
 }
@@ -140,6 +219,12 @@ impl From<&Model> for TicketDto {
             status: TicketStatus::from_str(m.status.as_str()).unwrap(),
             user_id: None,
             priority: TicketPriority(m.priority.as_ref().unwrap().to_owned()),
+            key: m.ticket_key.to_owned(),
+            version: m.version,
+            sprint_id: m.sprint_id,
+            parent_id: m.parent_id,
+            labels: Default::default(),
+            fields: Default::default(),
         }
     }
 }
@@ -154,6 +239,12 @@ impl From<Model> for TicketDto {
             status: TicketStatus::from_str(m.status.as_str()).unwrap(),
             user_id: None,
             priority: TicketPriority(m.priority.unwrap()),
+            key: m.ticket_key,
+            version: m.version,
+            sprint_id: m.sprint_id,
+            parent_id: m.parent_id,
+            labels: Default::default(),
+            fields: Default::default(),
         }
     }
 }
@@ -168,6 +259,12 @@ impl From<&TicketQueryResult> for TicketDto {
             status: TicketStatus::from_str(m.status.as_str()).unwrap(),
             user_id: m.user_id,
             priority: TicketPriority(m.priority.clone().unwrap()),
+            key: m.ticket_key.to_owned(),
+            version: m.version,
+            sprint_id: m.sprint_id,
+            parent_id: m.parent_id,
+            labels: Default::default(),
+            fields: Default::default(),
         }
     }
 }
@@ -182,8 +279,46 @@ impl From<TicketQueryResult> for TicketDto {
             status: TicketStatus::from_str(m.status.as_str()).unwrap(),
             user_id: m.user_id,
             priority: TicketPriority(m.priority.unwrap()),
+            key: m.ticket_key,
+            version: m.version,
+            sprint_id: m.sprint_id,
+            parent_id: m.parent_id,
+            labels: Default::default(),
+            fields: Default::default(),
         }
     }
 }
 
 impl TicketDto {}
+
+/// New position of a ticket on the board: right after `after` and right before `before`. A
+/// missing neighbour means the start or the end of the board.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct MoveTicketDto {
+    pub after: Option<u64>,
+    pub before: Option<u64>,
+}
+
+/// Title and description of a ticket being created, compared with the open tickets of its
+/// project to find one reporting the same.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize, Validate)]
+pub struct TicketDraftDto {
+    pub project_id: Option<u64>,
+    #[validate(min_length = 8)]
+    #[validate(max_length = 160)]
+    pub title: String,
+    #[serde(default)]
+    #[validate(min_length = 8)]
+    #[validate(max_length = 500)]
+    pub description: String,
+}
+
+/// An open ticket resembling a draft, with a similarity score from 0 to 100.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SimilarTicketDto {
+    pub id: u64,
+    pub key: Option<String>,
+    pub title: String,
+    pub status: TicketStatus,
+    pub score: u8,
+}
@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use implicit_clone::ImplicitClone;
+use serde::{Deserialize, Serialize};
+use strum::{Display, EnumIter, EnumString};
+
+#[derive(
+    Clone, Copy, Debug, Deserialize, Display, EnumIter, EnumString, Eq, PartialEq, Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum TrashKind {
+    Tickets,
+    Projects,
+    Users,
+}
+
+impl ImplicitClone for TrashKind {}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TrashEntryDto {
+    pub id: u64,
+    pub kind: TrashKind,
+    /// Title of the ticket, summary of the project or name of the user.
+    pub label: String,
+    pub deleted_at: DateTime<Utc>,
+    /// Name of the user who deleted the entry.
+    pub deleted_by: Option<String>,
+    /// When the entry is going to be removed for good.
+    pub purge_at: DateTime<Utc>,
+}
+
+impl ImplicitClone for TrashEntryDto {}
@@ -0,0 +1,13 @@
+use implicit_clone::ImplicitClone;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// A user receiving the update emails of a ticket.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct WatcherDto {
+    pub user_id: Uuid,
+    pub name: String,
+    pub username: String,
+}
+
+impl ImplicitClone for WatcherDto {}
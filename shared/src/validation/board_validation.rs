@@ -0,0 +1,80 @@
+use crate::dtos::board_dto::BoardColumnDto;
+use serde_valid::validation::Error;
+
+pub struct BoardValidation;
+
+impl BoardValidation {
+    /// A ticket shows in a single column, so the statuses of the columns cannot overlap.
+    pub fn columns_validation(columns: &[BoardColumnDto]) -> Result<(), Error> {
+        for (i, column) in columns.iter().enumerate() {
+            if let Some(status) = column
+                .statuses
+                .iter()
+                .enumerate()
+                .find(|(j, s)| {
+                    column.statuses[..*j].contains(s)
+                        || columns[..i].iter().any(|other| other.contains(s))
+                })
+                .map(|(_, s)| s)
+            {
+                return Err(Error::Custom(format!(
+                    "The status {} should be in one column only.",
+                    status
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dtos::board_dto::BoardDto, validation::ticket_validation::TicketStatus};
+    use serde_valid::Validate;
+
+    fn column(name: &str, statuses: &[TicketStatus]) -> BoardColumnDto {
+        BoardColumnDto {
+            name: String::from(name),
+            statuses: statuses.to_vec(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_columns_validation() {
+        assert!(BoardValidation::columns_validation(&[
+            column("To do", &[TicketStatus::Selected]),
+            column("Review", &[TicketStatus::Reviewing, TicketStatus::Testing]),
+        ])
+        .is_ok());
+        assert!(BoardValidation::columns_validation(&[
+            column("To do", &[TicketStatus::Selected]),
+            column("Also to do", &[TicketStatus::Selected]),
+        ])
+        .is_err());
+        assert!(BoardValidation::columns_validation(&[column(
+            "Twice",
+            &[TicketStatus::Done, TicketStatus::Done]
+        )])
+        .is_err());
+    }
+
+    #[test]
+    fn test_board_validation() {
+        assert!(BoardDto::standard().validate().is_ok());
+        assert!(BoardDto::default().validate().is_err(), "No columns");
+        let board = BoardDto {
+            columns: vec![BoardColumnDto {
+                wip_limit: Some(0),
+                ..column("Doing", &[TicketStatus::Started])
+            }],
+        };
+        assert!(board.validate().is_err(), "Limits start at one");
+        assert!(BoardDto {
+            columns: vec![column("Empty", &[])]
+        }
+        .validate()
+        .is_err());
+    }
+}
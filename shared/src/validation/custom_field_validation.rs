@@ -0,0 +1,204 @@
+use crate::dtos::custom_field_dto::{CustomFieldDto, CustomFieldType};
+use chrono::NaiveDate;
+use serde_valid::validation::Error;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+pub const MAX_VALUE_LENGTH: usize = 255;
+pub const DATE_FORMAT: &str = "%Y-%m-%d";
+
+pub struct CustomFieldValidation;
+
+impl CustomFieldValidation {
+    /// Options are stored separated by commas, so they cannot contain one.
+    pub fn options_validation(options: &[String]) -> Result<(), Error> {
+        if let Some(option) = options
+            .iter()
+            .find(|o| o.trim().is_empty() || o.contains(',') || o.len() > MAX_VALUE_LENGTH)
+        {
+            return Err(Error::Custom(format!(
+                "Invalid option: '{}'. Options should be non-empty, without commas.",
+                option
+            )));
+        }
+        match options
+            .iter()
+            .enumerate()
+            .find(|(i, o)| options[..*i].contains(o))
+        {
+            Some((_, option)) => Err(Error::Custom(format!("Duplicate option: '{}'.", option))),
+            None => Ok(()),
+        }
+    }
+
+    /// Select fields need something to select from, the other types take no options.
+    pub fn definition_validation(field: &CustomFieldDto) -> Result<(), Error> {
+        match (field.field_type.has_options(), field.options.is_empty()) {
+            (true, true) => Err(Error::Custom(
+                "Select fields should have at least one option.".to_owned(),
+            )),
+            (false, false) => Err(Error::Custom(format!(
+                "{} fields cannot have options.",
+                field.field_type
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the values of a ticket against the fields of its project. Returns every problem
+    /// found, prefixed with the name of the field.
+    pub fn values_validation(
+        fields: &[CustomFieldDto],
+        values: &BTreeMap<u64, Vec<String>>,
+    ) -> Result<(), Vec<String>> {
+        let mut errors = values
+            .keys()
+            .filter(|id| !fields.iter().any(|f| f.id == Some(**id)))
+            .map(|id| format!("Unknown field: {}.", id))
+            .collect::<Vec<String>>();
+        for field in fields {
+            let field_values = field
+                .id
+                .and_then(|id| values.get(&id))
+                .map_or(&[][..], |v| v.as_slice());
+            if let Err(e) = Self::value_validation(field, field_values) {
+                errors.push(format!("{}: {}", field.name, e));
+            }
+        }
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+
+    pub fn value_validation(field: &CustomFieldDto, values: &[String]) -> Result<(), String> {
+        if values.is_empty() {
+            return match field.required {
+                true => Err(String::from("A value is required.")),
+                false => Ok(()),
+            };
+        }
+        if values.len() > 1 && field.field_type != CustomFieldType::MultiSelect {
+            return Err(String::from("Only one value is allowed."));
+        }
+        for value in values {
+            match field.field_type {
+                CustomFieldType::Text if value.trim().is_empty() => {
+                    return Err(String::from("The text should not be empty."))
+                }
+                CustomFieldType::Text if value.len() > MAX_VALUE_LENGTH => {
+                    return Err(format!(
+                        "The text should be at most {} characters long.",
+                        MAX_VALUE_LENGTH
+                    ))
+                }
+                CustomFieldType::Number
+                    if value.parse::<f64>().map_or(true, |n| !n.is_finite()) =>
+                {
+                    return Err(format!("'{}' is not a number.", value))
+                }
+                CustomFieldType::Date if NaiveDate::parse_from_str(value, DATE_FORMAT).is_err() => {
+                    return Err(format!("'{}' is not a date like 2024-03-22.", value))
+                }
+                CustomFieldType::Select | CustomFieldType::MultiSelect
+                    if !field.options.contains(value) =>
+                {
+                    return Err(format!("'{}' is not one of the options.", value))
+                }
+                CustomFieldType::User if Uuid::parse_str(value).is_err() => {
+                    return Err(format!("'{}' is not a user id.", value))
+                }
+                _ => (),
+            }
+        }
+        match values
+            .iter()
+            .enumerate()
+            .find(|(i, v)| values[..*i].contains(v))
+        {
+            Some((_, value)) => Err(format!("'{}' is selected more than once.", value)),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(id: u64, field_type: CustomFieldType, options: &[&str]) -> CustomFieldDto {
+        CustomFieldDto {
+            id: Some(id),
+            name: format!("Field {}", id),
+            field_type,
+            options: options.iter().map(|o| o.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn values(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_options_validation() {
+        assert!(CustomFieldValidation::options_validation(&values(&["Low", "High"])).is_ok());
+        assert!(CustomFieldValidation::options_validation(&values(&["Low", ""])).is_err());
+        assert!(CustomFieldValidation::options_validation(&values(&["A,B"])).is_err());
+        assert!(CustomFieldValidation::options_validation(&values(&["A", "A"])).is_err());
+    }
+
+    #[test]
+    fn test_definition_validation() {
+        let select = field(1, CustomFieldType::Select, &[]);
+        assert!(CustomFieldValidation::definition_validation(&select).is_err());
+        let text = field(2, CustomFieldType::Text, &["A"]);
+        assert!(CustomFieldValidation::definition_validation(&text).is_err());
+        let multi = field(3, CustomFieldType::MultiSelect, &["A"]);
+        assert!(CustomFieldValidation::definition_validation(&multi).is_ok());
+    }
+
+    #[test]
+    fn test_value_validation_by_type() {
+        let number = field(1, CustomFieldType::Number, &[]);
+        assert!(CustomFieldValidation::value_validation(&number, &values(&["4.5"])).is_ok());
+        assert!(CustomFieldValidation::value_validation(&number, &values(&["four"])).is_err());
+        let date = field(2, CustomFieldType::Date, &[]);
+        assert!(CustomFieldValidation::value_validation(&date, &values(&["2024-03-22"])).is_ok());
+        assert!(CustomFieldValidation::value_validation(&date, &values(&["22/03/2024"])).is_err());
+        let select = field(3, CustomFieldType::Select, &["Dev", "Prod"]);
+        assert!(CustomFieldValidation::value_validation(&select, &values(&["Prod"])).is_ok());
+        assert!(CustomFieldValidation::value_validation(&select, &values(&["Test"])).is_err());
+        assert!(
+            CustomFieldValidation::value_validation(&select, &values(&["Dev", "Prod"])).is_err()
+        );
+        let multi = field(4, CustomFieldType::MultiSelect, &["Dev", "Prod"]);
+        assert!(CustomFieldValidation::value_validation(&multi, &values(&["Dev", "Prod"])).is_ok());
+        assert!(CustomFieldValidation::value_validation(&multi, &values(&["Dev", "Dev"])).is_err());
+        let user = field(5, CustomFieldType::User, &[]);
+        assert!(CustomFieldValidation::value_validation(
+            &user,
+            &values(&["67e55044-10b1-426f-9247-bb680e5fe0c8"])
+        )
+        .is_ok());
+        assert!(CustomFieldValidation::value_validation(&user, &values(&["alice"])).is_err());
+    }
+
+    #[test]
+    fn test_values_validation() {
+        let fields = vec![
+            CustomFieldDto {
+                required: true,
+                ..field(1, CustomFieldType::Text, &[])
+            },
+            field(2, CustomFieldType::Number, &[]),
+        ];
+        let mut ticket_values = BTreeMap::from([(1, values(&["Acme"]))]);
+        assert!(CustomFieldValidation::values_validation(&fields, &ticket_values).is_ok());
+        ticket_values.insert(3, values(&["Unknown"]));
+        ticket_values.remove(&1);
+        let errors = CustomFieldValidation::values_validation(&fields, &ticket_values)
+            .expect_err("Unknown and missing required fields should be reported.");
+        assert_eq!(errors.len(), 2);
+    }
+}
@@ -1,5 +1,10 @@
+pub mod board_validation;
+pub mod custom_field_validation;
 pub mod is_empty;
 pub mod project_validation;
+pub mod saved_view_validation;
+pub mod sla_validation;
+pub mod sprint_validation;
 pub mod ticket_validation;
 pub mod user_validation;
 pub mod validation_messages;
@@ -13,6 +13,22 @@ impl ProjectValidation {
         }
     }
 
+    /// Ticket keys are resolved uppercased, so the key has to be 2 to 10 uppercase letters or
+    /// digits, starting with a letter.
+    pub fn key_validation(key: &str) -> Result<(), Error> {
+        let mut chars = key.chars();
+        let valid = (2..=10).contains(&key.len())
+            && chars.next().is_some_and(|c| c.is_ascii_uppercase())
+            && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit());
+        match valid {
+            true => Ok(()),
+            false => Err(Error::Custom(
+                "The key should be 2 to 10 uppercase letters or digits, starting with a letter."
+                    .to_owned(),
+            )),
+        }
+    }
+
     fn is_deadline_valid(deadline: &DateTime<Utc>) -> bool {
         let tomorrow = Utc::now().checked_add_days(Days::new(1)).unwrap();
         deadline >= &tomorrow
@@ -40,4 +56,14 @@ mod tests {
             "Deadline should be invalid if expired."
         );
     }
+
+    #[test]
+    fn test_key_validation() {
+        for key in ["TCK", "A1", "ABCDEFGHIJ"] {
+            assert!(ProjectValidation::key_validation(key).is_ok(), "{}", key);
+        }
+        for key in ["tck", "T", "1TCK", "TCK-1", "ABCDEFGHIJK", ""] {
+            assert!(ProjectValidation::key_validation(key).is_err(), "{}", key);
+        }
+    }
 }
@@ -0,0 +1,43 @@
+use crate::dtos::ticket_dto::TicketField;
+use serde_valid::validation::Error;
+use std::str::FromStr;
+
+pub struct SavedViewValidation;
+
+impl SavedViewValidation {
+    pub fn columns_validation(columns: &[String]) -> Result<(), Error> {
+        match columns
+            .iter()
+            .find(|column| TicketField::from_str(column).is_err())
+        {
+            Some(column) => Err(Error::Custom(format!("Unknown column: '{}'.", column))),
+            None => Ok(()),
+        }
+    }
+
+    pub fn order_validation(order: &Option<String>) -> Result<(), Error> {
+        match order.as_deref() {
+            None | Some("asc") | Some("desc") => Ok(()),
+            Some(_) => Err(Error::Custom(
+                "The order should be either \"asc\" or \"desc\".".to_owned(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_validation() {
+        let columns = [TicketField::Title, TicketField::Custom(12)]
+            .iter()
+            .map(|field| field.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(columns, vec!["Title", "cf.12"]);
+        assert!(SavedViewValidation::columns_validation(&columns).is_ok());
+        assert!(SavedViewValidation::columns_validation(&[String::from("cf.x")]).is_err());
+        assert!(SavedViewValidation::columns_validation(&[String::from("Unknown")]).is_err());
+    }
+}
@@ -0,0 +1,85 @@
+use crate::dtos::sla_dto::{BusinessHoursDto, SlaPolicyDto};
+use serde_valid::validation::Error;
+
+pub struct SlaValidation;
+
+impl SlaValidation {
+    pub fn business_hours_validation(hours: &BusinessHoursDto) -> Result<(), Error> {
+        match hours.end <= hours.start {
+            true => Err(Error::Custom(
+                "The business day should end after it starts.".to_owned(),
+            )),
+            false => Ok(()),
+        }
+    }
+
+    pub fn policies_validation(policies: &[SlaPolicyDto]) -> Result<(), Error> {
+        match policies
+            .iter()
+            .enumerate()
+            .find(|(i, p)| policies[..*i].iter().any(|o| o.priority == p.priority))
+        {
+            Some((_, policy)) => Err(Error::Custom(format!(
+                "The priority {:?} should have one policy only.",
+                policy.priority
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dtos::sla_dto::SlaPoliciesDto;
+    use chrono::NaiveTime;
+    use entity::sea_orm_active_enums::Priority;
+    use serde_valid::Validate;
+
+    fn policy(priority: Priority) -> SlaPolicyDto {
+        SlaPolicyDto {
+            priority,
+            response_minutes: Some(240),
+            resolution_minutes: None,
+            business_hours: Some(BusinessHoursDto::default()),
+        }
+    }
+
+    #[test]
+    fn test_business_hours_validation() {
+        assert!(BusinessHoursDto::default().validate().is_ok());
+        let hours = BusinessHoursDto {
+            end: NaiveTime::from_hms_opt(9, 0, 0).unwrap(),
+            ..Default::default()
+        };
+        assert!(hours.validate().is_err(), "Ends when it starts");
+        assert!(BusinessHoursDto {
+            days: vec![],
+            ..Default::default()
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_policies_validation() {
+        assert!(SlaPoliciesDto {
+            policies: vec![policy(Priority::Critical), policy(Priority::High)]
+        }
+        .validate()
+        .is_ok());
+        assert!(SlaPoliciesDto {
+            policies: vec![policy(Priority::Critical), policy(Priority::Critical)]
+        }
+        .validate()
+        .is_err());
+        assert!(SlaPoliciesDto {
+            policies: vec![SlaPolicyDto {
+                response_minutes: Some(0),
+                ..policy(Priority::Low)
+            }]
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -0,0 +1,41 @@
+use crate::dtos::sprint_dto::SprintDto;
+use serde_valid::validation::Error;
+
+pub struct SprintValidation;
+
+impl SprintValidation {
+    pub fn dates_validation(sprint: &SprintDto) -> Result<(), Error> {
+        match sprint.end_date < sprint.start_date {
+            true => Err(Error::Custom(
+                "The sprint should not end before it starts.".to_owned(),
+            )),
+            false => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use serde_valid::Validate;
+
+    fn sprint(start: (i32, u32, u32), end: (i32, u32, u32)) -> SprintDto {
+        SprintDto {
+            name: String::from("Sprint 1"),
+            start_date: NaiveDate::from_ymd_opt(start.0, start.1, start.2).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(end.0, end.1, end.2).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_dates_validation() {
+        assert!(sprint((2024, 3, 25), (2024, 4, 5)).validate().is_ok());
+        assert!(
+            sprint((2024, 3, 25), (2024, 3, 25)).validate().is_ok(),
+            "One day sprints should be allowed."
+        );
+        assert!(sprint((2024, 4, 5), (2024, 3, 25)).validate().is_err());
+    }
+}
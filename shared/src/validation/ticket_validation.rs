@@ -1,6 +1,8 @@
+use crate::dtos::bulk_dto::LABEL_MAX_LENGTH;
 use entity::sea_orm_active_enums::Priority;
 use serde::{Deserialize, Serialize};
-use std::fmt::Display;
+use serde_valid::validation::Error;
+use std::{fmt::Display, str::FromStr};
 use strum::{Display, EnumIter, EnumString};
 
 #[derive(
@@ -27,6 +29,13 @@ pub enum TicketStatus {
     Closed,
 }
 
+impl TicketStatus {
+    /// Done and closed tickets need no more work, e.g. when completing a sprint.
+    pub fn is_finished(&self) -> bool {
+        matches!(self, TicketStatus::Done | TicketStatus::Closed)
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TicketPriority(pub Priority);
 
@@ -60,6 +69,108 @@ impl TryFrom<&str> for TicketPriority {
     }
 }
 
+impl FromStr for TicketPriority {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::try_from(s)
+    }
+}
+
 pub struct TicketValidation;
 
-impl TicketValidation {}
+impl TicketValidation {
+    /// Workflow rules of status changes: closed tickets can only be reopened, and tickets
+    /// have to belong to a project to enter the board (anything between Created and Closed).
+    pub fn transition_validation(
+        previous: TicketStatus,
+        next: TicketStatus,
+        has_project: bool,
+    ) -> Result<(), String> {
+        if previous == next {
+            return Ok(());
+        }
+        if previous == TicketStatus::Closed && next != TicketStatus::Created {
+            return Err(String::from(
+                "Closed tickets can only be reopened (set to Created).",
+            ));
+        }
+        if !has_project && !matches!(next, TicketStatus::Created | TicketStatus::Closed) {
+            return Err(format!(
+                "Ticket must belong to a project to be set to {}.",
+                next
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn labels_validation(labels: &[String]) -> Result<(), Error> {
+        match labels
+            .iter()
+            .find(|l| l.trim().is_empty() || l.trim().chars().count() > LABEL_MAX_LENGTH)
+        {
+            Some(_) => Err(Error::Custom(format!(
+                "Labels should be between 1 and {} characters long.",
+                LABEL_MAX_LENGTH
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transition_validation_reopen_closed() {
+        assert!(
+            TicketValidation::transition_validation(
+                TicketStatus::Closed,
+                TicketStatus::Created,
+                true
+            )
+            .is_ok(),
+            "Closed tickets should be reopenable."
+        );
+        assert!(
+            TicketValidation::transition_validation(
+                TicketStatus::Closed,
+                TicketStatus::Started,
+                true
+            )
+            .is_err(),
+            "Closed tickets should not go back to the board directly."
+        );
+    }
+
+    #[test]
+    fn test_transition_validation_requires_project() {
+        assert!(
+            TicketValidation::transition_validation(
+                TicketStatus::Created,
+                TicketStatus::Selected,
+                false
+            )
+            .is_err(),
+            "Tickets without project should not enter the board."
+        );
+    }
+
+    #[test]
+    fn test_labels_validation() {
+        assert!(
+            TicketValidation::labels_validation(&[String::from("backend"), String::from("ui")])
+                .is_ok(),
+            "Short labels should be accepted."
+        );
+        assert!(
+            TicketValidation::labels_validation(&[String::from("  ")]).is_err(),
+            "Blank labels should be rejected."
+        );
+        assert!(
+            TicketValidation::labels_validation(&["x".repeat(LABEL_MAX_LENGTH + 1)]).is_err(),
+            "Long labels should be rejected."
+        );
+    }
+}